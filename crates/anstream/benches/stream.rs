@@ -33,6 +33,16 @@ fn stream(c: &mut Criterion) {
                 black_box(stream)
             })
         });
+        group.bench_function("CoalesceStream", |b| {
+            b.iter(|| {
+                let buffer = Vec::with_capacity(content.len());
+                let mut stream = anstream::CoalesceStream::new(buffer);
+
+                stream.write_all(content).unwrap();
+
+                black_box(stream.finish().unwrap())
+            })
+        });
         #[cfg(all(windows, feature = "wincon"))]
         group.bench_function("WinconStream", |b| {
             b.iter(|| {
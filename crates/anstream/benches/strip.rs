@@ -19,8 +19,68 @@ impl anstyle_parse::Perform for Strip {
     }
 }
 
+/// `strip_str`, scanning for the end of printable text a byte at a time
+///
+/// This is the scan `strip_str` used before looking a word at a time, kept as a baseline.
+mod bytewise {
+    use anstyle_parse::state::state_change;
+    use anstyle_parse::state::Action;
+    use anstyle_parse::state::State;
+
+    pub(crate) fn strip_str(data: &str) -> String {
+        let mut stripped = String::with_capacity(data.len());
+        let mut bytes = data.as_bytes();
+        let mut state = State::Ground;
+        while let Some(printable) = next_str(&mut bytes, &mut state) {
+            stripped.push_str(printable);
+        }
+        stripped
+    }
+
+    fn next_str<'s>(bytes: &mut &'s [u8], state: &mut State) -> Option<&'s str> {
+        let offset = bytes.iter().copied().position(|b| {
+            let (next_state, action) = state_change(*state, b);
+            if next_state != State::Anywhere {
+                *state = next_state;
+            }
+            is_printable_bytes(action, b)
+        });
+        let (_, next) = bytes.split_at(offset.unwrap_or(bytes.len()));
+        *bytes = next;
+        *state = State::Ground;
+
+        let offset = bytes.iter().copied().position(|b| {
+            let (_next_state, action) = state_change(State::Ground, b);
+            !(is_printable_bytes(action, b) || is_utf8_continuation(b))
+        });
+        let (printable, next) = bytes.split_at(offset.unwrap_or(bytes.len()));
+        *bytes = next;
+        if printable.is_empty() {
+            None
+        } else {
+            Some(std::str::from_utf8(printable).expect("the parser preserves UTF-8 continuations"))
+        }
+    }
+
+    fn is_utf8_continuation(b: u8) -> bool {
+        matches!(b, 0x80..=0xbf)
+    }
+
+    fn is_printable_bytes(action: Action, byte: u8) -> bool {
+        const DEL: u8 = 0x7f;
+
+        (action == Action::Print && byte != DEL)
+            || action == Action::BeginUtf8
+            || (action == Action::Execute && byte.is_ascii_whitespace())
+    }
+}
+
 fn strip(c: &mut Criterion) {
+    // Mostly text, like CI logs
+    let build_log = "\x1b[1m\x1b[32m   Compiling\x1b[0m anstyle-parse v0.2.3 (/home/ci/src/crates/anstyle-parse)\nwarning: unused variable: `parser`\n  --> crates/anstream/src/adapter/strip.rs:42:9\n"
+        .repeat(1000);
     for (name, content) in [
+        ("build_log", build_log.as_bytes()),
         ("demo.vte", &include_bytes!("../tests/demo.vte")[..]),
         ("rg_help.vte", &include_bytes!("../tests/rg_help.vte")[..]),
         ("rg_linus.vte", &include_bytes!("../tests/rg_linus.vte")[..]),
@@ -40,6 +100,7 @@ fn strip(c: &mut Criterion) {
                 stripped.0,
                 anstream::adapter::strip_str(content).to_string()
             );
+            assert_eq!(stripped.0, bytewise::strip_str(content));
             assert_eq!(
                 stripped.0,
                 String::from_utf8(anstream::adapter::strip_bytes(content.as_bytes()).into_vec())
@@ -76,6 +137,13 @@ fn strip(c: &mut Criterion) {
                     black_box(stripped)
                 })
             });
+            group.bench_function("strip_str_bytewise", |b| {
+                b.iter(|| {
+                    let stripped = bytewise::strip_str(content);
+
+                    black_box(stripped)
+                })
+            });
             group.bench_function("StripStr", |b| {
                 b.iter(|| {
                     let mut stripped = String::with_capacity(content.len());
@@ -0,0 +1,261 @@
+use anstyle::Color;
+use anstyle::Effects;
+use anstyle::Style;
+
+use crate::StyledString;
+
+/// Markup accepted by a chat platform, for [`chat_str`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ChatMarkup {
+    /// Slack's `mrkdwn`, with `*bold*`, `_italic_`, and `~strikethrough~`
+    Slack,
+    /// Discord's Markdown, with `**bold**`, `*italic*`, `__underline__`, and `~~strikethrough~~`
+    Discord,
+    /// A Discord `ansi` code block, keeping bold, underline, and the 8 basic colors
+    DiscordAnsi,
+}
+
+const UNDERLINES: Effects = Effects::UNDERLINE
+    .insert(Effects::DOUBLE_UNDERLINE)
+    .insert(Effects::CURLY_UNDERLINE)
+    .insert(Effects::DOTTED_UNDERLINE)
+    .insert(Effects::DASHED_UNDERLINE);
+
+impl ChatMarkup {
+    /// Reduce `style` to what can be shown
+    fn adapt(self, style: Style) -> Style {
+        let effects = style.get_effects();
+        let mut kept = Effects::new();
+        for effect in [Effects::BOLD, Effects::ITALIC, Effects::STRIKETHROUGH] {
+            let supported = self != Self::DiscordAnsi || effect == Effects::BOLD;
+            if supported && effects.contains(effect) {
+                kept |= effect;
+            }
+        }
+        if self != Self::Slack && UNDERLINES.remove(effects) != UNDERLINES {
+            kept |= Effects::UNDERLINE;
+        }
+
+        let mut adapted = Style::new().effects(kept);
+        if self == Self::DiscordAnsi {
+            let profile = anstyle::Profile::new().colors(anstyle::ColorLevel::Ansi);
+            let colors = profile.adapt(style);
+            let basic = |color: Option<Color>| match color {
+                Some(Color::Ansi(color)) => Some(Color::Ansi(color.bright(false))),
+                _ => None,
+            };
+            adapted = adapted
+                .fg_color(basic(colors.get_fg_color()))
+                .bg_color(basic(colors.get_bg_color()));
+        }
+        adapted
+    }
+
+    /// The markers to open each effect with, outermost first
+    fn markers(self, effects: Effects) -> Vec<&'static str> {
+        let markers: &[(Effects, &str)] = match self {
+            Self::Slack => &[
+                (Effects::BOLD, "*"),
+                (Effects::ITALIC, "_"),
+                (Effects::STRIKETHROUGH, "~"),
+            ],
+            Self::Discord => &[
+                (Effects::BOLD, "**"),
+                (Effects::ITALIC, "*"),
+                (Effects::UNDERLINE, "__"),
+                (Effects::STRIKETHROUGH, "~~"),
+            ],
+            Self::DiscordAnsi => &[],
+        };
+        markers
+            .iter()
+            .filter(|(effect, _)| effects.contains(*effect))
+            .map(|(_, marker)| *marker)
+            .collect()
+    }
+}
+
+/// Convert styled `&str` into the markup accepted by a chat platform
+///
+/// This is for bots relaying CI or deploy output.  Colors are dropped, except for
+/// [`ChatMarkup::DiscordAnsi`], and so are other escape codes.  Text is escaped so it isn't
+/// mistaken for markup.
+///
+/// Chat platforms only apply markup at word boundaries, so a style change inside a word may
+/// show the markers as-is.
+///
+/// # Example
+///
+/// ```rust
+/// use anstream::adapter::ChatMarkup;
+///
+/// let output = "\x1b[1;31merror\x1b[0m: can't find `main`";
+/// assert_eq!(
+///     anstream::adapter::chat_str(output, ChatMarkup::Slack),
+///     "*error*: can't find `main`"
+/// );
+/// assert_eq!(
+///     anstream::adapter::chat_str(output, ChatMarkup::Discord),
+///     "**error**: can't find \\`main\\`"
+/// );
+/// assert_eq!(
+///     anstream::adapter::chat_str(output, ChatMarkup::DiscordAnsi),
+///     "```ansi\n\x1b[1m\x1b[31merror\x1b[0m: can't find `main`\n```"
+/// );
+/// ```
+pub fn chat_str(data: &str, markup: ChatMarkup) -> String {
+    let text = parse(data, markup);
+    let mut output = String::with_capacity(data.len());
+    if markup == ChatMarkup::DiscordAnsi {
+        output.push_str("```ansi\n");
+        // Keep the code block from being closed early
+        let rendered = text.render().to_string().replace("``", "`\u{200b}`");
+        output.push_str(&rendered);
+        if !rendered.ends_with('\n') {
+            output.push('\n');
+        }
+        output.push_str("```");
+        return output;
+    }
+
+    let mut line_start = true;
+    for (style, span) in text.spans() {
+        let markers = markup.markers(style.get_effects());
+        for (i, piece) in span.split('\n').enumerate() {
+            if i != 0 {
+                output.push('\n');
+                line_start = true;
+            }
+            let content = piece.trim();
+            if content.is_empty() {
+                output.push_str(piece);
+                continue;
+            }
+            // Markers can't be next to whitespace, so keep it outside of them
+            let leading = &piece[..piece.len() - piece.trim_start().len()];
+            let trailing = &piece[piece.trim_end().len()..];
+            output.push_str(leading);
+            for marker in &markers {
+                output.push_str(marker);
+            }
+            escape(
+                &mut output,
+                content,
+                markup,
+                line_start && markers.is_empty(),
+            );
+            for marker in markers.iter().rev() {
+                output.push_str(marker);
+            }
+            output.push_str(trailing);
+            line_start = false;
+        }
+    }
+    output
+}
+
+fn escape(output: &mut String, text: &str, markup: ChatMarkup, line_start: bool) {
+    for (i, c) in text.char_indices() {
+        match (markup, c) {
+            (ChatMarkup::Slack, '&') => output.push_str("&amp;"),
+            (ChatMarkup::Slack, '<') => output.push_str("&lt;"),
+            (ChatMarkup::Slack, '>') => output.push_str("&gt;"),
+            (ChatMarkup::Discord, '\\' | '*' | '_' | '~' | '`' | '|') => {
+                output.push('\\');
+                output.push(c);
+            }
+            // Quotes, headings, and lists
+            (ChatMarkup::Discord, '>' | '#' | '-') if line_start && i == 0 => {
+                output.push('\\');
+                output.push(c);
+            }
+            _ => output.push(c),
+        }
+    }
+}
+
+/// Collect the text of `data` in the styles `markup` can show
+fn parse(data: &str, markup: ChatMarkup) -> StyledString {
+    let mut parser: anstyle_parse::Parser = Default::default();
+    let mut capture = Capture {
+        markup,
+        style: Style::new(),
+        text: StyledString::new(),
+    };
+    for byte in data.as_bytes() {
+        parser.advance(&mut capture, *byte);
+    }
+    capture.text
+}
+
+struct Capture {
+    markup: ChatMarkup,
+    style: Style,
+    text: StyledString,
+}
+
+impl anstyle_parse::Perform for Capture {
+    fn print(&mut self, c: char) {
+        let mut buffer = [0; 4];
+        let style = self.markup.adapt(self.style);
+        self.text.push_str(style, c.encode_utf8(&mut buffer));
+    }
+
+    fn execute(&mut self, byte: u8) {
+        if matches!(byte, b'\n' | b'\t') {
+            self.print(byte as char);
+        }
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        if action == b'm' && intermediates.is_empty() && !ignore {
+            self.style = anstyle_parse::apply_sgr(self.style, params, anstyle::Sgr21::default()).0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn slack() {
+        let output = "\x1b[1m   Compiling\x1b[0m foo\n\x1b[3;9m<old> & new\x1b[0m\n";
+        assert_eq!(
+            chat_str(output, ChatMarkup::Slack),
+            "   *Compiling* foo\n_~&lt;old&gt; &amp; new~_\n"
+        );
+    }
+
+    #[test]
+    fn discord() {
+        let output = "> \x1b[4mnote\x1b[0m: run `cargo fix`\n- \x1b[1;3m*\x1b[0m";
+        assert_eq!(
+            chat_str(output, ChatMarkup::Discord),
+            "\\> __note__: run \\`cargo fix\\`\n\\- ***\\****"
+        );
+    }
+
+    #[test]
+    fn style_across_lines() {
+        assert_eq!(
+            chat_str("\x1b[1mone\n\ntwo \x1b[0m", ChatMarkup::Discord),
+            "**one**\n\n**two** "
+        );
+    }
+
+    #[test]
+    fn discord_ansi() {
+        let output = "\x1b[3;91mwarning\x1b[0m: \x1b[38;2;0;0;255;4mx\x1b[0m ```\n";
+        assert_eq!(
+            chat_str(output, ChatMarkup::DiscordAnsi),
+            "```ansi\n\x1b[31mwarning\x1b[0m: \x1b[4m\x1b[34mx\x1b[0m `\u{200b}``\n```"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use anstyle::Style;
+
+/// Split styled `&str` into runs of text and the [`Style`] they are shown in
+///
+/// This is for converting styled output into another format, like HTML or a GUI's text spans.
+/// Each run is borrowed from `data`, so a run ends at every escape code, even one that doesn't
+/// change the style.  Escape codes other than SGR, and control characters other than whitespace,
+/// are dropped.
+///
+/// For non-contiguous data, see [`WinconBytes`][super::WinconBytes].
+///
+/// # Example
+///
+/// ```rust
+/// use anstyle::AnsiColor;
+/// use anstyle::Style;
+///
+/// let styled_text = "\x1b[1;31merror\x1b[0m: file not found";
+/// let runs: Vec<_> = anstream::adapter::decode_str(styled_text).collect();
+/// assert_eq!(
+///     runs,
+///     [
+///         (AnsiColor::Red.on_default().bold(), "error"),
+///         (Style::new(), ": file not found"),
+///     ]
+/// );
+/// ```
+#[inline]
+pub fn decode_str(data: &str) -> DecodedStr<'_> {
+    DecodedStr::new(data)
+}
+
+/// See [`decode_str`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedStr<'s> {
+    data: &'s str,
+    offset: usize,
+    parser: anstyle_parse::Parser,
+    capture: DecodeCapture,
+    /// The byte range and style of the run not yet returned
+    run: Option<(usize, usize, Style)>,
+}
+
+impl<'s> DecodedStr<'s> {
+    #[inline]
+    fn new(data: &'s str) -> Self {
+        Self {
+            data,
+            offset: 0,
+            parser: Default::default(),
+            capture: Default::default(),
+            run: None,
+        }
+    }
+
+    fn slice(&self, (start, end, style): (usize, usize, Style)) -> (Style, &'s str) {
+        (style, &self.data[start..end])
+    }
+}
+
+impl<'s> Iterator for DecodedStr<'s> {
+    type Item = (Style, &'s str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.data.as_bytes();
+        while let Some(byte) = bytes.get(self.offset) {
+            self.offset += 1;
+            self.capture.printed = None;
+            self.parser.advance(&mut self.capture, *byte);
+            let Some(len) = self.capture.printed else {
+                continue;
+            };
+
+            let start = self.offset - len;
+            let style = self.capture.style;
+            match &mut self.run {
+                // Only escape codes change the style, so contiguous text is in the same style
+                Some((_, end, _)) if *end == start => *end = self.offset,
+                run => {
+                    if let Some(finished) = run.replace((start, self.offset, style)) {
+                        return Some(self.slice(finished));
+                    }
+                }
+            }
+        }
+        self.run.take().map(|run| self.slice(run))
+    }
+}
+
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+struct DecodeCapture {
+    style: Style,
+    /// The length of the character just printed
+    printed: Option<usize>,
+}
+
+impl anstyle_parse::Perform for DecodeCapture {
+    fn print(&mut self, c: char) {
+        self.printed = Some(c.len_utf8());
+    }
+
+    fn execute(&mut self, byte: u8) {
+        if byte.is_ascii_whitespace() {
+            self.printed = Some(1);
+        }
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        if action == b'm' && intermediates.is_empty() && !ignore {
+            self.style = anstyle_parse::apply_sgr(self.style, params, anstyle::Sgr21::default()).0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn runs_end_at_escape_codes() {
+        let bold = Style::new().bold();
+        let runs: Vec<_> =
+            decode_str("\x1b[1mé\x07😀\x1b]8;;x\x1b\\\tb\x1b[22m\x1b[0m\nc").collect();
+        assert_eq!(
+            runs,
+            [
+                (bold, "é"),
+                (bold, "😀"),
+                (bold, "\tb"),
+                (Style::new(), "\nc")
+            ]
+        );
+    }
+
+    #[test]
+    fn trailing_escape_code() {
+        let red = anstyle::AnsiColor::Red.on_default();
+        let runs: Vec<_> = decode_str("\x1b[31mred\x1b[0m").collect();
+        assert_eq!(runs, [(red, "red")]);
+        assert_eq!(decode_str("\x1b[31m").next(), None);
+    }
+
+    proptest! {
+        #[test]
+        #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+        fn same_text_as_wincon(s in "(\x1b\\[[0-9;]*m|\x1b\\]8;;[a-z]*\x1b\\\\|[a-z é😀\t\n\r\x07\x18])*") {
+            let expected: Vec<_> = crate::adapter::WinconBytes::new()
+                .extract_next(s.as_bytes())
+                .flat_map(|(style, text)| text.chars().map(move |c| (style, c)).collect::<Vec<_>>())
+                .collect();
+            let actual: Vec<_> = decode_str(&s)
+                .flat_map(|(style, text)| text.chars().map(move |c| (style, c)))
+                .collect();
+            assert_eq!(expected, actual);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::Write as _;
+
+/// Shrink styled `&str` without changing how it looks
+///
+/// This drops redundant resets, styles re-applied while already active, and styles that end
+/// before any text, merging what remains into one SGR escape code per change.  It is meant for
+/// storing colored output, like CI logs.
+///
+/// See [`CoalesceStream`][crate::CoalesceStream] for doing this while writing.
+///
+/// # Example
+///
+/// ```rust
+/// let styled_text = "\x1b[0m\x1b[1m\x1b[32mfoo\x1b[0m\x1b[32m\x1b[0m bar\x1b[0m";
+/// let minified = anstream::adapter::minify_str(styled_text);
+/// assert_eq!(minified, "\x1b[1;32mfoo\x1b[0m bar");
+/// ```
+pub fn minify_str(data: &str) -> String {
+    let minified = minify_bytes(data.as_bytes());
+    String::from_utf8(minified).expect("only whole escape codes are removed")
+}
+
+/// Shrink styled `&[u8]` without changing how it looks
+///
+/// See [`minify_str`]
+pub fn minify_bytes(data: &[u8]) -> Vec<u8> {
+    let mut stream = crate::CoalesceStream::new(Vec::with_capacity(data.len()));
+    stream
+        .write_all(data)
+        .expect("writing to a `Vec` can't fail");
+    stream.finish().expect("writing to a `Vec` can't fail")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn log_lines() {
+        let log = "\x1b[0m\x1b[1m\x1b[32m   Compiling\x1b[0m foo\n\x1b[0m\x1b[1m\x1b[32m    Finished\x1b[0m \x1b[0m\n";
+        assert_eq!(
+            minify_str(log),
+            "\x1b[1;32m   Compiling\x1b[0m foo\n\x1b[1;32m    Finished\x1b[0m \n"
+        );
+    }
+
+    #[test]
+    fn unstyled() {
+        let text = "ünïcode\r\n\ttext";
+        assert_eq!(minify_str(text), text);
+    }
+
+    #[test]
+    fn unknown_then_reset() {
+        let text = "\x1b[53ma\x1b[0mb";
+        assert_eq!(minify_str(text), text);
+    }
+
+    #[test]
+    fn truncated_escape_code() {
+        assert_eq!(minify_bytes(b"a\x1b[31mb\x1b[3"), b"a\x1b[31mb\x1b[3");
+    }
+}
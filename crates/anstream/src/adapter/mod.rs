@@ -1,8 +1,20 @@
 //! Gracefully degrade styled output
 
+mod chat;
+mod decode;
+mod minify;
+mod normalize;
 mod strip;
 mod wincon;
 
+pub use chat::chat_str;
+pub use chat::ChatMarkup;
+pub use decode::decode_str;
+pub use decode::DecodedStr;
+pub use minify::minify_bytes;
+pub use minify::minify_str;
+pub use normalize::normalize_bytes;
+pub use normalize::normalize_str;
 pub use strip::strip_bytes;
 pub use strip::strip_str;
 pub use strip::StripBytes;
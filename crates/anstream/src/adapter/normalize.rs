@@ -0,0 +1,70 @@
+use std::io::Write as _;
+
+/// Rewrite styled `&str` into one canonical encoding
+///
+/// Output that looks the same ends up as the same bytes, whatever produced it:
+/// - Each style change is one SGR escape code, with the fewest parameters to get there
+/// - Extended colors use `;`, like `38;2;r;g;b` rather than `38:2::r:g:b`
+/// - 256-color codes for the 16 ANSI colors become ANSI color codes, like `38;5;1` as `31`
+/// - Redundant and unused style changes are dropped, like with [`minify_str`][super::minify_str]
+///
+/// SGR parameters not representable by [`anstyle::Style`] are left as-is.
+///
+/// # Example
+///
+/// ```rust
+/// let a = anstream::adapter::normalize_str("\x1b[38:5:1m\x1b[1mfoo\x1b[m");
+/// let b = anstream::adapter::normalize_str("\x1b[1;31mfoo\x1b[0m");
+/// assert_eq!(a, b);
+/// assert_eq!(a, "\x1b[1;31mfoo\x1b[0m");
+/// ```
+pub fn normalize_str(data: &str) -> String {
+    let normalized = normalize_bytes(data.as_bytes());
+    String::from_utf8(normalized).expect("only whole escape codes are rewritten")
+}
+
+/// Rewrite styled `&[u8]` into one canonical encoding
+///
+/// See [`normalize_str`]
+pub fn normalize_bytes(data: &[u8]) -> Vec<u8> {
+    let mut stream = crate::CoalesceStream::new(Vec::with_capacity(data.len())).ansi_colors(true);
+    stream
+        .write_all(data)
+        .expect("writing to a `Vec` can't fail");
+    stream.finish().expect("writing to a `Vec` can't fail")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn equivalent_forms() {
+        let forms = [
+            "\x1b[38;2;255;128;0m\x1b[48;5;12mx\x1b[39;49m",
+            "\x1b[38:2::255:128:0;104mx\x1b[0m",
+            "\x1b[48;5;12m\x1b[38:2:255:128:0mx\x1b[m",
+        ];
+        for form in forms {
+            assert_eq!(
+                normalize_str(form),
+                "\x1b[38;2;255;128;0;104mx\x1b[0m",
+                "{form:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn extended_colors_kept() {
+        assert_eq!(
+            normalize_str("\x1b[38;5;16mx\x1b[0m"),
+            "\x1b[38;5;16mx\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn unknown_then_reset() {
+        let text = "\x1b[53ma\x1b[0mb";
+        assert_eq!(normalize_str(text), text);
+    }
+}
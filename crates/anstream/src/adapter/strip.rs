@@ -7,6 +7,8 @@ use anstyle_parse::state::State;
 /// This can be used to take output from a program that includes escape sequences and write it
 /// somewhere that does not easily support them, such as a log file.
 ///
+/// The result can be displayed, or iterated over for each run of printable content.
+///
 /// For non-contiguous data, see [`StripStr`].
 ///
 /// # Example
@@ -24,6 +26,17 @@ pub fn strip_str(data: &str) -> StrippedStr<'_> {
 }
 
 /// See [`strip_str`]
+///
+/// Iterating yields the runs of printable content, borrowed from the original `&str`, for
+/// processing them without copying into a new buffer.
+///
+/// # Example
+///
+/// ```rust
+/// let styled_text = "\x1b[32mfoo\x1b[m bar";
+/// let chunks: Vec<&str> = anstream::adapter::strip_str(styled_text).collect();
+/// assert_eq!(chunks, ["foo", " bar"]);
+/// ```
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct StrippedStr<'s> {
     bytes: &'s [u8],
@@ -124,10 +137,7 @@ fn next_str<'s>(bytes: &mut &'s [u8], state: &mut State) -> Option<&'s str> {
     *bytes = next;
     *state = State::Ground;
 
-    let offset = bytes.iter().copied().position(|b| {
-        let (_next_state, action) = state_change(State::Ground, b);
-        !(is_printable_bytes(action, b) || is_utf8_continuation(b))
-    });
+    let offset = find_non_printable_str(bytes);
     let (printable, next) = bytes.split_at(offset.unwrap_or(bytes.len()));
     *bytes = next;
     if printable.is_empty() {
@@ -153,9 +163,54 @@ unsafe fn from_utf8_unchecked<'b>(bytes: &'b [u8], safety_justification: &'stati
     }
 }
 
+/// Find the end of the printable text at the start of `bytes`, from `State::Ground`
+///
+/// In valid UTF-8, only C0 controls, other than whitespace, and DEL end printable text.  Those are
+/// looked for a word at a time, for stripping large logs that are mostly text.
 #[inline]
-fn is_utf8_continuation(b: u8) -> bool {
-    matches!(b, 0x80..=0xbf)
+fn find_non_printable_str(bytes: &[u8]) -> Option<usize> {
+    const WORD: usize = std::mem::size_of::<u64>();
+
+    let mut chunks = bytes.chunks_exact(WORD);
+    let mut offset = 0;
+    for chunk in &mut chunks {
+        let word = u64::from_le_bytes(
+            chunk
+                .try_into()
+                .expect("`chunks_exact` yields `WORD` bytes"),
+        );
+        if may_be_non_printable(word) {
+            if let Some(i) = chunk.iter().position(|b| is_non_printable_str(*b)) {
+                return Some(offset + i);
+            }
+        }
+        offset += WORD;
+    }
+    chunks
+        .remainder()
+        .iter()
+        .position(|b| is_non_printable_str(*b))
+        .map(|i| offset + i)
+}
+
+/// Whether any byte in `word` is a C0 control or DEL
+#[inline]
+fn may_be_non_printable(word: u64) -> bool {
+    const ONES: u64 = u64::from_ne_bytes([0x01; 8]);
+    const HIGH_BITS: u64 = u64::from_ne_bytes([0x80; 8]);
+    const fn has_byte_less_than(word: u64, n: u8) -> u64 {
+        word.wrapping_sub(ONES * n as u64) & !word & HIGH_BITS
+    }
+
+    let del = word ^ (ONES * 0x7f);
+    (has_byte_less_than(word, 0x20) | has_byte_less_than(del, 0x01)) != 0
+}
+
+#[inline]
+fn is_non_printable_str(b: u8) -> bool {
+    const DEL: u8 = 0x7f;
+
+    (b.is_ascii_control() && !b.is_ascii_whitespace()) || b == DEL
 }
 
 /// Strip ANSI escapes from bytes, returning the printable content
@@ -163,6 +218,8 @@ fn is_utf8_continuation(b: u8) -> bool {
 /// This can be used to take output from a program that includes escape sequences and write it
 /// somewhere that does not easily support them, such as a log file.
 ///
+/// The result can be iterated over for each run of printable content.
+///
 /// # Example
 ///
 /// ```rust
@@ -178,6 +235,17 @@ pub fn strip_bytes(data: &[u8]) -> StrippedBytes<'_> {
 }
 
 /// See [`strip_bytes`]
+///
+/// Iterating yields the runs of printable content, borrowed from the original bytes, for
+/// processing them without copying into a new buffer.
+///
+/// # Example
+///
+/// ```rust
+/// let styled_text = b"\x1b[32mfoo\x1b[m bar";
+/// let chunks: Vec<&[u8]> = anstream::adapter::strip_bytes(styled_text).collect();
+/// assert_eq!(chunks, [&b"foo"[..], &b" bar"[..]]);
+/// ```
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct StrippedBytes<'s> {
     bytes: &'s [u8],
@@ -297,6 +365,11 @@ fn next_bytes<'s>(
     });
     let (_, next) = bytes.split_at(offset.unwrap_or(bytes.len()));
     *bytes = next;
+    if offset.is_some() && *state != State::Utf8 {
+        // Printable data is processed from `Ground`, so leave the state consistent with that in
+        // case the data ends mid-way
+        *state = State::Ground;
+    }
 
     let offset = bytes.iter().copied().position(|b| {
         if *state == State::Utf8 {
@@ -470,6 +543,14 @@ mod test {
     }
 
     proptest! {
+        #[test]
+        #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+        fn word_at_a_time(word: [u8; 8]) {
+            let expected = word.iter().any(|b| b.is_ascii_control());
+            let actual = may_be_non_printable(u64::from_le_bytes(word));
+            assert_eq!(expected, actual);
+        }
+
         #[test]
         #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
         fn strip_str_no_escapes(s in "\\PC*") {
@@ -478,6 +559,14 @@ mod test {
             assert_eq!(expected, actual);
         }
 
+        #[test]
+        #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+        fn strip_str_escapes(s in "(\x1b\\[[0-9;]*m|\x1b\\]8;;[a-z]*\x1b\\\\|[a-z é😀\t\n\r\x07\x0b\x0c\x18])*") {
+            let expected = parser_strip(s.as_bytes());
+            let actual = strip_str(&s).to_string();
+            assert_eq!(expected, actual);
+        }
+
         #[test]
         #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
         fn strip_char_no_escapes(s in "\\PC*") {
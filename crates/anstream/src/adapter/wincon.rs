@@ -1,4 +1,7 @@
 /// Incrementally convert to wincon calls for non-contiguous data
+///
+/// Each item is text and the [`Style`][anstyle::Style] it is shown in.  For borrowing the text
+/// from contiguous data, see [`decode_str`][super::decode_str].
 #[derive(Default, Clone, Debug, PartialEq, Eq)]
 pub struct WinconBytes {
     parser: anstyle_parse::Parser,
@@ -11,6 +14,12 @@ impl WinconBytes {
         Default::default()
     }
 
+    /// The style of text after the data extracted so far
+    #[cfg(all(windows, feature = "wincon"))]
+    pub(crate) fn style(&self) -> anstyle::Style {
+        self.capture.style
+    }
+
     /// Strip the next segment of data
     pub fn extract_next<'s>(&'s mut self, bytes: &'s [u8]) -> WinconBytesIter<'s> {
         self.capture.reset();
@@ -104,103 +113,7 @@ impl anstyle_parse::Perform for WinconCapture {
             return;
         }
 
-        let mut style = self.style;
-        // param/value differences are dependent on the escape code
-        let mut state = State::Normal;
-        let mut r = None;
-        let mut g = None;
-        let mut is_bg = false;
-        for param in params {
-            for value in param {
-                match (state, *value) {
-                    (State::Normal, 0) => {
-                        style = anstyle::Style::default();
-                        break;
-                    }
-                    (State::Normal, 1) => {
-                        style = style.bold();
-                        break;
-                    }
-                    (State::Normal, 4) => {
-                        style = style.underline();
-                        break;
-                    }
-                    (State::Normal, 30..=37) => {
-                        let color = to_ansi_color(value - 30).unwrap();
-                        style = style.fg_color(Some(color.into()));
-                        break;
-                    }
-                    (State::Normal, 38) => {
-                        is_bg = false;
-                        state = State::PrepareCustomColor;
-                    }
-                    (State::Normal, 39) => {
-                        style = style.fg_color(None);
-                        break;
-                    }
-                    (State::Normal, 40..=47) => {
-                        let color = to_ansi_color(value - 40).unwrap();
-                        style = style.bg_color(Some(color.into()));
-                        break;
-                    }
-                    (State::Normal, 48) => {
-                        is_bg = true;
-                        state = State::PrepareCustomColor;
-                    }
-                    (State::Normal, 49) => {
-                        style = style.bg_color(None);
-                        break;
-                    }
-                    (State::Normal, 90..=97) => {
-                        let color = to_ansi_color(value - 90).unwrap().bright(true);
-                        style = style.fg_color(Some(color.into()));
-                        break;
-                    }
-                    (State::Normal, 100..=107) => {
-                        let color = to_ansi_color(value - 100).unwrap().bright(true);
-                        style = style.bg_color(Some(color.into()));
-                        break;
-                    }
-                    (State::PrepareCustomColor, 5) => {
-                        state = State::Ansi256;
-                    }
-                    (State::PrepareCustomColor, 2) => {
-                        state = State::Rgb;
-                        r = None;
-                        g = None;
-                    }
-                    (State::Ansi256, n) => {
-                        let color = anstyle::Ansi256Color(n as u8);
-                        if is_bg {
-                            style = style.bg_color(Some(color.into()));
-                        } else {
-                            style = style.fg_color(Some(color.into()));
-                        }
-                        break;
-                    }
-                    (State::Rgb, b) => match (r, g) {
-                        (None, _) => {
-                            r = Some(b);
-                        }
-                        (Some(_), None) => {
-                            g = Some(b);
-                        }
-                        (Some(r), Some(g)) => {
-                            let color = anstyle::RgbColor(r as u8, g as u8, b as u8);
-                            if is_bg {
-                                style = style.bg_color(Some(color.into()));
-                            } else {
-                                style = style.fg_color(Some(color.into()));
-                            }
-                            break;
-                        }
-                    },
-                    _ => {
-                        break;
-                    }
-                }
-            }
-        }
+        let (style, _) = anstyle_parse::apply_sgr(self.style, params, anstyle::Sgr21::default());
 
         if style != self.style && !self.printable.is_empty() {
             self.ready = Some(self.style);
@@ -209,28 +122,6 @@ impl anstyle_parse::Perform for WinconCapture {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, Debug)]
-enum State {
-    Normal,
-    PrepareCustomColor,
-    Ansi256,
-    Rgb,
-}
-
-fn to_ansi_color(digit: u16) -> Option<anstyle::AnsiColor> {
-    match digit {
-        0 => Some(anstyle::AnsiColor::Black),
-        1 => Some(anstyle::AnsiColor::Red),
-        2 => Some(anstyle::AnsiColor::Green),
-        3 => Some(anstyle::AnsiColor::Yellow),
-        4 => Some(anstyle::AnsiColor::Blue),
-        5 => Some(anstyle::AnsiColor::Magenta),
-        6 => Some(anstyle::AnsiColor::Cyan),
-        7 => Some(anstyle::AnsiColor::White),
-        _ => None,
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
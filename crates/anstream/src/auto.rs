@@ -1,7 +1,10 @@
+use crate::profile::ProfileStream;
+use crate::reset::ResetStream;
 use crate::stream::AsLockedWrite;
 use crate::stream::RawStream;
 #[cfg(feature = "auto")]
 use crate::ColorChoice;
+use crate::LinkFallback;
 use crate::StripStream;
 #[cfg(all(windows, feature = "wincon"))]
 use crate::WinconStream;
@@ -15,9 +18,12 @@ pub struct AutoStream<S: RawStream> {
 #[derive(Debug)]
 enum StreamInner<S: RawStream> {
     PassThrough(S),
+    ResetOnDrop(Box<ResetStream<S>>),
+    Profile(Box<ProfileStream<S>>),
     Strip(StripStream<S>),
     #[cfg(all(windows, feature = "wincon"))]
     Wincon(WinconStream<S>),
+    Discard(S),
 }
 
 impl<S> AutoStream<S>
@@ -37,10 +43,23 @@ where
     }
 
     /// Auto-adapt for the stream's capabilities
+    ///
+    /// Writes are [discarded][AutoStream::discard] when the stream is the null device, like
+    /// `/dev/null`.
     #[cfg(feature = "auto")]
     #[inline]
     pub fn auto(raw: S) -> Self {
-        let choice = Self::choice(&raw);
+        let probe = Probe::new(&raw);
+        Self::auto_(raw, probe)
+    }
+
+    #[cfg(feature = "auto")]
+    #[inline]
+    pub(crate) fn auto_(raw: S, probe: Probe) -> Self {
+        if probe.is_null_device {
+            return Self::discard(raw);
+        }
+        let choice = choice(probe.is_terminal);
         debug_assert_ne!(choice, ColorChoice::Auto);
         Self::new(raw, choice)
     }
@@ -48,7 +67,7 @@ where
     /// Report the desired choice for the given stream
     #[cfg(feature = "auto")]
     pub fn choice(raw: &S) -> ColorChoice {
-        choice(raw)
+        choice(raw.is_terminal())
     }
 
     /// Force ANSI escape codes to be passed through as-is, no matter what the inner `Write`
@@ -76,10 +95,10 @@ where
         if cfg!(windows) {
             #[cfg(feature = "auto")]
             let use_wincon = raw.is_terminal()
-                && !anstyle_query::windows::enable_ansi_colors().unwrap_or(true)
-                && !anstyle_query::term_supports_ansi_color();
+                && !supports_ansi(&raw)
+                && !anstyle_query::cached::term_supports_ansi_color();
             #[cfg(not(feature = "auto"))]
-            let use_wincon = true;
+            let use_wincon = !supports_ansi(&raw);
             if use_wincon {
                 Self::wincon(raw).unwrap_or_else(|raw| Self::always_ansi_(raw))
             } else {
@@ -97,6 +116,25 @@ where
         AutoStream { inner }
     }
 
+    /// Discard all writes without adapting or formatting them
+    ///
+    /// For output nobody will see, like with `--quiet`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write as _;
+    ///
+    /// let mut stream = anstream::AutoStream::discard(Vec::new());
+    /// write!(stream, "{}error", anstyle::AnsiColor::Red.render_fg()).unwrap();
+    /// assert!(stream.into_inner().is_empty());
+    /// ```
+    #[inline]
+    pub fn discard(raw: S) -> Self {
+        let inner = StreamInner::Discard(raw);
+        AutoStream { inner }
+    }
+
     #[inline]
     fn wincon(raw: S) -> Result<Self, S> {
         #[cfg(all(windows, feature = "wincon"))]
@@ -111,14 +149,199 @@ where
         }
     }
 
+    /// Reset any style left active when dropped
+    ///
+    /// For short-lived streams so styling can't leak into later output, like the user's shell,
+    /// when a trailing reset is missed.  This only applies when ANSI escape codes are passed
+    /// through as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(feature = "auto")] {
+    /// use std::io::Write as _;
+    ///
+    /// let mut stdout = anstream::stdout().reset_on_drop();
+    /// // Resets the color even though `render_reset` was forgotten
+    /// write!(stdout, "{}error", anstyle::AnsiColor::Red.render_fg()).unwrap();
+    /// # }
+    /// ```
+    #[inline]
+    pub fn reset_on_drop(self) -> Self {
+        let inner = match self.inner {
+            StreamInner::PassThrough(w) => StreamInner::ResetOnDrop(Box::new(ResetStream::new(w))),
+            inner => inner,
+        };
+        AutoStream { inner }
+    }
+
+    /// Degrade styling to what `profile` supports
+    ///
+    /// SGR escape codes are re-rendered, dropping or approximating what the terminal can't
+    /// display; see [`anstyle::Profile::adapt`].  This only applies when ANSI escape codes are
+    /// passed through as-is and takes the place of [`AutoStream::reset_on_drop`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write as _;
+    ///
+    /// let profile = anstyle::Profile::from_name("linux").unwrap();
+    /// let mut stream = anstream::AutoStream::always_ansi(Vec::new()).with_profile(profile);
+    /// let style = anstyle::RgbColor(250, 20, 20).on_default().italic();
+    /// write!(stream, "{}error{}", style.render(), style.render_reset()).unwrap();
+    /// assert_eq!(stream.into_inner(), b"\x1b[91merror\x1b[0m");
+    /// ```
+    #[inline]
+    pub fn with_profile(self, profile: anstyle::Profile) -> Self {
+        let inner = match self.inner {
+            StreamInner::PassThrough(w) => {
+                StreamInner::Profile(Box::new(ProfileStream::new(w, profile)))
+            }
+            StreamInner::ResetOnDrop(w) => {
+                StreamInner::Profile(Box::new(ProfileStream::new(w.into_inner(), profile)))
+            }
+            inner => inner,
+        };
+        AutoStream { inner }
+    }
+
+    /// Choose how OSC 8 hyperlinks are shown when the profile doesn't support them
+    ///
+    /// Rather than being passed through, links are rewritten as text when
+    /// [`anstyle::Profile::get_hyperlinks`] is `false`, defaulting to
+    /// [`LinkFallback::TextAndUrl`].  This only applies after [`AutoStream::with_profile`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write as _;
+    ///
+    /// let profile = anstyle::Profile::new().hyperlinks(false);
+    /// let mut stream = anstream::AutoStream::always_ansi(Vec::new())
+    ///     .with_profile(profile)
+    ///     .link_fallback(anstream::LinkFallback::TextAndUrl);
+    /// let url = "https://example.com";
+    /// write!(stream, "see \x1b]8;;{url}\x1b\\the docs\x1b]8;;\x1b\\").unwrap();
+    /// assert_eq!(stream.into_inner(), b"see the docs (https://example.com)");
+    /// ```
+    #[inline]
+    pub fn link_fallback(mut self, fallback: LinkFallback) -> Self {
+        if let StreamInner::Profile(w) = &mut self.inner {
+            w.link_fallback(fallback);
+        }
+        self
+    }
+
+    /// Choose how SGR 21 is interpreted, as it means double underline to some terminals and bold
+    /// off to others
+    ///
+    /// Written escape codes are interpreted this way and styles are re-rendered for it, defaulting
+    /// to [`anstyle::Sgr21::DoubleUnderline`].  This only applies after
+    /// [`AutoStream::with_profile`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write as _;
+    ///
+    /// let mut stream = anstream::AutoStream::always_ansi(Vec::new())
+    ///     .with_profile(anstyle::Profile::new())
+    ///     .sgr21(anstyle::Sgr21::BoldOff);
+    /// write!(stream, "\x1b[1mbold\x1b[21m plain").unwrap();
+    /// assert_eq!(stream.into_inner(), b"\x1b[1mbold\x1b[0m plain");
+    /// ```
+    #[inline]
+    pub fn sgr21(mut self, sgr21: anstyle::Sgr21) -> Self {
+        if let StreamInner::Profile(w) = &mut self.inner {
+            w.sgr21(sgr21);
+        }
+        self
+    }
+
+    /// Degrade styling to what the terminal named by `TERM` supports
+    ///
+    /// Rather than passing through escape codes that show as garbage or are ignored
+    /// inconsistently, like italic on the Linux console, curly underlines in xterm, or 256-color
+    /// indices in `screen`, those are dropped or approximated.  RGB colors are kept when
+    /// `COLORTERM` reports truecolor support.  See [`anstyle_query::quirks`] for the terminals
+    /// recognized.
+    ///
+    /// Like [`AutoStream::with_profile`], this only applies when ANSI escape codes are passed
+    /// through as-is.
+    #[cfg(feature = "auto")]
+    #[inline]
+    pub fn with_term_profile(self) -> Self {
+        let profile = term_profile(
+            anstyle_query::cached::term_quirks(),
+            anstyle_query::cached::truecolor(),
+        );
+        if profile == anstyle::Profile::new() {
+            return self;
+        }
+        self.with_profile(profile)
+    }
+
     /// Get the wrapped [`RawStream`]
     #[inline]
     pub fn into_inner(self) -> S {
         match self.inner {
             StreamInner::PassThrough(w) => w,
+            StreamInner::ResetOnDrop(w) => w.into_inner(),
+            StreamInner::Profile(w) => w.into_inner(),
             StreamInner::Strip(w) => w.into_inner(),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(w) => w.into_inner(),
+            StreamInner::Discard(w) => w,
+        }
+    }
+
+    /// Get the wrapped [`RawStream`] and the active [`ColorChoice`]
+    ///
+    /// See [`AutoStream::current_choice`] for the choice reported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// let stream = anstream::AutoStream::never(Vec::new());
+    /// let (buffer, choice) = stream.into_parts();
+    /// assert!(buffer.is_empty());
+    /// assert_eq!(choice, anstream::ColorChoice::Never);
+    /// ```
+    #[inline]
+    #[cfg(feature = "auto")]
+    pub fn into_parts(self) -> (S, ColorChoice) {
+        let choice = self.current_choice();
+        (self.into_inner(), choice)
+    }
+
+    /// Get a reference to the wrapped [`RawStream`]
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        match &self.inner {
+            StreamInner::PassThrough(w) => w,
+            StreamInner::ResetOnDrop(w) => w.get_ref(),
+            StreamInner::Profile(w) => w.get_ref(),
+            StreamInner::Strip(w) => w.get_ref(),
+            #[cfg(all(windows, feature = "wincon"))]
+            StreamInner::Wincon(w) => w.get_ref(),
+            StreamInner::Discard(w) => w,
+        }
+    }
+
+    /// Get a mutable reference to the wrapped [`RawStream`]
+    ///
+    /// Writing to it directly bypasses adapting the escape codes.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        match &mut self.inner {
+            StreamInner::PassThrough(w) => w,
+            StreamInner::ResetOnDrop(w) => w.get_mut(),
+            StreamInner::Profile(w) => w.get_mut(),
+            StreamInner::Strip(w) => w.get_mut(),
+            #[cfg(all(windows, feature = "wincon"))]
+            StreamInner::Wincon(w) => w.get_mut(),
+            StreamInner::Discard(w) => w,
         }
     }
 
@@ -126,9 +349,12 @@ where
     pub fn is_terminal(&self) -> bool {
         match &self.inner {
             StreamInner::PassThrough(w) => w.is_terminal(),
+            StreamInner::ResetOnDrop(w) => w.is_terminal(),
+            StreamInner::Profile(w) => w.is_terminal(),
             StreamInner::Strip(w) => w.is_terminal(),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(_) => true, // its only ever a terminal
+            StreamInner::Discard(w) => w.is_terminal(),
         }
     }
 
@@ -139,29 +365,52 @@ where
     #[cfg(feature = "auto")]
     pub fn current_choice(&self) -> ColorChoice {
         match &self.inner {
-            StreamInner::PassThrough(_) => ColorChoice::AlwaysAnsi,
-            StreamInner::Strip(_) => ColorChoice::Never,
+            StreamInner::PassThrough(_) | StreamInner::ResetOnDrop(_) | StreamInner::Profile(_) => {
+                ColorChoice::AlwaysAnsi
+            }
+            StreamInner::Strip(_) | StreamInner::Discard(_) => ColorChoice::Never,
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(_) => ColorChoice::Always,
         }
     }
 }
 
+/// Whether `raw`'s console interprets ANSI escape codes itself, like a ConPTY
+///
+/// This is checked per handle so a redirected stdout or stderr doesn't send the other one through
+/// the console API, translating escape codes that would have been passed through.
+#[cfg(all(windows, feature = "wincon"))]
+fn supports_ansi<S: RawStream>(raw: &S) -> bool {
+    anstyle_wincon::WinconStream::supports_ansi(raw)
+}
+
+#[cfg(not(all(windows, feature = "wincon")))]
+fn supports_ansi<S: RawStream>(_raw: &S) -> bool {
+    #[cfg(feature = "auto")]
+    {
+        anstyle_query::windows::enable_ansi_colors().unwrap_or(true)
+    }
+    #[cfg(not(feature = "auto"))]
+    {
+        true
+    }
+}
+
 #[cfg(feature = "auto")]
-fn choice(raw: &dyn RawStream) -> ColorChoice {
+fn choice(is_terminal: bool) -> ColorChoice {
     let choice = ColorChoice::global();
     match choice {
         ColorChoice::Auto => {
-            let clicolor = anstyle_query::clicolor();
+            let clicolor = anstyle_query::cached::clicolor();
             let clicolor_enabled = clicolor.unwrap_or(false);
             let clicolor_disabled = !clicolor.unwrap_or(true);
-            if raw.is_terminal()
-                && !anstyle_query::no_color()
+            if is_terminal
+                && !anstyle_query::cached::no_color()
                 && !clicolor_disabled
-                && (anstyle_query::term_supports_color()
+                && (anstyle_query::cached::term_supports_color()
                     || clicolor_enabled
-                    || anstyle_query::is_ci())
-                || anstyle_query::clicolor_force()
+                    || anstyle_query::cached::is_ci())
+                || anstyle_query::cached::clicolor_force()
             {
                 ColorChoice::Always
             } else {
@@ -172,19 +421,118 @@ fn choice(raw: &dyn RawStream) -> ColorChoice {
     }
 }
 
+/// What `quirks` can render, trusting `COLORTERM` when it reports `truecolor`
+///
+/// Whether to use colors at all is left to [`choice`].
+#[cfg(feature = "auto")]
+fn term_profile(
+    mut quirks: anstyle_query::quirks::TermQuirks,
+    truecolor: bool,
+) -> anstyle::Profile {
+    if truecolor && quirks.colors != anstyle::ColorLevel::None {
+        quirks.colors = anstyle::ColorLevel::Rgb;
+    }
+    quirks.into()
+}
+
+/// Forget the environment and terminal detection cached for [`AutoStream::auto`]
+///
+/// Detection is cached for the process as it otherwise runs for each [`stdout`][crate::stdout]
+/// or [`stderr`][crate::stderr] call.  Call this when the environment variables or where the
+/// output goes has changed, like in tests or after a daemon detaches from its terminal.
+/// This refreshes [`anstyle_query::cached`] while [`ColorChoice::global`] is never cached.
+#[cfg(feature = "auto")]
+pub fn invalidate() {
+    anstyle_query::cached::refresh();
+    STDOUT_PROBE.invalidate();
+    STDERR_PROBE.invalidate();
+}
+
+/// Where a [`RawStream`] writes to, as detected by [`AutoStream::auto`]
+#[cfg(feature = "auto")]
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Probe {
+    is_terminal: bool,
+    is_null_device: bool,
+}
+
+#[cfg(feature = "auto")]
+impl Probe {
+    fn new(raw: &dyn RawStream) -> Self {
+        let is_terminal = raw.is_terminal();
+        let is_null_device = !is_terminal && raw.is_null_device();
+        Self {
+            is_terminal,
+            is_null_device,
+        }
+    }
+
+    pub(crate) fn stdout(raw: &std::io::Stdout) -> Self {
+        STDOUT_PROBE.get_or_init(|| Self::new(raw))
+    }
+
+    pub(crate) fn stderr(raw: &std::io::Stderr) -> Self {
+        STDERR_PROBE.get_or_init(|| Self::new(raw))
+    }
+}
+
+#[cfg(feature = "auto")]
+static STDOUT_PROBE: Cache<Probe> = Cache::new();
+#[cfg(feature = "auto")]
+static STDERR_PROBE: Cache<Probe> = Cache::new();
+
+/// A value computed once until [`Cache::invalidate`]d
+#[cfg(feature = "auto")]
+struct Cache<T>(std::sync::RwLock<Option<T>>);
+
+#[cfg(feature = "auto")]
+impl<T: Copy> Cache<T> {
+    const fn new() -> Self {
+        Self(std::sync::RwLock::new(None))
+    }
+
+    fn get_or_init(&self, init: impl FnOnce() -> T) -> T {
+        let cached = *self
+            .0
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(value) = cached {
+            return value;
+        }
+        let value = init();
+        *self
+            .0
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = Some(value);
+        value
+    }
+
+    fn invalidate(&self) {
+        *self
+            .0
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner) = None;
+    }
+}
+
 impl AutoStream<std::io::Stdout> {
     /// Get exclusive access to the `AutoStream`
     ///
     /// Why?
     /// - Faster performance when writing in a loop
     /// - Avoid other threads interleaving output with the current thread
+    ///
+    /// The locked stream is itself a [`RawStream`] so it can be passed on while holding the lock.
     #[inline]
     pub fn lock(self) -> AutoStream<std::io::StdoutLock<'static>> {
         let inner = match self.inner {
             StreamInner::PassThrough(w) => StreamInner::PassThrough(w.lock()),
+            StreamInner::ResetOnDrop(w) => StreamInner::ResetOnDrop(Box::new(w.lock())),
+            StreamInner::Profile(w) => StreamInner::Profile(Box::new(w.lock())),
             StreamInner::Strip(w) => StreamInner::Strip(w.lock()),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(w) => StreamInner::Wincon(w.lock()),
+            StreamInner::Discard(w) => StreamInner::Discard(w.lock()),
         };
         AutoStream { inner }
     }
@@ -196,18 +544,114 @@ impl AutoStream<std::io::Stderr> {
     /// Why?
     /// - Faster performance when writing in a loop
     /// - Avoid other threads interleaving output with the current thread
+    ///
+    /// The locked stream is itself a [`RawStream`] so it can be passed on while holding the lock.
     #[inline]
     pub fn lock(self) -> AutoStream<std::io::StderrLock<'static>> {
         let inner = match self.inner {
             StreamInner::PassThrough(w) => StreamInner::PassThrough(w.lock()),
+            StreamInner::ResetOnDrop(w) => StreamInner::ResetOnDrop(Box::new(w.lock())),
+            StreamInner::Profile(w) => StreamInner::Profile(Box::new(w.lock())),
             StreamInner::Strip(w) => StreamInner::Strip(w.lock()),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(w) => StreamInner::Wincon(w.lock()),
+            StreamInner::Discard(w) => StreamInner::Discard(w.lock()),
         };
         AutoStream { inner }
     }
 }
 
+impl<S> AutoStream<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    /// Switch to the alternate screen, switching back when the returned guard is dropped
+    ///
+    /// The alternate screen is a separate screen without scrollback, for full-screen output like
+    /// dashboards.  Leaving it brings back what was on the terminal before.  When escape codes
+    /// aren't passed through, like when writing to a file, the screen isn't switched.
+    ///
+    /// The guard leaves the alternate screen while unwinding from a panic too but, by then, the
+    /// panic message was printed on the alternate screen and is lost.  Call
+    /// [`anstream::panic::install`][crate::panic::install] to leave it before the message.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # #[cfg(feature = "auto")] {
+    /// use std::io::Write as _;
+    ///
+    /// anstream::panic::install();
+    /// let mut stdout = anstream::stdout();
+    /// let mut screen = stdout.enter_alternate_screen().unwrap();
+    /// screen.hide_cursor().unwrap();
+    /// write!(screen, "\x1b[H\x1b[1mjobs:\x1b[0m 3").unwrap();
+    /// # }
+    /// ```
+    pub fn enter_alternate_screen(&mut self) -> std::io::Result<crate::AlternateScreen<'_, S>> {
+        let escapes = self.passes_escapes();
+        crate::AlternateScreen::enter(self, escapes)
+    }
+
+    /// Erase the current line, moving to its start to rewrite it
+    ///
+    /// For redrawing a spinner or progress bar in place.  Nothing is written when escape codes
+    /// aren't passed through, like when writing to a file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write as _;
+    ///
+    /// let mut stream = anstream::AutoStream::always_ansi(Vec::new());
+    /// write!(stream, "building 1/2").unwrap();
+    /// stream.erase_line().unwrap();
+    /// write!(stream, "building 2/2").unwrap();
+    /// assert_eq!(stream.into_inner(), b"building 1/2\r\x1b[Kbuilding 2/2");
+    /// ```
+    pub fn erase_line(&mut self) -> std::io::Result<()> {
+        self.erase_lines_up(0)
+    }
+
+    /// Erase the current line and the `count` lines above it, moving to the start of the top one
+    /// to rewrite them
+    ///
+    /// For redrawing multi-line progress in place.  Anything below the cursor is erased too.
+    /// Nothing is written when escape codes aren't passed through, like when writing to a file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write as _;
+    ///
+    /// let mut stream = anstream::AutoStream::always_ansi(Vec::new());
+    /// write!(stream, "crate a: done\ncrate b: 50%").unwrap();
+    /// stream.erase_lines_up(1).unwrap();
+    /// write!(stream, "crate a: done\ncrate b: 75%").unwrap();
+    /// ```
+    pub fn erase_lines_up(&mut self, count: usize) -> std::io::Result<()> {
+        use std::io::Write as _;
+
+        if !self.passes_escapes() {
+            return Ok(());
+        }
+        // Through the adapters, after anything they hold, so what they track matches the terminal
+        self.flush()?;
+        if count == 0 {
+            self.write_all(b"\r\x1b[K")
+        } else {
+            write!(self, "\r\x1b[{count}A\x1b[J")
+        }
+    }
+
+    fn passes_escapes(&self) -> bool {
+        matches!(
+            self.inner,
+            StreamInner::PassThrough(_) | StreamInner::ResetOnDrop(_) | StreamInner::Profile(_)
+        )
+    }
+}
+
 impl<S> std::io::Write for AutoStream<S>
 where
     S: RawStream + AsLockedWrite,
@@ -217,18 +661,31 @@ where
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
         match &mut self.inner {
             StreamInner::PassThrough(w) => w.as_locked_write().write(buf),
+            StreamInner::ResetOnDrop(w) => w.write(buf),
+            StreamInner::Profile(w) => w.write(buf),
             StreamInner::Strip(w) => w.write(buf),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(w) => w.write(buf),
+            StreamInner::Discard(_) => Ok(buf.len()),
         }
     }
     #[inline]
     fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
         match &mut self.inner {
             StreamInner::PassThrough(w) => w.as_locked_write().write_vectored(bufs),
+            StreamInner::ResetOnDrop(w) => w.write_vectored(bufs),
+            StreamInner::Profile(w) => {
+                let buf = bufs
+                    .iter()
+                    .find(|buf| !buf.is_empty())
+                    .map(|buf| &**buf)
+                    .unwrap_or(&[][..]);
+                w.write(buf)
+            }
             StreamInner::Strip(w) => w.write_vectored(bufs),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(w) => w.write_vectored(bufs),
+            StreamInner::Discard(_) => Ok(bufs.iter().map(|buf| buf.len()).sum()),
         }
     }
     // is_write_vectored: nightly only
@@ -236,18 +693,24 @@ where
     fn flush(&mut self) -> std::io::Result<()> {
         match &mut self.inner {
             StreamInner::PassThrough(w) => w.as_locked_write().flush(),
+            StreamInner::ResetOnDrop(w) => w.flush(),
+            StreamInner::Profile(w) => w.flush(),
             StreamInner::Strip(w) => w.flush(),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(w) => w.flush(),
+            StreamInner::Discard(_) => Ok(()),
         }
     }
     #[inline]
     fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
         match &mut self.inner {
             StreamInner::PassThrough(w) => w.as_locked_write().write_all(buf),
+            StreamInner::ResetOnDrop(w) => w.write_all(buf),
+            StreamInner::Profile(w) => w.write_all(buf),
             StreamInner::Strip(w) => w.write_all(buf),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(w) => w.write_all(buf),
+            StreamInner::Discard(_) => Ok(()),
         }
     }
     // write_all_vectored: nightly only
@@ -255,9 +718,97 @@ where
     fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
         match &mut self.inner {
             StreamInner::PassThrough(w) => w.as_locked_write().write_fmt(args),
+            StreamInner::ResetOnDrop(w) => w.write_fmt(args),
+            StreamInner::Profile(w) => w.write_fmt(args),
             StreamInner::Strip(w) => w.write_fmt(args),
             #[cfg(all(windows, feature = "wincon"))]
             StreamInner::Wincon(w) => w.write_fmt(args),
+            // Skip formatting as nothing would be written
+            StreamInner::Discard(_) => Ok(()),
         }
     }
 }
+
+#[cfg(test)]
+#[cfg(feature = "auto")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn cache() {
+        let cache = Cache::new();
+        let mut calls = 0;
+        assert_eq!(
+            cache.get_or_init(|| {
+                calls += 1;
+                1
+            }),
+            1
+        );
+        assert_eq!(cache.get_or_init(|| unreachable!()), 1);
+        cache.invalidate();
+        assert_eq!(
+            cache.get_or_init(|| {
+                calls += 1;
+                2
+            }),
+            2
+        );
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn term_profile() {
+        use anstyle_query::quirks::TermQuirks;
+        use std::io::Write as _;
+
+        let profile = super::term_profile(TermQuirks::from_term("linux"), false);
+        let mut stream = AutoStream::always_ansi(Vec::new()).with_profile(profile);
+        let style = anstyle::RgbColor(250, 20, 20)
+            .on_default()
+            .italic()
+            .dimmed();
+        let text = format!("{}error{}", style.render(), style.render_reset());
+        stream.write_all(text.as_bytes()).unwrap();
+        assert_eq!(stream.into_inner(), b"\x1b[2m\x1b[91merror\x1b[0m");
+
+        let profile = super::term_profile(TermQuirks::from_term("xterm-256color"), false);
+        let style = anstyle::RgbColor(255, 135, 0).on_default() | anstyle::Effects::CURLY_UNDERLINE;
+        assert_eq!(
+            profile.adapt(style),
+            anstyle::Ansi256Color(208).on_default().underline()
+        );
+
+        let profile = super::term_profile(TermQuirks::from_term("xterm-256color"), true);
+        assert_eq!(profile.get_colors(), anstyle::ColorLevel::Rgb);
+
+        let profile = super::term_profile(TermQuirks::from_term("dumb"), true);
+        assert_eq!(profile.get_colors(), anstyle::ColorLevel::None);
+
+        let profile = super::term_profile(TermQuirks::from_term("alacritty"), false);
+        assert_eq!(profile, anstyle::Profile::new());
+    }
+
+    #[test]
+    fn erase_lines_up() {
+        use std::io::Write as _;
+
+        let mut stream = AutoStream::always_ansi(Vec::new()).reset_on_drop();
+        stream.write_all(b"a\nb\nc").unwrap();
+        stream.erase_lines_up(2).unwrap();
+        stream.write_all(b"d").unwrap();
+        assert_eq!(stream.into_inner(), b"a\nb\nc\r\x1b[2A\x1b[Jd");
+
+        let mut stream = AutoStream::always_ansi(Vec::new()).with_profile(anstyle::Profile::new());
+        stream.write_all(b"\x1b[31ma").unwrap();
+        stream.erase_line().unwrap();
+        stream.write_all(b"b\x1b[0m").unwrap();
+        assert_eq!(stream.into_inner(), b"\x1b[31ma\r\x1b[Kb\x1b[0m");
+
+        let mut stream = AutoStream::never(Vec::new());
+        stream.write_all(b"a\nb").unwrap();
+        stream.erase_lines_up(1).unwrap();
+        stream.erase_line().unwrap();
+        assert_eq!(stream.into_inner(), b"a\nb");
+    }
+}
@@ -1,6 +1,10 @@
 #![allow(deprecated)]
 
 /// In-memory [`RawStream`][crate::stream::RawStream]
+///
+/// Use `Vec<u8>` instead, which is a `RawStream` too.  For reusing one across frames, reserve
+/// with [`Vec::with_capacity`] or [`Vec::reserve`], read it with [`Vec::as_slice`], and either
+/// [`Vec::clear`] it to keep its capacity or [`std::mem::take`] its content.
 #[derive(Clone, Default, Debug, PartialEq, Eq)]
 #[deprecated(since = "0.6.2", note = "Use Vec")]
 #[doc(hidden)]
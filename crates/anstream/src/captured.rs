@@ -0,0 +1,159 @@
+/// Stdout or stderr, written through [`std::print!`] or [`std::eprint!`] so `cargo test` captures
+/// it
+///
+/// The test harness only captures output written through the `print` family of macros, so
+/// output written to [`std::io::stdout`], like through [`anstream::stdout`][crate::stdout],
+/// shows up among the test results.  Wrap this in an [`AutoStream`][crate::AutoStream] instead
+/// for code that takes a stream and runs under test.
+///
+/// Like the macros, this panics if writing fails.  Invalid UTF-8 is replaced with `U+FFFD`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "auto")] {
+/// use std::io::Write as _;
+///
+/// let mut stdout = anstream::AutoStream::auto(anstream::CapturedStdio::stdout());
+/// writeln!(stdout, "\x1b[32mok\x1b[0m").unwrap();
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct CapturedStdio {
+    stderr: bool,
+    /// The start of a UTF-8 character split across writes
+    pending: Vec<u8>,
+}
+
+impl CapturedStdio {
+    /// Write with [`std::print!`]
+    #[inline]
+    pub fn stdout() -> Self {
+        Self {
+            stderr: false,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Write with [`std::eprint!`]
+    #[inline]
+    pub fn stderr() -> Self {
+        Self {
+            stderr: true,
+            pending: Vec::new(),
+        }
+    }
+
+    pub(crate) fn is_stderr(&self) -> bool {
+        self.stderr
+    }
+
+    fn print(&mut self, all: bool) {
+        let (text, consumed) = decode(&self.pending, all);
+        self.pending.drain(..consumed);
+        if text.is_empty() {
+            return;
+        }
+        if self.stderr {
+            ::std::eprint!("{text}");
+        } else {
+            ::std::print!("{text}");
+        }
+    }
+}
+
+impl std::io::Write for CapturedStdio {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        self.print(false);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.print(true);
+        if self.stderr {
+            std::io::stderr().flush()
+        } else {
+            std::io::stdout().flush()
+        }
+    }
+}
+
+impl Drop for CapturedStdio {
+    fn drop(&mut self) {
+        self.print(true);
+    }
+}
+
+#[cfg(all(windows, feature = "wincon"))]
+impl anstyle_wincon::WinconStream for CapturedStdio {
+    fn write_colored(
+        &mut self,
+        fg: Option<anstyle::AnsiColor>,
+        bg: Option<anstyle::AnsiColor>,
+        data: &[u8],
+    ) -> std::io::Result<usize> {
+        anstyle_wincon::ansi::write_colored(self, fg, bg, data)
+    }
+}
+
+#[cfg(all(windows, feature = "wincon"))]
+impl anstyle_wincon::WinconStream for &'_ mut CapturedStdio {
+    fn write_colored(
+        &mut self,
+        fg: Option<anstyle::AnsiColor>,
+        bg: Option<anstyle::AnsiColor>,
+        data: &[u8],
+    ) -> std::io::Result<usize> {
+        (**self).write_colored(fg, bg, data)
+    }
+}
+
+/// Decode `bytes` as UTF-8, returning the text and how many bytes it took
+///
+/// A character cut off at the end is left unless `all`.
+fn decode(mut bytes: &[u8], all: bool) -> (String, usize) {
+    let len = bytes.len();
+    let mut text = String::new();
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => {
+                text.push_str(valid);
+                return (text, len);
+            }
+            Err(error) => {
+                let (valid, invalid) = bytes.split_at(error.valid_up_to());
+                text.push_str(std::str::from_utf8(valid).expect("checked by `valid_up_to`"));
+                match error.error_len() {
+                    Some(error_len) => {
+                        text.push('\u{fffd}');
+                        bytes = &invalid[error_len..];
+                    }
+                    None if all => {
+                        text.push('\u{fffd}');
+                        return (text, len);
+                    }
+                    None => return (text, len - invalid.len()),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_character() {
+        let bytes = "aé".as_bytes();
+        assert_eq!(decode(&bytes[..2], false), ("a".to_owned(), 1));
+        assert_eq!(decode(&bytes[1..], false), ("é".to_owned(), 2));
+        assert_eq!(decode(&bytes[..2], true), ("a\u{fffd}".to_owned(), 2));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(decode(b"a\xffb\xc3", false), ("a\u{fffd}b".to_owned(), 3));
+    }
+}
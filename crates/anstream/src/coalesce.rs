@@ -0,0 +1,381 @@
+use anstyle::Style;
+
+/// Merge adjacent SGR escape codes, dropping those that don't change anything
+///
+/// Style changes are held until the text they apply to, then written as the smallest single SGR
+/// escape code that gets there, like `\x1b[1m\x1b[31m` becoming `\x1b[1;31m`.  Changes undone
+/// before any text, like a style immediately followed by a reset, aren't written at all.
+///
+/// Any pending change is written on [`flush`][std::io::Write::flush],
+/// [`CoalesceStream::finish`], or when dropped, except that `flush` holds a partial escape code
+/// until it is complete as the rest may make it a style change.  SGR parameters not
+/// representable by [`anstyle::Style`] are passed through as-is, as is every SGR escape code
+/// after them until a reset, since what the terminal shows is no longer known.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let bold = anstyle::Style::new().bold();
+/// let red = anstyle::AnsiColor::Red.on_default();
+/// let mut stream = anstream::CoalesceStream::new(Vec::new());
+/// write!(stream, "{}{}error{}", bold.render(), red.render(), red.render_reset()).unwrap();
+/// write!(stream, "{}{}: ", bold.render(), bold.render_reset()).unwrap();
+/// let buffer = stream.finish().unwrap();
+/// assert_eq!(buffer, b"\x1b[1;31merror\x1b[0m: ");
+/// ```
+#[derive(Debug)]
+pub struct CoalesceStream<S>
+where
+    S: std::io::Write,
+{
+    // Only `None` after `finish` or `into_inner`
+    raw: Option<S>,
+    parser: anstyle_parse::Parser,
+    capture: Capture,
+    /// Bytes of the sequence being parsed
+    pending: Vec<u8>,
+    /// The style last written
+    written: Style,
+}
+
+impl<S> CoalesceStream<S>
+where
+    S: std::io::Write,
+{
+    /// Start with no style active
+    #[inline]
+    pub fn new(raw: S) -> Self {
+        Self {
+            raw: Some(raw),
+            parser: Default::default(),
+            capture: Default::default(),
+            pending: Vec::new(),
+            written: Style::new(),
+        }
+    }
+
+    /// Write 256-color codes for the 16 ANSI colors, like `38;5;1`, as ANSI color codes, like `31`
+    ///
+    /// Terminals draw both from the same palette entry.
+    #[must_use]
+    #[inline]
+    pub fn ansi_colors(mut self, yes: bool) -> Self {
+        self.capture.ansi_colors = yes;
+        self
+    }
+
+    /// Write any pending style change and get the wrapped `Write`
+    pub fn finish(mut self) -> std::io::Result<S> {
+        self.write_pending()?;
+        Ok(self
+            .raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`"))
+    }
+
+    /// Get the wrapped `Write`, discarding any pending style change
+    #[inline]
+    pub fn into_inner(mut self) -> S {
+        self.raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a reference to the wrapped `Write`
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        self.raw
+            .as_ref()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a mutable reference to the wrapped `Write`
+    ///
+    /// Writing to it directly bypasses any pending style change.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        self.raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Write the style change and any partial escape code not yet written
+    fn write_pending(&mut self) -> std::io::Result<()> {
+        let mut output = Vec::new();
+        self.transition(&mut output);
+        output.append(&mut self.pending);
+        self.get_mut().write_all(&output)
+    }
+
+    /// Write the style change, keeping any partial escape code until it is complete
+    fn write_style(&mut self) -> std::io::Result<()> {
+        let mut output = Vec::new();
+        self.transition(&mut output);
+        self.get_mut().write_all(&output)
+    }
+
+    /// Move the written style to the current one
+    fn transition(&mut self, output: &mut Vec<u8>) {
+        let style = self.capture.style;
+        write_transition(output, self.written, style);
+        self.written = style;
+    }
+}
+
+impl<S> std::io::Write for CoalesceStream<S>
+where
+    S: std::io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut output = Vec::with_capacity(buf.len());
+        for byte in buf {
+            self.pending.push(*byte);
+            self.parser.advance(&mut self.capture, *byte);
+            match std::mem::take(&mut self.capture.event) {
+                Event::None => {}
+                Event::Style => {
+                    self.pending.clear();
+                }
+                Event::Other => {
+                    self.transition(&mut output);
+                    output.append(&mut self.pending);
+                }
+                Event::Unknown(before) => {
+                    // Only what came before is known to be written
+                    let style = self.capture.style;
+                    self.capture.style = before;
+                    self.transition(&mut output);
+                    output.append(&mut self.pending);
+                    self.capture.style = style;
+                    self.written = style;
+                }
+            }
+        }
+        self.get_mut().write_all(&output)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_style()?;
+        self.get_mut().flush()
+    }
+}
+
+impl<S> Drop for CoalesceStream<S>
+where
+    S: std::io::Write,
+{
+    fn drop(&mut self) {
+        if self.raw.is_some() {
+            // Ignore errors as there is no way to report them
+            let _ = self.write_pending();
+        }
+    }
+}
+
+/// Write the SGR escape codes for moving from `from` to `to` as a single escape code
+fn write_transition(output: &mut Vec<u8>, from: Style, to: Style) {
+    let codes = from.transition_to(to).to_string();
+    let mut params = codes
+        .split("\x1b[")
+        .filter_map(|code| code.strip_suffix('m'))
+        .filter(|params| !params.is_empty())
+        .peekable();
+    if params.peek().is_none() {
+        return;
+    }
+    output.extend_from_slice(b"\x1b[");
+    for (i, param) in params.enumerate() {
+        if i != 0 {
+            output.push(b';');
+        }
+        output.extend_from_slice(param.as_bytes());
+    }
+    output.push(b'm');
+}
+
+#[derive(Default, Debug)]
+struct Capture {
+    style: Style,
+    event: Event,
+    ansi_colors: bool,
+    /// Whether an SGR parameter `style` can't describe was written since the last reset
+    unknown: bool,
+}
+
+#[derive(Default, Debug)]
+enum Event {
+    #[default]
+    None,
+    /// The style changed to what is fully described by `Capture::style`
+    Style,
+    /// The style changed, from the one given, in ways `Capture::style` can't describe or while
+    /// `Capture::unknown`, so the escape code must be written as-is
+    Unknown(Style),
+    Other,
+}
+
+fn ansi_color(color: anstyle::Color) -> anstyle::Color {
+    match color {
+        anstyle::Color::Ansi256(color) => color.into_ansi().map(Into::into).unwrap_or(color.into()),
+        color => color,
+    }
+}
+
+impl anstyle_parse::Perform for Capture {
+    fn print(&mut self, _c: char) {
+        self.event = Event::Other;
+    }
+
+    fn execute(&mut self, _byte: u8) {
+        self.event = Event::Other;
+    }
+
+    fn unhook(&mut self) {
+        self.event = Event::Other;
+    }
+
+    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {
+        self.event = Event::Other;
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        self.event = if action == b'm' && intermediates.is_empty() && !ignore {
+            let before = self.style;
+            let (mut style, known) =
+                anstyle_parse::apply_sgr(before, params, anstyle::Sgr21::default());
+            if self.ansi_colors {
+                style = style
+                    .fg_color(style.get_fg_color().map(ansi_color))
+                    .bg_color(style.get_bg_color().map(ansi_color))
+                    .underline_color(style.get_underline_color().map(ansi_color));
+            }
+            self.style = style;
+            let passthrough = self.unknown || !known;
+            let reset = anstyle_parse::is_sgr_reset(params);
+            self.unknown = !known || (self.unknown && !reset);
+            if passthrough {
+                Event::Unknown(before)
+            } else {
+                Event::Style
+            }
+        } else {
+            Event::Other
+        };
+    }
+
+    fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, _byte: u8) {
+        self.event = Event::Other;
+    }
+
+    fn sos_pm_apc_end(&mut self) {
+        self.event = Event::Other;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    fn coalesce(chunks: &[&str]) -> String {
+        let mut stream = CoalesceStream::new(Vec::new());
+        for chunk in chunks {
+            stream.write_all(chunk.as_bytes()).unwrap();
+        }
+        String::from_utf8(stream.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn merges_across_writes() {
+        assert_eq!(
+            coalesce(&["\x1b[1m", "\x1b[4", "m\x1b[38;5;208m", "a\x1b[0m"]),
+            "\x1b[1;4;38;5;208ma\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn drops_no_ops() {
+        assert_eq!(
+            coalesce(&["\x1b[31ma\x1b[31mb\x1b[1m\x1b[22mc\x1b[0m\x1b[0m"]),
+            "\x1b[31mabc\x1b[0m"
+        );
+        assert_eq!(coalesce(&["\x1b[1m\x1b[0m", "a"]), "a");
+    }
+
+    #[test]
+    fn minimal_change() {
+        assert_eq!(
+            coalesce(&["\x1b[1;31ma\x1b[0m\x1b[1m\x1b[32mb\x1b[0m"]),
+            "\x1b[1;31ma\x1b[32mb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn before_other_sequences() {
+        // Erasing uses the background color
+        assert_eq!(
+            coalesce(&["\x1b[41m\x1b[K\x1b[0m\n"]),
+            "\x1b[41m\x1b[K\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn unknown_passed_through() {
+        assert_eq!(
+            coalesce(&["\x1b[1m\x1b[53ma\x1b[0m"]),
+            "\x1b[1m\x1b[53ma\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn reset_after_unknown() {
+        assert_eq!(coalesce(&["\x1b[53ma\x1b[0mb"]), "\x1b[53ma\x1b[0mb");
+        assert_eq!(
+            coalesce(&["\x1b[53ma\x1b[1m\x1b[22mb\x1b[0m\x1b[0mc"]),
+            "\x1b[53ma\x1b[1m\x1b[22mb\x1b[0mc"
+        );
+    }
+
+    #[test]
+    fn color_zero_after_unknown() {
+        assert_eq!(
+            coalesce(&["\x1b[53ma\x1b[38;5;0mb\x1b[1m\x1b[22mc\x1b[0m"]),
+            "\x1b[53ma\x1b[38;5;0mb\x1b[1m\x1b[22mc\x1b[0m"
+        );
+        assert_eq!(
+            coalesce(&["\x1b[53ma\x1b[48;2;0;0;0mb\x1b[1m\x1b[22mc\x1b[0m"]),
+            "\x1b[53ma\x1b[48;2;0;0;0mb\x1b[1m\x1b[22mc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn partial_escape_code_across_flush() {
+        let mut stream = CoalesceStream::new(Vec::new());
+        stream.write_all(b"\x1b[3").unwrap();
+        stream.flush().unwrap();
+        assert_eq!(stream.get_ref().as_slice(), b"");
+        stream.write_all(b"1m").unwrap();
+        stream.write_all(b"text\x1b[0m").unwrap();
+        assert_eq!(stream.finish().unwrap(), b"\x1b[31mtext\x1b[0m");
+    }
+
+    #[test]
+    fn pending_on_drop() {
+        let mut buffer = Vec::new();
+        {
+            let mut stream = CoalesceStream::new(&mut buffer);
+            write!(stream, "\x1b[31ma\x1b[0m").unwrap();
+            assert_eq!(stream.get_ref().as_slice(), b"\x1b[31ma");
+        }
+        assert_eq!(buffer, b"\x1b[31ma\x1b[0m");
+    }
+}
@@ -0,0 +1,126 @@
+//! Check styled output against the reference parser, like for testing converters
+//!
+//! Output is compared by how it looks rather than byte-for-byte: both sides are parsed and
+//! re-rendered in one canonical encoding (see [`adapter::normalize_bytes`][crate::adapter::normalize_bytes]),
+//! and style changes after the last text are ignored.
+//!
+//! # Example
+//!
+//! ```rust
+//! // A pipeline that re-renders styles with its own encoding
+//! fn convert(input: &[u8]) -> Vec<u8> {
+//!     input
+//!         .iter()
+//!         .flat_map(|b| if *b == b';' { b"m\x1b[".to_vec() } else { vec![*b] })
+//!         .collect()
+//! }
+//!
+//! anstream::conformance::assert_round_trip(b"\x1b[1;31merror\x1b[0m: oops", convert);
+//! ```
+
+/// Check that `actual` looks the same as `expected`
+pub fn check_equivalent(expected: &[u8], actual: &[u8]) -> Result<(), Mismatch> {
+    let expected = visible(expected);
+    let actual = visible(actual);
+    match expected.iter().zip(&actual).position(|(e, a)| e != a) {
+        None if expected.len() == actual.len() => Ok(()),
+        offset => Err(Mismatch {
+            offset: offset.unwrap_or(expected.len().min(actual.len())),
+            expected,
+            actual,
+        }),
+    }
+}
+
+/// Assert that `actual` looks the same as `expected`
+///
+/// # Panics
+///
+/// With a description of the first difference
+#[track_caller]
+pub fn assert_equivalent(expected: &[u8], actual: &[u8]) {
+    if let Err(mismatch) = check_equivalent(expected, actual) {
+        panic!("{mismatch}");
+    }
+}
+
+/// Assert that `input` looks the same after going through `pipeline`
+///
+/// # Panics
+///
+/// With a description of the first difference
+#[track_caller]
+pub fn assert_round_trip(input: &[u8], pipeline: impl FnOnce(&[u8]) -> Vec<u8>) {
+    let output = pipeline(input);
+    assert_equivalent(input, &output);
+}
+
+/// How styled output differs, from [`check_equivalent`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Where the normalized outputs first differ
+    pub offset: usize,
+    /// The normalized expected output
+    pub expected: Vec<u8>,
+    /// The normalized actual output
+    pub actual: Vec<u8>,
+}
+
+impl std::fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "styled output differs at byte {} after normalizing",
+            self.offset
+        )?;
+        writeln!(f, "expected: {:?}", String::from_utf8_lossy(&self.expected))?;
+        write!(f, "  actual: {:?}", String::from_utf8_lossy(&self.actual))
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
+/// Normalize `data`, dropping style changes that nothing is written with
+fn visible(data: &[u8]) -> Vec<u8> {
+    use std::io::Write as _;
+
+    let mut stream = crate::CoalesceStream::new(Vec::with_capacity(data.len())).ansi_colors(true);
+    stream
+        .write_all(data)
+        .expect("writing to a `Vec` can't fail");
+    stream.into_inner()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn trailing_styles_ignored() {
+        assert_eq!(check_equivalent(b"\x1b[31ma\x1b[0m", b"\x1b[31ma"), Ok(()));
+        assert_eq!(check_equivalent(b"a", b"a\x1b[1m\x1b[4m"), Ok(()));
+    }
+
+    #[test]
+    fn mismatch() {
+        let mismatch = check_equivalent(b"\x1b[31mab", b"\x1b[31ma\x1b[32mb").unwrap_err();
+        assert_eq!(mismatch.offset, 6);
+        assert_eq!(
+            mismatch.to_string(),
+            "styled output differs at byte 6 after normalizing
+expected: \"\\u{1b}[31mab\"
+  actual: \"\\u{1b}[31ma\\u{1b}[32mb\""
+        );
+
+        let mismatch = check_equivalent(b"ab", b"a").unwrap_err();
+        assert_eq!(mismatch.offset, 1);
+    }
+
+    #[test]
+    #[should_panic = "styled output differs at byte 0"]
+    fn round_trip_dropping_styles() {
+        assert_round_trip(b"\x1b[1ma", |input| {
+            crate::adapter::strip_bytes(input).into_vec()
+        });
+    }
+}
@@ -0,0 +1,182 @@
+/// Escape codes for saving and restoring the cursor position
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub enum CursorSave {
+    /// `ESC 7` and `ESC 8` (DECSC / DECRC), from the VT100
+    ///
+    /// These also save the style.
+    #[default]
+    Dec,
+    /// `CSI s` and `CSI u`, from ANSI.SYS
+    ///
+    /// In xterm, `CSI s` means something else while left and right margins are enabled.
+    Csi,
+}
+
+impl CursorSave {
+    /// Select the codes supported by the terminal described by `TERM`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anstream::CursorSave;
+    ///
+    /// assert_eq!(CursorSave::from_term("xterm-256color"), CursorSave::Dec);
+    /// assert_eq!(CursorSave::from_term("ansi"), CursorSave::Csi);
+    /// ```
+    pub fn from_term(term: &str) -> Self {
+        // Described after ANSI.SYS, which only supports `CSI s`
+        if matches!(term, "ansi" | "ansi.sys" | "pcansi") {
+            Self::Csi
+        } else {
+            Self::Dec
+        }
+    }
+
+    /// Select the codes supported by the current terminal, from the `TERM` environment variable
+    #[cfg(feature = "auto")]
+    pub fn detect() -> Self {
+        std::env::var("TERM")
+            .map(|term| Self::from_term(&term))
+            .unwrap_or_default()
+    }
+
+    /// The escape code to save the cursor position
+    #[inline]
+    pub const fn save_code(self) -> &'static str {
+        match self {
+            Self::Dec => "\x1b7",
+            Self::Csi => "\x1b[s",
+        }
+    }
+
+    /// The escape code to restore the cursor position
+    #[inline]
+    pub const fn restore_code(self) -> &'static str {
+        match self {
+            Self::Dec => "\x1b8",
+            Self::Csi => "\x1b[u",
+        }
+    }
+}
+
+/// Save the cursor position, restoring it when dropped
+///
+/// This is for updating text in place, like a status line, and writing through the guard.  The
+/// position is restored while unwinding from a panic too.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let mut buffer = Vec::new();
+/// {
+///     let mut guard = anstream::CursorGuard::new(&mut buffer).unwrap();
+///     write!(guard, "\x1b[1;1H50%").unwrap();
+/// }
+/// assert_eq!(buffer, b"\x1b7\x1b[1;1H50%\x1b8");
+/// ```
+#[derive(Debug)]
+pub struct CursorGuard<S>
+where
+    S: std::io::Write,
+{
+    // Only `None` after `restore`
+    raw: Option<S>,
+    save: CursorSave,
+}
+
+impl<S> CursorGuard<S>
+where
+    S: std::io::Write,
+{
+    /// Save the cursor position with [`CursorSave::Dec`]
+    #[inline]
+    pub fn new(raw: S) -> std::io::Result<Self> {
+        Self::with_save(raw, CursorSave::Dec)
+    }
+
+    /// Save the cursor position with the codes from `save`
+    pub fn with_save(mut raw: S, save: CursorSave) -> std::io::Result<Self> {
+        raw.write_all(save.save_code().as_bytes())?;
+        Ok(Self {
+            raw: Some(raw),
+            save,
+        })
+    }
+
+    /// Restore the cursor position, reporting any error
+    pub fn restore(mut self) -> std::io::Result<S> {
+        let mut raw = self.raw.take().expect("only taken on `restore`");
+        raw.write_all(self.save.restore_code().as_bytes())?;
+        raw.flush()?;
+        Ok(raw)
+    }
+
+    /// Get a reference to the wrapped [`std::io::Write`]
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        self.raw.as_ref().expect("only taken on `restore`")
+    }
+
+    /// Get a mutable reference to the wrapped [`std::io::Write`]
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        self.raw.as_mut().expect("only taken on `restore`")
+    }
+}
+
+impl<S> std::io::Write for CursorGuard<S>
+where
+    S: std::io::Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.get_mut().write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.get_mut().flush()
+    }
+}
+
+impl<S> Drop for CursorGuard<S>
+where
+    S: std::io::Write,
+{
+    fn drop(&mut self) {
+        if let Some(raw) = self.raw.as_mut() {
+            // Ignore errors as there is no way to report them
+            let _ = raw.write_all(self.save.restore_code().as_bytes());
+            let _ = raw.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn restore() {
+        let mut guard = CursorGuard::with_save(Vec::new(), CursorSave::Csi).unwrap();
+        guard.write_all(b"x").unwrap();
+        assert_eq!(guard.restore().unwrap(), b"\x1b[sx\x1b[u");
+    }
+
+    #[test]
+    fn restored_on_panic() {
+        let buffer = std::sync::Mutex::new(Vec::new());
+        let result = std::panic::catch_unwind(|| {
+            let mut buffer = buffer.lock().unwrap();
+            let mut guard = CursorGuard::new(&mut *buffer).unwrap();
+            guard.write_all(b"x").unwrap();
+            panic!("oops");
+        });
+        assert!(result.is_err());
+        let buffer = buffer.lock().unwrap_or_else(|e| e.into_inner());
+        assert_eq!(*buffer, b"\x1b7x\x1b8");
+    }
+}
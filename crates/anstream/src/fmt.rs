@@ -52,3 +52,137 @@ where
         }
     }
 }
+
+/// Like [`std::io::Write::write_all`] but reporting how much of `buf` was written on error
+///
+/// For buffering writers to drop only what was written, keeping the rest to retry.
+pub(crate) fn write_prefix<W>(writer: &mut W, buf: &[u8]) -> (usize, std::io::Result<()>)
+where
+    W: std::io::Write + ?Sized,
+{
+    let mut written = 0;
+    while written < buf.len() {
+        match writer.write(&buf[written..]) {
+            Ok(0) => {
+                let err = std::io::Error::new(
+                    std::io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                );
+                return (written, Err(err));
+            }
+            Ok(n) => written += n,
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return (written, Err(e)),
+        }
+    }
+    (written, Ok(()))
+}
+
+/// A [`std::fmt::Write`] that writes to a [`std::io::Write`], like an
+/// [`AutoStream`][crate::AutoStream] or a `Vec<u8>`
+///
+/// For APIs writing to a `fmt::Write` to write styled text straight to a stream, without first
+/// formatting into a `String`.  As [`std::fmt::Error`] carries no details, the I/O error is kept
+/// for [`FmtWriter::finish`].
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write as _;
+///
+/// let style = anstyle::AnsiColor::Red.on_default();
+/// let mut writer = anstream::FmtWriter::new(anstream::AutoStream::never(Vec::new()));
+/// write!(writer, "{}error{}: oops", style.render(), style.render_reset()).unwrap();
+/// let buffer = writer.finish().unwrap().into_inner();
+/// assert_eq!(buffer, b"error: oops");
+/// ```
+#[derive(Debug)]
+pub struct FmtWriter<W>
+where
+    W: std::io::Write,
+{
+    writer: W,
+    error: Option<std::io::Error>,
+}
+
+impl<W> FmtWriter<W>
+where
+    W: std::io::Write,
+{
+    #[inline]
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Get the wrapped `Write`, reporting the first I/O error
+    #[inline]
+    pub fn finish(self) -> std::io::Result<W> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.writer),
+        }
+    }
+
+    /// Get the wrapped `Write`, discarding any I/O error
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W> std::fmt::Write for FmtWriter<W>
+where
+    W: std::io::Write,
+{
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        if self.error.is_some() {
+            return Err(std::fmt::Error);
+        }
+        match self.writer.write_all(s.as_bytes()) {
+            Ok(()) => Ok(()),
+            Err(err) => {
+                self.error = Some(err);
+                Err(std::fmt::Error)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fmt::Write as _;
+
+    #[derive(Debug)]
+    struct Failing;
+
+    impl std::io::Write for Failing {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn fmt_writer() {
+        let mut writer = FmtWriter::new(Vec::new());
+        let name = "world";
+        write!(writer, "Hello {name}!").unwrap();
+        assert_eq!(writer.finish().unwrap(), b"Hello world!");
+    }
+
+    #[test]
+    fn fmt_writer_error() {
+        let mut writer = FmtWriter::new(Failing);
+        assert!(write!(writer, "Hello").is_err());
+        assert!(write!(writer, "world").is_err());
+        let err = writer.finish().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::BrokenPipe);
+    }
+}
@@ -0,0 +1,305 @@
+use anstyle::Style;
+
+/// A tree of styled text, like from rendering markup
+///
+/// Styles nest like with [`StyleStack`][crate::StyleStack]: a child's effects are added to its
+/// parent's and any colors it sets replace its parent's.  Rendering flattens the tree, only
+/// writing the escape codes needed to move from one run of text to the next.
+///
+/// # Examples
+///
+/// ```
+/// use anstream::Fragment;
+///
+/// let red = anstyle::AnsiColor::Red.on_default();
+/// let bold = anstyle::Style::new().bold();
+/// let tree = Fragment::styled(
+///     red,
+///     [
+///         Fragment::text("error: "),
+///         Fragment::styled(bold, [Fragment::text("file not found")]),
+///     ],
+/// );
+/// assert_eq!(
+///     tree.render().to_string(),
+///     "\x1b[31merror: \x1b[1mfile not found\x1b[0m"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Fragment {
+    /// Unstyled text, inheriting the style of its parent
+    Text(String),
+    /// Fragments with a style layered on top of their parent's
+    Styled(Style, Vec<Fragment>),
+}
+
+impl Fragment {
+    /// Create a [`Fragment::Text`]
+    #[inline]
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    /// Create a [`Fragment::Styled`]
+    #[inline]
+    pub fn styled(style: Style, children: impl IntoIterator<Item = Fragment>) -> Self {
+        Self::Styled(style, children.into_iter().collect())
+    }
+
+    /// Render the text with the minimal escape codes, ending with no style active
+    #[inline]
+    pub fn render(&self) -> impl std::fmt::Display + '_ {
+        FragmentDisplay {
+            fragment: self,
+            per_line: false,
+        }
+    }
+
+    /// Render like [`Fragment::render`], resetting the style before each newline and re-applying
+    /// it after
+    ///
+    /// `less -R`, `more`, and some CI log viewers reset the style at the end of each line, losing
+    /// it for the rest of a multi-line run of text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use anstream::Fragment;
+    ///
+    /// let red = anstyle::AnsiColor::Red.on_default();
+    /// let tree = Fragment::styled(red, [Fragment::text("one\ntwo")]);
+    /// assert_eq!(
+    ///     tree.render_per_line().to_string(),
+    ///     "\x1b[31mone\x1b[0m\n\x1b[31mtwo\x1b[0m"
+    /// );
+    /// ```
+    #[inline]
+    pub fn render_per_line(&self) -> impl std::fmt::Display + '_ {
+        FragmentDisplay {
+            fragment: self,
+            per_line: true,
+        }
+    }
+
+    /// Write the text with the minimal escape codes, ending with no style active
+    pub fn write_to(&self, write: &mut dyn std::io::Write) -> std::io::Result<()> {
+        write!(write, "{}", self.render())
+    }
+
+    fn fmt_runs(
+        &self,
+        style: Style,
+        active: &mut Style,
+        per_line: bool,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Self::Text(text) => {
+                let mut lines = text.split_inclusive('\n');
+                let mut next = lines.next();
+                while let Some(line) = next {
+                    next = lines.next();
+                    let (line, newline) = match line.strip_suffix('\n') {
+                        Some(line) if per_line => (line, "\n"),
+                        _ => (line, ""),
+                    };
+                    if !line.is_empty() {
+                        write!(f, "{}", active.transition_to(style))?;
+                        *active = style;
+                        f.write_str(line)?;
+                    }
+                    if !newline.is_empty() {
+                        write!(f, "{}", active.transition_to(Style::new()))?;
+                        *active = Style::new();
+                        f.write_str(newline)?;
+                    }
+                }
+            }
+            Self::Styled(layer, children) => {
+                let style = crate::stack::layer(style, *layer);
+                for child in children {
+                    child.fmt_runs(style, active, per_line, f)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<String> for Fragment {
+    #[inline]
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+impl From<&'_ str> for Fragment {
+    #[inline]
+    fn from(text: &'_ str) -> Self {
+        Self::text(text)
+    }
+}
+
+struct FragmentDisplay<'f> {
+    fragment: &'f Fragment,
+    per_line: bool,
+}
+
+impl std::fmt::Display for FragmentDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut active = Style::new();
+        self.fragment
+            .fmt_runs(Style::new(), &mut active, self.per_line, f)?;
+        write!(f, "{}", active.transition_to(Style::new()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anstyle::Effects;
+
+    #[test]
+    fn adjacent_identical_styles() {
+        let red = anstyle::AnsiColor::Red.on_default();
+        let tree = Fragment::styled(
+            Style::new(),
+            [
+                Fragment::styled(red, ["a".into()]),
+                Fragment::styled(red, ["b".into(), Fragment::styled(red, ["c".into()])]),
+            ],
+        );
+        assert_eq!(tree.render().to_string(), "\x1b[31mabc\x1b[0m");
+    }
+
+    #[test]
+    fn change_only_color() {
+        let red = anstyle::AnsiColor::Red.on_default().bold();
+        let blue = anstyle::AnsiColor::Blue.on_default().bold();
+        let tree = Fragment::styled(
+            Style::new(),
+            [
+                Fragment::styled(red, ["a".into()]),
+                Fragment::styled(blue, ["b".into()]),
+            ],
+        );
+        assert_eq!(
+            tree.render().to_string(),
+            "\x1b[1m\x1b[31ma\x1b[34mb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn drop_effect() {
+        let red = anstyle::AnsiColor::Red.on_default();
+        let tree = Fragment::styled(
+            red,
+            [
+                Fragment::styled(Style::new().bold(), ["a".into()]),
+                "b".into(),
+            ],
+        );
+        assert_eq!(
+            tree.render().to_string(),
+            "\x1b[1m\x1b[31ma\x1b[22mb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn keep_dimmed() {
+        let tree = Fragment::styled(
+            Style::new().dimmed(),
+            [
+                Fragment::styled(Style::new().bold(), ["a".into()]),
+                "b".into(),
+            ],
+        );
+        assert_eq!(
+            tree.render().to_string(),
+            "\x1b[1m\x1b[2ma\x1b[0m\x1b[2mb\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn skip_empty_text() {
+        let tree = Fragment::styled(
+            Style::new(),
+            [
+                Fragment::styled(Style::new().bold(), ["".into()]),
+                "plain".into(),
+            ],
+        );
+        assert_eq!(tree.render().to_string(), "plain");
+    }
+
+    #[test]
+    fn per_line() {
+        let red = anstyle::AnsiColor::Red.on_default();
+        let tree = Fragment::styled(
+            red,
+            [
+                "a\n".into(),
+                Fragment::styled(Style::new().bold(), ["b\n\nc".into()]),
+                "\n".into(),
+            ],
+        );
+        assert_eq!(
+            tree.render_per_line().to_string(),
+            "\x1b[31ma\x1b[0m\n\x1b[1m\x1b[31mb\x1b[0m\n\n\x1b[1m\x1b[31mc\x1b[0m\n"
+        );
+        assert_eq!(
+            tree.render().to_string(),
+            "\x1b[31ma\n\x1b[1mb\n\nc\x1b[22m\n\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn styles_match_stack() {
+        let dimmed = Style::new().dimmed();
+        let red = anstyle::AnsiColor::Red.on_default();
+        let bold = Style::new().bold().underline();
+        let tree = Fragment::styled(
+            dimmed,
+            [
+                "1".into(),
+                Fragment::styled(red, ["2".into(), Fragment::styled(bold, ["3".into()])]),
+                "4".into(),
+            ],
+        );
+        let mut stripped = String::new();
+        let mut styles = Vec::new();
+        let mut parser = anstyle_parse::Parser::<anstyle_parse::DefaultCharAccumulator>::new();
+        struct Capture<'c>(Style, &'c mut String, &'c mut Vec<Style>);
+        impl anstyle_parse::Perform for Capture<'_> {
+            fn print(&mut self, c: char) {
+                self.1.push(c);
+                self.2.push(self.0);
+            }
+            fn csi_dispatch(
+                &mut self,
+                params: &anstyle_parse::Params,
+                _intermediates: &[u8],
+                _ignore: bool,
+                _action: u8,
+            ) {
+                self.0 = anstyle_parse::apply_sgr(self.0, params, anstyle::Sgr21::default()).0;
+            }
+        }
+        let mut capture = Capture(Style::new(), &mut stripped, &mut styles);
+        for byte in tree.render().to_string().as_bytes() {
+            parser.advance(&mut capture, *byte);
+        }
+        let end = capture.0;
+        assert_eq!(stripped, "1234");
+        assert_eq!(
+            styles,
+            [
+                dimmed,
+                red | Effects::DIMMED,
+                red | Effects::BOLD | Effects::UNDERLINE | Effects::DIMMED,
+                dimmed
+            ]
+        );
+        assert_eq!(end, Style::new());
+    }
+}
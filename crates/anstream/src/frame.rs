@@ -0,0 +1,272 @@
+use std::fmt::Write as _;
+
+use anstyle::Style;
+
+/// Redraw a full-screen frame of styled text, writing only what changed
+///
+/// Each call to [`FrameWriter::render`] takes the whole frame, like a dashboard or status screen
+/// rendered every tick.  It is compared against the previous frame and only the changed parts of
+/// lines are written, using cursor positioning.  Frames are drawn from the top-left corner of the
+/// screen.
+///
+/// Only SGR escape codes are kept from frames; other escape codes are dropped.  Characters
+/// are assumed to take one column except where a line has anything other than printable ASCII,
+/// in which case the line is redrawn from its start.
+///
+/// # Examples
+///
+/// ```
+/// let mut frames = anstream::FrameWriter::new(Vec::new());
+/// frames.render("jobs: 1\n\x1b[32mok\x1b[0m\n").unwrap();
+/// frames.get_mut().clear();
+///
+/// frames.render("jobs: 2\n\x1b[32mok\x1b[0m\n").unwrap();
+/// assert_eq!(frames.get_ref().as_slice(), b"\x1b[1;7H2");
+/// ```
+#[derive(Debug)]
+pub struct FrameWriter<S>
+where
+    S: std::io::Write,
+{
+    raw: S,
+    /// What is on screen, `None` when unknown
+    previous: Option<Vec<Vec<Cell>>>,
+}
+
+impl<S> FrameWriter<S>
+where
+    S: std::io::Write,
+{
+    /// Draw onto `raw`, clearing the screen on the first frame
+    #[inline]
+    pub fn new(raw: S) -> Self {
+        Self {
+            raw,
+            previous: None,
+        }
+    }
+
+    /// Draw `frame`, with lines separated by `\n`, and flush
+    pub fn render(&mut self, frame: &str) -> std::io::Result<()> {
+        let lines = parse(frame);
+
+        let mut output = String::new();
+        let previous = match self.previous.take() {
+            Some(previous) => previous,
+            None => {
+                output.push_str("\x1b[0m\x1b[H\x1b[2J");
+                Vec::new()
+            }
+        };
+        for (row, line) in lines.iter().enumerate() {
+            let old = previous.get(row).map(Vec::as_slice).unwrap_or_default();
+            write_line_diff(&mut output, row + 1, old, line);
+        }
+        if lines.len() < previous.len() {
+            let _ = write!(&mut output, "\x1b[{};1H\x1b[J", lines.len() + 1);
+        }
+
+        self.previous = Some(lines);
+        self.raw.write_all(output.as_bytes())?;
+        self.raw.flush()
+    }
+
+    /// Redraw all of the next frame, like after the screen was resized or written to by
+    /// something else
+    #[inline]
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// Get the wrapped [`std::io::Write`]
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.raw
+    }
+
+    /// Get a reference to the wrapped [`std::io::Write`]
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.raw
+    }
+
+    /// Get a mutable reference to the wrapped [`std::io::Write`]
+    ///
+    /// Writing to it directly puts the screen out of sync; see [`FrameWriter::invalidate`].
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.raw
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Cell {
+    c: char,
+    style: Style,
+}
+
+impl Cell {
+    /// Whether the cell is known to take one column
+    fn is_narrow(&self) -> bool {
+        self.c.is_ascii_graphic() || self.c == ' '
+    }
+}
+
+/// Write the escape codes and text to change row `row` from `old` to `new`
+fn write_line_diff(output: &mut String, row: usize, old: &[Cell], new: &[Cell]) {
+    let prefix = old.iter().zip(new).take_while(|(o, n)| o == n).count();
+    if prefix == old.len() && prefix == new.len() {
+        return;
+    }
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(o, n)| o == n)
+        .count();
+
+    let narrow = |cells: &[Cell]| cells.iter().all(Cell::is_narrow);
+    let (start, end, to_end) = if !narrow(&new[..prefix]) {
+        (0, new.len(), true)
+    } else if old.len() == new.len() && narrow(&old[prefix..]) && narrow(&new[prefix..]) {
+        // Only the middle changed and nothing after it moves
+        (prefix, new.len() - suffix, false)
+    } else {
+        (prefix, new.len(), true)
+    };
+
+    let _ = write!(output, "\x1b[{};{}H", row, start + 1);
+    let mut active = Style::new();
+    for cell in &new[start..end] {
+        let _ = write!(output, "{}", active.transition_to(cell.style));
+        active = cell.style;
+        output.push(cell.c);
+    }
+    let _ = write!(output, "{}", active.transition_to(Style::new()));
+    if to_end {
+        output.push_str("\x1b[K");
+    }
+}
+
+/// Split `frame` into lines of styled characters
+fn parse(frame: &str) -> Vec<Vec<Cell>> {
+    let mut parser: anstyle_parse::Parser = Default::default();
+    let mut capture = Capture::default();
+    for byte in frame.as_bytes() {
+        parser.advance(&mut capture, *byte);
+    }
+    if capture.line.is_empty() {
+        capture.lines
+    } else {
+        let line = std::mem::take(&mut capture.line);
+        capture.lines.push(line);
+        capture.lines
+    }
+}
+
+#[derive(Default)]
+struct Capture {
+    style: Style,
+    line: Vec<Cell>,
+    lines: Vec<Vec<Cell>>,
+}
+
+impl anstyle_parse::Perform for Capture {
+    fn print(&mut self, c: char) {
+        self.line.push(Cell {
+            c,
+            style: self.style,
+        });
+    }
+
+    fn execute(&mut self, byte: u8) {
+        if byte == b'\n' {
+            let line = std::mem::take(&mut self.line);
+            self.lines.push(line);
+        }
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        if action == b'm' && intermediates.is_empty() && !ignore {
+            self.style = anstyle_parse::apply_sgr(self.style, params, anstyle::Sgr21::default()).0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn frames(frames: &[&str]) -> Vec<String> {
+        let mut writer = FrameWriter::new(Vec::new());
+        frames
+            .iter()
+            .map(|frame| {
+                writer.render(frame).unwrap();
+                let output = std::mem::take(writer.get_mut());
+                String::from_utf8(output).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn first_frame_clears() {
+        assert_eq!(
+            frames(&["a\n\x1b[1mb\x1b[0m"]),
+            ["\x1b[0m\x1b[H\x1b[2J\x1b[1;1Ha\x1b[K\x1b[2;1H\x1b[1mb\x1b[0m\x1b[K"]
+        );
+    }
+
+    #[test]
+    fn unchanged() {
+        assert_eq!(frames(&["a\nb\n", "a\nb\n"])[1], "");
+    }
+
+    #[test]
+    fn middle_changed() {
+        assert_eq!(
+            frames(&[
+                "cpu: 10%\nmem: \x1b[31m90%\x1b[0m",
+                "cpu: 12%\nmem: \x1b[33m90%\x1b[0m"
+            ])[1],
+            "\x1b[1;7H2\x1b[2;6H\x1b[33m90%\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn line_length_changed() {
+        assert_eq!(
+            frames(&["status: running", "status: ok"])[1],
+            "\x1b[1;9Hok\x1b[K"
+        );
+    }
+
+    #[test]
+    fn wide_characters() {
+        assert_eq!(frames(&["日本: 1", "日本: 2"])[1], "\x1b[1;1H日本: 2\x1b[K");
+    }
+
+    #[test]
+    fn fewer_lines() {
+        assert_eq!(frames(&["a\nb\nc", "a"])[1], "\x1b[2;1H\x1b[J");
+    }
+
+    #[test]
+    fn invalidate() {
+        let mut writer = FrameWriter::new(Vec::new());
+        writer.render("a").unwrap();
+        writer.invalidate();
+        writer.get_mut().clear();
+        writer.render("a").unwrap();
+        assert_eq!(
+            writer.get_ref().as_slice(),
+            b"\x1b[0m\x1b[H\x1b[2J\x1b[1;1Ha\x1b[K"
+        );
+    }
+}
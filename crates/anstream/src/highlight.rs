@@ -0,0 +1,359 @@
+use anstyle::Style;
+
+/// Style each line of text written to a [`HighlightStream`]
+///
+/// Implemented for closures, like `|line: &str| -> StyledString`.
+pub trait Highlight {
+    /// Style `line`, given without its line ending
+    fn highlight(&mut self, line: &str) -> StyledString;
+}
+
+impl<F> Highlight for F
+where
+    F: FnMut(&str) -> StyledString,
+{
+    #[inline]
+    fn highlight(&mut self, line: &str) -> StyledString {
+        self(line)
+    }
+}
+
+/// Text made up of styled spans
+///
+/// # Examples
+///
+/// ```
+/// let mut text = anstream::StyledString::new();
+/// text.push_str(anstyle::AnsiColor::Red.on_default(), "error");
+/// text.push_str(anstyle::Style::new(), ": file not found");
+/// assert_eq!(
+///     text.render().to_string(),
+///     "\x1b[31merror\x1b[0m: file not found"
+/// );
+/// ```
+#[derive(Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct StyledString {
+    spans: Vec<(Style, String)>,
+}
+
+impl StyledString {
+    /// No text
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `text` in `style`
+    pub fn push_str(&mut self, style: Style, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        match self.spans.last_mut() {
+            Some((last, span)) if *last == style => span.push_str(text),
+            _ => self.spans.push((style, text.to_owned())),
+        }
+    }
+
+    /// The styled spans, in order
+    pub fn spans(&self) -> impl Iterator<Item = (Style, &str)> {
+        self.spans
+            .iter()
+            .map(|(style, text)| (*style, text.as_str()))
+    }
+
+    /// Render the text with the minimal escape codes, ending with no style active
+    #[inline]
+    pub fn render(&self) -> impl std::fmt::Display + '_ {
+        StyledStringDisplay(self)
+    }
+}
+
+impl From<&'_ str> for StyledString {
+    #[inline]
+    fn from(text: &'_ str) -> Self {
+        let mut styled = Self::new();
+        styled.push_str(Style::new(), text);
+        styled
+    }
+}
+
+impl From<String> for StyledString {
+    #[inline]
+    fn from(text: String) -> Self {
+        let mut styled = Self::new();
+        if !text.is_empty() {
+            styled.spans.push((Style::new(), text));
+        }
+        styled
+    }
+}
+
+struct StyledStringDisplay<'s>(&'s StyledString);
+
+impl std::fmt::Display for StyledStringDisplay<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut active = Style::new();
+        for (style, text) in self.0.spans() {
+            write!(f, "{}", active.transition_to(style))?;
+            active = style;
+            f.write_str(text)?;
+        }
+        write!(f, "{}", active.transition_to(Style::new()))
+    }
+}
+
+/// Highlight each line before passing it on, like to an [`AutoStream`][crate::AutoStream]
+///
+/// Writes are buffered until a newline so the [`Highlight`] sees whole lines.  Any trailing
+/// partial line is highlighted and written on [`flush`][std::io::Write::flush],
+/// [`HighlightStream::finish`], or when dropped.  Invalid UTF-8 is replaced with `U+FFFD`.
+///
+/// The highlighted output is written as ANSI escape codes, leaving adapting them to the terminal
+/// to the wrapped stream.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let highlight = |line: &str| {
+///     let mut styled = anstream::StyledString::new();
+///     for word in line.split_inclusive(' ') {
+///         let style = if word.trim() == "ERROR" {
+///             anstyle::AnsiColor::Red.on_default()
+///         } else {
+///             anstyle::Style::new()
+///         };
+///         styled.push_str(style, word);
+///     }
+///     styled
+/// };
+/// let stream = anstream::AutoStream::always(Vec::new());
+/// let mut stream = anstream::HighlightStream::new(stream, highlight);
+/// writeln!(stream, "ERROR disk full").unwrap();
+/// let buffer = stream.finish().unwrap().into_inner();
+/// assert_eq!(buffer, b"\x1b[31mERROR \x1b[0mdisk full\n");
+/// ```
+#[derive(Debug)]
+pub struct HighlightStream<S, H>
+where
+    S: std::io::Write,
+    H: Highlight,
+{
+    // Only `None` after `finish` or `into_inner`
+    raw: Option<S>,
+    highlight: H,
+    buffer: Vec<u8>,
+    /// Highlighted lines left unwritten by an error, to be retried
+    pending: Vec<u8>,
+    /// Error from writing lines already accepted into `buffer`
+    error: Option<std::io::Error>,
+}
+
+impl<S, H> HighlightStream<S, H>
+where
+    S: std::io::Write,
+    H: Highlight,
+{
+    /// Highlight lines with `highlight`
+    #[inline]
+    pub fn new(raw: S, highlight: H) -> Self {
+        Self {
+            raw: Some(raw),
+            highlight,
+            buffer: Vec::new(),
+            pending: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Write any trailing partial line and get the wrapped `Write`
+    pub fn finish(mut self) -> std::io::Result<S> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.write_lines(self.buffer.len())?;
+        Ok(self
+            .raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`"))
+    }
+
+    /// Get the wrapped `Write`, discarding any trailing partial line
+    ///
+    /// Lines left unwritten by an error are discarded too.
+    #[inline]
+    pub fn into_inner(mut self) -> S {
+        self.raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a reference to the wrapped `Write`
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        self.raw
+            .as_ref()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a mutable reference to the wrapped `Write`
+    ///
+    /// Writing to it directly bypasses highlighting and any buffered partial line.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        self.raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    fn write_lines(&mut self, len: usize) -> std::io::Result<()> {
+        use std::fmt::Write as _;
+
+        let mut output = String::new();
+        for line in self.buffer[..len].split_inclusive(|b| *b == b'\n') {
+            let content = line.strip_suffix(b"\n").unwrap_or(line);
+            let content = content.strip_suffix(b"\r").unwrap_or(content);
+            let ending = &line[content.len()..];
+            let content = String::from_utf8_lossy(content);
+            let styled = self.highlight.highlight(&content);
+            let _ = write!(output, "{}", styled.render());
+            output.push_str(std::str::from_utf8(ending).expect("line endings are ASCII"));
+        }
+        self.buffer.drain(..len);
+        self.pending.extend_from_slice(output.as_bytes());
+
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        let raw = self
+            .raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`");
+        // Only drop what was written so the rest can be retried
+        let (written, result) = crate::fmt::write_prefix(raw, &self.pending);
+        self.pending.drain(..written);
+        result
+    }
+}
+
+impl<S, H> std::io::Write for HighlightStream<S, H>
+where
+    S: std::io::Write,
+    H: Highlight,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.buffer.extend_from_slice(buf);
+        if let Some(newline) = self.buffer.iter().rposition(|b| *b == b'\n') {
+            // `buf` was accepted, so the error is left for the next call
+            if let Err(err) = self.write_lines(newline + 1) {
+                self.error = Some(err);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.write_lines(self.buffer.len())?;
+        self.get_mut().flush()
+    }
+}
+
+impl<S, H> Drop for HighlightStream<S, H>
+where
+    S: std::io::Write,
+    H: Highlight,
+{
+    fn drop(&mut self) {
+        if self.raw.is_some() {
+            // Ignore errors as there is no way to report them
+            let _ = self.write_lines(self.buffer.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    fn digits(line: &str) -> StyledString {
+        let mut styled = StyledString::new();
+        for c in line.chars() {
+            let style = if c.is_ascii_digit() {
+                anstyle::AnsiColor::Cyan.on_default()
+            } else {
+                Style::new()
+            };
+            styled.push_str(style, c.encode_utf8(&mut [0; 4]));
+        }
+        styled
+    }
+
+    #[test]
+    fn merges_spans() {
+        let styled = digits("a12b");
+        assert_eq!(styled.spans().count(), 3);
+        assert_eq!(styled.render().to_string(), "a\x1b[36m12\x1b[0mb");
+    }
+
+    #[test]
+    fn whole_lines() {
+        let mut stream = HighlightStream::new(Vec::new(), digits);
+        stream.write_all(b"line 1").unwrap();
+        stream.write_all(b"0\r\nline 2").unwrap();
+        assert_eq!(stream.get_ref(), b"line \x1b[36m10\x1b[0m\r\n");
+        let buffer = stream.finish().unwrap();
+        assert_eq!(buffer, b"line \x1b[36m10\x1b[0m\r\nline \x1b[36m2\x1b[0m");
+    }
+
+    #[test]
+    fn into_strip_stream() {
+        let stream = crate::StripStream::new(Vec::new());
+        let mut stream = HighlightStream::new(stream, digits);
+        writeln!(stream, "line 3").unwrap();
+        let buffer = stream.finish().unwrap().into_inner();
+        assert_eq!(buffer, b"line 3\n");
+    }
+
+    #[derive(Default)]
+    struct Flaky {
+        buffer: Vec<u8>,
+        fail: bool,
+    }
+
+    impl std::io::Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.fail {
+                return Err(std::io::ErrorKind::Other.into());
+            }
+            self.buffer.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn retry_after_error() {
+        let mut stream = HighlightStream::new(Flaky::default(), digits);
+        stream.get_mut().fail = true;
+        assert_eq!(stream.write(b"line 4\n").unwrap(), 7);
+        assert!(stream.write(b"line 5\n").is_err());
+        stream.get_mut().fail = false;
+        stream.write_all(b"line 5\n").unwrap();
+        let raw = stream.finish().unwrap();
+        assert_eq!(
+            raw.buffer,
+            b"line \x1b[36m4\x1b[0m\nline \x1b[36m5\x1b[0m\n"
+        );
+    }
+}
@@ -0,0 +1,363 @@
+//! Pass styled text between processes without re-parsing escape codes
+//!
+//! A [`StyledString`] is encoded as a [CBOR](https://cbor.io/) message, so a daemon can send the
+//! spans it parsed to thin clients over a socket, and clients in other languages can decode them
+//! with any CBOR library.  Messages are self-delimiting, see [`decode_prefix`].
+//!
+//! # Format
+//!
+//! A message is the array `[version, spans]`, with `version` being [`VERSION`].  `spans` is an
+//! array of `[text, fg, bg, underline, effects]` arrays, with:
+//! - `text` as a text string
+//! - each color as `null` or an unsigned integer:
+//!   - `0..=15` for the [`AnsiColor`][anstyle::AnsiColor]s, in SGR order
+//!   - `0x100 + index` for an [`Ansi256Color`][anstyle::Ansi256Color]
+//!   - `0x1000000 + 0xRRGGBB` for an [`RgbColor`][anstyle::RgbColor]
+//! - `effects` as an unsigned integer, with bits from the least significant being bold, dimmed,
+//!   italic, underline, double underline, curly underline, dotted underline, dashed underline,
+//!   blink, invert, hidden, and strikethrough
+//!
+//! Changes that older decoders can't understand increase the version.
+//!
+//! # Examples
+//!
+//! ```
+//! let mut text = anstream::StyledString::new();
+//! text.push_str(anstyle::AnsiColor::Red.on_default().bold(), "error");
+//! text.push_str(anstyle::Style::new(), ": disk full");
+//!
+//! let message = anstream::ipc::encode(&text);
+//! assert_eq!(anstream::ipc::decode(&message), Ok(text));
+//! ```
+
+use anstyle::Color;
+use anstyle::Effects;
+use anstyle::Style;
+
+use crate::StyledString;
+
+/// The version of the format written by [`encode`], and the only one [`decode`] reads
+pub const VERSION: u64 = 1;
+
+/// The effects, by bit in the encoded `effects`
+const EFFECTS: [Effects; 12] = [
+    Effects::BOLD,
+    Effects::DIMMED,
+    Effects::ITALIC,
+    Effects::UNDERLINE,
+    Effects::DOUBLE_UNDERLINE,
+    Effects::CURLY_UNDERLINE,
+    Effects::DOTTED_UNDERLINE,
+    Effects::DASHED_UNDERLINE,
+    Effects::BLINK,
+    Effects::INVERT,
+    Effects::HIDDEN,
+    Effects::STRIKETHROUGH,
+];
+
+const ANSI256_BASE: u64 = 0x100;
+const RGB_BASE: u64 = 0x1000000;
+
+const MAJOR_UINT: u8 = 0;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const NULL: u8 = 0xf6;
+
+/// Encode `text` as a message
+pub fn encode(text: &StyledString) -> Vec<u8> {
+    let spans = text.spans().collect::<Vec<_>>();
+    let mut message = Vec::new();
+    write_head(&mut message, MAJOR_ARRAY, 2);
+    write_head(&mut message, MAJOR_UINT, VERSION);
+    write_head(&mut message, MAJOR_ARRAY, spans.len() as u64);
+    for (style, text) in spans {
+        write_head(&mut message, MAJOR_ARRAY, 5);
+        write_head(&mut message, MAJOR_TEXT, text.len() as u64);
+        message.extend_from_slice(text.as_bytes());
+        write_color(&mut message, style.get_fg_color());
+        write_color(&mut message, style.get_bg_color());
+        write_color(&mut message, style.get_underline_color());
+        let effects = style.get_effects();
+        let bits = EFFECTS
+            .iter()
+            .enumerate()
+            .filter(|(_, effect)| effects.contains(**effect))
+            .fold(0, |bits, (i, _)| bits | 1 << i);
+        write_head(&mut message, MAJOR_UINT, bits);
+    }
+    message
+}
+
+/// Decode a message that makes up all of `bytes`
+pub fn decode(bytes: &[u8]) -> Result<StyledString, DecodeError> {
+    let (text, len) = decode_prefix(bytes)?;
+    if len != bytes.len() {
+        return Err(DecodeError::TrailingData);
+    }
+    Ok(text)
+}
+
+/// Decode the message at the start of `bytes`, returning it and its length
+///
+/// For reading messages from a stream: on [`DecodeError::Truncated`], read more and try again.
+///
+/// # Examples
+///
+/// ```
+/// let first = anstream::ipc::encode(&"one".into());
+/// let second = anstream::ipc::encode(&"two".into());
+/// let received = [first.as_slice(), &second[..2]].concat();
+///
+/// let (text, len) = anstream::ipc::decode_prefix(&received).unwrap();
+/// assert_eq!(text, "one".into());
+/// assert_eq!(
+///     anstream::ipc::decode_prefix(&received[len..]),
+///     Err(anstream::ipc::DecodeError::Truncated)
+/// );
+/// ```
+pub fn decode_prefix(bytes: &[u8]) -> Result<(StyledString, usize), DecodeError> {
+    let mut reader = Reader { bytes, offset: 0 };
+    if reader.read_len(MAJOR_ARRAY)? != 2 {
+        return Err(DecodeError::Invalid);
+    }
+    let version = reader.read_len(MAJOR_UINT)?;
+    if version != VERSION {
+        return Err(DecodeError::UnsupportedVersion(version));
+    }
+
+    let mut text = StyledString::new();
+    for _ in 0..reader.read_len(MAJOR_ARRAY)? {
+        if reader.read_len(MAJOR_ARRAY)? != 5 {
+            return Err(DecodeError::Invalid);
+        }
+        let span = reader.read_text()?;
+        let style = Style::new()
+            .fg_color(reader.read_color()?)
+            .bg_color(reader.read_color()?)
+            .underline_color(reader.read_color()?)
+            .effects(reader.read_effects()?);
+        text.push_str(style, span);
+    }
+    Ok((text, reader.offset))
+}
+
+/// A message couldn't be decoded
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DecodeError {
+    /// The message is cut off
+    Truncated,
+    /// The message is from a newer version of the format
+    UnsupportedVersion(u64),
+    /// The message isn't in the format
+    Invalid,
+    /// More data follows the message
+    TrailingData,
+}
+
+impl std::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Truncated => f.write_str("message is truncated"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported message version {version}")
+            }
+            Self::Invalid => f.write_str("invalid message"),
+            Self::TrailingData => f.write_str("unexpected data after message"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Write the head of a CBOR data item, in its shortest form
+fn write_head(message: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+    if value < 24 {
+        message.push(major | value as u8);
+    } else if let Ok(value) = u8::try_from(value) {
+        message.push(major | 24);
+        message.push(value);
+    } else if let Ok(value) = u16::try_from(value) {
+        message.push(major | 25);
+        message.extend_from_slice(&value.to_be_bytes());
+    } else if let Ok(value) = u32::try_from(value) {
+        message.push(major | 26);
+        message.extend_from_slice(&value.to_be_bytes());
+    } else {
+        message.push(major | 27);
+        message.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_color(message: &mut Vec<u8>, color: Option<Color>) {
+    let value = match color {
+        None => {
+            message.push(NULL);
+            return;
+        }
+        Some(Color::Ansi(color)) => u64::from(anstyle::Ansi256Color::from_ansi(color).index()),
+        Some(Color::Ansi256(color)) => ANSI256_BASE + u64::from(color.index()),
+        Some(Color::Rgb(color)) => {
+            RGB_BASE
+                + (u64::from(color.r()) << 16 | u64::from(color.g()) << 8 | u64::from(color.b()))
+        }
+    };
+    write_head(message, MAJOR_UINT, value);
+}
+
+struct Reader<'b> {
+    bytes: &'b [u8],
+    offset: usize,
+}
+
+impl<'b> Reader<'b> {
+    fn take(&mut self, len: usize) -> Result<&'b [u8], DecodeError> {
+        let bytes = self
+            .bytes
+            .get(self.offset..)
+            .and_then(|rest| rest.get(..len))
+            .ok_or(DecodeError::Truncated)?;
+        self.offset += len;
+        Ok(bytes)
+    }
+
+    fn peek(&self) -> Result<u8, DecodeError> {
+        self.bytes
+            .get(self.offset)
+            .copied()
+            .ok_or(DecodeError::Truncated)
+    }
+
+    /// Read the head of a data item of type `major`, returning its argument
+    fn read_len(&mut self, major: u8) -> Result<u64, DecodeError> {
+        let initial = self.take(1)?[0];
+        if initial >> 5 != major {
+            return Err(DecodeError::Invalid);
+        }
+        let len = match initial & 0x1f {
+            value @ 0..=23 => return Ok(u64::from(value)),
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            // Reserved and indefinite lengths
+            _ => return Err(DecodeError::Invalid),
+        };
+        let value = self
+            .take(len)?
+            .iter()
+            .fold(0, |value, byte| value << 8 | u64::from(*byte));
+        Ok(value)
+    }
+
+    fn read_text(&mut self) -> Result<&'b str, DecodeError> {
+        let len = self.read_len(MAJOR_TEXT)?;
+        let len = usize::try_from(len).map_err(|_| DecodeError::Truncated)?;
+        let bytes = self.take(len)?;
+        std::str::from_utf8(bytes).map_err(|_| DecodeError::Invalid)
+    }
+
+    fn read_color(&mut self) -> Result<Option<Color>, DecodeError> {
+        if self.peek()? == NULL {
+            self.offset += 1;
+            return Ok(None);
+        }
+        let value = self.read_len(MAJOR_UINT)?;
+        let color = match value {
+            0..=15 => anstyle::Ansi256Color(value as u8)
+                .into_ansi()
+                .expect("0-15 are ANSI colors")
+                .into(),
+            ANSI256_BASE..=0x1ff => anstyle::Ansi256Color((value - ANSI256_BASE) as u8).into(),
+            RGB_BASE..=0x1ffffff => {
+                let [_, r, g, b] = ((value - RGB_BASE) as u32).to_be_bytes();
+                anstyle::RgbColor(r, g, b).into()
+            }
+            _ => return Err(DecodeError::Invalid),
+        };
+        Ok(Some(color))
+    }
+
+    fn read_effects(&mut self) -> Result<Effects, DecodeError> {
+        let bits = self.read_len(MAJOR_UINT)?;
+        if bits >> EFFECTS.len() != 0 {
+            return Err(DecodeError::Invalid);
+        }
+        let effects = EFFECTS
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| bits & 1 << i != 0)
+            .fold(Effects::new(), |effects, (_, effect)| effects | *effect);
+        Ok(effects)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encoding() {
+        let mut text = StyledString::new();
+        let style = anstyle::AnsiColor::BrightRed
+            .on(anstyle::Ansi256Color(208))
+            .underline_color(Some(anstyle::RgbColor(0, 0, 255).into()))
+            | Effects::BOLD
+            | Effects::STRIKETHROUGH;
+        text.push_str(style, "a");
+        text.push_str(Style::new(), "b");
+        assert_eq!(
+            encode(&text),
+            [
+                0x82, // [
+                0x01, // version
+                0x82, // spans: [
+                0x85, // [
+                0x61, b'a', // text
+                0x09, // fg
+                0x19, 0x01, 0xd0, // bg
+                0x1a, 0x01, 0x00, 0x00, 0xff, // underline
+                0x19, 0x08, 0x01, // effects
+                0x85, // [
+                0x61, b'b', // text
+                0xf6, 0xf6, 0xf6, // colors
+                0x00, // effects
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut text = StyledString::new();
+        for (i, effect) in EFFECTS.iter().enumerate() {
+            let style =
+                anstyle::Ansi256Color(i as u8 * 20).on(anstyle::RgbColor(i as u8, 2, 3)) | *effect;
+            text.push_str(style, &"ü".repeat(i * 3));
+        }
+        assert_eq!(decode(&encode(&text)), Ok(text));
+    }
+
+    #[test]
+    fn errors() {
+        let message = encode(&"text".into());
+        for len in 0..message.len() {
+            assert_eq!(decode(&message[..len]), Err(DecodeError::Truncated));
+        }
+        assert_eq!(
+            decode(&[&message[..], &[0]].concat()),
+            Err(DecodeError::TrailingData)
+        );
+        assert_eq!(
+            decode(&[0x82, 0x02, 0x80]),
+            Err(DecodeError::UnsupportedVersion(2))
+        );
+        // Effects outside of those known
+        assert_eq!(
+            decode(&[0x82, 0x01, 0x81, 0x85, 0x60, 0xf6, 0xf6, 0xf6, 0x19, 0x10, 0x00]),
+            Err(DecodeError::Invalid)
+        );
+        // Indefinite-length array
+        assert_eq!(decode(&[0x9f]), Err(DecodeError::Invalid));
+    }
+}
@@ -0,0 +1,328 @@
+use std::fmt::Write as _;
+
+use anstyle::Style;
+
+/// Convert styled output into [JSON Lines](https://jsonlines.org/), one record per styled span
+///
+/// Each record has the span's `text`, its `fg` and `bg` colors, and its `effects`, like
+/// `{"text":"error","fg":"red","bg":null,"effects":["bold"]}`.  Colors are ANSI color names,
+/// like `"bright-red"`, 256-color indices, like `"208"`, or RGB colors, like `"#ff8700"`.
+///
+/// A span ends when the style changes or after a newline, which is kept in its text, so joining
+/// the text of the records gives the output without escape codes.  Other escape codes are
+/// dropped.
+///
+/// Any partial span is written on [`flush`][std::io::Write::flush],
+/// [`JsonLinesStream::finish`], or when dropped.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let mut stream = anstream::JsonLinesStream::new(Vec::new());
+/// write!(stream, "\x1b[1;31merror\x1b[0m: oops\n").unwrap();
+/// let buffer = stream.finish().unwrap();
+/// assert_eq!(
+///     String::from_utf8(buffer).unwrap(),
+///     "{\"text\":\"error\",\"fg\":\"red\",\"bg\":null,\"effects\":[\"bold\"]}\n\
+///      {\"text\":\": oops\\n\",\"fg\":null,\"bg\":null,\"effects\":[]}\n"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct JsonLinesStream<S>
+where
+    S: std::io::Write,
+{
+    // Only `None` after `finish` or `into_inner`
+    raw: Option<S>,
+    parser: anstyle_parse::Parser,
+    capture: Capture,
+}
+
+impl<S> JsonLinesStream<S>
+where
+    S: std::io::Write,
+{
+    /// Start with no style active
+    #[inline]
+    pub fn new(raw: S) -> Self {
+        Self {
+            raw: Some(raw),
+            parser: Default::default(),
+            capture: Default::default(),
+        }
+    }
+
+    /// Write any partial span and get the wrapped `Write`
+    pub fn finish(mut self) -> std::io::Result<S> {
+        self.write_pending()?;
+        Ok(self
+            .raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`"))
+    }
+
+    /// Get the wrapped `Write`, discarding any partial span
+    #[inline]
+    pub fn into_inner(mut self) -> S {
+        self.raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a reference to the wrapped `Write`
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        self.raw
+            .as_ref()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a mutable reference to the wrapped `Write`
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        self.raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Write the records completed so far
+    fn write_records(&mut self) -> std::io::Result<()> {
+        let records = std::mem::take(&mut self.capture.records);
+        self.get_mut().write_all(records.as_bytes())
+    }
+
+    /// Write the records completed so far and any partial span
+    fn write_pending(&mut self) -> std::io::Result<()> {
+        self.capture.end_span();
+        self.write_records()
+    }
+}
+
+impl<S> std::io::Write for JsonLinesStream<S>
+where
+    S: std::io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        for byte in buf {
+            self.parser.advance(&mut self.capture, *byte);
+        }
+        self.write_records()?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_pending()?;
+        self.get_mut().flush()
+    }
+}
+
+impl<S> Drop for JsonLinesStream<S>
+where
+    S: std::io::Write,
+{
+    fn drop(&mut self) {
+        if self.raw.is_some() {
+            // Ignore errors as there is no way to report them
+            let _ = self.write_pending();
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+struct Capture {
+    style: Style,
+    /// The style of `text`
+    span_style: Style,
+    text: String,
+    /// Records not yet written
+    records: String,
+}
+
+impl Capture {
+    fn push(&mut self, c: char) {
+        if self.style != self.span_style {
+            self.end_span();
+            self.span_style = self.style;
+        }
+        self.text.push(c);
+        if c == '\n' {
+            self.end_span();
+        }
+    }
+
+    fn end_span(&mut self) {
+        if self.text.is_empty() {
+            return;
+        }
+        let text = std::mem::take(&mut self.text);
+        write_record(&mut self.records, &text, self.span_style);
+    }
+}
+
+impl anstyle_parse::Perform for Capture {
+    fn print(&mut self, c: char) {
+        self.push(c);
+    }
+
+    fn execute(&mut self, byte: u8) {
+        if matches!(byte, b'\n' | b'\t') {
+            self.push(byte as char);
+        }
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        if action == b'm' && intermediates.is_empty() && !ignore {
+            self.style = anstyle_parse::apply_sgr(self.style, params, anstyle::Sgr21::default()).0;
+        }
+    }
+}
+
+fn write_record(records: &mut String, text: &str, style: Style) {
+    records.push_str("{\"text\":");
+    write_json_str(records, text);
+    records.push_str(",\"fg\":");
+    write_color(records, style.get_fg_color());
+    records.push_str(",\"bg\":");
+    write_color(records, style.get_bg_color());
+    records.push_str(",\"effects\":[");
+    let effects = style.get_effects();
+    let names = EFFECT_NAMES
+        .iter()
+        .filter(|(effect, _)| effects.contains(*effect));
+    for (i, (_, name)) in names.enumerate() {
+        if i != 0 {
+            records.push(',');
+        }
+        let _ = write!(records, "\"{name}\"");
+    }
+    records.push_str("]}\n");
+}
+
+fn write_color(records: &mut String, color: Option<anstyle::Color>) {
+    let _ = match color {
+        None => write!(records, "null"),
+        Some(anstyle::Color::Ansi(color)) => write!(records, "\"{}\"", ansi_name(color)),
+        Some(anstyle::Color::Ansi256(color)) => write!(records, "\"{}\"", color.index()),
+        Some(anstyle::Color::Rgb(color)) => write!(
+            records,
+            "\"#{:02x}{:02x}{:02x}\"",
+            color.r(),
+            color.g(),
+            color.b()
+        ),
+    };
+}
+
+fn write_json_str(records: &mut String, s: &str) {
+    records.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => records.push_str("\\\""),
+            '\\' => records.push_str("\\\\"),
+            '\n' => records.push_str("\\n"),
+            '\t' => records.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(records, "\\u{:04x}", c as u32);
+            }
+            c => records.push(c),
+        }
+    }
+    records.push('"');
+}
+
+fn ansi_name(color: anstyle::AnsiColor) -> &'static str {
+    match color {
+        anstyle::AnsiColor::Black => "black",
+        anstyle::AnsiColor::Red => "red",
+        anstyle::AnsiColor::Green => "green",
+        anstyle::AnsiColor::Yellow => "yellow",
+        anstyle::AnsiColor::Blue => "blue",
+        anstyle::AnsiColor::Magenta => "magenta",
+        anstyle::AnsiColor::Cyan => "cyan",
+        anstyle::AnsiColor::White => "white",
+        anstyle::AnsiColor::BrightBlack => "bright-black",
+        anstyle::AnsiColor::BrightRed => "bright-red",
+        anstyle::AnsiColor::BrightGreen => "bright-green",
+        anstyle::AnsiColor::BrightYellow => "bright-yellow",
+        anstyle::AnsiColor::BrightBlue => "bright-blue",
+        anstyle::AnsiColor::BrightMagenta => "bright-magenta",
+        anstyle::AnsiColor::BrightCyan => "bright-cyan",
+        anstyle::AnsiColor::BrightWhite => "bright-white",
+    }
+}
+
+const EFFECT_NAMES: [(anstyle::Effects, &str); 12] = [
+    (anstyle::Effects::BOLD, "bold"),
+    (anstyle::Effects::DIMMED, "dimmed"),
+    (anstyle::Effects::ITALIC, "italic"),
+    (anstyle::Effects::UNDERLINE, "underline"),
+    (anstyle::Effects::DOUBLE_UNDERLINE, "double-underline"),
+    (anstyle::Effects::CURLY_UNDERLINE, "curly-underline"),
+    (anstyle::Effects::DOTTED_UNDERLINE, "dotted-underline"),
+    (anstyle::Effects::DASHED_UNDERLINE, "dashed-underline"),
+    (anstyle::Effects::BLINK, "blink"),
+    (anstyle::Effects::INVERT, "invert"),
+    (anstyle::Effects::HIDDEN, "hidden"),
+    (anstyle::Effects::STRIKETHROUGH, "strikethrough"),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    fn records(chunks: &[&[u8]]) -> String {
+        let mut stream = JsonLinesStream::new(Vec::new());
+        for chunk in chunks {
+            stream.write_all(chunk).unwrap();
+        }
+        String::from_utf8(stream.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(
+            records(&[b"\x1b[38;5;208;48;2;0;0;255;4;9ma\x1b[95mb"]),
+            "{\"text\":\"a\",\"fg\":\"208\",\"bg\":\"#0000ff\",\"effects\":[\"underline\",\"strikethrough\"]}\n\
+             {\"text\":\"b\",\"fg\":\"bright-magenta\",\"bg\":\"#0000ff\",\"effects\":[\"underline\",\"strikethrough\"]}\n"
+        );
+    }
+
+    #[test]
+    fn escaped() {
+        assert_eq!(
+            records(&[b"\"\\\t\x07\x1b]8;;x\x1b\\\xc3", b"\xa9"]),
+            "{\"text\":\"\\\"\\\\\\t\u{e9}\",\"fg\":null,\"bg\":null,\"effects\":[]}\n"
+        );
+    }
+
+    #[test]
+    fn split_at_newlines() {
+        assert_eq!(
+            records(&[b"\x1b[1mone\ntwo\x1b[0m\n"]),
+            "{\"text\":\"one\\n\",\"fg\":null,\"bg\":null,\"effects\":[\"bold\"]}\n\
+             {\"text\":\"two\",\"fg\":null,\"bg\":null,\"effects\":[\"bold\"]}\n\
+             {\"text\":\"\\n\",\"fg\":null,\"bg\":null,\"effects\":[]}\n"
+        );
+    }
+
+    #[test]
+    fn flush_writes_partial_span() {
+        let mut stream = JsonLinesStream::new(Vec::new());
+        stream.write_all(b"50%").unwrap();
+        assert_eq!(stream.get_ref(), b"");
+        stream.flush().unwrap();
+        assert_eq!(
+            stream.get_ref(),
+            b"{\"text\":\"50%\",\"fg\":null,\"bg\":null,\"effects\":[]}\n"
+        );
+    }
+}
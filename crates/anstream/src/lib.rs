@@ -35,19 +35,83 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
 pub mod adapter;
+pub mod conformance;
+#[cfg(feature = "ipc")]
+pub mod ipc;
+pub mod lint;
+pub mod panic;
 pub mod stream;
 
 mod buffer;
 #[macro_use]
 mod macros;
 mod auto;
+mod captured;
+mod coalesce;
+mod cursor;
 mod fmt;
+mod fragment;
+mod frame;
+mod highlight;
+mod json;
+mod lines;
+mod mux;
+mod profile;
+#[cfg(all(unix, feature = "pty"))]
+mod pty;
+#[cfg(feature = "raw-mode")]
+mod raw;
+mod reset;
+#[cfg(feature = "restore")]
+mod restore;
+mod restyle;
+mod restyler;
+mod screen;
+mod shared;
+mod stack;
 mod strip;
+mod suspend;
+mod throttle;
 #[cfg(all(windows, feature = "wincon"))]
 mod wincon;
 
+#[cfg(feature = "auto")]
+pub use auto::invalidate;
 pub use auto::AutoStream;
+pub use captured::CapturedStdio;
+pub use coalesce::CoalesceStream;
+pub use cursor::CursorGuard;
+pub use cursor::CursorSave;
+pub use fmt::FmtWriter;
+pub use fragment::Fragment;
+pub use frame::FrameWriter;
+pub use highlight::Highlight;
+pub use highlight::HighlightStream;
+pub use highlight::StyledString;
+pub use json::JsonLinesStream;
+pub use lines::Line;
+pub use lines::LineSplitter;
+pub use lines::Lines;
+pub use mux::LineMux;
+pub use mux::MuxSource;
+pub use profile::LinkFallback;
+#[cfg(all(unix, feature = "pty"))]
+pub use pty::PtyCommand;
+#[cfg(feature = "raw-mode")]
+pub use raw::RawMode;
+#[cfg(feature = "restore")]
+pub use restore::Restore;
+#[cfg(feature = "restore")]
+pub use restore::RestoreGuard;
+pub use restyle::RestyleStream;
+pub use screen::AlternateScreen;
+pub use shared::SharedStream;
+pub use stack::StyleStack;
 pub use strip::StripStream;
+pub use suspend::set_suspend_hook;
+pub use suspend::suspend;
+pub use suspend::SuspendHook;
+pub use throttle::ThrottleStream;
 #[cfg(all(windows, feature = "wincon"))]
 pub use wincon::WinconStream;
 
@@ -61,7 +125,8 @@ pub use buffer::Buffer;
 #[cfg(feature = "auto")]
 pub fn stdout() -> AutoStream<std::io::Stdout> {
     let stdout = std::io::stdout();
-    AutoStream::auto(stdout)
+    let probe = auto::Probe::stdout(&stdout);
+    AutoStream::auto_(stdout, probe)
 }
 
 /// Create an ANSI escape code compatible stderr
@@ -71,7 +136,8 @@ pub fn stdout() -> AutoStream<std::io::Stdout> {
 #[cfg(feature = "auto")]
 pub fn stderr() -> AutoStream<std::io::Stderr> {
     let stderr = std::io::stderr();
-    AutoStream::auto(stderr)
+    let probe = auto::Probe::stderr(&stderr);
+    AutoStream::auto_(stderr, probe)
 }
 
 /// Selection for overriding color output
@@ -0,0 +1,167 @@
+use crate::reset::SgrState;
+
+/// Split chunks of styled output into lines, tracking the style each line starts with
+///
+/// Chunks can split lines and escape codes anywhere, like when reading from a pipe.  The SGR
+/// state is carried across chunk and line boundaries, so a style set on one line and still active
+/// on the next is reported as that line's [`Line::style`].
+///
+/// # Examples
+///
+/// ```
+/// let mut splitter = anstream::LineSplitter::new();
+/// let red = anstyle::AnsiColor::Red.on_default();
+///
+/// let lines = splitter.push(b"\x1b[31mone\ntw").collect::<Vec<_>>();
+/// assert_eq!(lines.len(), 1);
+/// assert_eq!(lines[0].style(), anstyle::Style::new());
+/// assert_eq!(lines[0].text(), b"\x1b[31mone");
+///
+/// let lines = splitter.push(b"o\x1b[0m\nthree").collect::<Vec<_>>();
+/// assert_eq!(lines[0].style(), red);
+/// assert_eq!(lines[0].text(), b"two\x1b[0m");
+///
+/// let last = splitter.finish().unwrap();
+/// assert_eq!(last.style(), anstyle::Style::new());
+/// assert_eq!(last.text(), b"three");
+/// ```
+#[derive(Default, Debug)]
+pub struct LineSplitter {
+    buffer: Vec<u8>,
+    parser: anstyle_parse::Parser,
+    state: SgrState,
+}
+
+impl LineSplitter {
+    /// Start with no style active
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a chunk, returning the lines it completes
+    ///
+    /// Lines not taken from the iterator are returned by the next call.
+    #[inline]
+    pub fn push(&mut self, chunk: &[u8]) -> Lines<'_> {
+        self.buffer.extend_from_slice(chunk);
+        Lines {
+            splitter: self,
+            consumed: 0,
+        }
+    }
+
+    /// The style active at the end of the lines returned so far
+    #[inline]
+    pub fn current(&self) -> anstyle::Style {
+        self.state.style()
+    }
+
+    /// Take the trailing partial line, if any
+    pub fn finish(&mut self) -> Option<Line> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let text = std::mem::take(&mut self.buffer);
+        Some(self.line(text))
+    }
+
+    fn line(&mut self, text: Vec<u8>) -> Line {
+        let style = self.state.style();
+        for byte in &text {
+            self.parser.advance(&mut self.state, *byte);
+        }
+        Line { style, text }
+    }
+}
+
+/// Lines completed by [`LineSplitter::push`]
+#[derive(Debug)]
+pub struct Lines<'s> {
+    splitter: &'s mut LineSplitter,
+    consumed: usize,
+}
+
+impl Iterator for Lines<'_> {
+    type Item = Line;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let pending = &self.splitter.buffer[self.consumed..];
+        let newline = pending.iter().position(|b| *b == b'\n')?;
+        let line = &pending[..newline];
+        let text = line.strip_suffix(b"\r").unwrap_or(line).to_vec();
+        self.consumed += newline + 1;
+
+        let line = self.splitter.line(text);
+        // Keep the parser in step with the input
+        self.splitter
+            .parser
+            .advance(&mut self.splitter.state, b'\n');
+        Some(line)
+    }
+}
+
+impl Drop for Lines<'_> {
+    fn drop(&mut self) {
+        self.splitter.buffer.drain(..self.consumed);
+    }
+}
+
+/// A line from [`LineSplitter`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Line {
+    style: anstyle::Style,
+    text: Vec<u8>,
+}
+
+impl Line {
+    /// The style active at the start of the line
+    #[inline]
+    pub fn style(&self) -> anstyle::Style {
+        self.style
+    }
+
+    /// The text of the line, with its escape codes but without its line ending
+    #[inline]
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    /// Get the text of the line
+    #[inline]
+    pub fn into_text(self) -> Vec<u8> {
+        self.text
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn split_escape_code() {
+        let mut splitter = LineSplitter::new();
+        assert_eq!(splitter.push(b"a\x1b[3").count(), 0);
+        let lines = splitter.push(b"1mb\r\nc\n").collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].style(), anstyle::Style::new());
+        assert_eq!(lines[0].text(), b"a\x1b[31mb");
+        assert_eq!(lines[1].style(), anstyle::AnsiColor::Red.on_default());
+        assert_eq!(lines[1].text(), b"c");
+        assert_eq!(splitter.finish(), None);
+    }
+
+    #[test]
+    fn lines_left_for_next_push() {
+        let mut splitter = LineSplitter::new();
+        let mut lines = splitter.push(b"\x1b[1ma\nb\n");
+        assert_eq!(lines.next().unwrap().text(), b"\x1b[1ma");
+        drop(lines);
+        let lines = splitter.push(b"c\n").collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].text(), b"b");
+        assert_eq!(lines[1].text(), b"c");
+        assert_eq!(lines[1].style(), anstyle::Style::new().bold());
+        assert_eq!(splitter.current(), anstyle::Style::new().bold());
+    }
+}
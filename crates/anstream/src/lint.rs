@@ -0,0 +1,352 @@
+//! Find problems in styled output, like from a program under test in CI
+
+use anstyle::Style;
+
+/// Find problems in `data`, written without flushing in between
+///
+/// # Example
+///
+/// ```rust
+/// use anstream::lint::Problem;
+///
+/// let lints = anstream::lint::lint_bytes(b"\x1b[1mdone\x1b]0;title");
+/// let problems: Vec<_> = lints.iter().map(|lint| (lint.offset, lint.problem.clone())).collect();
+/// assert_eq!(
+///     problems,
+///     [
+///         (0, Problem::StyleLeftOpen(anstyle::Style::new().bold())),
+///         (8, Problem::UnterminatedOsc),
+///     ]
+/// );
+/// ```
+pub fn lint_bytes(data: &[u8]) -> Vec<Lint> {
+    let mut linter = Linter::new();
+    linter.lint_next(data);
+    linter.finish()
+}
+
+/// Find problems in output written in chunks, like between flushes
+///
+/// # Example
+///
+/// ```rust
+/// use anstream::lint::Problem;
+///
+/// let mut linter = anstream::lint::Linter::new();
+/// linter.lint_next(b"\x1b[31merror\x1b[");
+/// linter.lint_next(b"0m: oops\n");
+/// let lints = linter.finish();
+/// assert_eq!(lints.len(), 1);
+/// assert_eq!(lints[0].offset, 10);
+/// assert_eq!(lints[0].problem, Problem::SplitEscape);
+/// assert_eq!(lints[0].to_string(), "10: warning: escape code split across writes");
+/// ```
+#[derive(Default, Debug)]
+pub struct Linter {
+    parser: anstyle_parse::Parser,
+    capture: Capture,
+    offset: usize,
+    /// Start of an escape code left unfinished by the last chunk
+    split: Option<usize>,
+}
+
+impl Linter {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Scan the next chunk of output
+    ///
+    /// An escape code that isn't finished by the end of `bytes` is reported as split when the next
+    /// chunk continues it, or as unterminated on [`Linter::finish`].
+    pub fn lint_next(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+        if let Some(offset) = self.split.take() {
+            self.capture.push(offset, Problem::SplitEscape);
+        }
+
+        for byte in bytes {
+            self.parser.advance(&mut self.capture, *byte);
+            if *byte == 0x1b {
+                self.capture.sequence = Some(Sequence {
+                    start: self.offset,
+                    introducer: None,
+                });
+            } else if let Some(sequence) = self.capture.sequence.as_mut() {
+                sequence.introducer.get_or_insert(*byte);
+            }
+            self.offset += 1;
+        }
+
+        self.split = self
+            .capture
+            .sequence
+            .as_ref()
+            .map(|sequence| sequence.start);
+    }
+
+    /// Report the problems found, including those only known at the end of output
+    pub fn finish(mut self) -> Vec<Lint> {
+        if let Some(sequence) = self.capture.sequence.take() {
+            let problem = if sequence.introducer == Some(b']') {
+                Problem::UnterminatedOsc
+            } else {
+                Problem::UnterminatedEscape
+            };
+            self.capture.push(sequence.start, problem);
+        }
+        if self.capture.style != Style::new() {
+            let style = self.capture.style;
+            self.capture
+                .push(self.capture.style_start, Problem::StyleLeftOpen(style));
+        }
+        self.capture.lints.sort_by_key(|lint| lint.offset);
+        self.capture.lints
+    }
+}
+
+/// A problem found by [`Linter`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Lint {
+    /// Where the problem starts, in bytes from the start of output
+    pub offset: usize,
+    pub problem: Problem,
+}
+
+impl Lint {
+    /// How much the problem matters
+    #[inline]
+    pub fn severity(&self) -> Severity {
+        self.problem.severity()
+    }
+}
+
+impl std::fmt::Display for Lint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.offset, self.severity(), self.problem)
+    }
+}
+
+/// What is wrong with the output
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Problem {
+    /// An OSC sequence, like a hyperlink or title, is never ended
+    ///
+    /// Terminals swallow everything after it.
+    UnterminatedOsc,
+    /// An escape code other than OSC is cut off by the end of output
+    UnterminatedEscape,
+    /// Output ends with this style still active, styling whatever is written next
+    StyleLeftOpen(Style),
+    /// An SGR escape code has parameters, given here, that terminals may not support
+    UnknownSgr(String),
+    /// An escape code is split between chunks, where other output could be interleaved
+    SplitEscape,
+}
+
+impl Problem {
+    /// How much the problem matters
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::UnterminatedOsc | Self::UnterminatedEscape => Severity::Error,
+            Self::StyleLeftOpen(_) | Self::UnknownSgr(_) | Self::SplitEscape => Severity::Warning,
+        }
+    }
+}
+
+impl std::fmt::Display for Problem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnterminatedOsc => f.write_str("OSC sequence is never terminated"),
+            Self::UnterminatedEscape => f.write_str("escape code is cut off"),
+            Self::StyleLeftOpen(style) => {
+                write!(f, "style `{style:?}` is never reset")
+            }
+            Self::UnknownSgr(params) => write!(f, "unknown SGR parameters `{params}`"),
+            Self::SplitEscape => f.write_str("escape code split across writes"),
+        }
+    }
+}
+
+/// How much a [`Problem`] matters
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The output may look wrong on some terminals
+    Warning,
+    /// The output looks wrong
+    Error,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+#[derive(Default, Debug)]
+struct Capture {
+    /// The escape code being parsed
+    sequence: Option<Sequence>,
+    style: Style,
+    /// Start of the SGR escape code that changed `style` from the default
+    style_start: usize,
+    lints: Vec<Lint>,
+}
+
+impl Capture {
+    fn push(&mut self, offset: usize, problem: Problem) {
+        self.lints.push(Lint { offset, problem });
+    }
+
+    fn end_sequence(&mut self) {
+        self.sequence = None;
+    }
+}
+
+#[derive(Debug)]
+struct Sequence {
+    start: usize,
+    /// The byte after `ESC`, like `[` for CSI
+    introducer: Option<u8>,
+}
+
+impl anstyle_parse::Perform for Capture {
+    fn execute(&mut self, byte: u8) {
+        // `CAN` and `SUB` cancel escape codes
+        if matches!(byte, 0x18 | 0x1a) {
+            self.end_sequence();
+        }
+    }
+
+    fn unhook(&mut self) {
+        self.end_sequence();
+    }
+
+    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {
+        self.end_sequence();
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        let start = self.sequence.take().map(|sequence| sequence.start);
+        if action != b'm' || !intermediates.is_empty() || ignore {
+            return;
+        }
+        let start = start.unwrap_or_default();
+
+        let (style, known) =
+            anstyle_parse::apply_sgr(self.style, params, anstyle::Sgr21::default());
+        if !known {
+            self.push(start, Problem::UnknownSgr(format_params(params)));
+        }
+        if self.style == Style::new() {
+            self.style_start = start;
+        }
+        self.style = style;
+    }
+
+    fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, _byte: u8) {
+        self.end_sequence();
+    }
+
+    fn sos_pm_apc_end(&mut self) {
+        self.end_sequence();
+    }
+}
+
+fn format_params(params: &anstyle_parse::Params) -> String {
+    let params: Vec<_> = params
+        .iter()
+        .map(|param| {
+            let subparams: Vec<_> = param.iter().map(|p| p.to_string()).collect();
+            subparams.join(":")
+        })
+        .collect();
+    params.join(";")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn problems(chunks: &[&[u8]]) -> Vec<(usize, Problem)> {
+        let mut linter = Linter::new();
+        for chunk in chunks {
+            linter.lint_next(chunk);
+        }
+        linter
+            .finish()
+            .into_iter()
+            .map(|lint| (lint.offset, lint.problem))
+            .collect()
+    }
+
+    #[test]
+    fn clean() {
+        let output: &[&[u8]] = &[
+            b"\x1b[1;31merror\x1b[0m: \x1b]8;;https://example.com\x1b\\link\x1b]8;;\x07\n",
+            b"\x1b[38:2::1:2:3mx\x1b[39m\x1b[?25l\x1b[?25h\x1b7\x1b8",
+            b"\x1b[31\x18", // Cancelled by `CAN`
+        ];
+        assert_eq!(problems(output), []);
+    }
+
+    #[test]
+    fn unterminated() {
+        assert_eq!(
+            problems(&[b"\x1b]8;;https://example.com\nlink\n"]),
+            [(0, Problem::UnterminatedOsc)]
+        );
+        assert_eq!(problems(&[b"a\x1b[3"]), [(1, Problem::UnterminatedEscape)]);
+    }
+
+    #[test]
+    fn style_left_open() {
+        assert_eq!(
+            problems(&[b"\x1b[1ma\x1b[0m\x1b[31mb\x1b[1mc"]),
+            [(
+                9,
+                Problem::StyleLeftOpen(anstyle::AnsiColor::Red.on_default().bold())
+            )]
+        );
+    }
+
+    #[test]
+    fn unknown_sgr() {
+        assert_eq!(
+            problems(&[b"a\x1b[1;53;4:9m\x1b[0m"]),
+            [(1, Problem::UnknownSgr("1;53;4:9".to_owned()))]
+        );
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(
+            problems(&[b"a\x1b]0;ti", b"", b"tle\x1b", b"\\b"]),
+            [(1, Problem::SplitEscape), (10, Problem::SplitEscape)]
+        );
+    }
+
+    #[test]
+    fn severity() {
+        let lints = lint_bytes(b"\x1b[1m\x1b]0;");
+        let severities: Vec<_> = lints.iter().map(Lint::severity).collect();
+        assert_eq!(severities, [Severity::Warning, Severity::Error]);
+        assert_eq!(
+            lints[1].to_string(),
+            "4: error: OSC sequence is never terminated"
+        );
+    }
+}
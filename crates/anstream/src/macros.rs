@@ -72,12 +72,12 @@ macro_rules! print {
             let buffer = stream.into_inner();
             // Should be UTF-8 but not wanting to panic
             let buffer = String::from_utf8_lossy(&buffer);
-            ::std::print!("{}", buffer)
+            $crate::suspend(|| ::std::print!("{}", buffer))
         } else {
             use std::io::Write as _;
 
             let mut stream = $crate::stdout();
-            match ::std::write!(&mut stream, $($arg)*) {
+            match $crate::suspend(|| ::std::write!(&mut stream, $($arg)*)) {
                 Err(e) if e.kind() != ::std::io::ErrorKind::BrokenPipe => {
                     ::std::panic!("failed printing to stdout: {e}");
                 }
@@ -153,12 +153,12 @@ macro_rules! println {
             let buffer = stream.into_inner();
             // Should be UTF-8 but not wanting to panic
             let buffer = String::from_utf8_lossy(&buffer);
-            ::std::println!("{}", buffer)
+            $crate::suspend(|| ::std::println!("{}", buffer))
         } else {
             use std::io::Write as _;
 
             let mut stream = $crate::stdout();
-            match ::std::writeln!(&mut stream, $($arg)*) {
+            match $crate::suspend(|| ::std::writeln!(&mut stream, $($arg)*)) {
                 Err(e) if e.kind() != ::std::io::ErrorKind::BrokenPipe => {
                     ::std::panic!("failed printing to stdout: {e}");
                 }
@@ -213,12 +213,12 @@ macro_rules! eprint {
             let buffer = stream.into_inner();
             // Should be UTF-8 but not wanting to panic
             let buffer = String::from_utf8_lossy(&buffer);
-            ::std::eprint!("{}", buffer)
+            $crate::suspend(|| ::std::eprint!("{}", buffer))
         } else {
             use std::io::Write as _;
 
             let mut stream = $crate::stderr();
-            match ::std::write!(&mut stream, $($arg)*) {
+            match $crate::suspend(|| ::std::write!(&mut stream, $($arg)*)) {
                 Err(e) if e.kind() != ::std::io::ErrorKind::BrokenPipe => {
                     ::std::panic!("failed printing to stdout: {e}");
                 }
@@ -276,12 +276,12 @@ macro_rules! eprintln {
             let buffer = stream.into_inner();
             // Should be UTF-8 but not wanting to panic
             let buffer = String::from_utf8_lossy(&buffer);
-            ::std::eprintln!("{}", buffer)
+            $crate::suspend(|| ::std::eprintln!("{}", buffer))
         } else {
             use std::io::Write as _;
 
             let mut stream = $crate::stderr();
-            match ::std::writeln!(&mut stream, $($arg)*) {
+            match $crate::suspend(|| ::std::writeln!(&mut stream, $($arg)*)) {
                 Err(e) if e.kind() != ::std::io::ErrorKind::BrokenPipe => {
                     ::std::panic!("failed printing to stdout: {e}");
                 }
@@ -387,3 +387,49 @@ macro_rules! panic {
         ::std::panic!("{}", buffer)
     }};
 }
+
+/// Write styled, formatted data into a buffer
+///
+/// Equivalent to [`write!`] except the text is wrapped in the [`anstyle::Style`]'s escape codes,
+/// with the style reset afterwards.  This works with both [`std::io::Write`] and
+/// [`std::fmt::Write`] buffers.
+///
+/// When writing to an [`AutoStream`][crate::AutoStream], the escape codes are adapted to the
+/// terminal like any other output, including being stripped when color is disabled.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::Write as _;
+/// use anstream::write_styled;
+///
+/// let error = anstyle::AnsiColor::Red.on_default() | anstyle::Effects::BOLD;
+///
+/// let mut buffer = String::new();
+/// write_styled!(buffer, error, "error:").unwrap();
+/// write!(buffer, " {}", "file not found").unwrap();
+/// assert_eq!(buffer, "\x1b[1m\x1b[31merror:\x1b[0m file not found");
+/// ```
+///
+/// ```
+/// use std::io::Write as _;
+/// use anstream::write_styled;
+///
+/// let header = anstyle::Style::new().bold();
+///
+/// let mut stream = anstream::StripStream::new(Vec::new());
+/// write_styled!(stream, header, "{} items", 3).unwrap();
+/// assert_eq!(stream.into_inner(), b"3 items");
+/// ```
+#[macro_export]
+macro_rules! write_styled {
+    ($dst:expr, $style:expr, $($arg:tt)*) => {{
+        let style = $style;
+        $dst.write_fmt(::core::format_args!(
+            "{}{}{}",
+            style.render(),
+            ::core::format_args!($($arg)*),
+            style.render_reset()
+        ))
+    }};
+}
@@ -0,0 +1,313 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use crate::reset::SgrState;
+use crate::stream::AsLockedWrite;
+use crate::stream::RawStream;
+use crate::AutoStream;
+
+/// Merge output from several sources, line by line, onto one [`AutoStream`]
+///
+/// Each [`MuxSource`] tags its lines with a styled prefix, like `[build] `, and only writes whole
+/// lines so sources never interleave mid-line.  Styling is kept within each line: a style left
+/// active at the end of a line is reset before the newline and re-applied on that source's next
+/// line, so it doesn't bleed into other sources' lines.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let mux = anstream::LineMux::new(anstream::AutoStream::never(Vec::new()));
+/// let mut out = mux.source("[out] ", anstyle::AnsiColor::Green.on_default());
+/// let err = mux.forward(
+///     "[err] ",
+///     anstyle::AnsiColor::Red.on_default(),
+///     &b"warning: unused\nerror: failed\n"[..],
+/// );
+/// writeln!(out, "compiling").unwrap();
+/// err.join().unwrap().unwrap();
+/// drop(out);
+///
+/// let buffer = mux.into_inner().unwrap().into_inner();
+/// let buffer = String::from_utf8(buffer).unwrap();
+/// assert_eq!(buffer.lines().count(), 3);
+/// assert!(buffer.contains("[out] compiling\n"));
+/// assert!(buffer.contains("[err] warning: unused\n[err] error: failed\n"));
+/// ```
+#[derive(Debug)]
+pub struct LineMux<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    stream: Arc<Mutex<AutoStream<S>>>,
+}
+
+impl<S> LineMux<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    /// Merge lines onto `stream`
+    #[inline]
+    pub fn new(stream: AutoStream<S>) -> Self {
+        Self {
+            stream: Arc::new(Mutex::new(stream)),
+        }
+    }
+
+    /// Add a source, prefixing its lines with `tag` in `style`
+    ///
+    /// `tag` is written as-is, so include any separator, like `"[build] "`.
+    pub fn source(&self, tag: &str, style: anstyle::Style) -> MuxSource<S> {
+        MuxSource {
+            stream: self.stream.clone(),
+            tag: format!("{}{tag}{}", style.render(), style.render_reset()).into_bytes(),
+            buffer: Vec::new(),
+            parser: Default::default(),
+            state: Default::default(),
+            error: None,
+        }
+    }
+
+    /// Add a source that copies `reader` from a background thread, like a child's stdout
+    ///
+    /// The thread ends with `reader`, writing any trailing partial line.
+    pub fn forward<R>(
+        &self,
+        tag: &str,
+        style: anstyle::Style,
+        mut reader: R,
+    ) -> std::thread::JoinHandle<std::io::Result<()>>
+    where
+        R: std::io::Read + Send + 'static,
+        S: Send + 'static,
+    {
+        let mut source = self.source(tag, style);
+        std::thread::spawn(move || {
+            std::io::copy(&mut reader, &mut source)?;
+            source.finish()
+        })
+    }
+
+    /// Get the wrapped [`AutoStream`], if all sources have been dropped
+    pub fn into_inner(self) -> Option<AutoStream<S>> {
+        Arc::try_unwrap(self.stream)
+            .ok()
+            .map(|stream| stream.into_inner().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+/// A tagged source of lines for a [`LineMux`]
+///
+/// Any trailing partial line is written, with a newline added, on [`MuxSource::finish`] or when
+/// dropped.  As writes are buffered, an error writing a line is reported by the next write,
+/// flush, or [`MuxSource::finish`], with the unwritten lines kept to be retried.
+#[derive(Debug)]
+pub struct MuxSource<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    stream: Arc<Mutex<AutoStream<S>>>,
+    tag: Vec<u8>,
+    buffer: Vec<u8>,
+    parser: anstyle_parse::Parser,
+    state: SgrState,
+    /// Error from writing lines already accepted into `buffer`
+    error: Option<std::io::Error>,
+}
+
+impl<S> MuxSource<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    /// Write any trailing partial line
+    pub fn finish(mut self) -> std::io::Result<()> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.write_partial()
+    }
+
+    /// Write each line in `self.buffer[..len]`, which must end with a newline
+    fn write_lines(&mut self, len: usize) -> std::io::Result<()> {
+        if len == 0 {
+            return Ok(());
+        }
+
+        // Only committed once written, so the lines can be retried with the same styles
+        let mut parser = self.parser.clone();
+        let mut state = self.state;
+        let mut output = Vec::with_capacity(len + self.tag.len());
+        for line in self.buffer[..len].split_inclusive(|b| *b == b'\n') {
+            let line = line.strip_suffix(b"\n").unwrap_or(line);
+            output.extend_from_slice(&self.tag);
+            let _ = state.style().write_to(&mut output);
+            output.extend_from_slice(line);
+            for byte in line {
+                parser.advance(&mut state, *byte);
+            }
+            if state.is_active() {
+                output.extend_from_slice(b"\x1b[0m");
+            }
+            output.push(b'\n');
+        }
+
+        let mut stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        std::io::Write::write_all(&mut *stream, &output)?;
+        self.buffer.drain(..len);
+        self.parser = parser;
+        self.state = state;
+        Ok(())
+    }
+
+    fn write_partial(&mut self) -> std::io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        // Already ends with one when retrying after an error
+        if !self.buffer.ends_with(b"\n") {
+            self.buffer.push(b'\n');
+        }
+        self.write_lines(self.buffer.len())
+    }
+}
+
+impl<S> std::io::Write for MuxSource<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.buffer.extend_from_slice(buf);
+        if let Some(newline) = self.buffer.iter().rposition(|b| *b == b'\n') {
+            // `buf` was accepted, so the error is left for the next call
+            if let Err(err) = self.write_lines(newline + 1) {
+                self.error = Some(err);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    /// Flush the [`AutoStream`], holding back any partial line to keep lines whole
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.stream
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .flush()
+    }
+}
+
+impl<S> Drop for MuxSource<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    fn drop(&mut self) {
+        let _ = self.write_partial();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn whole_tagged_lines() {
+        let mux = LineMux::new(AutoStream::always(Vec::new()));
+        let workers = (0..8)
+            .map(|i| {
+                let mut source = mux.source(&format!("{i}:"), anstyle::Style::new().bold());
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        write!(source, "thread ").unwrap();
+                        writeln!(source, "{i}").unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let buffer = mux.into_inner().unwrap().into_inner();
+        let buffer = String::from_utf8(buffer).unwrap();
+        assert_eq!(buffer.lines().count(), 800);
+        for line in buffer.lines() {
+            let i = &line[4..5];
+            assert_eq!(line, format!("\x1b[1m{i}:\x1b[0mthread {i}"));
+        }
+    }
+
+    #[test]
+    fn style_kept_within_line() {
+        let mux = LineMux::new(AutoStream::always(Vec::new()));
+        let mut source = mux.source("> ", anstyle::Style::new());
+        write!(source, "\x1b[31mred\nstill red\x1b[0m\nplain\n").unwrap();
+        drop(source);
+
+        let buffer = mux.into_inner().unwrap().into_inner();
+        assert_eq!(
+            String::from_utf8(buffer).unwrap(),
+            "> \x1b[31mred\x1b[0m\n> \x1b[31mstill red\x1b[0m\n> plain\n"
+        );
+    }
+
+    #[test]
+    fn partial_line_on_drop() {
+        let mux = LineMux::new(AutoStream::never(Vec::new()));
+        let mut source = mux.source("> ", anstyle::AnsiColor::Red.on_default());
+        write!(source, "\x1b[1mno newline").unwrap();
+        source.flush().unwrap();
+        drop(source);
+
+        let buffer = mux.into_inner().unwrap().into_inner();
+        assert_eq!(buffer, b"> no newline\n");
+    }
+
+    #[derive(Clone, Default)]
+    struct Flaky {
+        buffer: Arc<Mutex<Vec<u8>>>,
+        fail: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl std::io::Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.fail.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(std::io::ErrorKind::Other.into());
+            }
+            self.buffer.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn retry_after_error() {
+        let raw = Flaky::default();
+        let mux = LineMux::new(AutoStream::always_ansi(
+            Box::new(raw.clone()) as Box<dyn std::io::Write>
+        ));
+        let mut source = mux.source("> ", anstyle::Style::new());
+
+        raw.fail.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(source.write(b"\x1b[31mred\n").unwrap(), 9);
+        assert!(source.write(b"still red\n").is_err());
+        raw.fail.store(false, std::sync::atomic::Ordering::SeqCst);
+        source.write_all(b"still red\n").unwrap();
+        source.finish().unwrap();
+
+        assert_eq!(
+            String::from_utf8(raw.buffer.lock().unwrap().clone()).unwrap(),
+            "> \x1b[31mred\x1b[0m\n> \x1b[31mstill red\x1b[0m\n"
+        );
+    }
+}
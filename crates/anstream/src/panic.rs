@@ -0,0 +1,63 @@
+//! Keep panic messages readable when panicking mid-styled-output
+
+use crate::stream::IsTerminal as _;
+
+/// Reset the style and show the cursor before the panic message
+///
+/// Any [`AlternateScreen`][crate::AlternateScreen] is left too so the message isn't lost with it,
+/// and any `RawMode` restored so it is readable.
+///
+/// This wraps the current panic hook, so call it after any other hook is installed.  Nothing is
+/// written if stderr isn't a terminal.  The style reset is written through an
+/// [`AutoStream`][crate::AutoStream] so it is adapted for legacy Windows consoles while leaving the
+/// alternate screen and showing the cursor is always written, even when colors are disabled.
+///
+/// # Examples
+///
+/// ```
+/// anstream::panic::install();
+/// ```
+pub fn install() {
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        #[cfg(feature = "raw-mode")]
+        crate::raw::restore_for_panic();
+        let raw = std::io::stderr();
+        if raw.is_terminal() {
+            #[cfg(feature = "auto")]
+            let styled = crate::stderr();
+            #[cfg(not(feature = "auto"))]
+            let styled = crate::AutoStream::always(std::io::stderr());
+            // Ignore errors rather than panic
+            let _ = reset(&mut raw.lock(), &mut styled.lock());
+        }
+        hook(info);
+    }));
+}
+
+/// Reset the style on `styled` and the terminal modes on `raw`
+///
+/// Terminal modes aren't colors, so they go to `raw` to not be stripped along with them.
+fn reset(raw: &mut dyn std::io::Write, styled: &mut dyn std::io::Write) -> std::io::Result<()> {
+    styled.write_all(b"\x1b[0m")?;
+    styled.flush()?;
+    crate::screen::leave_for_panic(raw)?;
+    raw.write_all(b"\x1b[?25h")?;
+    raw.flush()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::AutoStream;
+
+    #[test]
+    fn no_color() {
+        let mut raw = Vec::new();
+        let mut styled = AutoStream::never(Vec::new());
+        reset(&mut raw, &mut styled).unwrap();
+        assert!(raw.ends_with(b"\x1b[?25h"), "{raw:?}");
+        assert_eq!(styled.into_inner(), b"");
+    }
+}
@@ -0,0 +1,215 @@
+use crate::restyler::Restyler;
+use crate::stream::AsLockedWrite;
+use crate::stream::RawStream;
+
+type Adapt = Box<dyn FnMut(anstyle::Style, &str) -> anstyle::Style + Send + Sync>;
+
+/// How OSC 8 hyperlinks are shown when the terminal doesn't support them
+///
+/// See [`AutoStream::link_fallback`][crate::AutoStream::link_fallback].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LinkFallback {
+    /// Follow the link's text with its URL, like `text (url)`
+    ///
+    /// The URL is left out when it is the same as the text.
+    #[default]
+    TextAndUrl,
+    /// Only show the link's text
+    Text,
+}
+
+/// Re-render SGR escape codes within what an [`anstyle::Profile`] supports
+pub(crate) struct ProfileStream<S>
+where
+    S: RawStream,
+{
+    raw: S,
+    profile: anstyle::Profile,
+    restyler: Restyler<Adapt>,
+}
+
+impl<S> ProfileStream<S>
+where
+    S: RawStream,
+{
+    #[inline]
+    pub(crate) fn new(raw: S, profile: anstyle::Profile) -> Self {
+        let mut stream = Self {
+            raw,
+            profile,
+            restyler: Restyler::new(Box::new(move |style, _| profile.adapt(style))),
+        };
+        stream.link_fallback(LinkFallback::default());
+        stream
+    }
+
+    /// Rewrite hyperlinks with `fallback` when `profile` doesn't support them
+    #[inline]
+    pub(crate) fn link_fallback(&mut self, fallback: LinkFallback) {
+        let links = (!self.profile.get_hyperlinks()).then_some(fallback);
+        self.restyler.set_links(links);
+    }
+
+    /// Interpret SGR 21 as `sgr21`
+    #[inline]
+    pub(crate) fn sgr21(&mut self, sgr21: anstyle::Sgr21) {
+        self.restyler.set_sgr21(sgr21);
+    }
+
+    #[inline]
+    pub(crate) fn into_inner(self) -> S {
+        self.raw
+    }
+
+    #[inline]
+    pub(crate) fn get_ref(&self) -> &S {
+        &self.raw
+    }
+
+    #[inline]
+    pub(crate) fn get_mut(&mut self) -> &mut S {
+        &mut self.raw
+    }
+
+    #[inline]
+    pub(crate) fn is_terminal(&self) -> bool {
+        self.raw.is_terminal()
+    }
+}
+
+impl ProfileStream<std::io::Stdout> {
+    #[inline]
+    pub(crate) fn lock(self) -> ProfileStream<std::io::StdoutLock<'static>> {
+        ProfileStream {
+            raw: self.raw.lock(),
+            profile: self.profile,
+            restyler: self.restyler,
+        }
+    }
+}
+
+impl ProfileStream<std::io::Stderr> {
+    #[inline]
+    pub(crate) fn lock(self) -> ProfileStream<std::io::StderrLock<'static>> {
+        ProfileStream {
+            raw: self.raw.lock(),
+            profile: self.profile,
+            restyler: self.restyler,
+        }
+    }
+}
+
+impl<S> std::io::Write for ProfileStream<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    // Must forward all calls to ensure locking happens appropriately
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+    // is_write_vectored: nightly only
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.raw.as_locked_write().flush()
+    }
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        let mut raw = self.raw.as_locked_write();
+        self.restyler.advance(&mut raw, buf)?;
+        self.restyler.flush_style(&mut raw)
+    }
+    // write_all_vectored: nightly only
+    #[inline]
+    fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
+        let Self { raw, restyler, .. } = self;
+        let mut raw = raw.as_locked_write();
+        let write_all = |buf: &[u8]| {
+            restyler.advance(&mut raw, buf)?;
+            restyler.flush_style(&mut raw)
+        };
+        crate::fmt::Adapter::new(write_all).write_fmt(args)
+    }
+}
+
+impl<S> std::fmt::Debug for ProfileStream<S>
+where
+    S: RawStream + std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProfileStream")
+            .field("raw", &self.raw)
+            .field("profile", &self.profile)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn split_writes() {
+        let mut stream = ProfileStream::new(Vec::new(), anstyle::Profile::XTERM_256COLOR);
+        stream.write_all(b"\x1b[38;2;255;135").unwrap();
+        stream.write_all(b";0;4:3mwarn\x1b[2K").unwrap();
+        write!(stream, "ing\x1b[0m").unwrap();
+        assert_eq!(
+            stream.into_inner(),
+            b"\x1b[4m\x1b[38;5;208mwarn\x1b[2King\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn dumb() {
+        let mut stream = ProfileStream::new(Vec::new(), anstyle::Profile::DUMB);
+        write!(
+            stream,
+            "\x1b[1;31merror\x1b[0m: \x1b]8;;https://example.com\x07x"
+        )
+        .unwrap();
+        assert_eq!(stream.into_inner(), b"error: x");
+    }
+
+    #[test]
+    fn links() {
+        let text = "\x1b]8;;https://example.com\x1b\\\x1b[4mdocs\x1b[0m\x1b]8;;\x1b\\, \
+            \x1b]8;id=1;https://example.com\x07https://example.com\x1b]8;;\x07";
+
+        let mut stream = ProfileStream::new(Vec::new(), anstyle::Profile::DUMB);
+        stream.write_all(text.as_bytes()).unwrap();
+        assert_eq!(
+            stream.into_inner(),
+            b"docs (https://example.com), https://example.com"
+        );
+
+        let mut stream = ProfileStream::new(Vec::new(), anstyle::Profile::DUMB);
+        stream.link_fallback(LinkFallback::Text);
+        stream.write_all(text.as_bytes()).unwrap();
+        assert_eq!(stream.into_inner(), b"docs, https://example.com");
+
+        let text = "\x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\";
+        let mut stream = ProfileStream::new(Vec::new(), anstyle::Profile::new());
+        stream.write_all(text.as_bytes()).unwrap();
+        assert_eq!(stream.into_inner(), text.as_bytes());
+    }
+
+    #[test]
+    fn sgr21() {
+        let text = "\x1b[1;4:2mboth\x1b[21mdouble\x1b[0m";
+
+        let mut stream = ProfileStream::new(Vec::new(), anstyle::Profile::new());
+        stream.write_all(text.as_bytes()).unwrap();
+        assert_eq!(stream.into_inner(), b"\x1b[1m\x1b[21mbothdouble\x1b[0m");
+
+        let mut stream = ProfileStream::new(Vec::new(), anstyle::Profile::new());
+        stream.sgr21(anstyle::Sgr21::BoldOff);
+        stream.write_all(text.as_bytes()).unwrap();
+        assert_eq!(
+            stream.into_inner(),
+            b"\x1b[1m\x1b[4:2mboth\x1b[0m\x1b[4:2mdouble\x1b[0m"
+        );
+    }
+}
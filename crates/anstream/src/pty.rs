@@ -0,0 +1,215 @@
+use std::io::Read as _;
+use std::os::fd::AsRawFd as _;
+use std::os::fd::FromRawFd as _;
+use std::os::fd::OwnedFd;
+
+use anstyle::Style;
+
+use crate::restyler::Restyle;
+use crate::restyler::Restyler;
+
+/// Run a command attached to a pseudo-terminal, restyling its output
+///
+/// Programs only emit color when writing to a terminal, so the command is given one, like
+/// `unbuffer` or `script`.  Its output is parsed and each run of text can be restyled with
+/// [`PtyCommand::restyle`] before being forwarded.
+///
+/// Other escape codes, like cursor movement, are forwarded as-is.  SGR codes that
+/// [`anstyle::Style`] can't represent are dropped.
+///
+/// # Examples
+///
+/// ```no_run
+/// let mut command = std::process::Command::new("cargo");
+/// command.arg("build");
+/// let status = anstream::PtyCommand::new(command)
+///     .restyle(|style, text| {
+///         if text.contains("warning") {
+///             anstyle::AnsiColor::Yellow.on_default()
+///         } else {
+///             style
+///         }
+///     })
+///     .run(&mut anstream::stdout())
+///     .unwrap();
+/// assert!(status.success());
+/// ```
+pub struct PtyCommand {
+    command: std::process::Command,
+    restyle: Restyle,
+}
+
+impl PtyCommand {
+    /// Run `command` with its stdin, stdout, and stderr attached to a pseudo-terminal
+    #[inline]
+    pub fn new(command: std::process::Command) -> Self {
+        Self {
+            command,
+            restyle: Box::new(|style, _| style),
+        }
+    }
+
+    /// Choose the style for each run of text, given its original style
+    ///
+    /// A run is the text between escape codes and control characters, like newlines.  It may
+    /// also be split when the output is read in pieces.
+    #[inline]
+    pub fn restyle(mut self, restyle: impl FnMut(Style, &str) -> Style + 'static) -> Self {
+        self.restyle = Box::new(restyle);
+        self
+    }
+
+    /// Run the command to completion, writing its output to `write`
+    ///
+    /// The terminal size is copied from stdout, if it is a terminal.
+    pub fn run(
+        mut self,
+        write: &mut dyn std::io::Write,
+    ) -> std::io::Result<std::process::ExitStatus> {
+        let (master, slave) = open()?;
+        let slave = std::fs::File::from(slave);
+        self.command
+            .stdin(slave.try_clone()?)
+            .stdout(slave.try_clone()?)
+            .stderr(slave);
+        let mut child = self.command.spawn()?;
+        // Drop our copies of the slave so reading ends with the child
+        self.command
+            .stdin(std::process::Stdio::null())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null());
+
+        let mut master = std::fs::File::from(master);
+        let mut restyler = Restyler::new(self.restyle);
+        let mut buffer = [0; 4096];
+        let result = loop {
+            match master.read(&mut buffer) {
+                Ok(0) => break Ok(()),
+                Ok(len) => {
+                    if let Err(err) = restyler
+                        .advance(write, &buffer[..len])
+                        .and_then(|()| restyler.flush(write))
+                        .and_then(|()| write.flush())
+                    {
+                        break Err(err);
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+                // Linux reports the slave closing as an error
+                Err(err) if err.raw_os_error() == Some(libc::EIO) => break Ok(()),
+                Err(err) => break Err(err),
+            }
+        };
+        let status = child.wait()?;
+        result?;
+        restyler.finish(write)?;
+        write.flush()?;
+        Ok(status)
+    }
+}
+
+impl std::fmt::Debug for PtyCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PtyCommand")
+            .field("command", &self.command)
+            .finish_non_exhaustive()
+    }
+}
+
+fn open() -> std::io::Result<(OwnedFd, OwnedFd)> {
+    let mut size = std::mem::MaybeUninit::<libc::winsize>::zeroed();
+    // SAFETY: `TIOCGWINSZ` only writes to `size`
+    let has_size = unsafe {
+        libc::ioctl(
+            std::io::stdout().as_raw_fd(),
+            libc::TIOCGWINSZ,
+            size.as_mut_ptr(),
+        )
+    } == 0;
+    let size_ptr = if has_size {
+        size.as_mut_ptr()
+    } else {
+        std::ptr::null_mut()
+    };
+
+    let mut master = -1;
+    let mut slave = -1;
+    // SAFETY: the out-pointers are valid and the names are left null
+    let result = unsafe {
+        libc::openpty(
+            &mut master,
+            &mut slave,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            size_ptr,
+        )
+    };
+    if result != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // SAFETY: `openpty` succeeded, so these are open and owned by us
+    unsafe { Ok((OwnedFd::from_raw_fd(master), OwnedFd::from_raw_fd(slave))) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn restyle(input: &str, restyle: impl FnMut(Style, &str) -> Style + 'static) -> String {
+        let mut restyler = Restyler::new(Box::new(restyle));
+        let mut output = Vec::new();
+        restyler.advance(&mut output, input.as_bytes()).unwrap();
+        restyler.finish(&mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn passthrough() {
+        let input = "\x1b[1m\x1b[31merror\x1b[0m: \x1b[2Kdone\r\n";
+        assert_eq!(restyle(input, |style, _| style), input);
+    }
+
+    #[test]
+    fn replace_style() {
+        let input = "\x1b[31merror\x1b[0m: failed";
+        let actual = restyle(input, |style, _| {
+            if style == anstyle::AnsiColor::Red.on_default() {
+                anstyle::AnsiColor::Magenta.on_default().bold()
+            } else {
+                style
+            }
+        });
+        assert_eq!(actual, "\x1b[1m\x1b[35merror\x1b[0m: failed");
+    }
+
+    #[test]
+    fn inject_style() {
+        let actual = restyle("ok\nwarning: unused\n", |style, text| {
+            if text.starts_with("warning") {
+                anstyle::AnsiColor::Yellow.on_default()
+            } else {
+                style
+            }
+        });
+        assert_eq!(actual, "ok\n\x1b[33mwarning: unused\n\x1b[0m");
+    }
+
+    #[test]
+    fn run_in_pty() {
+        let mut command = std::process::Command::new("sh");
+        command.args([
+            "-c",
+            r"if test -t 1; then printf '\033[31mtty\033[0m\n'; else echo piped; fi",
+        ]);
+        let mut output = Vec::new();
+        let status = PtyCommand::new(command)
+            .restyle(|style, _| style.bold())
+            .run(&mut output)
+            .unwrap();
+        assert!(status.success());
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "\x1b[1m\x1b[31mtty\r\n\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use std::cell::UnsafeCell;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+
+/// Put the terminal in raw mode, restoring its previous mode when dropped
+///
+/// In raw mode, input is read a key at a time, without waiting for Enter, and isn't echoed, for
+/// building interactive prompts.  Keys like Ctrl-C are read as input rather than sending
+/// signals.  Output processing is left on so `\n` still starts a new line.
+///
+/// Guards nest: the terminal goes back to its original mode when the last one is dropped.
+/// [`anstream::panic::install`][crate::panic::install] restores it before the panic message,
+/// like it does for [`AlternateScreen`][crate::AlternateScreen], and so do the handlers of
+/// `Restore::install` on exit or a signal.  Create this after any `AlternateScreen` so it is
+/// dropped first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::io::Read as _;
+///
+/// let raw = anstream::RawMode::enable().unwrap();
+/// let mut key = [0; 1];
+/// std::io::stdin().read_exact(&mut key).unwrap();
+/// raw.disable().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct RawMode {
+    enabled: bool,
+}
+
+impl RawMode {
+    /// Switch the terminal connected to stdin, or the controlling terminal, to raw mode
+    ///
+    /// Fails when there isn't a terminal.
+    pub fn enable() -> std::io::Result<Self> {
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        if state.count == 0 {
+            let original = imp::enable()?;
+            SAVED.set(original.saved);
+            state.original = Some(original);
+        }
+        state.count += 1;
+        Ok(Self { enabled: true })
+    }
+
+    /// Restore the terminal's mode, if this is the last guard, reporting any error
+    pub fn disable(mut self) -> std::io::Result<()> {
+        self.disable_()
+    }
+
+    fn disable_(&mut self) -> std::io::Result<()> {
+        if !std::mem::take(&mut self.enabled) {
+            return Ok(());
+        }
+        let mut state = STATE.lock().unwrap_or_else(|e| e.into_inner());
+        // Already restored when a panic message was printed
+        if state.count == 0 {
+            return Ok(());
+        }
+        state.count -= 1;
+        if state.count != 0 {
+            return Ok(());
+        }
+        SAVED.clear();
+        match state.original.take() {
+            Some(original) => imp::restore(&original.saved),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        // Ignore errors as there is no way to report them
+        let _ = self.disable_();
+    }
+}
+
+struct State {
+    /// How many [`RawMode`]s are active
+    count: usize,
+    original: Option<imp::Mode>,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    count: 0,
+    original: None,
+});
+
+/// Restore the terminal's mode, if raw, so a panic message is readable
+///
+/// Any active [`RawMode`] won't restore it again when dropped.
+pub(crate) fn restore_for_panic() {
+    // Don't deadlock if the panic was while holding the lock
+    let Ok(mut state) = STATE.try_lock() else {
+        return;
+    };
+    state.count = 0;
+    SAVED.clear();
+    if let Some(original) = state.original.take() {
+        let _ = imp::restore(&original.saved);
+    }
+}
+
+/// Restore the terminal's mode, if raw, without allocating or locking, for signal handlers
+///
+/// Any active [`RawMode`] restores it again when dropped, which is harmless.
+#[cfg(feature = "restore")]
+pub(crate) fn restore_for_exit() {
+    if let Some(saved) = SAVED.take() {
+        let _ = imp::restore(&saved);
+    }
+}
+
+/// A copy of the original mode while raw, readable without locking [`STATE`]
+struct Saved {
+    raw: AtomicBool,
+    saved: UnsafeCell<Option<imp::Saved>>,
+}
+
+// SAFETY: `saved` is only written while `raw` is false, with `STATE` locked, and only read after
+// swapping `raw` from true to false
+unsafe impl Sync for Saved {}
+
+static SAVED: Saved = Saved {
+    raw: AtomicBool::new(false),
+    saved: UnsafeCell::new(None),
+};
+
+impl Saved {
+    /// Call with [`STATE`] locked
+    fn set(&self, saved: imp::Saved) {
+        self.clear();
+        // SAFETY: `raw` is false, so nothing reads this
+        unsafe {
+            *self.saved.get() = Some(saved);
+        }
+        self.raw.store(true, Ordering::SeqCst);
+    }
+
+    fn clear(&self) {
+        self.raw.store(false, Ordering::SeqCst);
+    }
+
+    #[cfg(feature = "restore")]
+    fn take(&self) -> Option<imp::Saved> {
+        if self.raw.swap(false, Ordering::SeqCst) {
+            // SAFETY: `raw` was true, so this was written and won't be until `raw` is set again
+            unsafe { *self.saved.get() }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(all(unix, not(test)))]
+mod imp {
+    use std::os::unix::io::AsRawFd as _;
+    use std::os::unix::io::RawFd;
+
+    pub(super) struct Mode {
+        pub(super) saved: Saved,
+        /// The controlling terminal, when stdin isn't a terminal
+        _tty: Option<std::fs::File>,
+    }
+
+    #[derive(Copy, Clone)]
+    pub(super) struct Saved {
+        fd: RawFd,
+        termios: libc::termios,
+    }
+
+    pub(super) fn enable() -> std::io::Result<Mode> {
+        let (fd, tty) = if std::io::IsTerminal::is_terminal(&std::io::stdin()) {
+            (std::io::stdin().as_raw_fd(), None)
+        } else {
+            let tty = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open("/dev/tty")?;
+            (tty.as_raw_fd(), Some(tty))
+        };
+
+        let mut termios = std::mem::MaybeUninit::<libc::termios>::zeroed();
+        // SAFETY: `tcgetattr` only writes to `termios`
+        if unsafe { libc::tcgetattr(fd, termios.as_mut_ptr()) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        // SAFETY: initialized by `zeroed` and `tcgetattr`
+        let original = unsafe { termios.assume_init() };
+
+        // Like `cfmakeraw`, except for output processing
+        let mut raw = original;
+        raw.c_iflag &= !(libc::IGNBRK
+            | libc::BRKINT
+            | libc::PARMRK
+            | libc::ISTRIP
+            | libc::INLCR
+            | libc::IGNCR
+            | libc::ICRNL
+            | libc::IXON);
+        raw.c_lflag &= !(libc::ECHO | libc::ECHONL | libc::ICANON | libc::ISIG | libc::IEXTEN);
+        raw.c_cflag &= !(libc::CSIZE | libc::PARENB);
+        raw.c_cflag |= libc::CS8;
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+        // SAFETY: `raw` is a valid `termios` from `tcgetattr`
+        if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(Mode {
+            saved: Saved {
+                fd,
+                termios: original,
+            },
+            _tty: tty,
+        })
+    }
+
+    /// Async-signal-safe
+    pub(super) fn restore(saved: &Saved) -> std::io::Result<()> {
+        // SAFETY: `termios` is a valid `termios` from `tcgetattr`
+        if unsafe { libc::tcsetattr(saved.fd, libc::TCSANOW, &saved.termios) } != 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(windows, not(test)))]
+mod imp {
+    use windows_sys::Win32::Foundation::HANDLE;
+    use windows_sys::Win32::System::Console::GetConsoleMode;
+    use windows_sys::Win32::System::Console::GetStdHandle;
+    use windows_sys::Win32::System::Console::SetConsoleMode;
+    use windows_sys::Win32::System::Console::CONSOLE_MODE;
+    use windows_sys::Win32::System::Console::ENABLE_ECHO_INPUT;
+    use windows_sys::Win32::System::Console::ENABLE_LINE_INPUT;
+    use windows_sys::Win32::System::Console::ENABLE_PROCESSED_INPUT;
+    use windows_sys::Win32::System::Console::ENABLE_VIRTUAL_TERMINAL_INPUT;
+    use windows_sys::Win32::System::Console::STD_INPUT_HANDLE;
+
+    pub(super) struct Mode {
+        pub(super) saved: Saved,
+    }
+
+    #[derive(Copy, Clone)]
+    pub(super) struct Saved {
+        handle: HANDLE,
+        mode: CONSOLE_MODE,
+    }
+
+    pub(super) fn enable() -> std::io::Result<Mode> {
+        // SAFETY: `GetStdHandle` has no preconditions and `GetConsoleMode` only writes to `mode`
+        let (handle, original) = unsafe {
+            let handle = GetStdHandle(STD_INPUT_HANDLE);
+            let mut mode = 0;
+            if GetConsoleMode(handle, &mut mode) == 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            (handle, mode)
+        };
+
+        let raw = (original & !(ENABLE_ECHO_INPUT | ENABLE_LINE_INPUT | ENABLE_PROCESSED_INPUT))
+            | ENABLE_VIRTUAL_TERMINAL_INPUT;
+        // SAFETY: `handle` is a console handle, from `GetConsoleMode` succeeding
+        if unsafe { SetConsoleMode(handle, raw) } == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        Ok(Mode {
+            saved: Saved {
+                handle,
+                mode: original,
+            },
+        })
+    }
+
+    pub(super) fn restore(saved: &Saved) -> std::io::Result<()> {
+        // SAFETY: `handle` is a console handle, from `GetConsoleMode` succeeding
+        if unsafe { SetConsoleMode(saved.handle, saved.mode) } == 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(unix, windows, test)))]
+mod imp {
+    pub(super) struct Mode {
+        pub(super) saved: Saved,
+    }
+
+    #[derive(Copy, Clone)]
+    pub(super) struct Saved;
+
+    pub(super) fn enable() -> std::io::Result<Mode> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "raw mode isn't supported on this platform",
+        ))
+    }
+
+    pub(super) fn restore(_saved: &Saved) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Count calls instead of changing the terminal's mode
+#[cfg(test)]
+mod imp {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    pub(super) static ENABLED: AtomicUsize = AtomicUsize::new(0);
+    pub(super) static RESTORED: AtomicUsize = AtomicUsize::new(0);
+
+    pub(super) struct Mode {
+        pub(super) saved: Saved,
+    }
+
+    #[derive(Copy, Clone)]
+    pub(super) struct Saved;
+
+    pub(super) fn enable() -> std::io::Result<Mode> {
+        ENABLED.fetch_add(1, Ordering::SeqCst);
+        Ok(Mode { saved: Saved })
+    }
+
+    pub(super) fn restore(_saved: &Saved) -> std::io::Result<()> {
+        RESTORED.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Tests share the raw mode state
+    static SERIAL: Mutex<()> = Mutex::new(());
+
+    fn counts() -> (usize, usize) {
+        (
+            imp::ENABLED.swap(0, Ordering::SeqCst),
+            imp::RESTORED.swap(0, Ordering::SeqCst),
+        )
+    }
+
+    #[test]
+    fn nesting() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        counts();
+
+        let outer = RawMode::enable().unwrap();
+        let inner = RawMode::enable().unwrap();
+        assert_eq!(counts(), (1, 0));
+        drop(inner);
+        assert_eq!(counts(), (0, 0));
+        outer.disable().unwrap();
+        assert_eq!(counts(), (0, 1));
+
+        drop(RawMode::enable().unwrap());
+        assert_eq!(counts(), (1, 1));
+    }
+
+    #[test]
+    fn panic_restores_once() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        counts();
+
+        let outer = RawMode::enable().unwrap();
+        let inner = RawMode::enable().unwrap();
+        restore_for_panic();
+        assert_eq!(counts(), (1, 1));
+        drop(inner);
+        drop(outer);
+        assert_eq!(counts(), (0, 0));
+
+        restore_for_panic();
+        assert_eq!(counts(), (0, 0));
+    }
+
+    #[test]
+    #[cfg(feature = "restore")]
+    fn exit_restores_once() {
+        let _serial = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+        counts();
+
+        let raw = RawMode::enable().unwrap();
+        restore_for_exit();
+        restore_for_exit();
+        assert_eq!(counts(), (1, 1));
+        drop(raw);
+        assert_eq!(counts(), (0, 1));
+        restore_for_exit();
+        assert_eq!(counts(), (0, 0));
+    }
+}
@@ -0,0 +1,297 @@
+use crate::stream::AsLockedWrite;
+use crate::stream::RawStream;
+
+/// Pass data through as-is, resetting any style left active when dropped
+#[derive(Debug)]
+pub(crate) struct ResetStream<S>
+where
+    S: RawStream,
+{
+    // Only `None` after `into_inner`
+    raw: Option<S>,
+    parser: anstyle_parse::Parser,
+    state: SgrState,
+}
+
+impl<S> ResetStream<S>
+where
+    S: RawStream,
+{
+    #[inline]
+    pub(crate) fn new(raw: S) -> Self {
+        Self {
+            raw: Some(raw),
+            parser: Default::default(),
+            state: Default::default(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn into_inner(mut self) -> S {
+        self.raw.take().expect("only taken on `into_inner`")
+    }
+
+    #[inline]
+    pub(crate) fn get_ref(&self) -> &S {
+        self.raw()
+    }
+
+    #[inline]
+    pub(crate) fn get_mut(&mut self) -> &mut S {
+        self.raw_mut()
+    }
+
+    #[inline]
+    pub(crate) fn is_terminal(&self) -> bool {
+        self.raw().is_terminal()
+    }
+
+    #[inline]
+    fn raw(&self) -> &S {
+        self.raw.as_ref().expect("only taken on `into_inner`")
+    }
+
+    #[inline]
+    fn raw_mut(&mut self) -> &mut S {
+        self.raw.as_mut().expect("only taken on `into_inner`")
+    }
+}
+
+impl ResetStream<std::io::Stdout> {
+    #[inline]
+    pub(crate) fn lock(mut self) -> ResetStream<std::io::StdoutLock<'static>> {
+        ResetStream {
+            raw: self.raw.take().map(|raw| raw.lock()),
+            parser: std::mem::take(&mut self.parser),
+            state: self.state,
+        }
+    }
+}
+
+impl ResetStream<std::io::Stderr> {
+    #[inline]
+    pub(crate) fn lock(mut self) -> ResetStream<std::io::StderrLock<'static>> {
+        ResetStream {
+            raw: self.raw.take().map(|raw| raw.lock()),
+            parser: std::mem::take(&mut self.parser),
+            state: self.state,
+        }
+    }
+}
+
+impl<S> std::io::Write for ResetStream<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    // Must forward all calls to ensure locking happens appropriately
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.raw_mut().as_locked_write().write(buf)?;
+        self.track(&buf[..written]);
+        Ok(written)
+    }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+        let written = self.raw_mut().as_locked_write().write_vectored(bufs)?;
+        let mut remaining = written;
+        for buf in bufs {
+            if remaining == 0 {
+                break;
+            }
+            let consumed = remaining.min(buf.len());
+            self.track(&buf[..consumed]);
+            remaining -= consumed;
+        }
+        Ok(written)
+    }
+    // is_write_vectored: nightly only
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.raw_mut().as_locked_write().flush()
+    }
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        self.raw_mut().as_locked_write().write_all(buf)?;
+        self.track(buf);
+        Ok(())
+    }
+    // write_all_vectored: nightly only
+    #[inline]
+    fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
+        let Self { raw, parser, state } = self;
+        let mut raw = raw
+            .as_mut()
+            .expect("only taken on `into_inner`")
+            .as_locked_write();
+        let write_all = |buf: &[u8]| {
+            raw.write_all(buf)?;
+            for byte in buf {
+                parser.advance(state, *byte);
+            }
+            Ok(())
+        };
+        crate::fmt::Adapter::new(write_all).write_fmt(args)
+    }
+}
+
+impl<S> ResetStream<S>
+where
+    S: RawStream,
+{
+    fn track(&mut self, buf: &[u8]) {
+        for byte in buf {
+            self.parser.advance(&mut self.state, *byte);
+        }
+    }
+}
+
+impl<S> Drop for ResetStream<S>
+where
+    S: RawStream,
+{
+    fn drop(&mut self) {
+        if let Some(raw) = self.raw.as_mut() {
+            if self.state.is_active() {
+                // Ignore errors as there is no way to report them
+                let _ = raw.write_all(anstyle::Reset.render().to_string().as_bytes());
+                let _ = raw.flush();
+            }
+        }
+    }
+}
+
+/// Track the SGR state of the terminal
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+pub(crate) struct SgrState {
+    style: anstyle::Style,
+    /// Whether an SGR code was seen that can't be represented by [`anstyle::Style`]
+    unknown: bool,
+}
+
+impl SgrState {
+    pub(crate) fn is_active(&self) -> bool {
+        !self.style.is_plain() || self.unknown
+    }
+
+    /// The active style, leaving out any SGR codes it can't represent
+    pub(crate) fn style(&self) -> anstyle::Style {
+        self.style
+    }
+}
+
+impl anstyle_parse::Perform for SgrState {
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        if ignore || action != b'm' || !intermediates.is_empty() {
+            return;
+        }
+
+        let (style, known) =
+            anstyle_parse::apply_sgr(self.style, params, anstyle::Sgr21::default());
+        let reset = anstyle_parse::is_sgr_reset(params);
+        self.style = style;
+        self.unknown = !known || (self.unknown && !reset);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[derive(Clone, Default)]
+    struct Shared(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write_vectored(bufs)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Shared {
+        fn stream(&self) -> ResetStream<Box<dyn std::io::Write>> {
+            ResetStream::new(Box::new(self.clone()))
+        }
+
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    #[track_caller]
+    fn verify(input: &str, expected: &str) {
+        let buffer = Shared::default();
+        let mut stream = buffer.stream();
+        stream.write_all(input.as_bytes()).unwrap();
+        drop(stream);
+        assert_eq!(buffer.contents(), expected);
+    }
+
+    #[test]
+    fn plain() {
+        verify("Hello world!", "Hello world!");
+    }
+
+    #[test]
+    fn reset() {
+        verify(
+            "\x1b[1;31mHello\x1b[0m world!",
+            "\x1b[1;31mHello\x1b[0m world!",
+        );
+        verify(
+            "\x1b[1;31mHello\x1b[22;39m world!",
+            "\x1b[1;31mHello\x1b[22;39m world!",
+        );
+    }
+
+    #[test]
+    fn active() {
+        verify("\x1b[1;31mHello world!", "\x1b[1;31mHello world!\x1b[0m");
+        verify("\x1b[53mHello world!", "\x1b[53mHello world!\x1b[0m");
+        // The `0` of a color isn't a reset
+        verify(
+            "\x1b[53m\x1b[38;5;0mHello\x1b[39m world!",
+            "\x1b[53m\x1b[38;5;0mHello\x1b[39m world!\x1b[0m",
+        );
+    }
+
+    #[test]
+    fn write_fmt() {
+        let buffer = Shared::default();
+        let mut stream = buffer.stream();
+        write!(
+            stream,
+            "{}Hello world!",
+            anstyle::AnsiColor::Red.render_fg()
+        )
+        .unwrap();
+        drop(stream);
+        assert_eq!(buffer.contents(), "\x1b[31mHello world!\x1b[0m");
+    }
+
+    #[test]
+    fn write_vectored() {
+        let buffer = Shared::default();
+        let mut stream = buffer.stream();
+        let bufs = [
+            std::io::IoSlice::new(b"\x1b[1;3"),
+            std::io::IoSlice::new(b"1mHello"),
+            std::io::IoSlice::new(b" world!"),
+        ];
+        let written = stream.write_vectored(&bufs).unwrap();
+        assert_eq!(written, 19);
+        drop(stream);
+        assert_eq!(buffer.contents(), "\x1b[1;31mHello world!\x1b[0m");
+    }
+}
@@ -0,0 +1,397 @@
+use std::sync::atomic::AtomicU8;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+/// Restore the terminal when the process exits, including from signals like Ctrl-C
+///
+/// Without this, a process killed while a style is active leaves the user's shell styled.
+/// [`Restore::install`] registers `atexit` and signal (Unix) or console control (Windows)
+/// handlers that write the selected escape codes to stdout and stderr, if they are terminals.
+/// Other signal handlers are preserved and called afterwards, and ignored signals stay ignored.
+///
+/// The handlers only write while a style or screen mode is active: while a [`RestoreGuard`] from
+/// [`Restore::arm`] is alive or an [`AlternateScreen`][crate::AlternateScreen] is entered, which
+/// is then also left.  With the `raw-mode` feature, they also restore the mode changed by any
+/// active `RawMode`.
+///
+/// # Examples
+///
+/// ```
+/// anstream::Restore::new().install();
+///
+/// let guard = anstream::Restore::arm();
+/// // Write styled output
+/// drop(guard);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Restore {
+    sequence: u8,
+}
+
+impl Restore {
+    /// Reset the style and show the cursor
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            sequence: RESET_STYLE | SHOW_CURSOR,
+        }
+    }
+
+    /// Register the handlers, or update what they restore if already registered
+    pub fn install(self) {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+
+        let mut terminals = 0;
+        if std::io::IsTerminal::is_terminal(&std::io::stdout()) {
+            terminals |= STDOUT;
+        }
+        if std::io::IsTerminal::is_terminal(&std::io::stderr()) {
+            terminals |= STDERR;
+        }
+        TERMINALS.store(terminals, Ordering::SeqCst);
+        SEQUENCE.store(self.sequence, Ordering::SeqCst);
+
+        INSTALL.call_once(|| {
+            // SAFETY: `restore_at_exit` only does async-signal-safe writes
+            unsafe {
+                libc::atexit(restore_at_exit);
+            }
+            imp::install();
+        });
+    }
+
+    /// Restore the terminal if the process exits before the returned guard is dropped
+    ///
+    /// Hold this while a style or screen mode written outside of an
+    /// [`AlternateScreen`][crate::AlternateScreen] is active.  Guards nest.
+    #[inline]
+    pub fn arm() -> RestoreGuard {
+        ARMED.fetch_add(1, Ordering::SeqCst);
+        RestoreGuard { _private: () }
+    }
+}
+
+impl Default for Restore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps [`Restore`]'s handlers writing until dropped, from [`Restore::arm`]
+#[derive(Debug)]
+pub struct RestoreGuard {
+    _private: (),
+}
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        ARMED.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+const RESET_STYLE: u8 = 1 << 0;
+const SHOW_CURSOR: u8 = 1 << 1;
+const LEAVE_ALTERNATE_SCREEN: u8 = 1 << 2;
+
+const STDOUT: u8 = 1 << 0;
+const STDERR: u8 = 1 << 1;
+
+static SEQUENCE: AtomicU8 = AtomicU8::new(0);
+static TERMINALS: AtomicU8 = AtomicU8::new(0);
+/// How many [`RestoreGuard`]s are alive
+static ARMED: AtomicUsize = AtomicUsize::new(0);
+
+extern "C" fn restore_at_exit() {
+    restore();
+}
+
+/// Restore the terminal without allocating or locking, for use from signal handlers
+fn restore() {
+    #[cfg(feature = "raw-mode")]
+    crate::raw::restore_for_exit();
+
+    let sequence = active_sequence(
+        SEQUENCE.load(Ordering::SeqCst),
+        ARMED.load(Ordering::SeqCst) != 0,
+        crate::screen::is_entered(),
+    );
+    write_sequence(sequence, TERMINALS.load(Ordering::SeqCst), imp::write);
+}
+
+/// What to write given whether a [`RestoreGuard`] is alive and an
+/// [`AlternateScreen`][crate::AlternateScreen] is entered
+fn active_sequence(sequence: u8, armed: bool, entered: bool) -> u8 {
+    let mut active = 0;
+    if armed || entered {
+        active |= sequence;
+    }
+    if entered {
+        // Only when entered as leaving also restores the cursor position saved on entering
+        active |= LEAVE_ALTERNATE_SCREEN;
+    }
+    active
+}
+
+/// Write each part of `sequence` to each of `terminals`, as `write(stream, part, code)`
+fn write_sequence(sequence: u8, terminals: u8, mut write: impl FnMut(u8, u8, &[u8])) {
+    let parts = [
+        (RESET_STYLE, "\x1b[0m"),
+        (SHOW_CURSOR, "\x1b[?25h"),
+        (LEAVE_ALTERNATE_SCREEN, "\x1b[?1049l"),
+    ];
+    for stream in [STDOUT, STDERR] {
+        if terminals & stream == 0 {
+            continue;
+        }
+        for (part, code) in parts {
+            if sequence & part != 0 {
+                write(stream, part, code.as_bytes());
+            }
+        }
+    }
+}
+
+#[cfg(unix)]
+mod imp {
+    use std::sync::OnceLock;
+
+    const SIGNALS: [libc::c_int; 4] = [libc::SIGHUP, libc::SIGINT, libc::SIGQUIT, libc::SIGTERM];
+
+    static PREVIOUS: OnceLock<[libc::sigaction; SIGNALS.len()]> = OnceLock::new();
+
+    pub(super) fn write(stream: u8, _part: u8, bytes: &[u8]) {
+        let fd = if stream == super::STDOUT {
+            libc::STDOUT_FILENO
+        } else {
+            libc::STDERR_FILENO
+        };
+        // SAFETY: `bytes` is valid for its length; errors are ignored as there is no recourse
+        unsafe {
+            libc::write(fd, bytes.as_ptr().cast(), bytes.len());
+        }
+    }
+
+    pub(super) fn install() {
+        // SAFETY: `sigaction` is zero-initializable and only passed to `libc::sigaction`
+        let mut previous: [libc::sigaction; SIGNALS.len()] = unsafe { std::mem::zeroed() };
+        for (signal, previous) in SIGNALS.iter().zip(previous.iter_mut()) {
+            // SAFETY: querying the current handler has no side effects
+            unsafe {
+                libc::sigaction(*signal, std::ptr::null(), previous);
+            }
+        }
+        if PREVIOUS.set(previous).is_err() {
+            return;
+        }
+
+        for (signal, previous) in SIGNALS.iter().zip(previous) {
+            // Keep ignoring signals, like `SIGHUP` under `nohup`, including in child processes
+            if previous.sa_sigaction == libc::SIG_IGN {
+                continue;
+            }
+            // SAFETY: `on_signal` only does async-signal-safe operations
+            unsafe {
+                let mut action: libc::sigaction = std::mem::zeroed();
+                action.sa_sigaction = on_signal as *const () as libc::sighandler_t;
+                action.sa_flags = libc::SA_SIGINFO;
+                libc::sigemptyset(&mut action.sa_mask);
+                libc::sigaction(*signal, &action, std::ptr::null_mut());
+            }
+        }
+    }
+
+    extern "C" fn on_signal(
+        signal: libc::c_int,
+        info: *mut libc::siginfo_t,
+        context: *mut libc::c_void,
+    ) {
+        super::restore();
+
+        let previous = PREVIOUS.get().and_then(|previous| {
+            SIGNALS
+                .iter()
+                .position(|s| *s == signal)
+                .map(|i| previous[i])
+        });
+        let Some(previous) = previous else {
+            return;
+        };
+        // SAFETY: calling the handler the way it was registered
+        unsafe {
+            match previous.sa_sigaction {
+                libc::SIG_IGN => {}
+                libc::SIG_DFL => {
+                    // Terminate the way the signal normally would
+                    libc::sigaction(signal, &previous, std::ptr::null_mut());
+                    libc::raise(signal);
+                }
+                handler if previous.sa_flags & libc::SA_SIGINFO != 0 => {
+                    let handler: extern "C" fn(
+                        libc::c_int,
+                        *mut libc::siginfo_t,
+                        *mut libc::c_void,
+                    ) = std::mem::transmute(handler);
+                    handler(signal, info, context);
+                }
+                handler => {
+                    let handler: extern "C" fn(libc::c_int) = std::mem::transmute(handler);
+                    handler(signal);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod imp {
+    use std::sync::atomic::AtomicU16;
+    use std::sync::atomic::Ordering;
+
+    use windows_sys::Win32::Foundation::BOOL;
+    use windows_sys::Win32::Foundation::FALSE;
+    use windows_sys::Win32::Foundation::HANDLE;
+    use windows_sys::Win32::Foundation::TRUE;
+    use windows_sys::Win32::System::Console;
+
+    /// The attributes of stdout and stderr at install, for consoles without escape codes
+    static INITIAL_ATTRIBUTES: [AtomicU16; 2] = [AtomicU16::new(0), AtomicU16::new(0)];
+
+    fn handle(stream: u8) -> HANDLE {
+        let handle = if stream == super::STDOUT {
+            Console::STD_OUTPUT_HANDLE
+        } else {
+            Console::STD_ERROR_HANDLE
+        };
+        // SAFETY: `GetStdHandle` has no preconditions
+        unsafe { Console::GetStdHandle(handle) }
+    }
+
+    fn index(stream: u8) -> usize {
+        usize::from(stream != super::STDOUT)
+    }
+
+    pub(super) fn write(stream: u8, part: u8, bytes: &[u8]) {
+        let handle = handle(stream);
+        let mut mode = 0;
+        // SAFETY: `GetConsoleMode` only writes to `mode`
+        let has_mode = unsafe { Console::GetConsoleMode(handle, &mut mode) } != 0;
+        if has_mode && mode & Console::ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+            let mut written = 0;
+            // SAFETY: `bytes` is valid for its length; errors are ignored as there is no recourse
+            unsafe {
+                Console::WriteConsoleA(
+                    handle,
+                    bytes.as_ptr().cast(),
+                    bytes.len() as u32,
+                    &mut written,
+                    std::ptr::null(),
+                );
+            }
+            return;
+        }
+
+        // Legacy consoles would print escape codes as text, so use the console API instead.
+        // They don't have an alternate screen to leave.
+        // SAFETY: `handle` is valid, if not a console, and errors are ignored as there is no
+        // recourse
+        unsafe {
+            match part {
+                super::RESET_STYLE => {
+                    let attributes = INITIAL_ATTRIBUTES[index(stream)].load(Ordering::SeqCst);
+                    Console::SetConsoleTextAttribute(handle, attributes);
+                }
+                super::SHOW_CURSOR => {
+                    let mut info: Console::CONSOLE_CURSOR_INFO = std::mem::zeroed();
+                    if Console::GetConsoleCursorInfo(handle, &mut info) != 0 {
+                        info.bVisible = TRUE;
+                        Console::SetConsoleCursorInfo(handle, &info);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub(super) fn install() {
+        for stream in [super::STDOUT, super::STDERR] {
+            // SAFETY: `GetConsoleScreenBufferInfo` only writes to `info`
+            unsafe {
+                let mut info: Console::CONSOLE_SCREEN_BUFFER_INFO = std::mem::zeroed();
+                if Console::GetConsoleScreenBufferInfo(handle(stream), &mut info) != 0 {
+                    INITIAL_ATTRIBUTES[index(stream)].store(info.wAttributes, Ordering::SeqCst);
+                }
+            }
+        }
+        // SAFETY: `on_control` is valid for the life of the process
+        unsafe {
+            Console::SetConsoleCtrlHandler(Some(on_control), TRUE);
+        }
+    }
+
+    unsafe extern "system" fn on_control(_control: u32) -> BOOL {
+        super::restore();
+        // Let the next handler, ultimately the default one, run
+        FALSE
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn written(sequence: u8, terminals: u8) -> Vec<(u8, String)> {
+        let mut written = Vec::new();
+        write_sequence(sequence, terminals, |stream, _part, code| {
+            written.push((stream, String::from_utf8(code.to_vec()).unwrap()));
+        });
+        written
+    }
+
+    #[test]
+    fn default_sequence() {
+        assert_eq!(
+            written(Restore::new().sequence, STDOUT | STDERR),
+            [
+                (STDOUT, "\x1b[0m".to_owned()),
+                (STDOUT, "\x1b[?25h".to_owned()),
+                (STDERR, "\x1b[0m".to_owned()),
+                (STDERR, "\x1b[?25h".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn alternate_screen() {
+        let sequence = active_sequence(Restore::new().sequence, false, true);
+        assert_eq!(
+            written(sequence, STDERR),
+            [
+                (STDERR, "\x1b[0m".to_owned()),
+                (STDERR, "\x1b[?25h".to_owned()),
+                (STDERR, "\x1b[?1049l".to_owned()),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_while_active() {
+        let sequence = Restore::new().sequence;
+        assert_eq!(active_sequence(sequence, true, false), sequence);
+        assert_eq!(active_sequence(sequence, false, false), 0);
+    }
+
+    #[test]
+    fn no_terminals() {
+        assert_eq!(written(Restore::new().sequence, 0), []);
+    }
+
+    #[test]
+    fn arm_nests() {
+        let outer = Restore::arm();
+        let inner = Restore::arm();
+        drop(inner);
+        assert_eq!(ARMED.load(Ordering::SeqCst), 1);
+        drop(outer);
+        assert_eq!(ARMED.load(Ordering::SeqCst), 0);
+    }
+}
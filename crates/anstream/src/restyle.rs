@@ -0,0 +1,256 @@
+use crate::reset::SgrState;
+
+/// Keep each write's styling self-contained, for writers sharing a terminal
+///
+/// The style left active by earlier writes is re-applied at the start of each write and reset at
+/// the end of it.  Output from other writers landing in between, like log lines or a progress
+/// bar's carriage returns and resets, neither picks up this stream's style nor clears it.
+///
+/// Only styles representable by [`anstyle::Style`] are re-applied.  A write ending partway
+/// through an escape code or character isn't reset, and the next write isn't restyled, so the
+/// code isn't split.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let red = anstyle::AnsiColor::Red.on_default();
+/// let mut stream = anstream::RestyleStream::new(Vec::new());
+/// write!(stream, "{}warning: ", red.render()).unwrap();
+/// // Another writer interrupts
+/// stream.get_mut().extend_from_slice(b"\x1b[0m\rdownloading...\n");
+/// write!(stream, "disk almost full{}", red.render_reset()).unwrap();
+/// let buffer = stream.into_inner();
+/// assert_eq!(
+///     buffer,
+///     b"\x1b[31mwarning: \x1b[0m\x1b[0m\rdownloading...\n\x1b[31mdisk almost full\x1b[0m"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct RestyleStream<S>
+where
+    S: std::io::Write,
+{
+    raw: S,
+    parser: anstyle_parse::Parser,
+    state: SgrState,
+    /// Whether the last write ended partway through an escape code or character
+    partial: bool,
+}
+
+impl<S> RestyleStream<S>
+where
+    S: std::io::Write,
+{
+    /// Start with no style active
+    #[inline]
+    pub fn new(raw: S) -> Self {
+        Self {
+            raw,
+            parser: Default::default(),
+            state: Default::default(),
+            partial: false,
+        }
+    }
+
+    /// The style re-applied at the start of the next write
+    #[inline]
+    pub fn current(&self) -> anstyle::Style {
+        self.state.style()
+    }
+
+    /// Get the wrapped `Write`
+    ///
+    /// Nothing is left active on it as each write is already reset.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.raw
+    }
+
+    /// Get a reference to the wrapped `Write`
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.raw
+    }
+
+    /// Get a mutable reference to the wrapped `Write`
+    ///
+    /// Writing to it directly is treated like output from another writer.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.raw
+    }
+
+    fn restyle(&mut self) -> std::io::Result<()> {
+        self.current().write_to(&mut self.raw)
+    }
+
+    fn reset(&mut self) -> std::io::Result<()> {
+        if self.state.is_active() {
+            write!(self.raw, "{}", anstyle::Reset.render())?;
+        }
+        Ok(())
+    }
+}
+
+impl<S> std::io::Write for RestyleStream<S>
+where
+    S: std::io::Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.write_all(buf)?;
+        Ok(buf.len())
+    }
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.raw.flush()
+    }
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        if !self.partial {
+            self.restyle()?;
+        }
+        self.raw.write_all(buf)?;
+        for byte in buf {
+            self.partial = advance(&mut self.parser, &mut self.state, *byte);
+        }
+        if !self.partial {
+            self.reset()?;
+        }
+        Ok(())
+    }
+    fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
+        if !self.partial {
+            self.restyle()?;
+        }
+        let Self {
+            raw,
+            parser,
+            state,
+            partial,
+        } = self;
+        let write_all = |buf: &[u8]| {
+            raw.write_all(buf)?;
+            for byte in buf {
+                *partial = advance(parser, state, *byte);
+            }
+            Ok(())
+        };
+        crate::fmt::Adapter::new(write_all).write_fmt(args)?;
+        if !self.partial {
+            self.reset()?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse `byte`, returning whether it left an escape code or character unfinished
+fn advance(parser: &mut anstyle_parse::Parser, state: &mut SgrState, byte: u8) -> bool {
+    let mut performer = Performer {
+        state,
+        finished: false,
+    };
+    parser.advance(&mut performer, byte);
+    !performer.finished
+}
+
+struct Performer<'s> {
+    state: &'s mut SgrState,
+    finished: bool,
+}
+
+impl anstyle_parse::Perform for Performer<'_> {
+    fn print(&mut self, _c: char) {
+        self.finished = true;
+    }
+
+    fn execute(&mut self, _byte: u8) {
+        self.finished = true;
+    }
+
+    fn unhook(&mut self) {
+        self.finished = true;
+    }
+
+    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {
+        self.finished = true;
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        self.state
+            .csi_dispatch(params, intermediates, ignore, action);
+        self.finished = true;
+    }
+
+    fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, _byte: u8) {
+        self.finished = true;
+    }
+
+    fn sos_pm_apc_end(&mut self) {
+        self.finished = true;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn plain() {
+        let mut stream = RestyleStream::new(Vec::new());
+        stream.write_all(b"Hello").unwrap();
+        stream.write_all(b" world!").unwrap();
+        assert_eq!(stream.into_inner(), b"Hello world!");
+    }
+
+    #[test]
+    fn each_write_is_isolated() {
+        let mut stream = RestyleStream::new(Vec::new());
+        stream.write_all(b"\x1b[1;31mHello").unwrap();
+        assert_eq!(
+            stream.current(),
+            anstyle::AnsiColor::Red.on_default().bold()
+        );
+        stream.write_all(b" world").unwrap();
+        stream.write_all(b"\x1b[22m!\x1b[0m").unwrap();
+        assert_eq!(stream.current(), anstyle::Style::new());
+        stream.write_all(b"\n").unwrap();
+        assert_eq!(
+            String::from_utf8(stream.into_inner()).unwrap(),
+            "\x1b[1;31mHello\x1b[0m\x1b[1m\x1b[31m world\x1b[0m\x1b[1m\x1b[31m\x1b[22m!\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn split_escape_code() {
+        let mut stream = RestyleStream::new(Vec::new());
+        stream.write_all(b"\x1b[1mA\x1b[3").unwrap();
+        stream.write_all(b"1mB").unwrap();
+        stream.write_all(b"\xc3").unwrap();
+        stream.write_all(b"\xa9").unwrap();
+        assert_eq!(
+            String::from_utf8(stream.into_inner()).unwrap(),
+            "\x1b[1mA\x1b[31mB\x1b[0m\x1b[1m\x1b[31mé\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn write_fmt() {
+        let mut stream = RestyleStream::new(Vec::new());
+        let red = anstyle::AnsiColor::Red.on_default();
+        write!(stream, "{}a", red.render()).unwrap();
+        write!(stream, "b").unwrap();
+        assert_eq!(
+            String::from_utf8(stream.into_inner()).unwrap(),
+            "\x1b[31ma\x1b[0m\x1b[31mb\x1b[0m"
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use anstyle::Style;
+
+use crate::LinkFallback;
+
+pub(crate) type Restyle = Box<dyn FnMut(Style, &str) -> Style>;
+
+/// Re-render styled output, passing it through `restyle`
+pub(crate) struct Restyler<R = Restyle> {
+    parser: anstyle_parse::Parser,
+    capture: Capture,
+    /// Bytes of the sequence being parsed
+    raw: Vec<u8>,
+    /// Text in `capture.style`, not yet written
+    run: String,
+    /// The style last written
+    written: Style,
+    restyle: R,
+    /// How to rewrite hyperlinks, if at all
+    links: Option<LinkFallback>,
+    /// The hyperlink being rewritten
+    link: Option<Link>,
+    /// Whether the last sequence was a rewritten hyperlink
+    after_link: bool,
+}
+
+impl<R> Restyler<R>
+where
+    R: FnMut(Style, &str) -> Style,
+{
+    pub(crate) fn new(restyle: R) -> Self {
+        Self {
+            parser: Default::default(),
+            capture: Default::default(),
+            raw: Vec::new(),
+            run: String::new(),
+            written: Style::new(),
+            restyle,
+            links: None,
+            link: None,
+            after_link: false,
+        }
+    }
+
+    /// Rewrite OSC 8 hyperlinks as text, rather than passing them through
+    pub(crate) fn set_links(&mut self, links: Option<LinkFallback>) {
+        self.links = links;
+    }
+
+    /// Interpret SGR 21 as `sgr21`, rendering styles for it too
+    pub(crate) fn set_sgr21(&mut self, sgr21: anstyle::Sgr21) {
+        self.capture.sgr21 = sgr21;
+    }
+
+    pub(crate) fn advance(
+        &mut self,
+        write: &mut dyn std::io::Write,
+        bytes: &[u8],
+    ) -> std::io::Result<()> {
+        for byte in bytes {
+            self.raw.push(*byte);
+            self.parser.advance(&mut self.capture, *byte);
+            let event = std::mem::take(&mut self.capture.event);
+            if matches!(event, Event::None) {
+                continue;
+            }
+            let after_link = std::mem::take(&mut self.after_link);
+            match event {
+                Event::None => {}
+                Event::Text(c) => {
+                    self.run.push(c);
+                    if let Some(link) = &mut self.link {
+                        link.text.push(c);
+                    }
+                    self.raw.clear();
+                }
+                Event::Link(url) if self.links.is_some() => {
+                    self.link_to(url);
+                    self.after_link = true;
+                    self.raw.clear();
+                }
+                // The `\\` ending a link's `ESC \\` string terminator
+                Event::Terminator if after_link => {
+                    self.raw.clear();
+                }
+                Event::Style(style) => {
+                    self.flush(write)?;
+                    self.capture.style = style;
+                    self.raw.clear();
+                }
+                Event::Link(_) | Event::Terminator | Event::Other => {
+                    self.flush(write)?;
+                    write.write_all(&self.raw)?;
+                    self.raw.clear();
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// End the current hyperlink, if any, and start one to `url` unless it is empty
+    fn link_to(&mut self, url: String) {
+        if let Some(link) = self.link.take() {
+            let show_url = self.links == Some(LinkFallback::TextAndUrl)
+                && !link.url.is_empty()
+                && link.text != link.url;
+            if show_url {
+                self.run.push_str(" (");
+                self.run.push_str(&link.url);
+                self.run.push(')');
+            }
+        }
+        if !url.is_empty() {
+            self.link = Some(Link {
+                url,
+                text: String::new(),
+            });
+        }
+    }
+
+    /// Write the pending run of text
+    pub(crate) fn flush(&mut self, write: &mut dyn std::io::Write) -> std::io::Result<()> {
+        if self.run.is_empty() {
+            return Ok(());
+        }
+        let style = (self.restyle)(self.capture.style, &self.run);
+        self.write_style(write, style)?;
+        write.write_all(self.run.as_bytes())?;
+        self.run.clear();
+        Ok(())
+    }
+
+    /// Write the pending run of text and the active style, even without any text in it
+    pub(crate) fn flush_style(&mut self, write: &mut dyn std::io::Write) -> std::io::Result<()> {
+        self.flush(write)?;
+        let style = (self.restyle)(self.capture.style, "");
+        self.write_style(write, style)
+    }
+
+    fn write_style(&mut self, write: &mut dyn std::io::Write, style: Style) -> std::io::Result<()> {
+        if style != self.written {
+            self.written.write_reset_to(write)?;
+            write!(write, "{}", style.render_with(self.capture.sgr21))?;
+            self.written = style;
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(not(all(unix, feature = "pty")), allow(dead_code))]
+    pub(crate) fn finish(&mut self, write: &mut dyn std::io::Write) -> std::io::Result<()> {
+        self.flush(write)?;
+        self.written.write_reset_to(write)?;
+        self.written = Style::new();
+        Ok(())
+    }
+}
+
+struct Link {
+    url: String,
+    text: String,
+}
+
+#[derive(Default)]
+struct Capture {
+    style: Style,
+    sgr21: anstyle::Sgr21,
+    event: Event,
+}
+
+#[derive(Default)]
+enum Event {
+    #[default]
+    None,
+    Text(char),
+    Style(Style),
+    /// An OSC 8 hyperlink to the URL, ending the link when it is empty
+    Link(String),
+    /// `ESC \\`, ending a string like OSC
+    Terminator,
+    Other,
+}
+
+impl anstyle_parse::Perform for Capture {
+    fn print(&mut self, c: char) {
+        self.event = Event::Text(c);
+    }
+
+    fn execute(&mut self, _byte: u8) {
+        self.event = Event::Other;
+    }
+
+    fn hook(
+        &mut self,
+        _params: &anstyle_parse::Params,
+        _intermediates: &[u8],
+        _ignore: bool,
+        _action: u8,
+    ) {
+    }
+
+    fn unhook(&mut self) {
+        self.event = Event::Other;
+    }
+
+    fn osc_dispatch(&mut self, params: &[&[u8]], _bell_terminated: bool) {
+        self.event = match params {
+            // `8 ; params ; url`, where the URL can have its own `;`s
+            [b"8", _, url @ ..] if !url.is_empty() => {
+                let url = url.join(&b';');
+                Event::Link(String::from_utf8_lossy(&url).into_owned())
+            }
+            _ => Event::Other,
+        };
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &anstyle_parse::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: u8,
+    ) {
+        self.event = if action == b'm' && intermediates.is_empty() && !ignore {
+            Event::Style(anstyle_parse::apply_sgr(self.style, params, self.sgr21).0)
+        } else {
+            Event::Other
+        };
+    }
+
+    fn esc_dispatch(&mut self, intermediates: &[u8], _ignore: bool, byte: u8) {
+        self.event = if byte == b'\\' && intermediates.is_empty() {
+            Event::Terminator
+        } else {
+            Event::Other
+        };
+    }
+}
@@ -0,0 +1,154 @@
+use std::io::Write as _;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+use crate::stream::AsLockedWrite;
+use crate::stream::RawStream;
+use crate::AutoStream;
+
+/// The alternate screen, left when dropped, from [`AutoStream::enter_alternate_screen`]
+///
+/// Write through the guard to draw on the alternate screen.
+#[derive(Debug)]
+pub struct AlternateScreen<'s, S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    stream: &'s mut AutoStream<S>,
+    /// Whether the stream passes escape codes through, so the screen was switched
+    entered: bool,
+    hidden_cursor: bool,
+}
+
+impl<'s, S> AlternateScreen<'s, S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    pub(crate) fn enter(stream: &'s mut AutoStream<S>, entered: bool) -> std::io::Result<Self> {
+        if entered {
+            stream.get_mut().write_all(b"\x1b[?1049h")?;
+            stream.get_mut().flush()?;
+            ENTERED.fetch_add(1, Ordering::SeqCst);
+        }
+        Ok(Self {
+            stream,
+            entered,
+            hidden_cursor: false,
+        })
+    }
+
+    /// Hide the cursor until the alternate screen is left
+    pub fn hide_cursor(&mut self) -> std::io::Result<()> {
+        if self.entered && !self.hidden_cursor {
+            self.stream.get_mut().write_all(b"\x1b[?25l")?;
+            self.hidden_cursor = true;
+        }
+        Ok(())
+    }
+
+    /// Get the [`AutoStream`] drawn on
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut AutoStream<S> {
+        self.stream
+    }
+
+    fn leave(&mut self) -> std::io::Result<()> {
+        if !self.entered {
+            return Ok(());
+        }
+        self.entered = false;
+        let raw = self.stream.get_mut();
+        if self.hidden_cursor {
+            raw.write_all(b"\x1b[?25h")?;
+        }
+        // Already left when a panic message was printed
+        let last = ENTERED
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .map(|n| n == 1)
+            .unwrap_or(false);
+        if last {
+            raw.write_all(b"\x1b[?1049l")?;
+        }
+        raw.flush()
+    }
+}
+
+impl<S> std::io::Write for AlternateScreen<'_, S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.stream.write(buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+impl<S> Drop for AlternateScreen<'_, S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    fn drop(&mut self) {
+        // Ignore errors as there is no way to report them
+        let _ = self.stream.flush();
+        let _ = self.leave();
+    }
+}
+
+/// How many [`AlternateScreen`]s are active
+static ENTERED: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether any [`AlternateScreen`] is active
+#[cfg(feature = "restore")]
+pub(crate) fn is_entered() -> bool {
+    ENTERED.load(Ordering::SeqCst) != 0
+}
+
+/// Leave the alternate screen, if entered, so a panic message stays visible
+///
+/// Any active [`AlternateScreen`] won't leave it again when dropped.
+pub(crate) fn leave_for_panic(write: &mut dyn std::io::Write) -> std::io::Result<()> {
+    if ENTERED.load(Ordering::SeqCst) != 0 {
+        write.write_all(b"\x1b[?1049l")?;
+        write.flush()?;
+        // Only once left, so the guards can still leave if this failed
+        ENTERED.store(0, Ordering::SeqCst);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Write as _;
+
+    use crate::AutoStream;
+
+    #[test]
+    fn enter_and_leave() {
+        let mut stream = AutoStream::always_ansi(Vec::new());
+        {
+            let mut screen = stream.enter_alternate_screen().unwrap();
+            screen.hide_cursor().unwrap();
+            write!(screen, "\x1b[Hdashboard").unwrap();
+        }
+        assert_eq!(
+            stream.into_inner(),
+            b"\x1b[?1049h\x1b[?25l\x1b[Hdashboard\x1b[?25h\x1b[?1049l"
+        );
+    }
+
+    #[test]
+    fn not_a_terminal() {
+        let mut stream = AutoStream::never(Vec::new());
+        {
+            let mut screen = stream.enter_alternate_screen().unwrap();
+            screen.hide_cursor().unwrap();
+            write!(screen, "\x1b[1mreport\x1b[0m").unwrap();
+        }
+        assert_eq!(stream.into_inner(), b"report");
+    }
+}
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use crate::stream::AsLockedWrite;
+use crate::stream::RawStream;
+use crate::AutoStream;
+
+/// Cloneable, thread-safe handle to an [`AutoStream`]
+///
+/// Each handle buffers its writes until a newline, writing only whole lines to the shared
+/// [`AutoStream`] so threads don't interleave output, or escape codes, mid-line.
+///
+/// Any trailing partial line is written on [`flush`][std::io::Write::flush] or when the handle
+/// is dropped.  As writes are buffered, an error writing a line is reported by the next write or
+/// flush, with the unwritten lines kept to be retried.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let stream = anstream::SharedStream::new(anstream::AutoStream::never(Vec::new()));
+/// let workers = (0..4)
+///     .map(|i| {
+///         let mut stream = stream.clone();
+///         std::thread::spawn(move || {
+///             let style = anstyle::AnsiColor::Green.on_default();
+///             writeln!(stream, "{}worker {i}{}", style.render(), style.render_reset()).unwrap();
+///         })
+///     })
+///     .collect::<Vec<_>>();
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+/// ```
+#[derive(Debug)]
+pub struct SharedStream<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    stream: Arc<Mutex<AutoStream<S>>>,
+    buffer: Vec<u8>,
+    /// Error from writing lines already accepted into `buffer`
+    error: Option<std::io::Error>,
+}
+
+impl<S> SharedStream<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    /// Share `stream` between handles
+    #[inline]
+    pub fn new(stream: AutoStream<S>) -> Self {
+        Self {
+            stream: Arc::new(Mutex::new(stream)),
+            buffer: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Get the wrapped [`AutoStream`], if this is the last handle to it
+    ///
+    /// Any partial line in this handle is written first.
+    pub fn into_inner(mut self) -> Option<AutoStream<S>> {
+        let _ = self.write_buffer(self.buffer.len());
+        let stream = self.stream.clone();
+        drop(self);
+        Arc::try_unwrap(stream)
+            .ok()
+            .map(|stream| stream.into_inner().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    /// Returns `true` if the descriptor/handle refers to a terminal/tty.
+    #[inline]
+    pub fn is_terminal(&self) -> bool {
+        self.stream
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_terminal()
+    }
+
+    fn write_buffer(&mut self, len: usize) -> std::io::Result<()> {
+        if len == 0 {
+            return Ok(());
+        }
+        let mut stream = self.stream.lock().unwrap_or_else(PoisonError::into_inner);
+        // Only drop what was written so the rest can be retried
+        let (written, result) = crate::fmt::write_prefix(&mut *stream, &self.buffer[..len]);
+        self.buffer.drain(..written);
+        result
+    }
+}
+
+impl<S> Clone for SharedStream<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    /// Create another handle to the same stream, with its own line buffer
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            stream: self.stream.clone(),
+            buffer: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+impl<S> std::io::Write for SharedStream<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.buffer.extend_from_slice(buf);
+        if let Some(newline) = self.buffer.iter().rposition(|b| *b == b'\n') {
+            // `buf` was accepted, so the error is left for the next call
+            if let Err(err) = self.write_buffer(newline + 1) {
+                self.error = Some(err);
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.write_buffer(self.buffer.len())?;
+        self.stream
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .flush()
+    }
+}
+
+impl<S> Drop for SharedStream<S>
+where
+    S: RawStream + AsLockedWrite,
+{
+    fn drop(&mut self) {
+        let _ = self.write_buffer(self.buffer.len());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn whole_lines() {
+        let stream = SharedStream::new(AutoStream::always(Vec::new()));
+        let workers = (0..8)
+            .map(|i| {
+                let mut stream = stream.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        write!(stream, "\x1b[3{i}m").unwrap();
+                        write!(stream, "thread {i}").unwrap();
+                        writeln!(stream, "\x1b[0m").unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for worker in workers {
+            worker.join().unwrap();
+        }
+
+        let buffer = stream.into_inner().unwrap().into_inner();
+        let buffer = String::from_utf8(buffer).unwrap();
+        assert_eq!(buffer.lines().count(), 800);
+        for line in buffer.lines() {
+            let i = &line[3..4];
+            assert_eq!(line, format!("\x1b[3{i}mthread {i}\x1b[0m"));
+        }
+    }
+
+    #[test]
+    fn partial_line_on_drop() {
+        let stream = SharedStream::new(AutoStream::never(Vec::new()));
+        let mut handle = stream.clone();
+        write!(handle, "\x1b[1mno newline\x1b[0m").unwrap();
+        drop(handle);
+
+        let buffer = stream.into_inner().unwrap().into_inner();
+        assert_eq!(buffer, b"no newline");
+    }
+
+    #[derive(Clone, Default)]
+    struct Flaky {
+        buffer: Arc<Mutex<Vec<u8>>>,
+        fail: Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl std::io::Write for Flaky {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.fail.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(std::io::ErrorKind::Other.into());
+            }
+            self.buffer.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn retry_after_error() {
+        let raw = Flaky::default();
+        let stream = SharedStream::new(AutoStream::always_ansi(
+            Box::new(raw.clone()) as Box<dyn std::io::Write>
+        ));
+        let mut handle = stream.clone();
+
+        raw.fail.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(handle.write(b"one\n").unwrap(), 4);
+        assert!(handle.write(b"two\n").is_err());
+        raw.fail.store(false, std::sync::atomic::Ordering::SeqCst);
+        handle.write_all(b"two\n").unwrap();
+        handle.flush().unwrap();
+
+        assert_eq!(*raw.buffer.lock().unwrap(), b"one\ntwo\n");
+    }
+}
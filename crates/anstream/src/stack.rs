@@ -0,0 +1,340 @@
+/// Nest styles, like bold inside of red inside of dimmed text
+///
+/// Each [`StyleStack::push_style`] layers a style on top of the active one: its effects are
+/// added and any colors it sets replace the enclosing ones.  [`StyleStack::pop`] returns to the
+/// enclosing style.  Escape codes are written to the inner `Write` as needed.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let mut stack = anstream::StyleStack::new(Vec::new());
+/// stack.push_style(anstyle::AnsiColor::Red.on_default()).unwrap();
+/// write!(stack, "error: ").unwrap();
+/// stack.push_style(anstyle::Style::new().bold()).unwrap();
+/// write!(stack, "file not found").unwrap();
+/// stack.pop().unwrap();
+/// stack.pop().unwrap();
+/// let buffer = stack.finish().unwrap();
+/// assert_eq!(
+///     buffer,
+///     b"\x1b[31merror: \x1b[1mfile not found\x1b[0m\x1b[31m\x1b[0m"
+/// );
+/// ```
+#[derive(Debug)]
+pub struct StyleStack<S>
+where
+    S: std::io::Write,
+{
+    raw: S,
+    stack: Vec<anstyle::Style>,
+    per_line: bool,
+    options: anstyle::RenderOptions,
+    // The style was reset at a newline and is re-applied before the next text
+    restyle: bool,
+}
+
+impl<S> StyleStack<S>
+where
+    S: std::io::Write,
+{
+    /// Start with no style active
+    #[inline]
+    pub fn new(raw: S) -> Self {
+        Self {
+            raw,
+            stack: Vec::new(),
+            per_line: false,
+            options: anstyle::RenderOptions::new(),
+            restyle: false,
+        }
+    }
+
+    /// Reset the active style before each newline written and re-apply it after
+    ///
+    /// `less -R`, `more`, and some CI log viewers reset the style at the end of each line, losing
+    /// it for the rest of a multi-line run of text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write as _;
+    ///
+    /// let mut stack = anstream::StyleStack::new(Vec::new()).per_line();
+    /// stack.push_style(anstyle::AnsiColor::Red.on_default()).unwrap();
+    /// write!(stack, "one\ntwo").unwrap();
+    /// let buffer = stack.finish().unwrap();
+    /// assert_eq!(buffer, b"\x1b[31mone\x1b[0m\n\x1b[31mtwo\x1b[0m");
+    /// ```
+    #[inline]
+    pub fn per_line(mut self) -> Self {
+        self.per_line = true;
+        self
+    }
+
+    /// Write escape codes in the forms chosen by `options`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Write as _;
+    ///
+    /// let options = anstyle::RenderOptions::new().colons(true);
+    /// let mut stack = anstream::StyleStack::new(Vec::new()).render_with(options);
+    /// stack.push_style(anstyle::Ansi256Color(208).on_default()).unwrap();
+    /// write!(stack, "orange").unwrap();
+    /// let buffer = stack.finish().unwrap();
+    /// assert_eq!(buffer, b"\x1b[38:5:208morange\x1b[0m");
+    /// ```
+    #[inline]
+    pub fn render_with(mut self, options: impl Into<anstyle::RenderOptions>) -> Self {
+        self.options = options.into();
+        self
+    }
+
+    /// Layer `style` on top of the active style
+    pub fn push_style(&mut self, style: anstyle::Style) -> std::io::Result<()> {
+        let layered = layer(self.current(), style);
+        if !self.restyle {
+            self.write_style(style)?;
+        }
+        self.stack.push(layered);
+        Ok(())
+    }
+
+    /// Return to the style active before the last [`StyleStack::push_style`]
+    ///
+    /// Does nothing if no style is active.
+    pub fn pop(&mut self) -> std::io::Result<()> {
+        let Some(popped) = self.stack.pop() else {
+            return Ok(());
+        };
+        let current = self.current();
+        if self.restyle {
+            self.restyle = current != anstyle::Style::new();
+        } else if popped != current {
+            popped.write_reset_to(&mut self.raw)?;
+            self.write_style(current)?;
+        }
+        Ok(())
+    }
+
+    /// The active style
+    #[inline]
+    pub fn current(&self) -> anstyle::Style {
+        self.stack.last().copied().unwrap_or_default()
+    }
+
+    /// The number of active [`StyleStack::push_style`] calls
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Reset any active style and get the wrapped `Write`
+    pub fn finish(mut self) -> std::io::Result<S> {
+        if !self.restyle {
+            self.current().write_reset_to(&mut self.raw)?;
+        }
+        self.stack.clear();
+        Ok(self.raw)
+    }
+
+    /// Get the wrapped `Write`, leaving any active style as-is
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.raw
+    }
+
+    /// Get a reference to the wrapped `Write`
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.raw
+    }
+
+    /// Get a mutable reference to the wrapped `Write`
+    ///
+    /// Writing to it directly bypasses the stack of styles.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.raw
+    }
+}
+
+impl<S> StyleStack<S>
+where
+    S: std::io::Write,
+{
+    fn write_style(&mut self, style: anstyle::Style) -> std::io::Result<()> {
+        write!(self.raw, "{}", style.render_with(self.options))
+    }
+
+    fn splits_lines(&self) -> bool {
+        self.per_line && self.current() != anstyle::Style::new()
+    }
+
+    fn write_line(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if buf.first() == Some(&b'\n') {
+            if !self.restyle {
+                self.current().write_reset_to(&mut self.raw)?;
+                self.restyle = true;
+            }
+            self.raw.write_all(b"\n")?;
+            return Ok(1);
+        }
+
+        if self.restyle && !buf.is_empty() {
+            self.write_style(self.current())?;
+            self.restyle = false;
+        }
+        match buf.iter().position(|b| *b == b'\n') {
+            Some(newline) => self.raw.write(&buf[..newline]),
+            None => self.raw.write(buf),
+        }
+    }
+
+    fn write_all_lines(&mut self, mut buf: &[u8]) -> std::io::Result<()> {
+        while !buf.is_empty() {
+            match self.write_line(buf) {
+                Ok(0) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        "failed to write whole buffer",
+                    ));
+                }
+                Ok(n) => buf = &buf[n..],
+                Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<S> std::io::Write for StyleStack<S>
+where
+    S: std::io::Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.splits_lines() {
+            self.write_line(buf)
+        } else {
+            self.raw.write(buf)
+        }
+    }
+    #[inline]
+    fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
+        if self.splits_lines() {
+            let buf = bufs.iter().find(|b| !b.is_empty());
+            let buf = buf.map(|b| &**b).unwrap_or_default();
+            self.write_line(buf)
+        } else {
+            self.raw.write_vectored(bufs)
+        }
+    }
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.raw.flush()
+    }
+    #[inline]
+    fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
+        if self.splits_lines() {
+            self.write_all_lines(buf)
+        } else {
+            self.raw.write_all(buf)
+        }
+    }
+    #[inline]
+    fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
+        if self.splits_lines() {
+            crate::fmt::Adapter::new(|buf: &[u8]| self.write_all_lines(buf)).write_fmt(args)
+        } else {
+            self.raw.write_fmt(args)
+        }
+    }
+}
+
+pub(crate) fn layer(base: anstyle::Style, top: anstyle::Style) -> anstyle::Style {
+    base.fg_color(top.get_fg_color().or(base.get_fg_color()))
+        .bg_color(top.get_bg_color().or(base.get_bg_color()))
+        .underline_color(top.get_underline_color().or(base.get_underline_color()))
+        .effects(base.get_effects() | top.get_effects())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn per_line() {
+        let mut stack = StyleStack::new(Vec::new()).per_line();
+        writeln!(stack, "a").unwrap();
+        stack.push_style(anstyle::Style::new().bold()).unwrap();
+        stack.write_all(b"b\n\nc").unwrap();
+        stack.write_all(b"\n").unwrap();
+        stack.pop().unwrap();
+        let buffer = stack.finish().unwrap();
+        assert_eq!(buffer, b"a\n\x1b[1mb\x1b[0m\n\n\x1b[1mc\x1b[0m\n");
+    }
+
+    #[test]
+    fn nested() {
+        let dimmed = anstyle::Style::new().dimmed();
+        let red = anstyle::AnsiColor::Red.on_default();
+        let bold = anstyle::Style::new().bold();
+
+        let mut stack = StyleStack::new(Vec::new());
+        stack.push_style(dimmed).unwrap();
+        stack.push_style(red).unwrap();
+        stack.push_style(bold).unwrap();
+        assert_eq!(stack.depth(), 3);
+        assert_eq!(
+            stack.current(),
+            red | anstyle::Effects::DIMMED | anstyle::Effects::BOLD
+        );
+        write!(stack, "all").unwrap();
+        stack.pop().unwrap();
+        assert_eq!(stack.current(), red | anstyle::Effects::DIMMED);
+        write!(stack, "dimmed red").unwrap();
+        stack.pop().unwrap();
+        stack.pop().unwrap();
+        write!(stack, "plain").unwrap();
+        let buffer = String::from_utf8(stack.finish().unwrap()).unwrap();
+        assert_eq!(
+            buffer,
+            "\x1b[2m\x1b[31m\x1b[1mall\x1b[0m\x1b[2m\x1b[31mdimmed red\x1b[0m\x1b[2m\x1b[0mplain"
+        );
+    }
+
+    #[test]
+    fn override_color() {
+        let red = anstyle::AnsiColor::Red.on_default();
+        let blue = anstyle::AnsiColor::Blue.on_default();
+
+        let mut stack = StyleStack::new(Vec::new());
+        stack.push_style(red).unwrap();
+        stack.push_style(blue).unwrap();
+        assert_eq!(stack.current(), blue);
+        stack.pop().unwrap();
+        assert_eq!(stack.current(), red);
+    }
+
+    #[test]
+    fn pop_empty() {
+        let mut stack = StyleStack::new(Vec::new());
+        stack.pop().unwrap();
+        write!(stack, "plain").unwrap();
+        assert_eq!(stack.finish().unwrap(), b"plain");
+    }
+
+    #[test]
+    fn finish_resets() {
+        let mut stack = StyleStack::new(Vec::new());
+        stack.push_style(anstyle::Style::new().bold()).unwrap();
+        write!(stack, "bold").unwrap();
+        assert_eq!(stack.finish().unwrap(), b"\x1b[1mbold\x1b[0m");
+    }
+}
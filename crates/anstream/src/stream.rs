@@ -2,38 +2,139 @@
 
 /// Required functionality for underlying [`std::io::Write`] for adaptation
 #[cfg(not(all(windows, feature = "wincon")))]
-pub trait RawStream: std::io::Write + IsTerminal + private::Sealed {}
+pub trait RawStream: std::io::Write + IsTerminal + private::Sealed {
+    /// Whether writes are discarded, like to `/dev/null`
+    ///
+    /// This is only detected on Unix.
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        false
+    }
+}
 
 /// Required functionality for underlying [`std::io::Write`] for adaptation
 #[cfg(all(windows, feature = "wincon"))]
 pub trait RawStream:
     std::io::Write + IsTerminal + anstyle_wincon::WinconStream + private::Sealed
 {
+    /// Whether writes are discarded, like to `/dev/null`
+    ///
+    /// This is only detected on Unix.
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        false
+    }
 }
 
-impl RawStream for std::io::Stdout {}
+impl RawStream for std::io::Stdout {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        is_null_device(self)
+    }
+}
 
-impl RawStream for std::io::StdoutLock<'_> {}
+impl RawStream for std::io::StdoutLock<'_> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        is_null_device(self)
+    }
+}
 
-impl RawStream for &'_ mut std::io::StdoutLock<'_> {}
+impl RawStream for &'_ mut std::io::StdoutLock<'_> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        (**self).is_null_device()
+    }
+}
 
-impl RawStream for std::io::Stderr {}
+impl RawStream for std::io::Stderr {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        is_null_device(self)
+    }
+}
 
-impl RawStream for std::io::StderrLock<'_> {}
+impl RawStream for std::io::StderrLock<'_> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        is_null_device(self)
+    }
+}
 
-impl RawStream for &'_ mut std::io::StderrLock<'_> {}
+impl RawStream for &'_ mut std::io::StderrLock<'_> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        (**self).is_null_device()
+    }
+}
 
 impl RawStream for Box<dyn std::io::Write> {}
 
 impl RawStream for &'_ mut Box<dyn std::io::Write> {}
 
+impl RawStream for Box<dyn RawStream + Send> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        (**self).is_null_device()
+    }
+}
+
+impl RawStream for &'_ mut Box<dyn RawStream + Send> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        (**self).is_null_device()
+    }
+}
+
 impl RawStream for Vec<u8> {}
 
 impl RawStream for &'_ mut Vec<u8> {}
 
-impl RawStream for std::fs::File {}
+impl RawStream for std::fs::File {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        is_null_device(self)
+    }
+}
+
+impl RawStream for &'_ mut std::fs::File {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        (**self).is_null_device()
+    }
+}
+
+impl RawStream for crate::AutoStream<std::io::StdoutLock<'static>> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        self.get_ref().is_null_device()
+    }
+}
+
+impl RawStream for &'_ mut crate::AutoStream<std::io::StdoutLock<'static>> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        (**self).is_null_device()
+    }
+}
+
+impl RawStream for crate::AutoStream<std::io::StderrLock<'static>> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        self.get_ref().is_null_device()
+    }
+}
+
+impl RawStream for &'_ mut crate::AutoStream<std::io::StderrLock<'static>> {
+    #[inline]
+    fn is_null_device(&self) -> bool {
+        (**self).is_null_device()
+    }
+}
+
+impl RawStream for crate::CapturedStdio {}
 
-impl RawStream for &'_ mut std::fs::File {}
+impl RawStream for &'_ mut crate::CapturedStdio {}
 
 #[allow(deprecated)]
 impl RawStream for crate::Buffer {}
@@ -41,6 +142,126 @@ impl RawStream for crate::Buffer {}
 #[allow(deprecated)]
 impl RawStream for &'_ mut crate::Buffer {}
 
+#[cfg(all(windows, feature = "wincon"))]
+impl anstyle_wincon::WinconStream for Box<dyn RawStream + Send> {
+    fn write_colored(
+        &mut self,
+        fg: Option<anstyle::AnsiColor>,
+        bg: Option<anstyle::AnsiColor>,
+        data: &[u8],
+    ) -> std::io::Result<usize> {
+        anstyle_wincon::WinconStream::write_colored(&mut **self, fg, bg, data)
+    }
+
+    fn supports_ansi(&self) -> bool {
+        anstyle_wincon::WinconStream::supports_ansi(&**self)
+    }
+}
+
+#[cfg(all(windows, feature = "wincon"))]
+impl anstyle_wincon::WinconStream for &'_ mut Box<dyn RawStream + Send> {
+    fn write_colored(
+        &mut self,
+        fg: Option<anstyle::AnsiColor>,
+        bg: Option<anstyle::AnsiColor>,
+        data: &[u8],
+    ) -> std::io::Result<usize> {
+        anstyle_wincon::WinconStream::write_colored(&mut **self, fg, bg, data)
+    }
+
+    fn supports_ansi(&self) -> bool {
+        anstyle_wincon::WinconStream::supports_ansi(&**self)
+    }
+}
+
+#[cfg(all(windows, feature = "wincon"))]
+impl anstyle_wincon::WinconStream for crate::AutoStream<std::io::StdoutLock<'static>> {
+    fn write_colored(
+        &mut self,
+        fg: Option<anstyle::AnsiColor>,
+        bg: Option<anstyle::AnsiColor>,
+        data: &[u8],
+    ) -> std::io::Result<usize> {
+        // Adapted like any other ANSI escape codes written to it
+        anstyle_wincon::ansi::write_colored(self, fg, bg, data)
+    }
+
+    fn supports_ansi(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(all(windows, feature = "wincon"))]
+impl anstyle_wincon::WinconStream for &'_ mut crate::AutoStream<std::io::StdoutLock<'static>> {
+    fn write_colored(
+        &mut self,
+        fg: Option<anstyle::AnsiColor>,
+        bg: Option<anstyle::AnsiColor>,
+        data: &[u8],
+    ) -> std::io::Result<usize> {
+        (**self).write_colored(fg, bg, data)
+    }
+
+    fn supports_ansi(&self) -> bool {
+        (**self).supports_ansi()
+    }
+}
+
+#[cfg(all(windows, feature = "wincon"))]
+impl anstyle_wincon::WinconStream for crate::AutoStream<std::io::StderrLock<'static>> {
+    fn write_colored(
+        &mut self,
+        fg: Option<anstyle::AnsiColor>,
+        bg: Option<anstyle::AnsiColor>,
+        data: &[u8],
+    ) -> std::io::Result<usize> {
+        // Adapted like any other ANSI escape codes written to it
+        anstyle_wincon::ansi::write_colored(self, fg, bg, data)
+    }
+
+    fn supports_ansi(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(all(windows, feature = "wincon"))]
+impl anstyle_wincon::WinconStream for &'_ mut crate::AutoStream<std::io::StderrLock<'static>> {
+    fn write_colored(
+        &mut self,
+        fg: Option<anstyle::AnsiColor>,
+        bg: Option<anstyle::AnsiColor>,
+        data: &[u8],
+    ) -> std::io::Result<usize> {
+        (**self).write_colored(fg, bg, data)
+    }
+
+    fn supports_ansi(&self) -> bool {
+        (**self).supports_ansi()
+    }
+}
+
+#[cfg(unix)]
+fn is_null_device(stream: &impl std::os::fd::AsFd) -> bool {
+    use std::os::unix::fs::FileTypeExt as _;
+    use std::os::unix::fs::MetadataExt as _;
+
+    let Ok(null) = std::fs::metadata("/dev/null") else {
+        return false;
+    };
+    let Ok(stream) = stream.as_fd().try_clone_to_owned() else {
+        return false;
+    };
+    let Ok(metadata) = std::fs::File::from(stream).metadata() else {
+        return false;
+    };
+    metadata.file_type().is_char_device() && metadata.rdev() == null.rdev()
+}
+
+#[cfg(not(unix))]
+fn is_null_device<S>(_stream: &S) -> bool {
+    false
+}
+
 pub trait IsTerminal: private::Sealed {
     fn is_terminal(&self) -> bool;
 }
@@ -101,6 +322,20 @@ impl IsTerminal for &'_ mut Box<dyn std::io::Write> {
     }
 }
 
+impl IsTerminal for Box<dyn RawStream + Send> {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        (**self).is_terminal()
+    }
+}
+
+impl IsTerminal for &'_ mut Box<dyn RawStream + Send> {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        (**self).is_terminal()
+    }
+}
+
 impl IsTerminal for Vec<u8> {
     #[inline]
     fn is_terminal(&self) -> bool {
@@ -129,6 +364,52 @@ impl IsTerminal for &'_ mut std::fs::File {
     }
 }
 
+impl IsTerminal for crate::AutoStream<std::io::StdoutLock<'static>> {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        self.is_terminal()
+    }
+}
+
+impl IsTerminal for &'_ mut crate::AutoStream<std::io::StdoutLock<'static>> {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        (**self).is_terminal()
+    }
+}
+
+impl IsTerminal for crate::AutoStream<std::io::StderrLock<'static>> {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        self.is_terminal()
+    }
+}
+
+impl IsTerminal for &'_ mut crate::AutoStream<std::io::StderrLock<'static>> {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        (**self).is_terminal()
+    }
+}
+
+impl IsTerminal for crate::CapturedStdio {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        if self.is_stderr() {
+            std::io::IsTerminal::is_terminal(&std::io::stderr())
+        } else {
+            std::io::IsTerminal::is_terminal(&std::io::stdout())
+        }
+    }
+}
+
+impl IsTerminal for &'_ mut crate::CapturedStdio {
+    #[inline]
+    fn is_terminal(&self) -> bool {
+        (**self).is_terminal()
+    }
+}
+
 #[allow(deprecated)]
 impl IsTerminal for crate::Buffer {
     #[inline]
@@ -198,6 +479,15 @@ impl AsLockedWrite for Box<dyn std::io::Write> {
     }
 }
 
+impl AsLockedWrite for Box<dyn RawStream + Send> {
+    type Write<'w> = &'w mut Self;
+
+    #[inline]
+    fn as_locked_write(&mut self) -> Self::Write<'_> {
+        self
+    }
+}
+
 impl AsLockedWrite for Vec<u8> {
     type Write<'w> = &'w mut Self;
 
@@ -216,6 +506,33 @@ impl AsLockedWrite for std::fs::File {
     }
 }
 
+impl AsLockedWrite for crate::AutoStream<std::io::StdoutLock<'static>> {
+    type Write<'w> = &'w mut Self;
+
+    #[inline]
+    fn as_locked_write(&mut self) -> Self::Write<'_> {
+        self
+    }
+}
+
+impl AsLockedWrite for crate::AutoStream<std::io::StderrLock<'static>> {
+    type Write<'w> = &'w mut Self;
+
+    #[inline]
+    fn as_locked_write(&mut self) -> Self::Write<'_> {
+        self
+    }
+}
+
+impl AsLockedWrite for crate::CapturedStdio {
+    type Write<'w> = &'w mut Self;
+
+    #[inline]
+    fn as_locked_write(&mut self) -> Self::Write<'_> {
+        self
+    }
+}
+
 #[allow(deprecated)]
 impl AsLockedWrite for crate::Buffer {
     type Write<'w> = &'w mut Self;
@@ -226,25 +543,142 @@ impl AsLockedWrite for crate::Buffer {
     }
 }
 
-mod private {
-    pub trait Sealed {}
+/// Set the colors of following writes on the legacy console behind `stream`
+#[cfg(all(windows, feature = "wincon"))]
+fn set_console_colors<S: std::os::windows::io::AsHandle + std::io::Write>(
+    stream: &mut S,
+    fg: Option<anstyle::AnsiColor>,
+    bg: Option<anstyle::AnsiColor>,
+    initial: std::io::Result<(anstyle::AnsiColor, anstyle::AnsiColor)>,
+) -> std::io::Result<()> {
+    let (initial_fg, initial_bg) = initial?;
+    // Ensure everything is written with the last set of colors before applying the next set
+    stream.flush()?;
+    anstyle_wincon::windows::set_colors(stream, fg.unwrap_or(initial_fg), bg.unwrap_or(initial_bg))
+}
+
+pub(crate) mod private {
+    pub trait Sealed {
+        /// Set the colors of following writes on the legacy console behind this stream
+        ///
+        /// `None` colors are the console's initial ones.  Returns `None` for streams not styled
+        /// through console attributes, which write colors with
+        /// [`anstyle_wincon::WinconStream::write_colored`] instead.
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            _fg: Option<anstyle::AnsiColor>,
+            _bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            None
+        }
+    }
 
-    impl Sealed for std::io::Stdout {}
+    impl Sealed for std::io::Stdout {
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            // Ensure exclusive access
+            self.lock().set_console_colors(fg, bg)
+        }
+    }
 
-    impl Sealed for std::io::StdoutLock<'_> {}
+    impl Sealed for std::io::StdoutLock<'_> {
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            let initial = anstyle_wincon::windows::stdout_initial_colors();
+            Some(super::set_console_colors(self, fg, bg, initial))
+        }
+    }
 
-    impl Sealed for &'_ mut std::io::StdoutLock<'_> {}
+    impl Sealed for &'_ mut std::io::StdoutLock<'_> {
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            (**self).set_console_colors(fg, bg)
+        }
+    }
 
-    impl Sealed for std::io::Stderr {}
+    impl Sealed for std::io::Stderr {
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            // Ensure exclusive access
+            self.lock().set_console_colors(fg, bg)
+        }
+    }
 
-    impl Sealed for std::io::StderrLock<'_> {}
+    impl Sealed for std::io::StderrLock<'_> {
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            let initial = anstyle_wincon::windows::stderr_initial_colors();
+            Some(super::set_console_colors(self, fg, bg, initial))
+        }
+    }
 
-    impl Sealed for &'_ mut std::io::StderrLock<'_> {}
+    impl Sealed for &'_ mut std::io::StderrLock<'_> {
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            (**self).set_console_colors(fg, bg)
+        }
+    }
 
     impl Sealed for Box<dyn std::io::Write> {}
 
     impl Sealed for &'_ mut Box<dyn std::io::Write> {}
 
+    impl Sealed for Box<dyn super::RawStream + Send> {
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            (**self).set_console_colors(fg, bg)
+        }
+    }
+
+    impl Sealed for &'_ mut Box<dyn super::RawStream + Send> {
+        #[cfg(all(windows, feature = "wincon"))]
+        #[inline]
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            (**self).set_console_colors(fg, bg)
+        }
+    }
+
     impl Sealed for Vec<u8> {}
 
     impl Sealed for &'_ mut Vec<u8> {}
@@ -253,9 +687,41 @@ mod private {
 
     impl Sealed for &'_ mut std::fs::File {}
 
+    impl Sealed for crate::AutoStream<std::io::StdoutLock<'static>> {}
+
+    impl Sealed for &'_ mut crate::AutoStream<std::io::StdoutLock<'static>> {}
+
+    impl Sealed for crate::AutoStream<std::io::StderrLock<'static>> {}
+
+    impl Sealed for &'_ mut crate::AutoStream<std::io::StderrLock<'static>> {}
+
+    impl Sealed for crate::CapturedStdio {}
+
+    impl Sealed for &'_ mut crate::CapturedStdio {}
+
     #[allow(deprecated)]
     impl Sealed for crate::Buffer {}
 
     #[allow(deprecated)]
     impl Sealed for &'_ mut crate::Buffer {}
 }
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn null_device() {
+        let null = std::fs::OpenOptions::new()
+            .write(true)
+            .open("/dev/null")
+            .unwrap();
+        assert!(null.is_null_device());
+
+        let file = std::fs::File::open(std::env::current_exe().unwrap()).unwrap();
+        assert!(!file.is_null_device());
+
+        assert!(!Vec::new().is_null_device());
+    }
+}
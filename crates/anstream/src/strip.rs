@@ -31,6 +31,20 @@ where
         self.raw
     }
 
+    /// Get a reference to the wrapped [`RawStream`]
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        &self.raw
+    }
+
+    /// Get a mutable reference to the wrapped [`RawStream`]
+    ///
+    /// Writing to it directly bypasses adapting the escape codes.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.raw
+    }
+
     #[inline]
     pub fn is_terminal(&self) -> bool {
         self.raw.is_terminal()
@@ -78,12 +92,7 @@ where
     }
     #[inline]
     fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
-        let buf = bufs
-            .iter()
-            .find(|b| !b.is_empty())
-            .map(|b| &**b)
-            .unwrap_or(&[][..]);
-        self.write(buf)
+        write_vectored(&mut self.raw.as_locked_write(), &mut self.state, bufs)
     }
     // is_write_vectored: nightly only
     #[inline]
@@ -110,12 +119,20 @@ fn write(
 
     for printable in state.strip_next(buf) {
         let possible = printable.len();
-        let written = raw.write(printable)?;
+        let (written, err) = match raw.write(printable) {
+            Ok(written) => (written, None),
+            Err(err) => (0, Some(err)),
+        };
         if possible != written {
             let divergence = &printable[written..];
             let offset = offset_to(buf, divergence);
-            let consumed = &buf[offset..];
             *state = initial_state;
+            if offset == 0 {
+                // Nothing was consumed, so the caller can retry the same `buf`, e.g. on
+                // `ErrorKind::WouldBlock`
+                return err.map(Err).unwrap_or(Ok(0));
+            }
+            let consumed = &buf[..offset];
             state.strip_next(consumed).last();
             return Ok(offset);
         }
@@ -123,6 +140,62 @@ fn write(
     Ok(buf.len())
 }
 
+fn write_vectored(
+    raw: &mut dyn std::io::Write,
+    state: &mut StripBytes,
+    bufs: &[std::io::IoSlice<'_>],
+) -> std::io::Result<usize> {
+    let initial_state = state.clone();
+
+    // Printables borrow `state`, so track them by position until all `bufs` are stripped
+    let mut ranges = Vec::new();
+    for (index, buf) in bufs.iter().enumerate() {
+        for printable in state.strip_next(buf) {
+            let start = offset_to(buf, printable);
+            ranges.push((index, start..start + printable.len()));
+        }
+    }
+    let printables = ranges
+        .iter()
+        .map(|(index, range)| std::io::IoSlice::new(&bufs[*index][range.clone()]))
+        .collect::<Vec<_>>();
+
+    let written = if printables.is_empty() {
+        0
+    } else {
+        match raw.write_vectored(&printables) {
+            Ok(written) => written,
+            Err(err) => {
+                // Nothing was consumed, so the caller can retry the same `bufs`, e.g. on
+                // `ErrorKind::WouldBlock`
+                *state = initial_state;
+                return Err(err);
+            }
+        }
+    };
+
+    let mut remaining = written;
+    for (index, range) in &ranges {
+        if remaining < range.len() {
+            let offset =
+                bufs[..*index].iter().map(|buf| buf.len()).sum::<usize>() + range.start + remaining;
+            *state = initial_state;
+            let mut unstripped = offset;
+            for buf in bufs {
+                let consumed = unstripped.min(buf.len());
+                state.strip_next(&buf[..consumed]).last();
+                unstripped -= consumed;
+                if unstripped == 0 {
+                    break;
+                }
+            }
+            return Ok(offset);
+        }
+        remaining -= range.len();
+    }
+    Ok(bufs.iter().map(|buf| buf.len()).sum())
+}
+
 fn write_all(
     raw: &mut dyn std::io::Write,
     state: &mut StripBytes,
@@ -215,5 +288,82 @@ mod test {
                 }
             }
         }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+        fn write_would_block(s in any::<Vec<u8>>(), max in 1..8_usize) {
+            let mut expected = Vec::new();
+            let mut state = StripBytes::default();
+            write_all(&mut expected, &mut state, s.as_slice()).unwrap();
+
+            let mut raw = WouldBlock { buffer: Vec::new(), max, block: false };
+            let mut state = StripBytes::default();
+            let mut buf = s.as_slice();
+            while !buf.is_empty() {
+                match write(&mut raw, &mut state, buf) {
+                    Ok(written) => buf = &buf[written..],
+                    Err(err) => assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock),
+                }
+            }
+            assert_eq!(raw.buffer, expected);
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
+        fn write_vectored_would_block(s in any::<Vec<u8>>(), max in 1..8_usize, split in 1..8_usize) {
+            let mut expected = Vec::new();
+            let mut state = StripBytes::default();
+            write_all(&mut expected, &mut state, s.as_slice()).unwrap();
+
+            let mut raw = WouldBlock { buffer: Vec::new(), max, block: false };
+            let mut state = StripBytes::default();
+            let mut offset = 0;
+            while offset < s.len() {
+                let bufs = s[offset..].chunks(split).map(std::io::IoSlice::new).collect::<Vec<_>>();
+                match write_vectored(&mut raw, &mut state, &bufs) {
+                    Ok(written) => offset += written,
+                    Err(err) => assert_eq!(err.kind(), std::io::ErrorKind::WouldBlock),
+                }
+            }
+            assert_eq!(raw.buffer, expected);
+        }
+    }
+
+    #[test]
+    fn write_vectored_split_escapes() {
+        let buffer = Vec::new();
+        let mut stream = StripStream::new(buffer);
+        let bufs = [
+            std::io::IoSlice::new(b"\x1b[3"),
+            std::io::IoSlice::new(b"1mHello\x1b"),
+            std::io::IoSlice::new(b"[0m world!"),
+        ];
+        let written = stream.write_vectored(&bufs).unwrap();
+        assert_eq!(written, 21);
+        let buffer = stream.into_inner();
+        assert_eq!(std::str::from_utf8(&buffer).unwrap(), "Hello world!");
+    }
+
+    /// Non-blocking writer that only accepts some data before blocking
+    struct WouldBlock {
+        buffer: Vec<u8>,
+        max: usize,
+        block: bool,
+    }
+
+    impl std::io::Write for WouldBlock {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.block = !self.block;
+            if self.block {
+                return Err(std::io::ErrorKind::WouldBlock.into());
+            }
+            let len = buf.len().min(self.max);
+            self.buffer.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
     }
 }
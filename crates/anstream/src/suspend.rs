@@ -0,0 +1,93 @@
+use std::cell::Cell;
+use std::sync::Arc;
+use std::sync::RwLock;
+
+/// Coordinate writes with other output drawn on the terminal, like progress bars
+///
+/// Register an implementation with [`set_suspend_hook`] so the [`print!`][crate::print],
+/// [`eprint!`][crate::eprint], etc macros and [`suspend`] can clear that output before writing
+/// and redraw it afterwards.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "auto")] {
+/// struct Bars;
+///
+/// impl anstream::SuspendHook for Bars {
+///     fn suspend(&self, write: &mut dyn FnMut()) {
+///         // e.g. `indicatif::MultiProgress::suspend(|| write())`
+///         write();
+///     }
+/// }
+///
+/// anstream::set_suspend_hook(Some(Box::new(Bars)));
+/// anstream::println!("Downloaded 3 files");
+/// anstream::set_suspend_hook(None);
+/// # }
+/// ```
+pub trait SuspendHook: Send + Sync {
+    /// Call `write` while the other output is cleared from the terminal
+    fn suspend(&self, write: &mut dyn FnMut());
+}
+
+static HOOK: RwLock<Option<Arc<dyn SuspendHook>>> = RwLock::new(None);
+
+thread_local! {
+    static SUSPENDED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Set the process-wide [`SuspendHook`], replacing any previous one
+pub fn set_suspend_hook(hook: Option<Box<dyn SuspendHook>>) {
+    let hook = hook.map(Arc::from);
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = hook;
+}
+
+/// Run `write` with the [`SuspendHook`]'s output cleared from the terminal
+///
+/// Nested calls only suspend once.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "auto")] {
+/// use std::io::Write as _;
+///
+/// let mut stderr = anstream::stderr().lock();
+/// anstream::suspend(|| writeln!(stderr, "Downloaded 3 files")).unwrap();
+/// # }
+/// ```
+pub fn suspend<R>(write: impl FnOnce() -> R) -> R {
+    let hook = HOOK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    let hook = match hook {
+        Some(hook) if !SUSPENDED.with(Cell::get) => hook,
+        _ => return write(),
+    };
+
+    let _guard = SuspendedGuard::new();
+    let mut write = Some(write);
+    let mut result = None;
+    hook.suspend(&mut || {
+        if let Some(write) = write.take() {
+            result = Some(write());
+        }
+    });
+
+    // Don't lose the output if the hook didn't call `write`
+    result.unwrap_or_else(|| (write.take().expect("only taken on success"))())
+}
+
+struct SuspendedGuard;
+
+impl SuspendedGuard {
+    fn new() -> Self {
+        SUSPENDED.with(|s| s.set(true));
+        Self
+    }
+}
+
+impl Drop for SuspendedGuard {
+    fn drop(&mut self) {
+        SUSPENDED.with(|s| s.set(false));
+    }
+}
@@ -0,0 +1,202 @@
+use std::time::Duration;
+use std::time::Instant;
+
+/// Limit how often animated output is redrawn
+///
+/// Each [`flush`][std::io::Write::flush] ends a frame, like a spinner tick or progress update.
+/// A frame is only passed to the inner `Write` if at least `interval` has elapsed since the last
+/// one was, otherwise it replaces any earlier frame waiting to be shown.  The most recent frame is
+/// shown on [`ThrottleStream::finish`] or when dropped.
+///
+/// Frames should be self-contained redraws as any of them may be dropped, including their
+/// escape codes.
+///
+/// # Examples
+///
+/// ```
+/// use std::io::Write as _;
+///
+/// let mut stream = anstream::ThrottleStream::new(Vec::new(), std::time::Duration::from_secs(60));
+/// for percent in 0..=100 {
+///     write!(stream, "\r{percent:>3}%").unwrap();
+///     stream.flush().unwrap();
+/// }
+/// let buffer = stream.finish().unwrap();
+/// assert_eq!(buffer, b"\r  0%\r100%");
+/// ```
+#[derive(Debug)]
+pub struct ThrottleStream<S>
+where
+    S: std::io::Write,
+{
+    // Only `None` after `finish` or `into_inner`
+    raw: Option<S>,
+    interval: Duration,
+    last_shown: Option<Instant>,
+    frame: Vec<u8>,
+    dropped: Vec<u8>,
+}
+
+impl<S> ThrottleStream<S>
+where
+    S: std::io::Write,
+{
+    /// Show at most one frame per `interval`
+    #[inline]
+    pub fn new(raw: S, interval: Duration) -> Self {
+        Self {
+            raw: Some(raw),
+            interval,
+            last_shown: None,
+            frame: Vec::new(),
+            dropped: Vec::new(),
+        }
+    }
+
+    /// Show the most recent frame and get the wrapped `Write`
+    pub fn finish(mut self) -> std::io::Result<S> {
+        self.show_last_frame()?;
+        Ok(self
+            .raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`"))
+    }
+
+    /// Get the wrapped `Write`, discarding any frame not yet shown
+    #[inline]
+    pub fn into_inner(mut self) -> S {
+        self.raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a reference to the wrapped `Write`
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        self.raw
+            .as_ref()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a mutable reference to the wrapped `Write`
+    ///
+    /// Writing to it directly bypasses throttling.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        self.raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    fn show_last_frame(&mut self) -> std::io::Result<()> {
+        if self.frame.is_empty() {
+            std::mem::swap(&mut self.frame, &mut self.dropped);
+        }
+        self.show_frame(Instant::now())
+    }
+
+    fn show_frame(&mut self, now: Instant) -> std::io::Result<()> {
+        let raw = self
+            .raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`");
+        raw.write_all(&self.frame)?;
+        raw.flush()?;
+        self.last_shown = Some(now);
+        self.frame.clear();
+        self.dropped.clear();
+        Ok(())
+    }
+}
+
+impl<S> std::io::Write for ThrottleStream<S>
+where
+    S: std::io::Write,
+{
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.frame.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        if self.frame.is_empty() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let due = self
+            .last_shown
+            .map(|last| self.interval <= now.duration_since(last))
+            .unwrap_or(true);
+        if due {
+            self.show_frame(now)
+        } else {
+            std::mem::swap(&mut self.frame, &mut self.dropped);
+            self.frame.clear();
+            Ok(())
+        }
+    }
+}
+
+impl<S> Drop for ThrottleStream<S>
+where
+    S: std::io::Write,
+{
+    fn drop(&mut self) {
+        if self.raw.is_some() {
+            // Ignore errors as there is no way to report them
+            let _ = self.show_last_frame();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn unlimited() {
+        let mut stream = ThrottleStream::new(Vec::new(), Duration::ZERO);
+        for frame in ["\r-", "\r\\", "\r|", "\r/"] {
+            write!(stream, "{frame}").unwrap();
+            stream.flush().unwrap();
+        }
+        assert_eq!(stream.finish().unwrap(), b"\r-\r\\\r|\r/");
+    }
+
+    #[test]
+    fn drops_intermediate_frames() {
+        let mut stream = ThrottleStream::new(Vec::new(), Duration::from_secs(3600));
+        for frame in ["\r-", "\r\\", "\r|", "\r/"] {
+            write!(stream, "{frame}").unwrap();
+            stream.flush().unwrap();
+        }
+        assert_eq!(stream.finish().unwrap(), b"\r-\r/");
+    }
+
+    #[test]
+    fn unflushed_frame() {
+        let mut stream = ThrottleStream::new(Vec::new(), Duration::from_secs(3600));
+        write!(stream, "\r-").unwrap();
+        stream.flush().unwrap();
+        write!(stream, "\r\\").unwrap();
+        stream.flush().unwrap();
+        write!(stream, "\rdone\n").unwrap();
+        assert_eq!(stream.finish().unwrap(), b"\r-\rdone\n");
+    }
+
+    #[test]
+    fn last_frame_on_drop() {
+        let mut buffer = Vec::new();
+        {
+            let mut stream = ThrottleStream::new(&mut buffer, Duration::from_secs(3600));
+            for frame in ["\r-", "\r\\", "\r|"] {
+                write!(stream, "{frame}").unwrap();
+                stream.flush().unwrap();
+            }
+            assert_eq!(stream.get_ref().as_slice(), b"\r-");
+        }
+        assert_eq!(buffer, b"\r-\r|");
+    }
+}
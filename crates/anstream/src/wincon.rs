@@ -9,11 +9,16 @@ pub struct WinconStream<S>
 where
     S: RawStream,
 {
-    raw: S,
+    // Only `None` after `finish` or `into_inner`
+    raw: Option<S>,
     // `WinconBytes` is especially large compared to other variants of `AutoStream`, so boxing it
     // here so `AutoStream` doesn't have to discard one allocation and create another one when
     // calling `AutoStream::lock`
     state: Box<WinconBytes>,
+    // Adapted data the `raw` stream couldn't accept yet, e.g. from `ErrorKind::WouldBlock`
+    pending: Vec<(Colors, Vec<u8>)>,
+    // Colors left set on a legacy console, with `(None, None)` being its initial ones
+    console: Colors,
 }
 
 impl<S> WinconStream<S>
@@ -24,20 +29,81 @@ where
     #[inline]
     pub fn new(raw: S) -> Self {
         Self {
-            raw,
+            raw: Some(raw),
             state: Default::default(),
+            pending: Default::default(),
+            console: Default::default(),
         }
     }
 
+    /// Write any data the wrapped [`RawStream`] couldn't accept yet and get it
+    ///
+    /// Data is left over when a write fails with [`ErrorKind::WouldBlock`][std::io::ErrorKind]
+    /// partway through.
+    ///
+    /// The console's initial colors are restored too.
+    pub fn finish(mut self) -> std::io::Result<S> {
+        self.write_pending_and_restore()?;
+        Ok(self
+            .raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`"))
+    }
+
     /// Get the wrapped [`RawStream`]
+    ///
+    /// Any data it couldn't accept yet is written first, ignoring errors and discarding what is
+    /// still left; use [`WinconStream::finish`] to handle them.  The same is done when dropped.
     #[inline]
-    pub fn into_inner(self) -> S {
+    pub fn into_inner(mut self) -> S {
+        let _ = self.write_pending_and_restore();
         self.raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a reference to the wrapped [`RawStream`]
+    #[inline]
+    pub fn get_ref(&self) -> &S {
+        self.raw
+            .as_ref()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a mutable reference to the wrapped [`RawStream`]
+    ///
+    /// Writing to it directly bypasses adapting the escape codes.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut S {
+        self.raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`")
     }
 
     #[inline]
     pub fn is_terminal(&self) -> bool {
-        self.raw.is_terminal()
+        self.get_ref().is_terminal()
+    }
+
+    fn write_pending_and_restore(&mut self) -> std::io::Result<()> {
+        let raw = self
+            .raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`");
+        write_pending(raw, &mut self.console, &mut self.pending)?;
+        restore(raw, &mut self.console)
+    }
+}
+
+impl<S> Drop for WinconStream<S>
+where
+    S: RawStream,
+{
+    fn drop(&mut self) {
+        if self.raw.is_some() {
+            // Ignore errors as there is no way to report them
+            let _ = self.write_pending_and_restore();
+        }
     }
 }
 
@@ -48,10 +114,12 @@ impl WinconStream<std::io::Stdout> {
     /// - Faster performance when writing in a loop
     /// - Avoid other threads interleaving output with the current thread
     #[inline]
-    pub fn lock(self) -> WinconStream<std::io::StdoutLock<'static>> {
+    pub fn lock(mut self) -> WinconStream<std::io::StdoutLock<'static>> {
         WinconStream {
-            raw: self.raw.lock(),
-            state: self.state,
+            raw: self.raw.take().map(|raw| raw.lock()),
+            state: std::mem::take(&mut self.state),
+            pending: std::mem::take(&mut self.pending),
+            console: std::mem::take(&mut self.console),
         }
     }
 }
@@ -63,10 +131,12 @@ impl WinconStream<std::io::Stderr> {
     /// - Faster performance when writing in a loop
     /// - Avoid other threads interleaving output with the current thread
     #[inline]
-    pub fn lock(self) -> WinconStream<std::io::StderrLock<'static>> {
+    pub fn lock(mut self) -> WinconStream<std::io::StderrLock<'static>> {
         WinconStream {
-            raw: self.raw.lock(),
-            state: self.state,
+            raw: self.raw.take().map(|raw| raw.lock()),
+            state: std::mem::take(&mut self.state),
+            pending: std::mem::take(&mut self.pending),
+            console: std::mem::take(&mut self.console),
         }
     }
 }
@@ -78,54 +148,123 @@ where
     // Must forward all calls to ensure locking happens appropriately
     #[inline]
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        write(&mut self.raw.as_locked_write(), &mut self.state, buf)
+        write(
+            &mut self
+                .raw
+                .as_mut()
+                .expect("only taken on `finish` or `into_inner`")
+                .as_locked_write(),
+            &mut self.state,
+            &mut self.console,
+            &mut self.pending,
+            buf,
+        )
     }
     #[inline]
     fn write_vectored(&mut self, bufs: &[std::io::IoSlice<'_>]) -> std::io::Result<usize> {
-        let buf = bufs
-            .iter()
-            .find(|b| !b.is_empty())
-            .map(|b| &**b)
-            .unwrap_or(&[][..]);
-        self.write(buf)
+        // Printables are copied while adapting anyways, so join `bufs` to merge runs across them
+        let mut buf = Vec::with_capacity(bufs.iter().map(|buf| buf.len()).sum());
+        for slice in bufs {
+            buf.extend_from_slice(slice);
+        }
+        self.write(&buf)
     }
     // is_write_vectored: nightly only
     #[inline]
     fn flush(&mut self) -> std::io::Result<()> {
-        self.raw.as_locked_write().flush()
+        let mut raw = self
+            .raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`")
+            .as_locked_write();
+        write_pending(&mut raw, &mut self.console, &mut self.pending)?;
+        raw.flush()
     }
     #[inline]
     fn write_all(&mut self, buf: &[u8]) -> std::io::Result<()> {
-        write_all(&mut self.raw.as_locked_write(), &mut self.state, buf)
+        write_all(
+            &mut self
+                .raw
+                .as_mut()
+                .expect("only taken on `finish` or `into_inner`")
+                .as_locked_write(),
+            &mut self.state,
+            &mut self.console,
+            &mut self.pending,
+            buf,
+        )
     }
     // write_all_vectored: nightly only
     #[inline]
     fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
-        write_fmt(&mut self.raw.as_locked_write(), &mut self.state, args)
+        write_fmt(
+            &mut self
+                .raw
+                .as_mut()
+                .expect("only taken on `finish` or `into_inner`")
+                .as_locked_write(),
+            &mut self.state,
+            &mut self.console,
+            &mut self.pending,
+            args,
+        )
     }
 }
 
-fn write(raw: &mut dyn RawStream, state: &mut WinconBytes, buf: &[u8]) -> std::io::Result<usize> {
-    for (style, printable) in state.extract_next(buf) {
-        let fg = style.get_fg_color().and_then(cap_wincon_color);
-        let bg = style.get_bg_color().and_then(cap_wincon_color);
-        let written = raw.write_colored(fg, bg, printable.as_bytes())?;
-        let possible = printable.len();
-        if possible != written {
-            // HACK: Unsupported atm
-            break;
-        }
+fn write(
+    raw: &mut dyn RawStream,
+    state: &mut WinconBytes,
+    console: &mut Colors,
+    pending: &mut Vec<(Colors, Vec<u8>)>,
+    buf: &[u8],
+) -> std::io::Result<usize> {
+    // Nothing from `buf` is consumed until the pending data is written, so the caller can retry
+    write_pending(raw, console, pending)?;
+
+    // Restored if nothing gets written, so the caller can retry `buf` from the same state
+    let initial = state.clone();
+    let mut consumed = false;
+    let mut runs = runs(state.extract_next(buf));
+    while let Some((colors, printable)) = runs.next() {
+        let printable = printable.into_bytes();
+        let remaining = match write_colored(raw, console, colors, &printable) {
+            Ok(written) if written == printable.len() => {
+                consumed = true;
+                continue;
+            }
+            Ok(written) => printable[written..].to_owned(),
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => printable,
+            Err(err) if !consumed => {
+                drop(runs);
+                *state = initial;
+                return Err(err);
+            }
+            // Part of `buf` was written so the rest is queued, with the error reported when retried
+            Err(_) => printable,
+        };
+        pending.push((colors, remaining));
+        pending.extend(runs.map(|(colors, printable)| (colors, printable.into_bytes())));
+        return Ok(buf.len());
     }
+    drop(runs);
+    // `buf` was accepted, so an error restoring the colors can't be reported as not writing it
+    let _ = restore_if_plain(raw, state, console);
     Ok(buf.len())
 }
 
-fn write_all(raw: &mut dyn RawStream, state: &mut WinconBytes, buf: &[u8]) -> std::io::Result<()> {
-    for (style, printable) in state.extract_next(buf) {
+fn write_all(
+    raw: &mut dyn RawStream,
+    state: &mut WinconBytes,
+    console: &mut Colors,
+    pending: &mut Vec<(Colors, Vec<u8>)>,
+    buf: &[u8],
+) -> std::io::Result<()> {
+    write_pending(raw, console, pending)?;
+
+    for (colors, printable) in runs(state.extract_next(buf)) {
         let mut buf = printable.as_bytes();
-        let fg = style.get_fg_color().and_then(cap_wincon_color);
-        let bg = style.get_bg_color().and_then(cap_wincon_color);
         while !buf.is_empty() {
-            match raw.write_colored(fg, bg, buf) {
+            match write_colored(raw, console, colors, buf) {
                 Ok(0) => {
                     return Err(std::io::Error::new(
                         std::io::ErrorKind::WriteZero,
@@ -138,18 +277,125 @@ fn write_all(raw: &mut dyn RawStream, state: &mut WinconBytes, buf: &[u8]) -> st
             }
         }
     }
+    restore_if_plain(raw, state, console)
+}
+
+/// Restore the console's initial colors once the style has none, like after a reset
+///
+/// Other colors are left set across writes, like with ANSI escape codes, so runs of them don't
+/// each cost a change of console attributes.
+fn restore_if_plain(
+    raw: &mut dyn RawStream,
+    state: &WinconBytes,
+    console: &mut Colors,
+) -> std::io::Result<()> {
+    if wincon_colors(state.style()) == (None, None) {
+        restore(raw, console)?;
+    }
+    Ok(())
+}
+
+/// Set back the console's initial colors, if others were left set
+fn restore(raw: &mut dyn RawStream, console: &mut Colors) -> std::io::Result<()> {
+    if *console != (None, None) {
+        if let Some(result) = raw.set_console_colors(None, None) {
+            result?;
+        }
+        *console = (None, None);
+    }
     Ok(())
 }
 
 fn write_fmt(
     raw: &mut dyn RawStream,
     state: &mut WinconBytes,
+    console: &mut Colors,
+    pending: &mut Vec<(Colors, Vec<u8>)>,
     args: std::fmt::Arguments<'_>,
 ) -> std::io::Result<()> {
-    let write_all = |buf: &[u8]| write_all(raw, state, buf);
+    let write_all = |buf: &[u8]| write_all(raw, state, console, pending, buf);
     crate::fmt::Adapter::new(write_all).write_fmt(args)
 }
 
+/// Write out data left over from a previous partial write
+fn write_pending(
+    raw: &mut dyn RawStream,
+    console: &mut Colors,
+    pending: &mut Vec<(Colors, Vec<u8>)>,
+) -> std::io::Result<()> {
+    while let Some((colors, printable)) = pending.first_mut() {
+        match write_colored(raw, console, *colors, printable) {
+            Ok(n) if n == printable.len() => {
+                pending.remove(0);
+            }
+            Ok(0) => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::WriteZero,
+                    "failed to write whole buffer",
+                ));
+            }
+            Ok(n) => {
+                printable.drain(..n);
+            }
+            Err(ref e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(())
+}
+
+/// Foreground and background colors the console can show
+type Colors = (Option<anstyle::AnsiColor>, Option<anstyle::AnsiColor>);
+
+/// Merge neighboring printables that the console shows with the same [`Colors`]
+///
+/// Styles that only differ in effects or in colors the console can't show would otherwise each
+/// cost a write and a change of console attributes.
+fn runs(
+    printables: impl Iterator<Item = (anstyle::Style, String)>,
+) -> impl Iterator<Item = (Colors, String)> {
+    let mut printables = printables.peekable();
+    std::iter::from_fn(move || {
+        let (style, mut run) = printables.next()?;
+        let colors = wincon_colors(style);
+        while let Some((_, printable)) =
+            printables.next_if(|(style, _)| wincon_colors(*style) == colors)
+        {
+            run.push_str(&printable);
+        }
+        Some((colors, run))
+    })
+}
+
+fn wincon_colors(style: anstyle::Style) -> Colors {
+    let fg = style.get_fg_color().and_then(cap_wincon_color);
+    let bg = style.get_bg_color().and_then(cap_wincon_color);
+    (fg, bg)
+}
+
+/// Write `printable` in `colors`, leaving them set on a legacy console
+///
+/// [`anstyle_wincon::WinconStream::write_colored`] restores the console's initial colors after
+/// every write, costing two console calls each.  Tracking the colors left set on the console in
+/// `console` instead, they are only changed when they differ.
+fn write_colored(
+    raw: &mut dyn RawStream,
+    console: &mut Colors,
+    colors: Colors,
+    printable: &[u8],
+) -> std::io::Result<usize> {
+    if colors != *console {
+        match raw.set_console_colors(colors.0, colors.1) {
+            Some(result) => {
+                result?;
+                *console = colors;
+            }
+            None => return raw.write_colored(colors.0, colors.1, printable),
+        }
+    }
+    raw.write(printable)
+}
+
 fn cap_wincon_color(color: anstyle::Color) -> Option<anstyle::AnsiColor> {
     match color {
         anstyle::Color::Ansi(c) => Some(c),
@@ -164,6 +410,230 @@ mod test {
     use proptest::prelude::*;
     use std::io::Write as _;
 
+    #[test]
+    fn write_merges_runs() {
+        let buffer = Vec::new();
+        let mut stream = WinconStream::new(buffer);
+        stream
+            .write_all(b"\x1b[32;1mA\x1b[22mB\x1b[38;2;1;2;3mC\x1b[0mD\x1b[31mE")
+            .unwrap();
+        let buffer = stream.into_inner();
+        let actual = std::str::from_utf8(buffer.as_ref()).unwrap();
+        assert_eq!(actual, "\x1b[32mAB\x1b[0mCD\x1b[31mE\x1b[0m");
+    }
+
+    #[test]
+    fn write_vectored_merges_runs() {
+        let buffer = Vec::new();
+        let mut stream = WinconStream::new(buffer);
+        let bufs = [
+            std::io::IoSlice::new(b"\x1b[32mA"),
+            std::io::IoSlice::new(b"\x1b[1mB\x1b[0m"),
+        ];
+        let written = stream.write_vectored(&bufs).unwrap();
+        assert_eq!(written, 15);
+        let buffer = stream.into_inner();
+        let actual = std::str::from_utf8(buffer.as_ref()).unwrap();
+        assert_eq!(actual, "\x1b[32mAB\x1b[0m");
+    }
+
+    #[derive(Clone, Default)]
+    struct WouldBlock {
+        buffer: std::sync::Arc<std::sync::Mutex<Vec<u8>>>,
+        block: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    }
+
+    impl std::io::Write for WouldBlock {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if self.block.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(std::io::ErrorKind::WouldBlock.into());
+            }
+            self.buffer.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn finish_writes_pending() {
+        let raw = WouldBlock::default();
+        let mut stream = WinconStream::new(Box::new(raw.clone()) as Box<dyn std::io::Write>);
+        raw.block.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(stream.write(b"\x1b[31mA\x1b[0mB").unwrap(), 11);
+        assert!(raw.buffer.lock().unwrap().is_empty());
+
+        raw.block.store(false, std::sync::atomic::Ordering::SeqCst);
+        stream.finish().unwrap();
+        assert_eq!(
+            std::str::from_utf8(&raw.buffer.lock().unwrap()).unwrap(),
+            "\x1b[31mA\x1b[0mB"
+        );
+    }
+
+    #[test]
+    fn into_inner_writes_pending() {
+        let raw = WouldBlock::default();
+        let mut stream = WinconStream::new(Box::new(raw.clone()) as Box<dyn std::io::Write>);
+        raw.block.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(stream.write(b"A").unwrap(), 1);
+
+        raw.block.store(false, std::sync::atomic::Ordering::SeqCst);
+        stream.into_inner();
+        assert_eq!(raw.buffer.lock().unwrap().as_slice(), b"A");
+    }
+
+    /// Fails writes containing `reject`
+    #[derive(Clone, Default)]
+    struct Reject {
+        buffer: std::sync::Arc<std::sync::Mutex<Vec<u8>>>,
+        reject: std::sync::Arc<std::sync::Mutex<Option<u8>>>,
+    }
+
+    impl std::io::Write for Reject {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            if let Some(reject) = *self.reject.lock().unwrap() {
+                if buf.contains(&reject) {
+                    return Err(std::io::ErrorKind::Interrupted.into());
+                }
+            }
+            self.buffer.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn error_after_write_queues_rest() {
+        let raw = Reject::default();
+        let mut stream = WinconStream::new(Box::new(raw.clone()) as Box<dyn std::io::Write>);
+        *raw.reject.lock().unwrap() = Some(b'B');
+        assert_eq!(stream.write(b"A\x1b[31mB").unwrap(), 7);
+        assert_eq!(raw.buffer.lock().unwrap().as_slice(), b"A\x1b[31m");
+
+        *raw.reject.lock().unwrap() = None;
+        stream.flush().unwrap();
+        assert_eq!(
+            std::str::from_utf8(&raw.buffer.lock().unwrap()).unwrap(),
+            "A\x1b[31m\x1b[31mB\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn error_before_write_is_reported() {
+        let raw = Reject::default();
+        let mut stream = WinconStream::new(Box::new(raw.clone()) as Box<dyn std::io::Write>);
+        *raw.reject.lock().unwrap() = Some(b'A');
+        let err = stream.write(b"A").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::Interrupted);
+
+        *raw.reject.lock().unwrap() = None;
+        assert_eq!(stream.write(b"A").unwrap(), 1);
+        assert_eq!(raw.buffer.lock().unwrap().as_slice(), b"A");
+    }
+
+    /// Tracks the colors set like a legacy console would
+    #[derive(Default)]
+    struct Console {
+        colors: Colors,
+        set_calls: usize,
+        written: Vec<u8>,
+    }
+
+    impl std::io::Write for Console {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl crate::stream::private::Sealed for Console {
+        fn set_console_colors(
+            &mut self,
+            fg: Option<anstyle::AnsiColor>,
+            bg: Option<anstyle::AnsiColor>,
+        ) -> Option<std::io::Result<()>> {
+            self.colors = (fg, bg);
+            self.set_calls += 1;
+            Some(Ok(()))
+        }
+    }
+
+    impl crate::stream::IsTerminal for Console {
+        fn is_terminal(&self) -> bool {
+            true
+        }
+    }
+
+    impl anstyle_wincon::WinconStream for Console {
+        fn write_colored(
+            &mut self,
+            _fg: Option<anstyle::AnsiColor>,
+            _bg: Option<anstyle::AnsiColor>,
+            _data: &[u8],
+        ) -> std::io::Result<usize> {
+            unreachable!("colors are set on the console")
+        }
+    }
+
+    impl RawStream for Console {}
+
+    #[test]
+    fn console_colors_set_once() {
+        let mut console = Console::default();
+        let mut state = WinconBytes::default();
+        let mut colors = Colors::default();
+        let mut pending = Vec::new();
+        for _ in 0..3 {
+            write_all(
+                &mut console,
+                &mut state,
+                &mut colors,
+                &mut pending,
+                b"\x1b[31merror",
+            )
+            .unwrap();
+        }
+        assert_eq!(console.colors, (Some(anstyle::AnsiColor::Red), None));
+        assert_eq!(console.set_calls, 1);
+
+        write_all(
+            &mut console,
+            &mut state,
+            &mut colors,
+            &mut pending,
+            b"\x1b[0m: plain",
+        )
+        .unwrap();
+        assert_eq!(console.colors, (None, None));
+        assert_eq!(console.set_calls, 2);
+        assert_eq!(console.written, b"errorerrorerror: plain");
+    }
+
+    #[test]
+    fn console_colors_restored() {
+        let mut console = Console::default();
+        let mut state = WinconBytes::default();
+        let mut colors = Colors::default();
+        let mut pending = Vec::new();
+        write_all(
+            &mut console,
+            &mut state,
+            &mut colors,
+            &mut pending,
+            b"\x1b[31merror",
+        )
+        .unwrap();
+        for _ in 0..2 {
+            restore(&mut console, &mut colors).unwrap();
+        }
+        assert_eq!(console.colors, (None, None));
+        assert_eq!(console.set_calls, 2);
+    }
+
     proptest! {
         #[test]
         #[cfg_attr(miri, ignore)]  // See https://github.com/AltSysrq/proptest/issues/253
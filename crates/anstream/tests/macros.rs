@@ -48,3 +48,17 @@ fn panic() {
         anstyle::Reset.render()
     );
 }
+
+#[test]
+fn write_styled() {
+    use std::io::Write as _;
+
+    let style = anstyle::AnsiColor::Red.on_default();
+    let mut buffer = Vec::new();
+    anstream::write_styled!(&mut buffer, style, "{} {name}", "Hello", name = "world").unwrap();
+    assert_eq!(buffer, b"\x1b[31mHello world\x1b[0m");
+
+    let mut stream = anstream::StripStream::new(Vec::new());
+    anstream::write_styled!(stream, style, "Hello world").unwrap();
+    assert_eq!(stream.into_inner(), b"Hello world");
+}
@@ -0,0 +1,23 @@
+use std::io::Write as _;
+
+use anstream::stream::RawStream;
+
+#[test]
+fn boxed_raw_stream() {
+    let streams: Vec<Box<dyn RawStream + Send>> =
+        vec![Box::new(std::io::stderr()), Box::new(Vec::new())];
+    for raw in streams {
+        let mut stream = anstream::AutoStream::never(raw);
+        write!(stream, "{}", anstyle::AnsiColor::Red.render_fg()).unwrap();
+        stream.flush().unwrap();
+    }
+}
+
+#[test]
+fn locked_auto_stream_is_raw_stream() {
+    let locked = anstream::AutoStream::never(std::io::stderr()).lock();
+    let mut stream = anstream::AutoStream::never(locked);
+    write!(stream, "{}", anstyle::AnsiColor::Red.render_fg()).unwrap();
+    stream.flush().unwrap();
+    let _locked: anstream::AutoStream<std::io::StderrLock<'static>> = stream.into_inner();
+}
@@ -0,0 +1,37 @@
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+struct Counter(Arc<AtomicUsize>);
+
+impl anstream::SuspendHook for Counter {
+    fn suspend(&self, write: &mut dyn FnMut()) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        write();
+    }
+}
+
+#[test]
+fn suspend() {
+    let count = Arc::new(AtomicUsize::new(0));
+    anstream::set_suspend_hook(Some(Box::new(Counter(count.clone()))));
+
+    let value = anstream::suspend(|| anstream::suspend(|| 5));
+    assert_eq!(value, 5);
+    assert_eq!(count.load(Ordering::SeqCst), 1);
+
+    #[cfg(feature = "auto")]
+    {
+        anstream::print!("captured");
+        anstream::println!("captured");
+        anstream::eprint!("captured");
+        anstream::eprintln!("captured");
+        assert_eq!(count.load(Ordering::SeqCst), 5);
+    }
+
+    anstream::set_suspend_hook(None);
+    let suspended = count.load(Ordering::SeqCst);
+    let value = anstream::suspend(|| 10);
+    assert_eq!(value, 10);
+    assert_eq!(count.load(Ordering::SeqCst), suspended);
+}
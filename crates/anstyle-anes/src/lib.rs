@@ -0,0 +1,188 @@
+mod sealed {
+    pub(crate) trait Sealed {}
+}
+
+trait Ext: sealed::Sealed {
+    fn to_anes(self) -> AnesStyle;
+}
+
+impl sealed::Sealed for anstyle::Style {}
+
+impl Ext for anstyle::Style {
+    fn to_anes(self) -> AnesStyle {
+        to_anes(self)
+    }
+}
+
+/// The `anes` sequences to apply an `anstyle::Style`
+///
+/// `Display` writes each sequence in turn.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct AnesStyle {
+    pub foreground: Option<anes::SetForegroundColor>,
+    pub background: Option<anes::SetBackgroundColor>,
+    pub attributes: Vec<anes::SetAttribute>,
+}
+
+impl std::fmt::Display for AnesStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(foreground) = self.foreground {
+            foreground.fmt(f)?;
+        }
+        if let Some(background) = self.background {
+            background.fmt(f)?;
+        }
+        for attribute in &self.attributes {
+            attribute.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Extended underlines are written as [`anes::Attribute::Underline`] and underline colors are
+/// dropped
+pub fn to_anes(style: anstyle::Style) -> AnesStyle {
+    AnesStyle {
+        foreground: style
+            .get_fg_color()
+            .map(|color| anes::SetForegroundColor(to_anes_color(color))),
+        background: style
+            .get_bg_color()
+            .map(|color| anes::SetBackgroundColor(to_anes_color(color))),
+        attributes: to_anes_attributes(style.get_effects())
+            .into_iter()
+            .map(anes::SetAttribute)
+            .collect(),
+    }
+}
+
+pub fn to_anes_attributes(effects: anstyle::Effects) -> Vec<anes::Attribute> {
+    let mut attributes = Vec::new();
+    if effects.contains(anstyle::Effects::BOLD) {
+        attributes.push(anes::Attribute::Bold);
+    }
+    if effects.contains(anstyle::Effects::DIMMED) {
+        attributes.push(anes::Attribute::Faint);
+    }
+    if effects.contains(anstyle::Effects::ITALIC) {
+        attributes.push(anes::Attribute::Italic);
+    }
+    let underlines = anstyle::Effects::UNDERLINE
+        | anstyle::Effects::DOUBLE_UNDERLINE
+        | anstyle::Effects::CURLY_UNDERLINE
+        | anstyle::Effects::DOTTED_UNDERLINE
+        | anstyle::Effects::DASHED_UNDERLINE;
+    if underlines.remove(effects) != underlines {
+        attributes.push(anes::Attribute::Underline);
+    }
+    if effects.contains(anstyle::Effects::BLINK) {
+        attributes.push(anes::Attribute::Blink);
+    }
+    if effects.contains(anstyle::Effects::INVERT) {
+        attributes.push(anes::Attribute::Reverse);
+    }
+    if effects.contains(anstyle::Effects::HIDDEN) {
+        attributes.push(anes::Attribute::Conceal);
+    }
+    if effects.contains(anstyle::Effects::STRIKETHROUGH) {
+        attributes.push(anes::Attribute::Crossed);
+    }
+    attributes
+}
+
+pub fn to_anes_color(color: anstyle::Color) -> anes::Color {
+    match color {
+        anstyle::Color::Ansi(ansi) => ansi_to_anes_color(ansi),
+        anstyle::Color::Ansi256(xterm) => anes::Color::Ansi(xterm.0),
+        anstyle::Color::Rgb(rgb) => anes::Color::Rgb(rgb.0, rgb.1, rgb.2),
+    }
+}
+
+fn ansi_to_anes_color(color: anstyle::AnsiColor) -> anes::Color {
+    match color {
+        anstyle::AnsiColor::Black => anes::Color::Black,
+        anstyle::AnsiColor::Red => anes::Color::DarkRed,
+        anstyle::AnsiColor::Green => anes::Color::DarkGreen,
+        anstyle::AnsiColor::Yellow => anes::Color::DarkYellow,
+        anstyle::AnsiColor::Blue => anes::Color::DarkBlue,
+        anstyle::AnsiColor::Magenta => anes::Color::DarkMagenta,
+        anstyle::AnsiColor::Cyan => anes::Color::DarkCyan,
+        anstyle::AnsiColor::White => anes::Color::Gray,
+        anstyle::AnsiColor::BrightBlack => anes::Color::DarkGray,
+        anstyle::AnsiColor::BrightRed => anes::Color::Red,
+        anstyle::AnsiColor::BrightGreen => anes::Color::Green,
+        anstyle::AnsiColor::BrightYellow => anes::Color::Yellow,
+        anstyle::AnsiColor::BrightBlue => anes::Color::Blue,
+        anstyle::AnsiColor::BrightMagenta => anes::Color::Magenta,
+        anstyle::AnsiColor::BrightCyan => anes::Color::Cyan,
+        anstyle::AnsiColor::BrightWhite => anes::Color::White,
+    }
+}
+
+/// [`anes::Color::Default`] is `None`
+pub fn to_anstyle_color(color: anes::Color) -> Option<anstyle::Color> {
+    let color = match color {
+        anes::Color::Default => return None,
+        anes::Color::Black => anstyle::AnsiColor::Black.into(),
+        anes::Color::DarkRed => anstyle::AnsiColor::Red.into(),
+        anes::Color::DarkGreen => anstyle::AnsiColor::Green.into(),
+        anes::Color::DarkYellow => anstyle::AnsiColor::Yellow.into(),
+        anes::Color::DarkBlue => anstyle::AnsiColor::Blue.into(),
+        anes::Color::DarkMagenta => anstyle::AnsiColor::Magenta.into(),
+        anes::Color::DarkCyan => anstyle::AnsiColor::Cyan.into(),
+        anes::Color::Gray => anstyle::AnsiColor::White.into(),
+        anes::Color::DarkGray => anstyle::AnsiColor::BrightBlack.into(),
+        anes::Color::Red => anstyle::AnsiColor::BrightRed.into(),
+        anes::Color::Green => anstyle::AnsiColor::BrightGreen.into(),
+        anes::Color::Yellow => anstyle::AnsiColor::BrightYellow.into(),
+        anes::Color::Blue => anstyle::AnsiColor::BrightBlue.into(),
+        anes::Color::Magenta => anstyle::AnsiColor::BrightMagenta.into(),
+        anes::Color::Cyan => anstyle::AnsiColor::BrightCyan.into(),
+        anes::Color::White => anstyle::AnsiColor::BrightWhite.into(),
+        anes::Color::Ansi(index) => anstyle::Ansi256Color(index).into(),
+        anes::Color::Rgb(r, g, b) => anstyle::RgbColor(r, g, b).into(),
+    };
+    Some(color)
+}
+
+/// Apply an [`anes::SetForegroundColor`] to `style`
+pub fn apply_foreground(
+    style: anstyle::Style,
+    sequence: anes::SetForegroundColor,
+) -> anstyle::Style {
+    style.fg_color(to_anstyle_color(sequence.0))
+}
+
+/// Apply an [`anes::SetBackgroundColor`] to `style`
+pub fn apply_background(
+    style: anstyle::Style,
+    sequence: anes::SetBackgroundColor,
+) -> anstyle::Style {
+    style.bg_color(to_anstyle_color(sequence.0))
+}
+
+/// Apply an [`anes::SetAttribute`] to `style`, including the attributes that turn others off
+pub fn apply_attribute(style: anstyle::Style, sequence: anes::SetAttribute) -> anstyle::Style {
+    let underlines = anstyle::Effects::UNDERLINE
+        | anstyle::Effects::DOUBLE_UNDERLINE
+        | anstyle::Effects::CURLY_UNDERLINE
+        | anstyle::Effects::DOTTED_UNDERLINE
+        | anstyle::Effects::DASHED_UNDERLINE;
+    match sequence.0 {
+        anes::Attribute::Bold => style | anstyle::Effects::BOLD,
+        anes::Attribute::Faint => style | anstyle::Effects::DIMMED,
+        anes::Attribute::Normal => style - (anstyle::Effects::BOLD | anstyle::Effects::DIMMED),
+        anes::Attribute::Italic => style | anstyle::Effects::ITALIC,
+        anes::Attribute::ItalicOff => style - anstyle::Effects::ITALIC,
+        anes::Attribute::Underline => (style - underlines) | anstyle::Effects::UNDERLINE,
+        anes::Attribute::UnderlineOff => style - underlines,
+        anes::Attribute::Blink => style | anstyle::Effects::BLINK,
+        anes::Attribute::BlinkOff => style - anstyle::Effects::BLINK,
+        anes::Attribute::Reverse => style | anstyle::Effects::INVERT,
+        anes::Attribute::ReverseOff => style - anstyle::Effects::INVERT,
+        anes::Attribute::Conceal => style | anstyle::Effects::HIDDEN,
+        anes::Attribute::ConcealOff => style - anstyle::Effects::HIDDEN,
+        anes::Attribute::Crossed => style | anstyle::Effects::STRIKETHROUGH,
+        anes::Attribute::CrossedOff => style - anstyle::Effects::STRIKETHROUGH,
+    }
+}
@@ -0,0 +1,174 @@
+use colored::Colorize as _;
+
+mod sealed {
+    pub(crate) trait Sealed {}
+}
+
+trait Ext: sealed::Sealed {
+    fn to_anstyle(&self) -> anstyle::Style;
+}
+
+impl sealed::Sealed for colored::ColoredString {}
+
+impl Ext for colored::ColoredString {
+    fn to_anstyle(&self) -> anstyle::Style {
+        to_anstyle(self)
+    }
+}
+
+/// The style of a [`colored::ColoredString`]
+pub fn to_anstyle(colored: &colored::ColoredString) -> anstyle::Style {
+    let style = colored.style;
+    let mut effects = anstyle::Effects::new();
+    if style.contains(colored::Styles::Bold) {
+        effects |= anstyle::Effects::BOLD;
+    }
+    if style.contains(colored::Styles::Dimmed) {
+        effects |= anstyle::Effects::DIMMED;
+    }
+    if style.contains(colored::Styles::Italic) {
+        effects |= anstyle::Effects::ITALIC;
+    }
+    if style.contains(colored::Styles::Underline) {
+        effects |= anstyle::Effects::UNDERLINE;
+    }
+    if style.contains(colored::Styles::Blink) {
+        effects |= anstyle::Effects::BLINK;
+    }
+    if style.contains(colored::Styles::Reversed) {
+        effects |= anstyle::Effects::INVERT;
+    }
+    if style.contains(colored::Styles::Hidden) {
+        effects |= anstyle::Effects::HIDDEN;
+    }
+    if style.contains(colored::Styles::Strikethrough) {
+        effects |= anstyle::Effects::STRIKETHROUGH;
+    }
+
+    anstyle::Style::new()
+        .fg_color(colored.fgcolor.map(to_anstyle_color))
+        .bg_color(colored.bgcolor.map(to_anstyle_color))
+        .effects(effects)
+}
+
+pub fn to_anstyle_color(color: colored::Color) -> anstyle::Color {
+    match color {
+        colored::Color::Black => anstyle::AnsiColor::Black.into(),
+        colored::Color::Red => anstyle::AnsiColor::Red.into(),
+        colored::Color::Green => anstyle::AnsiColor::Green.into(),
+        colored::Color::Yellow => anstyle::AnsiColor::Yellow.into(),
+        colored::Color::Blue => anstyle::AnsiColor::Blue.into(),
+        colored::Color::Magenta => anstyle::AnsiColor::Magenta.into(),
+        colored::Color::Cyan => anstyle::AnsiColor::Cyan.into(),
+        colored::Color::White => anstyle::AnsiColor::White.into(),
+        colored::Color::BrightBlack => anstyle::AnsiColor::BrightBlack.into(),
+        colored::Color::BrightRed => anstyle::AnsiColor::BrightRed.into(),
+        colored::Color::BrightGreen => anstyle::AnsiColor::BrightGreen.into(),
+        colored::Color::BrightYellow => anstyle::AnsiColor::BrightYellow.into(),
+        colored::Color::BrightBlue => anstyle::AnsiColor::BrightBlue.into(),
+        colored::Color::BrightMagenta => anstyle::AnsiColor::BrightMagenta.into(),
+        colored::Color::BrightCyan => anstyle::AnsiColor::BrightCyan.into(),
+        colored::Color::BrightWhite => anstyle::AnsiColor::BrightWhite.into(),
+        colored::Color::TrueColor { r, g, b } => anstyle::RgbColor(r, g, b).into(),
+    }
+}
+
+/// 256-colors are written as RGB, as `colored` has no 256-color support
+pub fn to_colored_color(color: anstyle::Color) -> colored::Color {
+    let ansi = match color {
+        anstyle::Color::Ansi(ansi) => Some(ansi),
+        anstyle::Color::Ansi256(xterm) => xterm.into_ansi(),
+        anstyle::Color::Rgb(_) => None,
+    };
+    match ansi {
+        Some(ansi) => ansi_to_colored_color(ansi),
+        None => {
+            let rgb = anstyle_lossy::color_to_rgb(color, anstyle_lossy::palette::VGA);
+            colored::Color::TrueColor {
+                r: rgb.0,
+                g: rgb.1,
+                b: rgb.2,
+            }
+        }
+    }
+}
+
+fn ansi_to_colored_color(color: anstyle::AnsiColor) -> colored::Color {
+    match color {
+        anstyle::AnsiColor::Black => colored::Color::Black,
+        anstyle::AnsiColor::Red => colored::Color::Red,
+        anstyle::AnsiColor::Green => colored::Color::Green,
+        anstyle::AnsiColor::Yellow => colored::Color::Yellow,
+        anstyle::AnsiColor::Blue => colored::Color::Blue,
+        anstyle::AnsiColor::Magenta => colored::Color::Magenta,
+        anstyle::AnsiColor::Cyan => colored::Color::Cyan,
+        anstyle::AnsiColor::White => colored::Color::White,
+        anstyle::AnsiColor::BrightBlack => colored::Color::BrightBlack,
+        anstyle::AnsiColor::BrightRed => colored::Color::BrightRed,
+        anstyle::AnsiColor::BrightGreen => colored::Color::BrightGreen,
+        anstyle::AnsiColor::BrightYellow => colored::Color::BrightYellow,
+        anstyle::AnsiColor::BrightBlue => colored::Color::BrightBlue,
+        anstyle::AnsiColor::BrightMagenta => colored::Color::BrightMagenta,
+        anstyle::AnsiColor::BrightCyan => colored::Color::BrightCyan,
+        anstyle::AnsiColor::BrightWhite => colored::Color::BrightWhite,
+    }
+}
+
+/// Replace the style of a [`colored::ColoredString`], keeping its text
+///
+/// Extended underlines are written as a plain underline and underline colors are dropped.
+pub fn restyle(colored: colored::ColoredString, style: anstyle::Style) -> colored::ColoredString {
+    let mut colored = colored.clear();
+    if let Some(fg) = style.get_fg_color() {
+        colored = colored.color(to_colored_color(fg));
+    }
+    if let Some(bg) = style.get_bg_color() {
+        colored = colored.on_color(to_colored_color(bg));
+    }
+
+    let effects = style.get_effects();
+    if effects.contains(anstyle::Effects::BOLD) {
+        colored = colored.bold();
+    }
+    if effects.contains(anstyle::Effects::DIMMED) {
+        colored = colored.dimmed();
+    }
+    if effects.contains(anstyle::Effects::ITALIC) {
+        colored = colored.italic();
+    }
+    let underlines = anstyle::Effects::UNDERLINE
+        | anstyle::Effects::DOUBLE_UNDERLINE
+        | anstyle::Effects::CURLY_UNDERLINE
+        | anstyle::Effects::DOTTED_UNDERLINE
+        | anstyle::Effects::DASHED_UNDERLINE;
+    if underlines.remove(effects) != underlines {
+        colored = colored.underline();
+    }
+    if effects.contains(anstyle::Effects::BLINK) {
+        colored = colored.blink();
+    }
+    if effects.contains(anstyle::Effects::INVERT) {
+        colored = colored.reversed();
+    }
+    if effects.contains(anstyle::Effects::HIDDEN) {
+        colored = colored.hidden();
+    }
+    if effects.contains(anstyle::Effects::STRIKETHROUGH) {
+        colored = colored.strikethrough();
+    }
+    colored
+}
+
+/// Render a [`colored::ColoredString`] with `anstyle`
+///
+/// Unlike its `Display`, this ignores `colored`'s own color detection, leaving that to the
+/// writer, like `anstream`.
+pub fn render(colored: &colored::ColoredString) -> String {
+    let style = to_anstyle(colored);
+    format!(
+        "{}{}{}",
+        style.render(),
+        colored.input,
+        style.render_reset()
+    )
+}
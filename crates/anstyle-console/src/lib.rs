@@ -0,0 +1,153 @@
+mod sealed {
+    pub(crate) trait Sealed {}
+}
+
+trait Ext: sealed::Sealed {
+    fn to_console(self) -> console::Style;
+}
+
+impl sealed::Sealed for anstyle::Style {}
+
+impl Ext for anstyle::Style {
+    fn to_console(self) -> console::Style {
+        to_console(self)
+    }
+}
+
+/// RGB colors are approximated with 256-colors, as `console` has no RGB support
+pub fn to_console(astyle: anstyle::Style) -> console::Style {
+    let mut style = console::Style::new();
+
+    if let Some((fg, fg_bright)) = astyle.get_fg_color().map(to_console_color) {
+        style = style.fg(fg);
+        if fg_bright {
+            style = style.bright();
+        }
+    }
+    if let Some((bg, bg_bright)) = astyle.get_bg_color().map(to_console_color) {
+        style = style.bg(bg);
+        if bg_bright {
+            style = style.on_bright();
+        }
+    }
+
+    let effects = astyle.get_effects();
+    if effects.contains(anstyle::Effects::BOLD) {
+        style = style.bold();
+    }
+    if effects.contains(anstyle::Effects::DIMMED) {
+        style = style.dim();
+    }
+    if effects.contains(anstyle::Effects::ITALIC) {
+        style = style.italic();
+    }
+    let underlines = anstyle::Effects::UNDERLINE
+        | anstyle::Effects::DOUBLE_UNDERLINE
+        | anstyle::Effects::CURLY_UNDERLINE
+        | anstyle::Effects::DOTTED_UNDERLINE
+        | anstyle::Effects::DASHED_UNDERLINE;
+    if underlines.remove(effects) != underlines {
+        style = style.underlined();
+    }
+    if effects.contains(anstyle::Effects::BLINK) {
+        style = style.blink();
+    }
+    if effects.contains(anstyle::Effects::INVERT) {
+        style = style.reverse();
+    }
+    if effects.contains(anstyle::Effects::HIDDEN) {
+        style = style.hidden();
+    }
+    if effects.contains(anstyle::Effects::STRIKETHROUGH) {
+        style = style.strikethrough();
+    }
+    style
+}
+
+fn to_console_color(color: anstyle::Color) -> (console::Color, bool) {
+    match color {
+        anstyle::Color::Ansi(ansi) => ansi_to_console_color(ansi),
+        anstyle::Color::Ansi256(xterm) => (console::Color::Color256(xterm.0), false),
+        anstyle::Color::Rgb(rgb) => (
+            console::Color::Color256(anstyle_lossy::rgb_to_xterm(rgb).0),
+            false,
+        ),
+    }
+}
+
+fn ansi_to_console_color(color: anstyle::AnsiColor) -> (console::Color, bool) {
+    match color {
+        anstyle::AnsiColor::Black => (console::Color::Black, false),
+        anstyle::AnsiColor::Red => (console::Color::Red, false),
+        anstyle::AnsiColor::Green => (console::Color::Green, false),
+        anstyle::AnsiColor::Yellow => (console::Color::Yellow, false),
+        anstyle::AnsiColor::Blue => (console::Color::Blue, false),
+        anstyle::AnsiColor::Magenta => (console::Color::Magenta, false),
+        anstyle::AnsiColor::Cyan => (console::Color::Cyan, false),
+        anstyle::AnsiColor::White => (console::Color::White, false),
+        anstyle::AnsiColor::BrightBlack => (console::Color::Black, true),
+        anstyle::AnsiColor::BrightRed => (console::Color::Red, true),
+        anstyle::AnsiColor::BrightGreen => (console::Color::Green, true),
+        anstyle::AnsiColor::BrightYellow => (console::Color::Yellow, true),
+        anstyle::AnsiColor::BrightBlue => (console::Color::Blue, true),
+        anstyle::AnsiColor::BrightMagenta => (console::Color::Magenta, true),
+        anstyle::AnsiColor::BrightCyan => (console::Color::Cyan, true),
+        anstyle::AnsiColor::BrightWhite => (console::Color::White, true),
+    }
+}
+
+/// `console::Style` doesn't expose its colors and attributes, so they are read back from the
+/// escape codes it writes
+pub fn to_anstyle(style: &console::Style) -> anstyle::Style {
+    let rendered = style.clone().force_styling(true).apply_to("").to_string();
+
+    let mut astyle = anstyle::Style::new();
+    for code in rendered
+        .split("\x1b[")
+        .filter_map(|code| code.strip_suffix('m'))
+    {
+        let codes = code
+            .split(';')
+            .map(|n| n.parse::<u8>().unwrap_or(0))
+            .collect::<Vec<_>>();
+        astyle = match codes.as_slice() {
+            [38, 5, n] => astyle.fg_color(Some(from_xterm(*n))),
+            [48, 5, n] => astyle.bg_color(Some(from_xterm(*n))),
+            [n @ 30..=37] => astyle.fg_color(Some(from_xterm(n - 30))),
+            [n @ 40..=47] => astyle.bg_color(Some(from_xterm(n - 40))),
+            [1] => astyle | anstyle::Effects::BOLD,
+            [2] => astyle | anstyle::Effects::DIMMED,
+            [3] => astyle | anstyle::Effects::ITALIC,
+            [4] => astyle | anstyle::Effects::UNDERLINE,
+            [5] | [6] => astyle | anstyle::Effects::BLINK,
+            [7] => astyle | anstyle::Effects::INVERT,
+            [8] => astyle | anstyle::Effects::HIDDEN,
+            [9] => astyle | anstyle::Effects::STRIKETHROUGH,
+            _ => astyle,
+        };
+    }
+    astyle
+}
+
+pub fn to_anstyle_color(color: console::Color) -> anstyle::Color {
+    match color {
+        console::Color::Black => anstyle::AnsiColor::Black.into(),
+        console::Color::Red => anstyle::AnsiColor::Red.into(),
+        console::Color::Green => anstyle::AnsiColor::Green.into(),
+        console::Color::Yellow => anstyle::AnsiColor::Yellow.into(),
+        console::Color::Blue => anstyle::AnsiColor::Blue.into(),
+        console::Color::Magenta => anstyle::AnsiColor::Magenta.into(),
+        console::Color::Cyan => anstyle::AnsiColor::Cyan.into(),
+        console::Color::White => anstyle::AnsiColor::White.into(),
+        console::Color::Color256(index) => anstyle::Ansi256Color(index).into(),
+    }
+}
+
+/// `console` writes bright colors as 256-colors
+fn from_xterm(index: u8) -> anstyle::Color {
+    let xterm = anstyle::Ansi256Color(index);
+    match xterm.into_ansi() {
+        Some(ansi) => ansi.into(),
+        None => xterm.into(),
+    }
+}
@@ -10,6 +10,13 @@
 //! let hyperlink_style = anstyle_git::parse("#0000ee ul").unwrap();
 //! assert_eq!(hyperlink_style, anstyle::RgbColor(0x00, 0x00, 0xee).on_default() | anstyle::Effects::UNDERLINE);
 //! ```
+//!
+//! `anstyle_git::render` goes the other way, for writing styles back to a Git config:
+//!
+//! ```rust
+//! let style = anstyle::AnsiColor::Red.on(anstyle::AnsiColor::Blue) | anstyle::Effects::BOLD;
+//! assert_eq!(anstyle_git::render(style).unwrap(), "bold red blue");
+//! ```
 
 mod sealed {
     pub(crate) trait Sealed {}
@@ -108,6 +115,86 @@ pub fn parse(s: &str) -> Result<anstyle::Style, Error> {
     Ok(style)
 }
 
+/// Render an `anstyle::Style` in Git's color configuration syntax.
+///
+/// The result can be read back with [`parse`].  Bright colors are written as their 256-color
+/// index (e.g. `9` for bright red) and the underline variants as `ul`, as Git has no other way to
+/// express them.  Styles that can't be expressed at all, those with [`anstyle::Effects::HIDDEN`]
+/// or an underline color, are an error.
+pub fn render(style: anstyle::Style) -> Result<String, Error> {
+    if let Some(color) = style.get_underline_color() {
+        return Err(Error::UnsupportedUnderlineColor { color });
+    }
+    let supported = EFFECTS
+        .iter()
+        .fold(anstyle::Effects::new(), |all, (effect, _)| all | *effect);
+    let unsupported = style.get_effects() - supported;
+    if !unsupported.is_plain() {
+        return Err(Error::UnsupportedEffects {
+            effects: unsupported,
+        });
+    }
+
+    let mut words = Vec::new();
+    for (effect, word) in EFFECTS {
+        // Any of the underline variants
+        let missing = effect - style.get_effects();
+        if missing != effect {
+            words.push(word.to_string());
+        }
+    }
+    match (style.get_fg_color(), style.get_bg_color()) {
+        (None, None) => {}
+        (Some(fg), None) => words.push(render_color(fg)),
+        (fg, Some(bg)) => {
+            words.push(fg.map(render_color).unwrap_or_else(|| "normal".to_owned()));
+            words.push(render_color(bg));
+        }
+    }
+    if words.is_empty() {
+        words.push("normal".to_owned());
+    }
+    Ok(words.join(" "))
+}
+
+/// Effects Git supports, in the order they are rendered
+const EFFECTS: [(anstyle::Effects, &str); 7] = [
+    (anstyle::Effects::BOLD, "bold"),
+    (anstyle::Effects::DIMMED, "dim"),
+    (anstyle::Effects::ITALIC, "italic"),
+    (
+        anstyle::Effects::UNDERLINE
+            .insert(anstyle::Effects::DOUBLE_UNDERLINE)
+            .insert(anstyle::Effects::CURLY_UNDERLINE)
+            .insert(anstyle::Effects::DOTTED_UNDERLINE)
+            .insert(anstyle::Effects::DASHED_UNDERLINE),
+        "ul",
+    ),
+    (anstyle::Effects::BLINK, "blink"),
+    (anstyle::Effects::INVERT, "reverse"),
+    (anstyle::Effects::STRIKETHROUGH, "strike"),
+];
+
+fn render_color(color: anstyle::Color) -> String {
+    match color {
+        anstyle::Color::Ansi(color) => match color {
+            anstyle::AnsiColor::Black => "black".to_owned(),
+            anstyle::AnsiColor::Red => "red".to_owned(),
+            anstyle::AnsiColor::Green => "green".to_owned(),
+            anstyle::AnsiColor::Yellow => "yellow".to_owned(),
+            anstyle::AnsiColor::Blue => "blue".to_owned(),
+            anstyle::AnsiColor::Magenta => "magenta".to_owned(),
+            anstyle::AnsiColor::Cyan => "cyan".to_owned(),
+            anstyle::AnsiColor::White => "white".to_owned(),
+            bright => anstyle::Ansi256Color::from_ansi(bright).index().to_string(),
+        },
+        anstyle::Color::Ansi256(color) => color.index().to_string(),
+        anstyle::Color::Rgb(color) => {
+            format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+        }
+    }
+}
+
 fn parse_color(word: &str) -> Result<Option<anstyle::Color>, ()> {
     let color = match word {
         "normal" => None,
@@ -149,6 +236,10 @@ pub enum Error {
     ExtraColor { style: String, word: String },
     /// An unknown word appeared.
     UnknownWord { style: String, word: String },
+    /// Git has no way to express these effects.
+    UnsupportedEffects { effects: anstyle::Effects },
+    /// Git has no way to express an underline color.
+    UnsupportedUnderlineColor { color: anstyle::Color },
 }
 
 impl std::fmt::Display for Error {
@@ -168,6 +259,20 @@ impl std::fmt::Display for Error {
                     style, word
                 )
             }
+            Self::UnsupportedEffects { effects } => {
+                write!(
+                    fmt,
+                    "Error rendering style: unsupported effects {:?}",
+                    effects
+                )
+            }
+            Self::UnsupportedUnderlineColor { color } => {
+                write!(
+                    fmt,
+                    "Error rendering style: unsupported underline color {:?}",
+                    color
+                )
+            }
         }
     }
 }
@@ -273,6 +378,47 @@ mod tests {
         test!("blue#123456" => UnknownWord "blue#123456");
     }
 
+    #[test]
+    fn test_render_style() {
+        macro_rules! test {
+            ($style:expr => $s:expr) => {
+                assert_eq!(render($style).unwrap(), $s);
+                assert_eq!(parse($s).unwrap(), $style);
+            };
+        }
+
+        test!(Style::new() => "normal");
+        test!(Red.on_default() => "red");
+        test!(Red.on(Blue) => "red blue");
+        test!(Style::new().bg_color(Some(Red.into())) => "normal red");
+        test!(Ansi256Color(208).on(Ansi256Color(3)) => "208 3");
+        test!(RgbColor(0x20, 0x40, 0x60).on_default() => "#204060");
+        test!(Cyan.on(White).bold().underline().dimmed() => "bold dim ul cyan white");
+        test!(Cyan.on(White).italic().blink().invert().strikethrough() => "italic blink reverse strike cyan white");
+        test!(Style::new().bold() => "bold");
+
+        // Closest expressible form
+        assert_eq!(render(BrightRed.on(BrightBlack)).unwrap(), "9 8");
+        assert_eq!(
+            render(Style::new().effects(Effects::CURLY_UNDERLINE)).unwrap(),
+            "ul"
+        );
+    }
+
+    #[test]
+    fn test_render_style_err() {
+        assert_eq!(
+            render(Red.on_default().hidden().bold()),
+            Err(UnsupportedEffects {
+                effects: Effects::HIDDEN
+            })
+        );
+        assert_eq!(
+            render(Style::new().underline().underline_color(Some(Red.into()))),
+            Err(UnsupportedUnderlineColor { color: Red.into() })
+        );
+    }
+
     #[test]
     fn test_extension_trait() {
         let style = anstyle::Style::parse_git("red blue");
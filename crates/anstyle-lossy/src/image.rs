@@ -0,0 +1,254 @@
+//! Render small images as colored terminal cells, or as half blocks for twice the resolution
+//!
+//! This is for banner art and thumbnails in terminals without a graphics protocol.  Colors are
+//! reduced to what the terminal supports with
+//! [Floyd–Steinberg dithering](https://en.wikipedia.org/wiki/Floyd%E2%80%93Steinberg_dithering),
+//! so gradients still read as gradients in 256 or 16 colors.
+
+use std::fmt::Write as _;
+
+use anstyle::Color;
+use anstyle::ColorLevel;
+use anstyle::RgbColor;
+use anstyle::Style;
+
+use crate::palette::Palette;
+
+/// An RGB image, stored row by row
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Image<'p> {
+    pixels: &'p [RgbColor],
+    width: usize,
+}
+
+impl<'p> Image<'p> {
+    /// # Panics
+    ///
+    /// If `width` is zero or `pixels` isn't made up of whole rows
+    pub fn new(pixels: &'p [RgbColor], width: usize) -> Self {
+        assert!(width != 0, "images must be at least one pixel wide");
+        assert!(
+            pixels.len() % width == 0,
+            "{} pixels aren't whole rows of {width}",
+            pixels.len()
+        );
+        Self { pixels, width }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.pixels.len() / self.width
+    }
+}
+
+/// Render `image` as a background-colored cell per pixel
+///
+/// Each pixel is two spaces wide, to be about square.  Each row ends with a reset, if needed, and
+/// a newline.  For [`ColorLevel::None`], the image is left blank.
+///
+/// # Examples
+///
+/// ```rust
+/// use anstyle::RgbColor;
+/// use anstyle_lossy::image::Image;
+///
+/// let pixels = [RgbColor(255, 0, 0), RgbColor(0, 0, 255)];
+/// let image = Image::new(&pixels, 2);
+/// let rendered =
+///     anstyle_lossy::image::render_background(image, anstyle::ColorLevel::Ansi256, Default::default());
+/// assert_eq!(rendered, "\x1b[48;5;196m  \x1b[48;5;21m  \x1b[0m\n");
+/// ```
+pub fn render_background(image: Image<'_>, colors: ColorLevel, palette: Palette) -> String {
+    let quantized = quantize(image, colors, palette);
+    let mut output = String::new();
+    for row in quantized.chunks_exact(image.width) {
+        let mut current = Style::new();
+        for color in row {
+            let style = Style::new().bg_color(*color);
+            switch(&mut output, current, style);
+            current = style;
+            output.push_str("  ");
+        }
+        switch(&mut output, current, Style::new());
+        output.push('\n');
+    }
+    output
+}
+
+/// Render `image` as a `▀` half block per two pixels, one above the other
+///
+/// The top pixel is the foreground color and the bottom pixel is the background color, so
+/// pixels are about square.  An odd last row has the default background.  Each row ends with a
+/// reset, if needed, and a newline.  For [`ColorLevel::None`], the image is left blank.
+///
+/// # Examples
+///
+/// ```rust
+/// use anstyle::RgbColor;
+/// use anstyle_lossy::image::Image;
+///
+/// let pixels = [RgbColor(255, 0, 0), RgbColor(0, 0, 255), RgbColor(0, 0, 255)];
+/// let image = Image::new(&pixels, 1);
+/// let rendered =
+///     anstyle_lossy::image::render_half_blocks(image, anstyle::ColorLevel::Rgb, Default::default());
+/// assert_eq!(
+///     rendered,
+///     "\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[0m\n\x1b[38;2;0;0;255m▀\x1b[0m\n"
+/// );
+/// ```
+pub fn render_half_blocks(image: Image<'_>, colors: ColorLevel, palette: Palette) -> String {
+    let quantized = quantize(image, colors, palette);
+    let mut output = String::new();
+    for rows in quantized.chunks(image.width * 2) {
+        let (top, bottom) = rows.split_at(image.width);
+        let mut current = Style::new();
+        for (x, fg) in top.iter().enumerate() {
+            let bg = bottom.get(x).copied().flatten();
+            let style = Style::new().fg_color(*fg).bg_color(bg);
+            switch(&mut output, current, style);
+            current = style;
+            // Without a color, the block would be drawn in the default foreground color
+            output.push(if fg.is_some() { '▀' } else { ' ' });
+        }
+        switch(&mut output, current, Style::new());
+        output.push('\n');
+    }
+    output
+}
+
+/// Write the escape codes to go from showing `from` to showing `to`
+fn switch(output: &mut String, from: Style, to: Style) {
+    if from == to {
+        return;
+    }
+    let dropped = |color: fn(Style) -> Option<Color>| color(from).is_some() && color(to).is_none();
+    if dropped(Style::get_fg_color) || dropped(Style::get_bg_color) {
+        let _ = write!(output, "{}", from.render_reset());
+    }
+    let _ = write!(output, "{}", to.render());
+}
+
+/// Reduce each pixel to a color the terminal supports, carrying the difference to its neighbors
+fn quantize(image: Image<'_>, colors: ColorLevel, palette: Palette) -> Vec<Option<Color>> {
+    match colors {
+        ColorLevel::None => return vec![None; image.pixels.len()],
+        ColorLevel::Rgb => return image.pixels.iter().map(|c| Some(Color::Rgb(*c))).collect(),
+        ColorLevel::Ansi | ColorLevel::Ansi256 => {}
+    }
+
+    let width = image.width;
+    let mut quantized = Vec::with_capacity(image.pixels.len());
+    // Error, in sixteenths, carried into this row and the next, with a column of padding on
+    // each side
+    let mut current = vec![[0; 3]; width + 2];
+    let mut next = vec![[0; 3]; width + 2];
+    for row in image.pixels.chunks_exact(width) {
+        for (x, pixel) in row.iter().enumerate() {
+            let error = current[x + 1];
+            let channel = |value: u8, i: usize| (i32::from(value) + error[i] / 16).clamp(0, 255);
+            let wanted = [
+                channel(pixel.r(), 0),
+                channel(pixel.g(), 1),
+                channel(pixel.b(), 2),
+            ];
+            let wanted_rgb = RgbColor(wanted[0] as u8, wanted[1] as u8, wanted[2] as u8);
+
+            let (color, shown) = match colors {
+                ColorLevel::Ansi256 => {
+                    let color = crate::rgb_to_xterm(wanted_rgb);
+                    (Color::Ansi256(color), crate::xterm_to_rgb(color, palette))
+                }
+                _ => {
+                    let color = crate::rgb_to_ansi(wanted_rgb, palette);
+                    (Color::Ansi(color), crate::ansi_to_rgb(color, palette))
+                }
+            };
+            quantized.push(Some(color));
+
+            let shown = [shown.r(), shown.g(), shown.b()];
+            for i in 0..3 {
+                let error = wanted[i] - i32::from(shown[i]);
+                current[x + 2][i] += error * 7;
+                next[x][i] += error * 3;
+                next[x + 1][i] += error * 5;
+                next[x + 2][i] += error;
+            }
+        }
+        std::mem::swap(&mut current, &mut next);
+        next.fill([0; 3]);
+    }
+    quantized
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use anstyle::AnsiColor;
+
+    #[test]
+    fn dither_flat_gray() {
+        // Half-way between `BrightBlack` and `White`
+        let pixels = [RgbColor(128, 128, 128); 64];
+        let image = Image::new(&pixels, 8);
+        let quantized = quantize(image, ColorLevel::Ansi, crate::palette::VGA);
+        let count = |ansi: AnsiColor| {
+            quantized
+                .iter()
+                .filter(|color| **color == Some(Color::Ansi(ansi)))
+                .count()
+        };
+        let dark = count(AnsiColor::BrightBlack);
+        let light = count(AnsiColor::White);
+        assert_eq!(dark + light, pixels.len());
+        assert!(16 < dark && 16 < light, "{dark} dark, {light} light");
+    }
+
+    #[test]
+    fn error_stays_in_row() {
+        // Rounding the end of the first row down would round the start of the second row up, if
+        // carried over
+        let black = RgbColor(0, 0, 0);
+        let pixels = [
+            black,
+            black,
+            RgbColor(120, 120, 120),
+            RgbColor(127, 127, 127),
+            black,
+            black,
+        ];
+        let image = Image::new(&pixels, 3);
+        let quantized = quantize(image, ColorLevel::Ansi, crate::palette::VGA);
+        assert_eq!(quantized[2], Some(Color::Ansi(AnsiColor::BrightBlack)));
+        assert_eq!(quantized[3], Some(Color::Ansi(AnsiColor::BrightBlack)));
+    }
+
+    #[test]
+    fn no_colors() {
+        let pixels = [RgbColor(255, 0, 0), RgbColor(0, 0, 255)];
+        let image = Image::new(&pixels, 1);
+        assert_eq!(
+            render_background(image, ColorLevel::None, Default::default()),
+            "  \n  \n"
+        );
+        assert_eq!(
+            render_half_blocks(image, ColorLevel::None, Default::default()),
+            " \n"
+        );
+    }
+
+    #[test]
+    fn half_blocks_odd_rows() {
+        let red = RgbColor(255, 0, 0);
+        let blue = RgbColor(0, 0, 255);
+        let pixels = [red, red, blue, blue, red, blue];
+        let image = Image::new(&pixels, 2);
+        assert_eq!(
+            render_half_blocks(image, ColorLevel::Rgb, Default::default()),
+            "\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀▀\x1b[0m\n\
+             \x1b[38;2;255;0;0m▀\x1b[38;2;0;0;255m▀\x1b[0m\n"
+        );
+    }
+}
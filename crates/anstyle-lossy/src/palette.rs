@@ -11,40 +11,12 @@ impl Palette {
         self.0[color.index() as usize]
     }
 
-    pub(crate) const fn rgb_from_index(&self, index: u8) -> Option<anstyle::RgbColor> {
-        let index = index as usize;
-        if index <= self.0.len() {
-            Some(self.0[index])
-        } else {
-            None
-        }
+    pub(crate) const fn rgb_from_xterm(&self, color: anstyle::Ansi256Color) -> anstyle::RgbColor {
+        color.to_rgb(&self.0)
     }
 
     pub(crate) const fn find_match(&self, color: anstyle::RgbColor) -> anstyle::AnsiColor {
-        let mut best_index = 0;
-        let mut best_distance = crate::distance(color, self.0[best_index]);
-
-        let mut index = best_index + 1;
-        while index < self.0.len() {
-            let distance = crate::distance(color, self.0[index]);
-            if distance < best_distance {
-                best_index = index;
-                best_distance = distance;
-            }
-
-            index += 1;
-        }
-
-        match anstyle::Ansi256Color(best_index as u8).into_ansi() {
-            Some(color) => color,
-            None => {
-                // Panic
-                #[allow(clippy::no_effect)]
-                ["best_index is out of bounds"][best_index];
-                // Make compiler happy
-                anstyle::AnsiColor::Black
-            }
-        }
+        color.to_ansi16(&self.0)
     }
 }
 
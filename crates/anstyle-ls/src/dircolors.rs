@@ -0,0 +1,198 @@
+use crate::StyleSheet;
+
+/// Parse a `dircolors` database, like `~/.dir_colors` or the output of `dircolors -p`
+///
+/// See [`StyleSheet::parse_dircolors`]
+pub(crate) fn parse(text: &str, term: &str, colorterm: &str) -> StyleSheet {
+    let mut sheet = StyleSheet::new();
+    let mut state = State::Global;
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        let Some(keyword) = words.next() else {
+            continue;
+        };
+        if keyword.starts_with('#') {
+            continue;
+        }
+        let Some(arg) = words.next().filter(|arg| !arg.starts_with('#')) else {
+            continue;
+        };
+
+        let env = if keyword.eq_ignore_ascii_case("TERM") {
+            Some(term)
+        } else if keyword.eq_ignore_ascii_case("COLORTERM") {
+            Some(colorterm)
+        } else {
+            None
+        };
+        if let Some(env) = env {
+            // Consecutive `TERM` lines form one section, applying if any of them match
+            if glob_match(arg.as_bytes(), env.as_bytes()) {
+                state = State::TermSure;
+            } else if state != State::TermSure {
+                state = State::TermNo;
+            }
+            continue;
+        }
+
+        if state == State::TermSure {
+            state = State::TermYes;
+        }
+        if state == State::TermNo {
+            continue;
+        }
+
+        let key = if keyword.starts_with('.') {
+            format!("*{keyword}")
+        } else if keyword.starts_with('*') {
+            keyword.to_owned()
+        } else if let Some((_, key)) = KEYWORDS
+            .iter()
+            .find(|(name, _)| keyword.eq_ignore_ascii_case(name))
+        {
+            (*key).to_owned()
+        } else {
+            // `OPTIONS`, `COLOR`, `EIGHTBIT`, or unrecognized
+            continue;
+        };
+        if let Some(style) = crate::stylesheet::parse_code(arg) {
+            sheet.insert(key, style);
+        }
+    }
+    sheet
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum State {
+    /// Before any `TERM` section
+    Global,
+    /// In a `TERM` section for another terminal
+    TermNo,
+    /// In the `TERM` lines for a section that applies
+    TermSure,
+    /// In a `TERM` section that applies
+    TermYes,
+}
+
+/// Long names of the `LS_COLORS` keys, from coreutils' `dircolors`
+const KEYWORDS: &[(&str, &str)] = &[
+    ("NORMAL", "no"),
+    ("NORM", "no"),
+    ("FILE", "fi"),
+    ("RESET", "rs"),
+    ("DIR", "di"),
+    ("LNK", "ln"),
+    ("LINK", "ln"),
+    ("SYMLINK", "ln"),
+    ("ORPHAN", "or"),
+    ("MISSING", "mi"),
+    ("FIFO", "pi"),
+    ("PIPE", "pi"),
+    ("SOCK", "so"),
+    ("BLK", "bd"),
+    ("BLOCK", "bd"),
+    ("CHR", "cd"),
+    ("CHAR", "cd"),
+    ("DOOR", "do"),
+    ("EXEC", "ex"),
+    ("LEFT", "lc"),
+    ("LEFTCODE", "lc"),
+    ("RIGHT", "rc"),
+    ("RIGHTCODE", "rc"),
+    ("END", "ec"),
+    ("ENDCODE", "ec"),
+    ("SUID", "su"),
+    ("SETUID", "su"),
+    ("SGID", "sg"),
+    ("SETGID", "sg"),
+    ("STICKY", "st"),
+    ("OTHER_WRITABLE", "ow"),
+    ("OWR", "ow"),
+    ("STICKY_OTHER_WRITABLE", "tw"),
+    ("OWT", "tw"),
+    ("CAPABILITY", "ca"),
+    ("MULTIHARDLINK", "mh"),
+    ("CLRTOEOL", "cl"),
+];
+
+/// Shell-style glob matching, supporting `*` and `?`
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match (pattern.split_first(), text.split_first()) {
+        (None, None) => true,
+        (Some((b'*', rest)), _) => {
+            glob_match(rest, text) || (!text.is_empty() && glob_match(pattern, &text[1..]))
+        }
+        (Some((b'?', rest)), Some((_, text_rest))) => glob_match(rest, text_rest),
+        (Some((p, rest)), Some((t, text_rest))) if p == t => glob_match(rest, text_rest),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATABASE: &str = "\
+# Configuration file for dircolors
+COLOR tty
+
+TERM xterm*
+TERM screen # comment
+COLORTERM ?*
+NORMAL 00 # no color code at all
+DIR 01;34
+LINK 01;36
+ORPHAN 40;31;01
+.tar 01;31
+*README 00;33
+EIGHTBIT 1
+
+TERM vt100
+DIR 07
+";
+
+    #[test]
+    fn matching_term() {
+        let sheet = parse(DATABASE, "xterm-256color", "");
+        assert_eq!(
+            sheet.to_ls_colors(),
+            "no=0:di=01;34:ln=01;36:or=01;31;40:*.tar=01;31:*README=33"
+        );
+        let sheet = parse(DATABASE, "screen", "");
+        assert_eq!(sheet.len(), 6);
+    }
+
+    #[test]
+    fn matching_colorterm() {
+        let sheet = parse(DATABASE, "dumb", "truecolor");
+        assert_eq!(sheet.len(), 6);
+    }
+
+    #[test]
+    fn later_section() {
+        let sheet = parse(DATABASE, "vt100", "");
+        assert_eq!(sheet.to_ls_colors(), "di=07");
+    }
+
+    #[test]
+    fn no_match() {
+        let sheet = parse(DATABASE, "dumb", "");
+        assert!(sheet.is_empty());
+    }
+
+    #[test]
+    fn global() {
+        let sheet = parse("dir 01;34\n  exec 01;32\nOPTIONS -F\n", "dumb", "");
+        assert_eq!(sheet.to_ls_colors(), "di=01;34:ex=01;32");
+    }
+
+    #[test]
+    fn glob() {
+        assert!(glob_match(b"xterm*", b"xterm"));
+        assert!(glob_match(b"xterm*", b"xterm-256color"));
+        assert!(glob_match(b"*color*", b"xterm-256color"));
+        assert!(glob_match(b"?*", b"truecolor"));
+        assert!(!glob_match(b"?*", b""));
+        assert!(!glob_match(b"xterm", b"xterm-256color"));
+    }
+}
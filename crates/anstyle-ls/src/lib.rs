@@ -7,6 +7,13 @@
 //! let style = anstyle_ls::parse("34;03").unwrap();
 //! assert_eq!(style, anstyle::AnsiColor::Blue.on_default() | anstyle::Effects::ITALIC);
 //! ```
+//!
+//! A whole `LS_COLORS` string, or `dircolors` database, is a [`StyleSheet`].
+
+mod dircolors;
+mod stylesheet;
+
+pub use stylesheet::StyleSheet;
 
 mod sealed {
     pub(crate) trait Sealed {}
@@ -175,6 +182,79 @@ pub fn parse(code: &str) -> Option<anstyle::Style> {
     )
 }
 
+/// Render an `anstyle::Style` in `LS_COLORS`'s color configuration syntax.
+///
+/// The underline variants are written as a plain underline.
+///
+/// # Examples
+///
+/// ```rust
+/// let style = anstyle::AnsiColor::Blue.on_default() | anstyle::Effects::BOLD;
+/// assert_eq!(anstyle_ls::render(style), "01;34");
+/// ```
+pub fn render(style: anstyle::Style) -> String {
+    let mut codes = Vec::new();
+    let effects = style.get_effects();
+    for (effect, code) in [
+        (anstyle::Effects::BOLD, "01"),
+        (anstyle::Effects::DIMMED, "02"),
+        (anstyle::Effects::ITALIC, "03"),
+        (
+            anstyle::Effects::UNDERLINE
+                .insert(anstyle::Effects::DOUBLE_UNDERLINE)
+                .insert(anstyle::Effects::CURLY_UNDERLINE)
+                .insert(anstyle::Effects::DOTTED_UNDERLINE)
+                .insert(anstyle::Effects::DASHED_UNDERLINE),
+            "04",
+        ),
+        (anstyle::Effects::BLINK, "05"),
+        (anstyle::Effects::INVERT, "07"),
+        (anstyle::Effects::HIDDEN, "08"),
+        (anstyle::Effects::STRIKETHROUGH, "09"),
+    ] {
+        // Any of the underline variants
+        if effect - effects != effect {
+            codes.push(code.to_owned());
+        }
+    }
+    if let Some(color) = style.get_fg_color() {
+        codes.push(render_color(color, 30, 90, 38));
+    }
+    if let Some(color) = style.get_bg_color() {
+        codes.push(render_color(color, 40, 100, 48));
+    }
+    if let Some(color) = style.get_underline_color() {
+        let color = match color {
+            anstyle::Color::Ansi(color) => anstyle::Ansi256Color::from_ansi(color).into(),
+            color => color,
+        };
+        codes.push(render_color(color, 0, 0, 58));
+    }
+
+    if codes.is_empty() {
+        "0".to_owned()
+    } else {
+        codes.join(";")
+    }
+}
+
+fn render_color(color: anstyle::Color, base: u8, bright_base: u8, extended: u8) -> String {
+    match color {
+        anstyle::Color::Ansi(color) => {
+            let index = anstyle::Ansi256Color::from_ansi(color).index();
+            if index < 8 {
+                (base + index).to_string()
+            } else {
+                (bright_base + index - 8).to_string()
+            }
+        }
+        anstyle::Color::Ansi256(color) => format!("{extended};5;{}", color.index()),
+        anstyle::Color::Rgb(color) => {
+            format!("{extended};2;{};{};{}", color.r(), color.g(), color.b())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -283,6 +363,46 @@ mod tests {
         );
     }
 
+    #[track_caller]
+    fn assert_render(style: impl Into<anstyle::Style>, expected: &str) {
+        let style = style.into();
+        assert_eq!(render(style), expected);
+        assert_eq!(parse(expected).unwrap_or_default(), style);
+    }
+
+    #[test]
+    fn render_style() {
+        assert_render(anstyle::Style::new(), "0");
+        assert_render(anstyle::AnsiColor::Red.on_default(), "31");
+        assert_render(
+            anstyle::AnsiColor::Green.on(anstyle::AnsiColor::BrightBlack),
+            "32;100",
+        );
+        assert_render(
+            anstyle::AnsiColor::BrightCyan.on_default() | anstyle::Effects::BOLD,
+            "01;96",
+        );
+        assert_render(
+            anstyle::Effects::BOLD
+                | anstyle::Effects::DIMMED
+                | anstyle::Effects::ITALIC
+                | anstyle::Effects::UNDERLINE
+                | anstyle::Effects::BLINK
+                | anstyle::Effects::INVERT
+                | anstyle::Effects::HIDDEN
+                | anstyle::Effects::STRIKETHROUGH,
+            "01;02;03;04;05;07;08;09",
+        );
+        assert_render(
+            anstyle::Ansi256Color(115).on(anstyle::RgbColor(100, 200, 0)),
+            "38;5;115;48;2;100;200;0",
+        );
+        assert_render(
+            anstyle::Style::new().underline_color(Some(anstyle::RgbColor(64, 64, 64).into())),
+            "58;2;64;64;64",
+        );
+    }
+
     #[test]
     fn parse_24_bit_colors() {
         assert_style(
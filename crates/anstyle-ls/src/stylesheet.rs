@@ -0,0 +1,198 @@
+/// The styles of an `LS_COLORS` string
+///
+/// Styles are keyed by file type (like `di` for directories) or glob (like `*.rs`), in the order
+/// they were added.
+///
+/// # Examples
+///
+/// ```rust
+/// let mut sheet = anstyle_ls::StyleSheet::parse("di=01;34:*.rs=33");
+/// assert_eq!(sheet.get("di"), Some(anstyle::AnsiColor::Blue.on_default() | anstyle::Effects::BOLD));
+///
+/// sheet.insert("ln", anstyle::AnsiColor::Cyan.on_default());
+/// assert_eq!(sheet.to_ls_colors(), "di=01;34:*.rs=33:ln=36");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StyleSheet {
+    entries: Vec<(String, anstyle::Style)>,
+}
+
+impl StyleSheet {
+    /// Create an empty `StyleSheet`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse an `LS_COLORS` string
+    ///
+    /// Entries that can't be parsed are skipped, like `ls` does.
+    pub fn parse(ls_colors: &str) -> Self {
+        let mut sheet = Self::new();
+        for entry in ls_colors.split(':') {
+            let Some((key, code)) = entry.split_once('=') else {
+                continue;
+            };
+            if key.is_empty() {
+                continue;
+            }
+            if let Some(style) = parse_code(code) {
+                sheet.insert(key, style);
+            }
+        }
+        sheet
+    }
+
+    /// Parse a `dircolors` database, like `~/.dir_colors` or the output of `dircolors -p`
+    ///
+    /// Only the `TERM` and `COLORTERM` sections matching `term` and `colorterm`, usually from
+    /// the environment variables of the same names, are used.  Unrecognized lines are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let database = "\
+    /// # Directories
+    /// DIR 01;34
+    /// .rs 33
+    /// ";
+    /// let sheet = anstyle_ls::StyleSheet::parse_dircolors(database, "xterm-256color", "");
+    /// assert_eq!(sheet.to_ls_colors(), "di=01;34:*.rs=33");
+    /// ```
+    pub fn parse_dircolors(text: &str, term: &str, colorterm: &str) -> Self {
+        crate::dircolors::parse(text, term, colorterm)
+    }
+
+    /// Set the style for `key`, replacing any existing one
+    pub fn insert(&mut self, key: impl Into<String>, style: anstyle::Style) {
+        let key = key.into();
+        if let Some(entry) = self.entries.iter_mut().find(|(k, _)| *k == key) {
+            entry.1 = style;
+        } else {
+            self.entries.push((key, style));
+        }
+    }
+
+    /// Remove the style for `key`
+    pub fn remove(&mut self, key: &str) -> Option<anstyle::Style> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        Some(self.entries.remove(index).1)
+    }
+
+    /// The style for `key`
+    pub fn get(&self, key: &str) -> Option<anstyle::Style> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, style)| *style)
+    }
+
+    /// All keys and styles, in the order they were added
+    pub fn iter(&self) -> impl Iterator<Item = (&str, anstyle::Style)> + '_ {
+        self.entries
+            .iter()
+            .map(|(key, style)| (key.as_str(), *style))
+    }
+
+    /// The number of styles
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no styles
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Render as an `LS_COLORS` string
+    pub fn to_ls_colors(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl std::fmt::Display for StyleSheet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, (key, style)) in self.entries.iter().enumerate() {
+            if i != 0 {
+                f.write_str(":")?;
+            }
+            write!(f, "{}={}", key, crate::render(*style))?;
+        }
+        Ok(())
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, anstyle::Style)> for StyleSheet {
+    fn from_iter<I: IntoIterator<Item = (K, anstyle::Style)>>(iter: I) -> Self {
+        let mut sheet = Self::new();
+        sheet.extend(iter);
+        sheet
+    }
+}
+
+impl<K: Into<String>> Extend<(K, anstyle::Style)> for StyleSheet {
+    fn extend<I: IntoIterator<Item = (K, anstyle::Style)>>(&mut self, iter: I) {
+        for (key, style) in iter {
+            self.insert(key, style);
+        }
+    }
+}
+
+/// Like [`parse`][crate::parse] but resets, like `rs=0`, are a plain style
+pub(crate) fn parse_code(code: &str) -> Option<anstyle::Style> {
+    if code.is_empty() || code.bytes().all(|b| b == b'0') {
+        Some(anstyle::Style::new())
+    } else {
+        crate::parse(code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let ls_colors = "rs=0:di=01;34:ln=01;36:mh=0:pi=33;40:*.tar=01;31:*.rs=38;5;208";
+        let sheet = StyleSheet::parse(ls_colors);
+        assert_eq!(sheet.len(), 7);
+        assert_eq!(sheet.get("rs"), Some(anstyle::Style::new()));
+        assert_eq!(
+            sheet.get("*.rs"),
+            Some(anstyle::Ansi256Color(208).on_default())
+        );
+        assert_eq!(sheet.to_ls_colors(), ls_colors);
+        assert_eq!(StyleSheet::parse(&sheet.to_ls_colors()), sheet);
+    }
+
+    #[test]
+    fn normalize() {
+        let sheet = StyleSheet::parse("no=00:di=1;34:pi=40;33:");
+        assert_eq!(sheet.to_ls_colors(), "no=0:di=01;34:pi=33;40");
+    }
+
+    #[test]
+    fn skip_invalid() {
+        let sheet = StyleSheet::parse("di:=01:ln=a:ex=01;32");
+        assert_eq!(sheet.to_ls_colors(), "ex=01;32");
+    }
+
+    #[test]
+    fn replace_duplicate() {
+        let sheet = StyleSheet::parse("di=01;34:ex=01;32:di=35");
+        assert_eq!(sheet.to_ls_colors(), "di=35:ex=01;32");
+    }
+
+    #[test]
+    fn from_iter() {
+        let mut sheet: StyleSheet = [("di", anstyle::AnsiColor::Blue.on_default())]
+            .into_iter()
+            .collect();
+        sheet.insert("*.md", anstyle::Style::new().underline());
+        assert_eq!(
+            sheet.remove("di"),
+            Some(anstyle::AnsiColor::Blue.on_default())
+        );
+        assert_eq!(sheet.to_ls_colors(), "*.md=04");
+        assert!(!sheet.is_empty());
+    }
+}
@@ -0,0 +1,383 @@
+//! Write styled text using inline markup, validated at compile-time
+//!
+//! [`styled!`] works like [`write!`] but its format string may contain style tags:
+//! - `[<style>]` applies a [Git style description](https://git-scm.com/docs/git-config#Documentation/git-config.txt-color),
+//!   like `[bold red]` or `[#0000ee ul]`, on top of any enclosing style
+//! - `[/]` closes the most recently opened tag, restoring the enclosing style
+//! - `[[` writes a literal `[`
+//!
+//! Tags are parsed when the macro is expanded, so an unknown style or an unbalanced `[/]` is a
+//! compile error and the escape codes are baked into the format string.  Any tags left open at
+//! the end of the format string are reset.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use std::fmt::Write as _;
+//!
+//! let err = "file not found";
+//! let path = "Cargo.toml";
+//!
+//! let mut buffer = String::new();
+//! anstyle_markup::styled!(buffer, "[bold red]error:[/] {err} at [cyan]{path}[/]").unwrap();
+//! assert_eq!(
+//!     buffer,
+//!     "\x1b[1m\x1b[31merror:\x1b[0m file not found at \x1b[36mCargo.toml\x1b[0m"
+//! );
+//! ```
+//!
+//! Mistakes are caught at compile-time:
+//! ```compile_fail
+//! use std::fmt::Write as _;
+//!
+//! let mut buffer = String::new();
+//! anstyle_markup::styled!(buffer, "[bold rde]error:[/]").unwrap();
+//! ```
+
+use proc_macro::Delimiter;
+use proc_macro::Group;
+use proc_macro::Ident;
+use proc_macro::Literal;
+use proc_macro::Punct;
+use proc_macro::Spacing;
+use proc_macro::Span;
+use proc_macro::TokenStream;
+use proc_macro::TokenTree;
+
+/// Write formatted data with inline style markup into a buffer
+///
+/// Usage is `styled!(writer, "format string", args...)`, with the same `writer` and `args` as
+/// [`write!`].  See the [crate documentation][crate] for the markup syntax.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Write as _;
+///
+/// let count = 3;
+/// let mut stdout = anstream::stdout();
+/// anstyle_markup::styled!(stdout, "[green]Compiled[/] {count} crates in [bold]{:.1}s[/]", 0.5).unwrap();
+/// ```
+#[proc_macro]
+pub fn styled(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output,
+        Err(err) => err.into_compile_error(),
+    }
+}
+
+fn expand(input: TokenStream) -> Result<TokenStream, Error> {
+    let mut tokens = input.into_iter();
+
+    let mut writer = Vec::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => break,
+            Some(token) => writer.push(token),
+            None => {
+                return Err(Error::new(
+                    Span::call_site(),
+                    "expected `styled!(writer, \"format string\", args...)`",
+                ))
+            }
+        }
+    }
+    if writer.is_empty() {
+        return Err(Error::new(Span::call_site(), "expected a writer"));
+    }
+
+    let format = tokens
+        .next()
+        .ok_or_else(|| Error::new(Span::call_site(), "expected a format string"))?;
+    let (format, span) = string_literal(format)?;
+
+    let args = tokens.collect::<Vec<_>>();
+    if let Some(first) = args.first() {
+        if !is_comma(first) {
+            return Err(Error::new(first.span(), "expected `,`"));
+        }
+    }
+
+    let markup = Markup::parse(&format).map_err(|message| Error::new(span, message))?;
+
+    let explicit = named_args(&args);
+    let mut literal = Literal::string(&markup.format);
+    literal.set_span(span);
+
+    let mut write_args = Vec::<TokenTree>::new();
+    write_args.push(Group::new(Delimiter::Parenthesis, writer.into_iter().collect()).into());
+    write_args.push(Punct::new(',', Spacing::Alone).into());
+    write_args.push(literal.into());
+    let mut args = args;
+    if args.last().map(is_comma).unwrap_or(false) {
+        args.pop();
+    }
+    write_args.extend(args);
+    for name in markup.captures {
+        if explicit.contains(&name) {
+            continue;
+        }
+        write_args.push(Punct::new(',', Spacing::Alone).into());
+        write_args.push(Ident::new(&name, span).into());
+        write_args.push(Punct::new('=', Spacing::Alone).into());
+        write_args.push(Ident::new(&name, span).into());
+    }
+
+    Ok(macro_call(
+        &["core", "write"],
+        write_args.into_iter().collect(),
+        Span::call_site(),
+    ))
+}
+
+/// Format string with the style tags replaced by their escape codes
+struct Markup {
+    format: String,
+    /// Arguments implicitly captured by the format string
+    ///
+    /// These are passed explicitly as the format string no longer comes from the caller's source.
+    captures: Vec<String>,
+}
+
+impl Markup {
+    fn parse(input: &str) -> Result<Self, String> {
+        let mut format = String::with_capacity(input.len());
+        let mut captures = Vec::new();
+        let mut stack: Vec<anstyle::Style> = Vec::new();
+
+        let mut rest = input;
+        while let Some(c) = rest.chars().next() {
+            match c {
+                '[' if rest.starts_with("[[") => {
+                    format.push('[');
+                    rest = &rest[2..];
+                }
+                '[' => {
+                    let end = rest
+                        .find(']')
+                        .ok_or_else(|| format!("unterminated style tag `{rest}`"))?;
+                    let tag = rest[1..end].trim();
+                    if tag == "/" {
+                        let current = stack
+                            .pop()
+                            .ok_or_else(|| "`[/]` without a matching style tag".to_owned())?;
+                        let enclosing = stack.last().copied().unwrap_or_default();
+                        if current != enclosing {
+                            format.push_str(&anstyle::Reset.render().to_string());
+                            format.push_str(&enclosing.render().to_string());
+                        }
+                    } else {
+                        let style = anstyle_git::parse(tag).map_err(|err| err.to_string())?;
+                        let enclosing = stack.last().copied().unwrap_or_default();
+                        format.push_str(&style.render().to_string());
+                        stack.push(compose(enclosing, style));
+                    }
+                    rest = &rest[end + 1..];
+                }
+                '{' if rest.starts_with("{{") => {
+                    format.push_str("{{");
+                    rest = &rest[2..];
+                }
+                '{' => {
+                    let end = rest
+                        .find('}')
+                        .ok_or_else(|| format!("unterminated format argument `{rest}`"))?;
+                    let argument = &rest[..=end];
+                    for name in argument_names(&argument[1..end]) {
+                        if !captures.iter().any(|c| c == name) {
+                            captures.push(name.to_owned());
+                        }
+                    }
+                    format.push_str(argument);
+                    rest = &rest[end + 1..];
+                }
+                _ => {
+                    format.push(c);
+                    rest = &rest[c.len_utf8()..];
+                }
+            }
+        }
+        if stack.last().map(|s| !s.is_plain()).unwrap_or(false) {
+            format.push_str(&anstyle::Reset.render().to_string());
+        }
+
+        Ok(Self { format, captures })
+    }
+}
+
+/// Apply `style` on top of `enclosing`
+fn compose(enclosing: anstyle::Style, style: anstyle::Style) -> anstyle::Style {
+    let mut composed = enclosing | style.get_effects();
+    if let Some(fg) = style.get_fg_color() {
+        composed = composed.fg_color(Some(fg));
+    }
+    if let Some(bg) = style.get_bg_color() {
+        composed = composed.bg_color(Some(bg));
+    }
+    if let Some(underline) = style.get_underline_color() {
+        composed = composed.underline_color(Some(underline));
+    }
+    composed
+}
+
+/// Named arguments referenced by a format argument, e.g. `name` and `width` in `{name:width$}`
+fn argument_names(argument: &str) -> impl Iterator<Item = &str> {
+    let (name, spec) = argument.split_once(':').unwrap_or((argument, ""));
+    let name = Some(name.trim());
+    let counts = spec.split('$').rev().skip(1).map(|s| {
+        let start = s
+            .rfind(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        &s[start..]
+    });
+    name.into_iter().chain(counts).filter(|name| is_ident(name))
+}
+
+fn is_ident(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .map(|c| c.is_alphabetic() || c == '_')
+        .unwrap_or(false)
+        && name != "_"
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Names of the `name = value` arguments
+fn named_args(args: &[TokenTree]) -> Vec<String> {
+    args.windows(3)
+        .filter_map(|window| match window {
+            [comma, TokenTree::Ident(name), TokenTree::Punct(eq)]
+                if is_comma(comma) && eq.as_char() == '=' && eq.spacing() == Spacing::Alone =>
+            {
+                Some(name.to_string())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn is_comma(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == ',')
+}
+
+fn string_literal(token: TokenTree) -> Result<(String, Span), Error> {
+    match token {
+        TokenTree::Group(group) if group.delimiter() == Delimiter::None => {
+            let mut inner = group.stream().into_iter();
+            match (inner.next(), inner.next()) {
+                (Some(token), None) => string_literal(token),
+                _ => Err(Error::new(group.span(), "expected a string literal")),
+            }
+        }
+        TokenTree::Literal(literal) => {
+            let span = literal.span();
+            unquote(&literal.to_string())
+                .map(|s| (s, span))
+                .ok_or_else(|| Error::new(span, "expected a string literal"))
+        }
+        token => Err(Error::new(token.span(), "expected a string literal")),
+    }
+}
+
+/// Get the value of a string literal from its source representation
+fn unquote(literal: &str) -> Option<String> {
+    if let Some(raw) = literal.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let raw = &raw[hashes..];
+        let raw = raw.strip_prefix('"')?;
+        let raw = raw.strip_suffix(&"#".repeat(hashes))?;
+        let raw = raw.strip_suffix('"')?;
+        return Some(raw.to_owned());
+    }
+
+    let cooked = literal.strip_prefix('"')?.strip_suffix('"')?;
+    let mut value = String::with_capacity(cooked.len());
+    let mut chars = cooked.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            '0' => value.push('\0'),
+            '\\' => value.push('\\'),
+            '\'' => value.push('\''),
+            '"' => value.push('"'),
+            'x' => {
+                let hex = [chars.next()?, chars.next()?].iter().collect::<String>();
+                value.push(char::from(u8::from_str_radix(&hex, 16).ok()?));
+            }
+            'u' => {
+                if chars.next()? != '{' {
+                    return None;
+                }
+                let mut hex = String::new();
+                loop {
+                    match chars.next()? {
+                        '}' => break,
+                        '_' => {}
+                        c => hex.push(c),
+                    }
+                }
+                value.push(char::from_u32(u32::from_str_radix(&hex, 16).ok()?)?);
+            }
+            '\n' => {
+                while chars.peek().map(|c| c.is_whitespace()).unwrap_or(false) {
+                    chars.next();
+                }
+            }
+            _ => return None,
+        }
+    }
+    Some(value)
+}
+
+/// Build `::path::to::macro!(args)`
+fn macro_call(path: &[&str], args: TokenStream, span: Span) -> TokenStream {
+    let mut tokens = Vec::<TokenTree>::new();
+    for segment in path {
+        let mut colon = Punct::new(':', Spacing::Joint);
+        colon.set_span(span);
+        tokens.push(colon.into());
+        let mut colon = Punct::new(':', Spacing::Alone);
+        colon.set_span(span);
+        tokens.push(colon.into());
+        tokens.push(Ident::new(segment, span).into());
+    }
+    let mut bang = Punct::new('!', Spacing::Alone);
+    bang.set_span(span);
+    tokens.push(bang.into());
+    let mut args = Group::new(Delimiter::Parenthesis, args);
+    args.set_span(span);
+    tokens.push(args.into());
+    tokens.into_iter().collect()
+}
+
+struct Error {
+    span: Span,
+    message: String,
+}
+
+impl Error {
+    fn new(span: Span, message: impl Into<String>) -> Self {
+        Self {
+            span,
+            message: message.into(),
+        }
+    }
+
+    fn into_compile_error(self) -> TokenStream {
+        let mut message = Literal::string(&self.message);
+        message.set_span(self.span);
+        macro_call(
+            &["core", "compile_error"],
+            TokenTree::from(message).into(),
+            self.span,
+        )
+    }
+}
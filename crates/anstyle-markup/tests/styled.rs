@@ -0,0 +1,64 @@
+use std::fmt::Write as _;
+
+use anstyle_markup::styled;
+
+#[test]
+fn plain() {
+    let mut buffer = String::new();
+    styled!(buffer, "Hello {}!", "world").unwrap();
+    assert_eq!(buffer, "Hello world!");
+}
+
+#[test]
+fn tags() {
+    let mut buffer = String::new();
+    styled!(buffer, "[red]Hello[/] [green blue]world[/]!").unwrap();
+    assert_eq!(buffer, "\x1b[31mHello\x1b[0m \x1b[32m\x1b[44mworld\x1b[0m!");
+}
+
+#[test]
+fn nested() {
+    let mut buffer = String::new();
+    styled!(buffer, "[bold]a [red]b[/] c[/] d").unwrap();
+    assert_eq!(buffer, "\x1b[1ma \x1b[31mb\x1b[0m\x1b[1m c\x1b[0m d");
+}
+
+#[test]
+fn unclosed() {
+    let mut buffer = String::new();
+    styled!(buffer, "[bold]a").unwrap();
+    assert_eq!(buffer, "\x1b[1ma\x1b[0m");
+}
+
+#[test]
+fn escaped_bracket() {
+    let mut buffer = String::new();
+    styled!(buffer, "[[bold] {{}}").unwrap();
+    assert_eq!(buffer, "[bold] {}");
+}
+
+#[test]
+fn captures() {
+    let name = "world";
+    let width = 6;
+    let mut buffer = String::new();
+    styled!(buffer, "[ul]{name:>width$}[/] {name} {}", 1).unwrap();
+    assert_eq!(buffer, "\x1b[4m world\x1b[0m world 1");
+}
+
+#[test]
+fn explicit_named() {
+    let name = "world";
+    let mut buffer = String::new();
+    styled!(buffer, "[ul]{name:>width$}[/]", width = 7,).unwrap();
+    assert_eq!(buffer, "\x1b[4m  world\x1b[0m");
+}
+
+#[test]
+fn io_write() {
+    use std::io::Write as _;
+
+    let mut buffer = Vec::new();
+    styled!(&mut buffer, r"[#c8c8ee]\o/[/]").unwrap();
+    assert_eq!(buffer, b"\x1b[38;2;200;200;238m\\o/\x1b[0m");
+}
@@ -0,0 +1,124 @@
+mod sealed {
+    pub(crate) trait Sealed {}
+}
+
+trait Ext: sealed::Sealed {
+    fn to_nu_ansi_term(self) -> nu_ansi_term::Style;
+}
+
+impl sealed::Sealed for anstyle::Style {}
+
+impl Ext for anstyle::Style {
+    fn to_nu_ansi_term(self) -> nu_ansi_term::Style {
+        to_nu_ansi_term(self)
+    }
+}
+
+pub fn to_nu_ansi_term(astyle: anstyle::Style) -> nu_ansi_term::Style {
+    let effects = astyle.get_effects();
+    nu_ansi_term::Style {
+        foreground: astyle.get_fg_color().map(to_nu_ansi_term_color),
+        background: astyle.get_bg_color().map(to_nu_ansi_term_color),
+        is_bold: effects.contains(anstyle::Effects::BOLD),
+        is_dimmed: effects.contains(anstyle::Effects::DIMMED),
+        is_italic: effects.contains(anstyle::Effects::ITALIC),
+        is_underline: effects.contains(anstyle::Effects::UNDERLINE),
+        is_blink: effects.contains(anstyle::Effects::BLINK),
+        is_reverse: effects.contains(anstyle::Effects::INVERT),
+        is_hidden: effects.contains(anstyle::Effects::HIDDEN),
+        is_strikethrough: effects.contains(anstyle::Effects::STRIKETHROUGH),
+        ..Default::default()
+    }
+}
+
+pub fn to_nu_ansi_term_color(color: anstyle::Color) -> nu_ansi_term::Color {
+    match color {
+        anstyle::Color::Ansi(ansi) => ansi_to_nu_ansi_term_color(ansi),
+        anstyle::Color::Ansi256(xterm) => nu_ansi_term::Color::Fixed(xterm.0),
+        anstyle::Color::Rgb(rgb) => nu_ansi_term::Color::Rgb(rgb.0, rgb.1, rgb.2),
+    }
+}
+
+fn ansi_to_nu_ansi_term_color(color: anstyle::AnsiColor) -> nu_ansi_term::Color {
+    match color {
+        anstyle::AnsiColor::Black => nu_ansi_term::Color::Black,
+        anstyle::AnsiColor::Red => nu_ansi_term::Color::Red,
+        anstyle::AnsiColor::Green => nu_ansi_term::Color::Green,
+        anstyle::AnsiColor::Yellow => nu_ansi_term::Color::Yellow,
+        anstyle::AnsiColor::Blue => nu_ansi_term::Color::Blue,
+        anstyle::AnsiColor::Magenta => nu_ansi_term::Color::Purple,
+        anstyle::AnsiColor::Cyan => nu_ansi_term::Color::Cyan,
+        anstyle::AnsiColor::White => nu_ansi_term::Color::White,
+        anstyle::AnsiColor::BrightBlack => nu_ansi_term::Color::DarkGray,
+        anstyle::AnsiColor::BrightRed => nu_ansi_term::Color::LightRed,
+        anstyle::AnsiColor::BrightGreen => nu_ansi_term::Color::LightGreen,
+        anstyle::AnsiColor::BrightYellow => nu_ansi_term::Color::LightYellow,
+        anstyle::AnsiColor::BrightBlue => nu_ansi_term::Color::LightBlue,
+        anstyle::AnsiColor::BrightMagenta => nu_ansi_term::Color::LightPurple,
+        anstyle::AnsiColor::BrightCyan => nu_ansi_term::Color::LightCyan,
+        anstyle::AnsiColor::BrightWhite => nu_ansi_term::Color::LightGray,
+    }
+}
+
+pub fn to_anstyle(style: nu_ansi_term::Style) -> anstyle::Style {
+    let mut effects = anstyle::Effects::new();
+    if style.is_bold {
+        effects |= anstyle::Effects::BOLD;
+    }
+    if style.is_dimmed {
+        effects |= anstyle::Effects::DIMMED;
+    }
+    if style.is_italic {
+        effects |= anstyle::Effects::ITALIC;
+    }
+    if style.is_underline {
+        effects |= anstyle::Effects::UNDERLINE;
+    }
+    if style.is_blink {
+        effects |= anstyle::Effects::BLINK;
+    }
+    if style.is_reverse {
+        effects |= anstyle::Effects::INVERT;
+    }
+    if style.is_hidden {
+        effects |= anstyle::Effects::HIDDEN;
+    }
+    if style.is_strikethrough {
+        effects |= anstyle::Effects::STRIKETHROUGH;
+    }
+
+    anstyle::Style::new()
+        .fg_color(style.foreground.and_then(to_anstyle_color))
+        .bg_color(style.background.and_then(to_anstyle_color))
+        .effects(effects)
+}
+
+/// [`nu_ansi_term::Color::Default`] is `None`
+pub fn to_anstyle_color(color: nu_ansi_term::Color) -> Option<anstyle::Color> {
+    let color = match color {
+        nu_ansi_term::Color::Black => anstyle::AnsiColor::Black.into(),
+        nu_ansi_term::Color::Red => anstyle::AnsiColor::Red.into(),
+        nu_ansi_term::Color::Green => anstyle::AnsiColor::Green.into(),
+        nu_ansi_term::Color::Yellow => anstyle::AnsiColor::Yellow.into(),
+        nu_ansi_term::Color::Blue => anstyle::AnsiColor::Blue.into(),
+        nu_ansi_term::Color::Purple | nu_ansi_term::Color::Magenta => {
+            anstyle::AnsiColor::Magenta.into()
+        }
+        nu_ansi_term::Color::Cyan => anstyle::AnsiColor::Cyan.into(),
+        nu_ansi_term::Color::White => anstyle::AnsiColor::White.into(),
+        nu_ansi_term::Color::DarkGray => anstyle::AnsiColor::BrightBlack.into(),
+        nu_ansi_term::Color::LightRed => anstyle::AnsiColor::BrightRed.into(),
+        nu_ansi_term::Color::LightGreen => anstyle::AnsiColor::BrightGreen.into(),
+        nu_ansi_term::Color::LightYellow => anstyle::AnsiColor::BrightYellow.into(),
+        nu_ansi_term::Color::LightBlue => anstyle::AnsiColor::BrightBlue.into(),
+        nu_ansi_term::Color::LightPurple | nu_ansi_term::Color::LightMagenta => {
+            anstyle::AnsiColor::BrightMagenta.into()
+        }
+        nu_ansi_term::Color::LightCyan => anstyle::AnsiColor::BrightCyan.into(),
+        nu_ansi_term::Color::LightGray => anstyle::AnsiColor::BrightWhite.into(),
+        nu_ansi_term::Color::Fixed(index) => anstyle::Ansi256Color(index).into(),
+        nu_ansi_term::Color::Rgb(r, g, b) => anstyle::RgbColor(r, g, b).into(),
+        nu_ansi_term::Color::Default => return None,
+    };
+    Some(color)
+}
@@ -35,6 +35,9 @@ impl Perform for Log {
             "[osc_dispatch] params={:?} bell_terminated={}",
             params, bell_terminated
         );
+        if let Some(osc) = anstyle_parse::Osc::parse(params) {
+            println!("  {:?}", osc);
+        }
     }
 
     fn csi_dispatch(&mut self, params: &Params, intermediates: &[u8], ignore: bool, c: u8) {
@@ -50,6 +53,18 @@ impl Perform for Log {
             intermediates, ignore, byte
         );
     }
+
+    fn sos_pm_apc_start(&mut self, kind: u8) {
+        println!("[sos_pm_apc_start] kind={:?}", kind as char);
+    }
+
+    fn sos_pm_apc_put(&mut self, byte: u8) {
+        println!("[sos_pm_apc_put] {:02x}", byte);
+    }
+
+    fn sos_pm_apc_end(&mut self) {
+        println!("[sos_pm_apc_end]");
+    }
 }
 
 fn main() {
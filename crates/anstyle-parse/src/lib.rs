@@ -24,8 +24,9 @@
 //!
 //! * UTF-8 Support for Input
 //! * OSC Strings can be terminated by 0x07
-//! * Only supports 7-bit codes. Some 8-bit codes are still supported, but they no longer work in
-//!   all states.
+//! * SOS, PM, and APC strings are passed to the [`Perform`] rather than ignored
+//! * Only supports 7-bit codes by default. Some 8-bit codes are still supported, but they no
+//!   longer work in all states. See [`Parser::c1_controls`] to recognize them.
 //!
 //! [Paul Williams' ANSI parser state machine]: https://vt100.net/emu/dec_ansi_parser
 #![cfg_attr(not(test), no_std)]
@@ -40,10 +41,15 @@ use arrayvec::ArrayVec;
 #[cfg(feature = "utf8")]
 use utf8parse as utf8;
 
+mod osc;
 mod params;
+mod sgr;
 pub mod state;
 
+pub use osc::{FileUrl, Osc, OscText};
 pub use params::{Params, ParamsIter};
+pub use sgr::apply_sgr;
+pub use sgr::is_sgr_reset;
 
 use state::{state_change, Action, State};
 
@@ -67,6 +73,7 @@ pub struct Parser<C = DefaultCharAccumulator> {
     osc_params: [(usize, usize); MAX_OSC_PARAMS],
     osc_num_params: usize,
     ignoring: bool,
+    c1_controls: bool,
     utf8_parser: C,
 }
 
@@ -79,6 +86,20 @@ where
         Parser::default()
     }
 
+    /// Recognize 8-bit C1 control codes, like `0x9B` for CSI and `0x9D` for OSC
+    ///
+    /// Each is treated like its 7-bit form, `ESC` followed by the byte minus `0x40`.  Within OSC,
+    /// DCS, SOS, PM, and APC strings, only `0x9C` is recognized, ending the string.
+    ///
+    /// This is off by default as these bytes are also UTF-8 continuation bytes.  Multi-byte
+    /// characters are still decoded, except within strings where one containing `0x9C` will end
+    /// the string.
+    #[inline]
+    pub fn c1_controls(mut self, yes: bool) -> Self {
+        self.c1_controls = yes;
+        self
+    }
+
     #[inline]
     fn params(&self) -> &Params {
         &self.params
@@ -100,6 +121,30 @@ where
             return;
         }
 
+        if self.c1_controls && (0x80..=0x9f).contains(&byte) {
+            match self.state {
+                State::OscString
+                | State::DcsPassthrough
+                | State::DcsIgnore
+                | State::SosPmApcString => {
+                    if byte == 0x9c {
+                        self.perform_state_change(performer, State::Ground, Action::Nop, byte);
+                        return;
+                    }
+                }
+                _ => {
+                    self.advance_state(performer, 0x1b);
+                    self.advance_state(performer, byte - 0x40);
+                    return;
+                }
+            }
+        }
+
+        self.advance_state(performer, byte);
+    }
+
+    #[inline]
+    fn advance_state<P: Perform>(&mut self, performer: &mut P, byte: u8) {
         let (state, action) = state_change(self.state, byte);
         self.perform_state_change(performer, state, action, byte);
     }
@@ -122,8 +167,15 @@ where
     {
         match state {
             State::Anywhere => {
-                // Just run the action
-                self.perform_action(performer, action, byte);
+                if self.state == State::SosPmApcString
+                    && matches!(action, Action::Ignore | Action::Nop)
+                {
+                    // Pass along the payload the state table otherwise drops
+                    performer.sos_pm_apc_put(byte);
+                } else {
+                    // Just run the action
+                    self.perform_action(performer, action, byte);
+                }
             }
             state => {
                 match self.state {
@@ -133,6 +185,7 @@ where
                     State::OscString => {
                         self.perform_action(performer, Action::OscEnd, byte);
                     }
+                    State::SosPmApcString => performer.sos_pm_apc_end(),
                     _ => (),
                 }
 
@@ -153,6 +206,7 @@ where
                     State::OscString => {
                         self.perform_action(performer, Action::OscStart, byte);
                     }
+                    State::SosPmApcString => performer.sos_pm_apc_start(byte),
                     _ => (),
                 }
 
@@ -407,6 +461,8 @@ pub trait Perform {
     fn unhook(&mut self) {}
 
     /// Dispatch an operating system command.
+    ///
+    /// See [`Osc::parse`] for recognizing common commands, like setting the window title.
     fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {}
 
     /// A final character has arrived for a CSI sequence
@@ -428,4 +484,18 @@ pub trait Perform {
     /// The `ignore` flag indicates that more than two intermediates arrived and
     /// subsequent characters were ignored.
     fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, _byte: u8) {}
+
+    /// Invoked when a start of string (SOS), privacy message (PM), or application program command
+    /// (APC) begins.
+    ///
+    /// `kind` is the final character of the introducer: `X` for SOS, `^` for PM, and `_` for APC.
+    /// The payload is subsequently passed to `sos_pm_apc_put`.
+    fn sos_pm_apc_start(&mut self, _kind: u8) {}
+
+    /// Pass bytes as part of the string started in `sos_pm_apc_start`. C0 controls will also be
+    /// passed to the handler.
+    fn sos_pm_apc_put(&mut self, _byte: u8) {}
+
+    /// Called when the string started in `sos_pm_apc_start` is terminated or cancelled.
+    fn sos_pm_apc_end(&mut self) {}
 }
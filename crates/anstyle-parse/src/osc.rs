@@ -0,0 +1,165 @@
+/// An OSC sequence with a known meaning, from [`Perform::osc_dispatch`][crate::Perform]
+///
+/// # Examples
+///
+/// ```rust
+/// use anstyle_parse::Osc;
+///
+/// let params: &[&[u8]] = &[b"2", b"vim", b" main.rs"];
+/// let Some(Osc::Title(title)) = Osc::parse(params) else {
+///     panic!("not a title");
+/// };
+/// assert_eq!(title.bytes().collect::<Vec<_>>(), b"vim; main.rs");
+///
+/// let params: &[&[u8]] = &[b"7", b"file://laptop/home/me/my%20project"];
+/// let Some(Osc::WorkingDirectory(cwd)) = Osc::parse(params) else {
+///     panic!("not a working directory");
+/// };
+/// assert_eq!(cwd.host().collect::<Vec<_>>(), b"laptop");
+/// assert_eq!(cwd.path().collect::<Vec<_>>(), b"/home/me/my project");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Osc<'a> {
+    /// OSC 0, setting the window title and icon name
+    TitleAndIconName(OscText<'a>),
+    /// OSC 1, setting the icon name
+    IconName(OscText<'a>),
+    /// OSC 2, setting the window title
+    Title(OscText<'a>),
+    /// OSC 7, reporting the current working directory
+    WorkingDirectory(FileUrl<'a>),
+}
+
+impl<'a> Osc<'a> {
+    /// Recognize the `params` of an OSC sequence
+    ///
+    /// Returns `None` for OSC sequences without a variant or when they are malformed.
+    pub fn parse(params: &'a [&'a [u8]]) -> Option<Self> {
+        let (kind, rest) = params.split_first()?;
+        let text = OscText { parts: rest };
+        let osc = match *kind {
+            b"0" => Self::TitleAndIconName(text),
+            b"1" => Self::IconName(text),
+            b"2" => Self::Title(text),
+            b"7" => {
+                let url = rest.first()?;
+                if !url.starts_with(FILE_SCHEME) {
+                    return None;
+                }
+                Self::WorkingDirectory(FileUrl { url: text })
+            }
+            _ => return None,
+        };
+        Some(osc)
+    }
+}
+
+/// Text from the parameters of an OSC sequence
+///
+/// The parser splits OSC sequences on `;`, including within text, so this joins them back up.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct OscText<'a> {
+    parts: &'a [&'a [u8]],
+}
+
+impl<'a> OscText<'a> {
+    /// The bytes of the text, which is usually, but not always, UTF-8
+    pub fn bytes(&self) -> impl Iterator<Item = u8> + 'a {
+        self.parts.iter().enumerate().flat_map(|(i, part)| {
+            let separator = (i != 0).then_some(b';');
+            separator.into_iter().chain(part.iter().copied())
+        })
+    }
+
+    /// The text as split by the parser
+    pub fn parts(&self) -> &'a [&'a [u8]] {
+        self.parts
+    }
+}
+
+/// A `file://` URL, like from OSC 7
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FileUrl<'a> {
+    url: OscText<'a>,
+}
+
+impl<'a> FileUrl<'a> {
+    /// The whole URL, still percent-encoded
+    pub fn url(&self) -> OscText<'a> {
+        self.url
+    }
+
+    /// The percent-decoded host, empty for the local machine
+    pub fn host(&self) -> impl Iterator<Item = u8> + 'a {
+        let host = self
+            .url
+            .bytes()
+            .skip(FILE_SCHEME.len())
+            .take_while(|b| *b != b'/');
+        PercentDecode::new(host)
+    }
+
+    /// The percent-decoded path
+    pub fn path(&self) -> impl Iterator<Item = u8> + 'a {
+        let path = self
+            .url
+            .bytes()
+            .skip(FILE_SCHEME.len())
+            .skip_while(|b| *b != b'/');
+        PercentDecode::new(path)
+    }
+}
+
+const FILE_SCHEME: &[u8] = b"file://";
+
+/// Decode `%XX` escapes, leaving malformed ones as-is
+struct PercentDecode<I> {
+    bytes: I,
+    pending: [Option<u8>; 2],
+}
+
+impl<I> PercentDecode<I> {
+    fn new(bytes: I) -> Self {
+        Self {
+            bytes,
+            pending: [None; 2],
+        }
+    }
+}
+
+impl<I> Iterator for PercentDecode<I>
+where
+    I: Iterator<Item = u8>,
+{
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if let Some(byte) = self.pending[0].take() {
+            self.pending = [self.pending[1].take(), None];
+            return Some(byte);
+        }
+
+        let byte = self.bytes.next()?;
+        if byte != b'%' {
+            return Some(byte);
+        }
+        let high = self.bytes.next();
+        let low = high.and_then(|_| self.bytes.next());
+        match (high.and_then(hex_value), low.and_then(hex_value)) {
+            (Some(high), Some(low)) => Some(high << 4 | low),
+            _ => {
+                self.pending = [high, low];
+                Some(byte)
+            }
+        }
+    }
+}
+
+fn hex_value(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
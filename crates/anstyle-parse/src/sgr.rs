@@ -0,0 +1,315 @@
+//! Interpret SGR ("Select Graphic Rendition") escape codes
+
+use anstyle::Effects;
+
+/// Apply the parameters of an SGR escape code (`CSI ... m`) to `style`
+///
+/// `params` are those passed to [`Perform::csi_dispatch`][crate::Perform::csi_dispatch], like
+/// from [`Params::iter`][crate::Params::iter], and SGR 21 is interpreted as `sgr21`.
+///
+/// Returns the new style and whether every parameter was understood.
+///
+/// # Examples
+///
+/// ```rust
+/// let params: &[&[u16]] = &[&[1], &[38, 5, 208]];
+/// let (style, known) = anstyle_parse::apply_sgr(
+///     anstyle::Style::new(),
+///     params.iter().copied(),
+///     anstyle::Sgr21::default(),
+/// );
+/// assert_eq!(style, anstyle::Ansi256Color(208).on_default().bold());
+/// assert!(known);
+/// ```
+pub fn apply_sgr<'p>(
+    style: anstyle::Style,
+    params: impl IntoIterator<Item = &'p [u16]>,
+    sgr21: anstyle::Sgr21,
+) -> (anstyle::Style, bool) {
+    let (style, known, _) = interpret(style, params, sgr21);
+    (style, known)
+}
+
+/// Whether the parameters of an SGR escape code (`CSI ... m`) reset the style
+///
+/// That is when there are none, like `CSI m`, or one is a `0` on its own, like `CSI 1;0 m`, rather
+/// than part of a color, like `CSI 38;5;0 m`.
+///
+/// # Examples
+///
+/// ```rust
+/// let reset: &[&[u16]] = &[&[1], &[0]];
+/// assert!(anstyle_parse::is_sgr_reset(reset.iter().copied()));
+/// let black: &[&[u16]] = &[&[38], &[5], &[0]];
+/// assert!(!anstyle_parse::is_sgr_reset(black.iter().copied()));
+/// ```
+pub fn is_sgr_reset<'p>(params: impl IntoIterator<Item = &'p [u16]>) -> bool {
+    let (_, _, reset) = interpret(anstyle::Style::new(), params, anstyle::Sgr21::default());
+    reset
+}
+
+/// Apply SGR parameters, returning the style, whether they were all understood, and whether they
+/// reset the style
+fn interpret<'p>(
+    mut style: anstyle::Style,
+    params: impl IntoIterator<Item = &'p [u16]>,
+    sgr21: anstyle::Sgr21,
+) -> (anstyle::Style, bool, bool) {
+    let mut params = params.into_iter().peekable();
+    if params.peek().is_none() {
+        return (anstyle::Style::new(), true, true);
+    }
+
+    let mut known = true;
+    let mut reset = false;
+    while let Some(param) = params.next() {
+        let Some((code, subparams)) = param.split_first() else {
+            continue;
+        };
+        match *code {
+            0 => {
+                style = anstyle::Style::new();
+                reset = true;
+            }
+            1 => style |= Effects::BOLD,
+            2 => style |= Effects::DIMMED,
+            3 => style |= Effects::ITALIC,
+            4 => {
+                let underline = match subparams.first() {
+                    None | Some(1) => Effects::UNDERLINE,
+                    Some(0) => Effects::new(),
+                    Some(2) => Effects::DOUBLE_UNDERLINE,
+                    Some(3) => Effects::CURLY_UNDERLINE,
+                    Some(4) => Effects::DOTTED_UNDERLINE,
+                    Some(5) => Effects::DASHED_UNDERLINE,
+                    Some(_) => {
+                        known = false;
+                        continue;
+                    }
+                };
+                style = (style - UNDERLINES) | underline;
+            }
+            5 | 6 => style |= Effects::BLINK,
+            7 => style |= Effects::INVERT,
+            8 => style |= Effects::HIDDEN,
+            9 => style |= Effects::STRIKETHROUGH,
+            21 => style = style.effects(sgr21.apply(style.get_effects())),
+            22 => style -= Effects::BOLD | Effects::DIMMED,
+            23 => style -= Effects::ITALIC,
+            24 => style -= UNDERLINES,
+            25 => style -= Effects::BLINK,
+            27 => style -= Effects::INVERT,
+            28 => style -= Effects::HIDDEN,
+            29 => style -= Effects::STRIKETHROUGH,
+            30..=37 => style = style.fg_color(Some(to_ansi_color(code - 30).into())),
+            38 => match extended_color(subparams, &mut params) {
+                Some(color) => style = style.fg_color(Some(color)),
+                None => known = false,
+            },
+            39 => style = style.fg_color(None),
+            40..=47 => style = style.bg_color(Some(to_ansi_color(code - 40).into())),
+            48 => match extended_color(subparams, &mut params) {
+                Some(color) => style = style.bg_color(Some(color)),
+                None => known = false,
+            },
+            49 => style = style.bg_color(None),
+            58 => match extended_color(subparams, &mut params) {
+                Some(color) => style = style.underline_color(Some(color)),
+                None => known = false,
+            },
+            59 => style = style.underline_color(None),
+            90..=97 => {
+                style = style.fg_color(Some(to_ansi_color(code - 90).bright(true).into()));
+            }
+            100..=107 => {
+                style = style.bg_color(Some(to_ansi_color(code - 100).bright(true).into()));
+            }
+            _ => known = false,
+        }
+    }
+    (style, known, reset)
+}
+
+const UNDERLINES: Effects = Effects::UNDERLINE
+    .insert(Effects::DOUBLE_UNDERLINE)
+    .insert(Effects::CURLY_UNDERLINE)
+    .insert(Effects::DOTTED_UNDERLINE)
+    .insert(Effects::DASHED_UNDERLINE);
+
+/// Parse the color of `38`, `48`, and `58`
+///
+/// Supports both sub-parameters (`38:5:n`, `38:2::r:g:b`, `38:2:r:g:b`) and parameters
+/// (`38;5;n`, `38;2;r;g;b`).  Out of range values are not understood, rather than truncated.
+fn extended_color<'p>(
+    subparams: &[u16],
+    params: &mut impl Iterator<Item = &'p [u16]>,
+) -> Option<anstyle::Color> {
+    if !subparams.is_empty() {
+        return match subparams {
+            [5, n] => Some(anstyle::Ansi256Color(u8::try_from(*n).ok()?).into()),
+            [2, r, g, b] | [2, _, r, g, b, ..] => Some(rgb(*r, *g, *b)?.into()),
+            _ => None,
+        };
+    }
+
+    let mut next = || params.next().and_then(|param| param.first().copied());
+    match next()? {
+        5 => Some(anstyle::Ansi256Color(u8::try_from(next()?).ok()?).into()),
+        2 => {
+            let r = next()?;
+            let g = next()?;
+            let b = next()?;
+            Some(rgb(r, g, b)?.into())
+        }
+        _ => None,
+    }
+}
+
+fn rgb(r: u16, g: u16, b: u16) -> Option<anstyle::RgbColor> {
+    Some(anstyle::RgbColor(
+        u8::try_from(r).ok()?,
+        u8::try_from(g).ok()?,
+        u8::try_from(b).ok()?,
+    ))
+}
+
+fn to_ansi_color(digit: u16) -> anstyle::AnsiColor {
+    match digit {
+        0 => anstyle::AnsiColor::Black,
+        1 => anstyle::AnsiColor::Red,
+        2 => anstyle::AnsiColor::Green,
+        3 => anstyle::AnsiColor::Yellow,
+        4 => anstyle::AnsiColor::Blue,
+        5 => anstyle::AnsiColor::Magenta,
+        6 => anstyle::AnsiColor::Cyan,
+        _ => anstyle::AnsiColor::White,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[track_caller]
+    fn apply(input: &str, sgr21: anstyle::Sgr21) -> (anstyle::Style, bool) {
+        struct Capture(anstyle::Style, anstyle::Sgr21, bool);
+        impl crate::Perform for Capture {
+            fn csi_dispatch(
+                &mut self,
+                params: &crate::Params,
+                _intermediates: &[u8],
+                _ignore: bool,
+                action: u8,
+            ) {
+                assert_eq!(action, b'm');
+                let (style, known) = apply_sgr(self.0, params, self.1);
+                self.0 = style;
+                self.2 &= known;
+            }
+        }
+
+        let mut parser = crate::Parser::<crate::DefaultCharAccumulator>::new();
+        let mut capture = Capture(anstyle::Style::new(), sgr21, true);
+        for byte in input.as_bytes() {
+            parser.advance(&mut capture, *byte);
+        }
+        (capture.0, capture.2)
+    }
+
+    #[track_caller]
+    fn verify(input: &str, expected: anstyle::Style) {
+        verify_with(input, anstyle::Sgr21::default(), expected);
+    }
+
+    #[track_caller]
+    fn verify_with(input: &str, sgr21: anstyle::Sgr21, expected: anstyle::Style) {
+        assert_eq!(apply(input, sgr21), (expected, true), "{input:?}");
+    }
+
+    #[test]
+    fn round_trip() {
+        let styles = [
+            anstyle::Style::new(),
+            anstyle::Style::new().bold().italic().strikethrough(),
+            anstyle::AnsiColor::Red.on(anstyle::AnsiColor::BrightBlue),
+            anstyle::Ansi256Color(208).on(anstyle::Ansi256Color(17)),
+            anstyle::RgbColor(200, 100, 150).on(anstyle::RgbColor(1, 2, 3)),
+            anstyle::Style::new()
+                .effects(Effects::CURLY_UNDERLINE)
+                .underline_color(Some(anstyle::RgbColor(255, 0, 0).into())),
+            anstyle::Style::new().effects(Effects::DOUBLE_UNDERLINE | Effects::INVERT),
+        ];
+        for style in styles {
+            verify(&style.render().to_string(), style);
+            let sgr21 = anstyle::Sgr21::BoldOff;
+            verify_with(&style.render_with(sgr21).to_string(), sgr21, style);
+        }
+    }
+
+    #[test]
+    fn combined() {
+        verify(
+            "\x1b[1;4;38;2;200;100;150;48;5;17m",
+            anstyle::RgbColor(200, 100, 150).on(anstyle::Ansi256Color(17))
+                | Effects::BOLD
+                | Effects::UNDERLINE,
+        );
+        verify(
+            "\x1b[38:2::200:100:150;4:3m",
+            anstyle::RgbColor(200, 100, 150).on_default() | Effects::CURLY_UNDERLINE,
+        );
+    }
+
+    #[test]
+    fn reset() {
+        verify("\x1b[1;31m\x1b[0m", anstyle::Style::new());
+        verify("\x1b[1;31m\x1b[m", anstyle::Style::new());
+        verify("\x1b[1;2;31m\x1b[22;39m", anstyle::Style::new());
+        verify("\x1b[4:3m\x1b[24m", anstyle::Style::new());
+    }
+
+    #[test]
+    fn is_reset() {
+        for (params, expected) in [
+            (&[][..], true),
+            (&[&[0][..]][..], true),
+            (&[&[1], &[0], &[31]], true),
+            (&[&[1], &[31]], false),
+            (&[&[38], &[5], &[0]], false),
+            (&[&[48], &[2], &[0], &[0], &[0]], false),
+            (&[&[38, 5, 0]], false),
+        ] {
+            assert_eq!(is_sgr_reset(params.iter().copied()), expected, "{params:?}");
+        }
+    }
+
+    #[test]
+    fn sgr21() {
+        let input = "\x1b[1;4m\x1b[21m";
+        verify(
+            input,
+            anstyle::Style::new() | Effects::BOLD | Effects::DOUBLE_UNDERLINE,
+        );
+        verify_with(
+            input,
+            anstyle::Sgr21::BoldOff,
+            anstyle::Style::new() | Effects::UNDERLINE,
+        );
+    }
+
+    #[test]
+    fn out_of_range() {
+        for input in [
+            "\x1b[38;5;300m",
+            "\x1b[38:5:300m",
+            "\x1b[48;2;256;0;0m",
+            "\x1b[58:2::0:0:1000m",
+        ] {
+            assert_eq!(
+                apply(input, anstyle::Sgr21::default()),
+                (anstyle::Style::new(), false),
+                "{input:?}"
+            );
+        }
+    }
+}
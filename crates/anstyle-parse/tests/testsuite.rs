@@ -61,6 +61,18 @@ impl Perform for Dispatcher {
     fn unhook(&mut self) {
         self.dispatched.push(Sequence::DcsUnhook);
     }
+
+    fn sos_pm_apc_start(&mut self, kind: u8) {
+        self.dispatched.push(Sequence::StringStart(kind));
+    }
+
+    fn sos_pm_apc_put(&mut self, byte: u8) {
+        self.dispatched.push(Sequence::StringPut(byte));
+    }
+
+    fn sos_pm_apc_end(&mut self) {
+        self.dispatched.push(Sequence::StringEnd);
+    }
 }
 
 impl std::ops::Deref for Dispatcher {
@@ -129,6 +141,9 @@ enum Sequence {
     DcsHook(Vec<Vec<u16>>, Vec<u8>, bool, u8),
     DcsPut(u8),
     DcsUnhook,
+    StringStart(u8),
+    StringPut(u8),
+    StringEnd,
 }
 
 impl From<char> for Sequence {
@@ -389,6 +404,118 @@ advance!(
     gen_intermediate_reset_on_dcs_exit
 );
 
+fn gen_apc() -> (Vec<u8>, Dispatcher) {
+    let input = b"\x1b_Ga=q;AA\x1b\\z".to_vec();
+    let expected = start()
+        + Sequence::StringStart(b'_')
+        + b"Ga=q;AA"
+            .iter()
+            .map(|b| Sequence::StringPut(*b))
+            .collect::<Vec<_>>()
+        + Sequence::StringEnd
+        + Sequence::Esc(vec![], false, b'\\')
+        + 'z';
+    (input, expected)
+}
+
+advance!(advance_apc, gen_apc);
+
+fn gen_pm_c1_terminated() -> (Vec<u8>, Dispatcher) {
+    let input = b"\x1b^\x07\xc3\xa9\x9c".to_vec();
+    let expected = start()
+        + Sequence::StringStart(b'^')
+        + Sequence::StringPut(0x07)
+        + Sequence::StringPut(0xc3)
+        + Sequence::StringPut(0xa9)
+        + Sequence::StringEnd;
+    (input, expected)
+}
+
+advance!(advance_pm_c1_terminated, gen_pm_c1_terminated);
+
+fn gen_sos_cancelled() -> (Vec<u8>, Dispatcher) {
+    let input = b"\x1bXa\x18b".to_vec();
+    let expected = start()
+        + Sequence::StringStart(b'X')
+        + Sequence::StringPut(b'a')
+        + Sequence::StringEnd
+        + 'b';
+    (input, expected)
+}
+
+advance!(advance_sos_cancelled, gen_sos_cancelled);
+
+fn advance_c1(input: &[u8]) -> Dispatcher {
+    let mut dispatcher = Dispatcher::default();
+    let mut parser = Parser::<DefaultCharAccumulator>::new().c1_controls(true);
+    for byte in input {
+        parser.advance(&mut dispatcher, *byte);
+    }
+    dispatcher
+}
+
+#[test]
+fn c1_csi() {
+    let expected = start() + Sequence::Csi(vec![vec![31]], vec![], false, b'm') + 'a';
+    assert_eq!(advance_c1(b"\x9b31ma"), expected);
+}
+
+#[test]
+fn c1_osc() {
+    let expected = start() + Sequence::Osc(vec![b"2".to_vec(), b"t\x90".to_vec()], false) + 'a';
+    assert_eq!(advance_c1(b"\x9d2;t\x90\x9ca"), expected);
+}
+
+#[test]
+fn c1_utf8() {
+    let expected = start() + "\u{e9}\u{2718}";
+    assert_eq!(advance_c1("\u{e9}\u{2718}".as_bytes()), expected);
+}
+
+#[test]
+fn c1_off_by_default() {
+    let mut dispatcher = Dispatcher::default();
+    let mut parser = Parser::<DefaultCharAccumulator>::new();
+    for byte in b"\x9b31ma" {
+        parser.advance(&mut dispatcher, *byte);
+    }
+    assert_eq!(dispatcher, start() + "31ma");
+}
+
+#[test]
+fn osc_title() {
+    let params: &[&[u8]] = &[b"0", b"a", b"b"];
+    let Some(Osc::TitleAndIconName(title)) = Osc::parse(params) else {
+        panic!("not a title");
+    };
+    assert_eq!(title.bytes().collect::<Vec<_>>(), b"a;b");
+
+    let params: &[&[u8]] = &[b"2"];
+    let Some(Osc::Title(title)) = Osc::parse(params) else {
+        panic!("not a title");
+    };
+    assert_eq!(title.bytes().count(), 0);
+
+    let params: &[&[u8]] = &[b"52", b"c", b"aGk="];
+    assert_eq!(Osc::parse(params), None);
+    assert_eq!(Osc::parse(&[]), None);
+}
+
+#[test]
+fn osc_working_directory() {
+    let params: &[&[u8]] = &[b"7", b"file:///tmp/50%25", b"%3b%zz%4"];
+    let Some(Osc::WorkingDirectory(cwd)) = Osc::parse(params) else {
+        panic!("not a working directory");
+    };
+    assert_eq!(cwd.host().count(), 0);
+    assert_eq!(cwd.path().collect::<Vec<_>>(), b"/tmp/50%;;%zz%4");
+
+    let params: &[&[u8]] = &[b"7", b"/tmp"];
+    assert_eq!(Osc::parse(params), None);
+    let params: &[&[u8]] = &[b"7"];
+    assert_eq!(Osc::parse(params), None);
+}
+
 fn gen_esc_reset() -> (Vec<u8>, Dispatcher) {
     let input = b"\x1b[3;1\x1b(A".to_vec();
     let expected = start() + Sequence::Esc(vec![b'('], false, b'A');
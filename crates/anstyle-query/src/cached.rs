@@ -0,0 +1,103 @@
+//! Cached style queries
+//!
+//! The environment is read once for the process, making these cheap enough for hot paths.
+//! Environment variables and the terminal can change, like when daemonizing or `exec`ing into
+//! `tmux`, so call [`refresh`] to re-read them.
+
+use std::sync::OnceLock;
+use std::sync::PoisonError;
+use std::sync::RwLock;
+
+/// Cached [`crate::clicolor`]
+#[inline]
+pub fn clicolor() -> Option<bool> {
+    snapshot().clicolor
+}
+
+/// Cached [`crate::clicolor_force`]
+#[inline]
+pub fn clicolor_force() -> bool {
+    snapshot().clicolor_force
+}
+
+/// Cached [`crate::no_color`]
+#[inline]
+pub fn no_color() -> bool {
+    snapshot().no_color
+}
+
+/// Cached [`crate::term_supports_color`]
+#[inline]
+pub fn term_supports_color() -> bool {
+    snapshot().term_supports_color
+}
+
+/// Cached [`crate::term_supports_ansi_color`]
+#[inline]
+pub fn term_supports_ansi_color() -> bool {
+    snapshot().term_supports_ansi_color
+}
+
+/// Cached [`crate::term_quirks`]
+#[inline]
+pub fn term_quirks() -> crate::quirks::TermQuirks {
+    snapshot().term_quirks
+}
+
+/// Cached [`crate::truecolor`]
+#[inline]
+pub fn truecolor() -> bool {
+    snapshot().truecolor
+}
+
+/// Cached [`crate::is_ci`]
+#[inline]
+pub fn is_ci() -> bool {
+    snapshot().is_ci
+}
+
+/// Re-read the environment for the cached queries
+///
+/// The environment is read as part of this call, so later queries see the state at the time of
+/// the last refresh.
+pub fn refresh() {
+    let fresh = Snapshot::query();
+    let mut cached = cache().write().unwrap_or_else(PoisonError::into_inner);
+    *cached = fresh;
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Snapshot {
+    clicolor: Option<bool>,
+    clicolor_force: bool,
+    no_color: bool,
+    term_supports_color: bool,
+    term_supports_ansi_color: bool,
+    term_quirks: crate::quirks::TermQuirks,
+    truecolor: bool,
+    is_ci: bool,
+}
+
+impl Snapshot {
+    fn query() -> Self {
+        Self {
+            clicolor: crate::clicolor(),
+            clicolor_force: crate::clicolor_force(),
+            no_color: crate::no_color(),
+            term_supports_color: crate::term_supports_color(),
+            term_supports_ansi_color: crate::term_supports_ansi_color(),
+            term_quirks: crate::term_quirks(),
+            truecolor: crate::truecolor(),
+            is_ci: crate::is_ci(),
+        }
+    }
+}
+
+fn cache() -> &'static RwLock<Snapshot> {
+    static CACHE: OnceLock<RwLock<Snapshot>> = OnceLock::new();
+    CACHE.get_or_init(|| RwLock::new(Snapshot::query()))
+}
+
+fn snapshot() -> Snapshot {
+    *cache().read().unwrap_or_else(PoisonError::into_inner)
+}
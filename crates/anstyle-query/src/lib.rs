@@ -1,3 +1,12 @@
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+
+pub mod cached;
+pub mod multiplexer;
+pub mod notification;
+pub mod quirks;
+pub mod terminal;
+#[cfg(feature = "terminfo")]
+pub mod terminfo;
 pub mod windows;
 
 /// Check [CLICOLOR] status
@@ -57,7 +66,7 @@ pub fn term_supports_color() -> bool {
         // probably doesn't support colors.
         None => return false,
         Some(k) => {
-            if k == "dumb" {
+            if term_quirks_(&k).colors == quirks::ColorLevel::None {
                 return false;
             }
         }
@@ -73,7 +82,7 @@ pub fn term_supports_color() -> bool {
     // assume that colors aren't allowed. This is unlike Unix environments
     // where TERM is more rigorously set.
     if let Some(k) = std::env::var_os("TERM") {
-        if k == "dumb" {
+        if term_quirks_(&k).colors == quirks::ColorLevel::None {
             return false;
         }
     }
@@ -99,7 +108,7 @@ pub fn term_supports_ansi_color() -> bool {
             // cygwin doesn't seem to support ANSI escape sequences
             // and instead has its own variety. However, the Windows
             // console API may be available.
-            if k == "dumb" || k == "cygwin" {
+            if k == "cygwin" || term_quirks_(&k).colors == quirks::ColorLevel::None {
                 return false;
             }
         }
@@ -107,8 +116,48 @@ pub fn term_supports_ansi_color() -> bool {
     true
 }
 
+/// Look up known limitations of the terminal named by `TERM`
+///
+/// See [`quirks::TermQuirks::from_term`]
+#[inline]
+pub fn term_quirks() -> quirks::TermQuirks {
+    match std::env::var_os("TERM") {
+        Some(k) => term_quirks_(&k),
+        None => quirks::TermQuirks::NONE,
+    }
+}
+
+/// Look up the embedded terminfo entry for the terminal named by `TERM`
+///
+/// See [`terminfo::TermInfo::from_term`]
+#[inline]
+#[cfg(feature = "terminfo")]
+pub fn term_info() -> Option<&'static terminfo::TermInfo> {
+    std::env::var_os("TERM")
+        .as_deref()
+        .and_then(std::ffi::OsStr::to_str)
+        .and_then(terminfo::TermInfo::from_term)
+}
+
+fn term_quirks_(term: &std::ffi::OsStr) -> quirks::TermQuirks {
+    term.to_str()
+        .map(quirks::TermQuirks::from_term)
+        .unwrap_or(quirks::TermQuirks::NONE)
+}
+
+/// Look up the size of the terminal, for wrapping and laying out text
+///
+/// See [`terminal::size`]
+#[inline]
+pub fn terminal_size() -> Option<terminal::Size> {
+    terminal::size()
+}
+
 /// Check [COLORTERM] for truecolor support
 ///
+/// `COLORTERM` is frequently missing inside of terminal multiplexers, see
+/// [`multiplexer::truecolor`].
+///
 /// [COLORTERM]: https://github.com/termstandard/colors
 #[inline]
 pub fn truecolor() -> bool {
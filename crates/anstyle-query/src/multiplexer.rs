@@ -0,0 +1,175 @@
+//! Terminal multiplexer detection
+//!
+//! Multiplexers like `tmux` and `screen` sit between the application and the terminal, so
+//! `COLORTERM` frequently doesn't make it through and `TERM` describes the multiplexer rather
+//! than the terminal.  Whether truecolor works depends on both the outer terminal and the
+//! multiplexer's configuration.
+
+use std::ffi::OsStr;
+
+/// A terminal multiplexer
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Multiplexer {
+    /// [tmux](https://github.com/tmux/tmux)
+    Tmux,
+    /// [GNU screen](https://www.gnu.org/software/screen/)
+    Screen,
+}
+
+/// Detect whether this is running inside of a terminal multiplexer
+///
+/// This checks `TMUX` and `STY`, falling back to `TERM` for when those aren't inherited, like
+/// across `ssh`.
+#[inline]
+pub fn detect() -> Option<Multiplexer> {
+    detect_(
+        std::env::var_os("TMUX").as_deref(),
+        std::env::var_os("STY").as_deref(),
+        std::env::var_os("TERM").as_deref(),
+    )
+}
+
+/// Check the environment for truecolor support through a multiplexer
+///
+/// Outside of a multiplexer, this is the same as [`crate::truecolor`].  Inside of one, a
+/// `-direct` `TERM` is also accepted.  When this is `false` inside of `tmux`,
+/// [`probe_truecolor`] can ask `tmux` directly.
+#[inline]
+pub fn truecolor() -> bool {
+    if crate::truecolor() {
+        return true;
+    }
+    detect().is_some()
+        && std::env::var_os("TERM")
+            .as_deref()
+            .and_then(OsStr::to_str)
+            .map(|term| term.contains("-direct"))
+            .unwrap_or(false)
+}
+
+/// Ask the running multiplexer whether the attached terminal supports truecolor
+///
+/// This runs the `tmux` client, so prefer caching the result.  `tmux` 3.2+ reports the client's
+/// terminal features; older versions are checked for the `Tc` or `RGB` flags in `tmux info`.
+///
+/// Returns `None` when not running inside of `tmux` or when `tmux` can't be queried.  `screen`
+/// can't be probed; its truecolor support requires `truecolor on` in version 5.
+pub fn probe_truecolor() -> Option<bool> {
+    if detect()? != Multiplexer::Tmux || std::env::var_os("TMUX").is_none() {
+        return None;
+    }
+
+    if let Some(features) = tmux(&["display-message", "-p", "#{client_termfeatures}"]) {
+        if !features.trim().is_empty() {
+            return Some(has_rgb_feature(&features));
+        }
+    }
+
+    let info = tmux(&["info"])?;
+    Some(has_rgb_flag(&info))
+}
+
+fn detect_(tmux: Option<&OsStr>, sty: Option<&OsStr>, term: Option<&OsStr>) -> Option<Multiplexer> {
+    if tmux.map(|v| !v.is_empty()).unwrap_or(false) {
+        return Some(Multiplexer::Tmux);
+    }
+    if sty.map(|v| !v.is_empty()).unwrap_or(false) {
+        return Some(Multiplexer::Screen);
+    }
+
+    let term = term?.to_str()?;
+    let base = term.split(['-', '.']).next().unwrap_or(term);
+    match base {
+        "tmux" => Some(Multiplexer::Tmux),
+        "screen" => Some(Multiplexer::Screen),
+        _ => None,
+    }
+}
+
+fn tmux(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("tmux")
+        .args(args)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// Parse `#{client_termfeatures}`, a comma-separated list like `256,RGB,title`
+fn has_rgb_feature(features: &str) -> bool {
+    features.trim().split(',').any(|f| f == "RGB")
+}
+
+/// Parse `tmux info`, looking for lines like ` 224: Tc: (flag) true`
+fn has_rgb_flag(info: &str) -> bool {
+    info.lines().any(|line| {
+        let mut fields = line.split(':').map(str::trim).skip(1);
+        let name = fields.next();
+        let value = fields.next();
+        matches!(name, Some("Tc" | "RGB")) && value == Some("(flag) true")
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn os(s: &str) -> Option<&OsStr> {
+        Some(OsStr::new(s))
+    }
+
+    #[test]
+    fn detect_env() {
+        assert_eq!(
+            detect_(os("/tmp/tmux-1000/default,1234,0"), None, os("xterm")),
+            Some(Multiplexer::Tmux)
+        );
+        assert_eq!(
+            detect_(None, os("1234.pts-0.host"), os("xterm")),
+            Some(Multiplexer::Screen)
+        );
+        assert_eq!(detect_(os(""), os(""), os("xterm-256color")), None);
+        assert_eq!(detect_(None, None, None), None);
+    }
+
+    #[test]
+    fn detect_term() {
+        assert_eq!(
+            detect_(None, None, os("tmux-256color")),
+            Some(Multiplexer::Tmux)
+        );
+        assert_eq!(detect_(None, None, os("screen")), Some(Multiplexer::Screen));
+        assert_eq!(
+            detect_(None, None, os("screen.xterm-256color")),
+            Some(Multiplexer::Screen)
+        );
+        assert_eq!(detect_(None, None, os("screenish")), None);
+    }
+
+    #[test]
+    fn termfeatures() {
+        assert!(has_rgb_feature("256,RGB,bpaste,clipboard,title\n"));
+        assert!(!has_rgb_feature("256,bpaste,clipboard,title\n"));
+        assert!(!has_rgb_feature(""));
+    }
+
+    #[test]
+    fn info() {
+        let info = "\
+ 221: Smulx: (string) \\033[4::%p1%dm
+ 222: Tc: (flag) true
+ 223: Smol: [missing]
+";
+        assert!(has_rgb_flag(info));
+
+        let info = "\
+ 222: Tc: [missing]
+ 223: RGB: [missing]
+";
+        assert!(!has_rgb_flag(info));
+    }
+}
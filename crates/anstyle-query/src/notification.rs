@@ -0,0 +1,100 @@
+//! Desktop notification support
+//!
+//! Some terminals show a desktop notification for an OSC sequence, letting long-running commands
+//! report that they're done.  There is no way to ask the terminal, so this goes by the
+//! environment variables terminals set.
+
+use std::ffi::OsStr;
+
+/// An escape sequence for showing a desktop notification
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum NotificationProtocol {
+    /// OSC 9, from [iTerm2](https://iterm2.com/documentation-escape-codes.html), with only a body
+    Osc9,
+    /// OSC 777, from rxvt-unicode's `notify` extension, with a title and body
+    Osc777,
+}
+
+/// Detect which notification sequence the terminal supports, if any
+///
+/// This checks `TERM_PROGRAM` and `LC_TERMINAL`, falling back to `TERM`.  Inside of a multiplexer
+/// like `tmux`, these describe the multiplexer and no support is detected.
+#[inline]
+pub fn detect() -> Option<NotificationProtocol> {
+    detect_(
+        std::env::var_os("TERM_PROGRAM").as_deref(),
+        std::env::var_os("LC_TERMINAL").as_deref(),
+        std::env::var_os("TERM").as_deref(),
+    )
+}
+
+fn detect_(
+    term_program: Option<&OsStr>,
+    lc_terminal: Option<&OsStr>,
+    term: Option<&OsStr>,
+) -> Option<NotificationProtocol> {
+    let term_program = term_program.and_then(OsStr::to_str);
+    match term_program {
+        Some("iTerm.app") => return Some(NotificationProtocol::Osc9),
+        Some("WezTerm" | "ghostty") => return Some(NotificationProtocol::Osc777),
+        _ => {}
+    }
+    // Set by iTerm2 and passed along by `ssh`, unlike `TERM_PROGRAM`
+    if term_program.is_none() && lc_terminal.and_then(OsStr::to_str) == Some("iTerm2") {
+        return Some(NotificationProtocol::Osc9);
+    }
+
+    let term = term?.to_str()?;
+    let osc777 = term == "foot"
+        || term.starts_with("foot-")
+        || term.starts_with("rxvt-unicode")
+        || term == "xterm-ghostty";
+    osc777.then_some(NotificationProtocol::Osc777)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn os(s: &str) -> Option<&OsStr> {
+        Some(OsStr::new(s))
+    }
+
+    #[test]
+    fn term_program() {
+        assert_eq!(
+            detect_(os("iTerm.app"), os("iTerm2"), os("xterm-256color")),
+            Some(NotificationProtocol::Osc9)
+        );
+        assert_eq!(
+            detect_(os("WezTerm"), None, os("xterm-256color")),
+            Some(NotificationProtocol::Osc777)
+        );
+        assert_eq!(detect_(os("tmux"), os("iTerm2"), os("tmux-256color")), None);
+        assert_eq!(detect_(os("vscode"), None, os("xterm-256color")), None);
+    }
+
+    #[test]
+    fn over_ssh() {
+        assert_eq!(
+            detect_(None, os("iTerm2"), os("xterm-256color")),
+            Some(NotificationProtocol::Osc9)
+        );
+    }
+
+    #[test]
+    fn term() {
+        let cases = [
+            ("foot", Some(NotificationProtocol::Osc777)),
+            ("foot-extra", Some(NotificationProtocol::Osc777)),
+            ("rxvt-unicode-256color", Some(NotificationProtocol::Osc777)),
+            ("xterm-ghostty", Some(NotificationProtocol::Osc777)),
+            ("rxvt", None),
+            ("xterm-256color", None),
+        ];
+        for (term, expected) in cases {
+            assert_eq!(detect_(None, None, os(term)), expected, "{term}");
+        }
+        assert_eq!(detect_(None, None, None), None);
+    }
+}
@@ -0,0 +1,169 @@
+//! Known limitations of terminals, by `TERM` name
+//!
+//! Terminals generally over-report what they support through `TERM`, either because the name is
+//! shared with more capable emulators or because a multiplexer sits in between.  What is actually
+//! safe to emit comes from the built-in profiles, see [`anstyle::Profile::from_name`].
+
+pub use anstyle::ColorLevel;
+
+/// Capability adjustments for a terminal
+///
+/// These are upper bounds; whether color should be used at all is still decided by the other
+/// queries, like [`crate::no_color`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TermQuirks {
+    /// Most colors the terminal can display
+    pub colors: ColorLevel,
+    /// Whether italic text is rendered, rather than ignored or shown as a color
+    pub italic: bool,
+    /// Whether dimmed text is rendered
+    pub dimmed: bool,
+    /// Whether strikethrough text is rendered
+    pub strikethrough: bool,
+    /// Whether curly, dotted, and other underline styles are rendered, rather than as a plain
+    /// underline or garbage
+    pub styled_underlines: bool,
+}
+
+impl TermQuirks {
+    /// No known limitations
+    pub const NONE: Self = Self {
+        colors: ColorLevel::Rgb,
+        italic: true,
+        dimmed: true,
+        strikethrough: true,
+        styled_underlines: true,
+    };
+
+    /// Look up the limitations for a `TERM` value
+    ///
+    /// Unknown terminals report [`TermQuirks::NONE`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use anstyle_query::quirks::*;
+    /// let quirks = TermQuirks::from_term("screen");
+    /// assert_eq!(quirks.colors, ColorLevel::Ansi);
+    /// assert!(!quirks.italic);
+    ///
+    /// let quirks = TermQuirks::from_term("screen-256color");
+    /// assert_eq!(quirks.colors, ColorLevel::Ansi256);
+    /// ```
+    pub fn from_term(term: &str) -> Self {
+        let Some(profile) = base_profile(term) else {
+            return Self::NONE;
+        };
+        let mut quirks = Self::from(profile);
+
+        if quirks.colors != ColorLevel::None {
+            // `-88color` is deliberately left at `Ansi`, as 256-color indices would be misread
+            if term.contains("-direct") {
+                quirks.colors = ColorLevel::Rgb;
+            } else if term.contains("-256color") {
+                quirks.colors = ColorLevel::Ansi256;
+            }
+        }
+
+        quirks
+    }
+}
+
+impl Default for TermQuirks {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+impl From<anstyle::Profile> for TermQuirks {
+    fn from(profile: anstyle::Profile) -> Self {
+        let effects = profile.get_effects();
+        Self {
+            colors: profile.get_colors(),
+            italic: effects && profile.get_italic(),
+            dimmed: effects && profile.get_dimmed(),
+            strikethrough: effects && profile.get_strikethrough(),
+            styled_underlines: effects && profile.get_styled_underlines(),
+        }
+    }
+}
+
+impl From<TermQuirks> for anstyle::Profile {
+    /// Hyperlinks aren't covered by quirks and are left enabled
+    fn from(quirks: TermQuirks) -> Self {
+        anstyle::Profile::new()
+            .colors(quirks.colors)
+            .effects(quirks.colors != ColorLevel::None)
+            .italic(quirks.italic)
+            .dimmed(quirks.dimmed)
+            .strikethrough(quirks.strikethrough)
+            .styled_underlines(quirks.styled_underlines)
+    }
+}
+
+/// The built-in profile for `term` or what it is a variant of, like `base-256color` or
+/// `base.xterm`, most specific first
+fn base_profile(mut term: &str) -> Option<anstyle::Profile> {
+    loop {
+        if let Some(profile) = anstyle::Profile::from_name(term) {
+            return Some(profile);
+        }
+        let end = term.rfind(['-', '.'])?;
+        term = &term[..end];
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn unknown() {
+        assert_eq!(TermQuirks::from_term("alacritty"), TermQuirks::NONE);
+        assert_eq!(TermQuirks::from_term("screenish"), TermQuirks::NONE);
+        assert_eq!(TermQuirks::from_term(""), TermQuirks::NONE);
+    }
+
+    #[test]
+    fn dumb() {
+        let quirks = TermQuirks::from_term("dumb");
+        assert_eq!(quirks.colors, ColorLevel::None);
+        assert!(!quirks.italic);
+    }
+
+    #[test]
+    fn colors() {
+        let cases = [
+            ("linux", ColorLevel::Ansi),
+            ("screen", ColorLevel::Ansi),
+            ("screen-bce", ColorLevel::Ansi),
+            ("screen-256color", ColorLevel::Ansi256),
+            ("screen.xterm-256color", ColorLevel::Ansi256),
+            ("tmux", ColorLevel::Ansi),
+            ("tmux-256color", ColorLevel::Ansi256),
+            ("tmux-direct", ColorLevel::Rgb),
+            ("rxvt", ColorLevel::Ansi),
+            ("rxvt-88color", ColorLevel::Ansi),
+            ("rxvt-unicode", ColorLevel::Ansi),
+            ("rxvt-unicode-256color", ColorLevel::Ansi256),
+            ("xterm", ColorLevel::Ansi),
+            ("xterm-256color", ColorLevel::Ansi256),
+            ("xterm-direct", ColorLevel::Rgb),
+        ];
+        for (term, expected) in cases {
+            assert_eq!(TermQuirks::from_term(term).colors, expected, "{term}");
+        }
+    }
+
+    #[test]
+    fn effects() {
+        assert!(!TermQuirks::from_term("linux").italic);
+        assert!(!TermQuirks::from_term("screen-256color").italic);
+        assert!(TermQuirks::from_term("tmux-256color").italic);
+        assert!(TermQuirks::from_term("rxvt-unicode-256color").italic);
+        assert!(!TermQuirks::from_term("rxvt").italic);
+        assert!(!TermQuirks::from_term("xterm-256color").styled_underlines);
+        assert!(TermQuirks::from_term("tmux-256color").styled_underlines);
+        assert!(TermQuirks::from_term("xterm-kitty").styled_underlines);
+    }
+}
@@ -0,0 +1,282 @@
+//! Terminal size and cursor position
+//!
+//! These talk to the terminal itself, unlike the other queries which only read the environment.
+
+/// The visible size of a terminal, in character cells
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Size {
+    /// Number of columns, the width
+    pub columns: u16,
+    /// Number of rows, the height
+    pub rows: u16,
+}
+
+/// Where the cursor is in the visible part of a terminal
+///
+/// Counting starts at `1`, as reported by the terminal and expected by
+/// [CUP](https://vt100.net/docs/vt510-rm/CUP.html).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Position {
+    /// Row from the top
+    pub row: u16,
+    /// Column from the left
+    pub column: u16,
+}
+
+/// Look up the size of the terminal connected to stdout, stderr, or stdin
+///
+/// When none of them are a terminal, this falls back to the `COLUMNS` and `LINES` environment
+/// variables, like for output piped through a pager that sets them.
+pub fn size() -> Option<Size> {
+    sys::size().or_else(env_size)
+}
+
+fn env_size() -> Option<Size> {
+    let columns = std::env::var("COLUMNS").ok()?.parse().ok()?;
+    let rows = std::env::var("LINES").ok()?.parse().ok()?;
+    (columns != 0 && rows != 0).then_some(Size { columns, rows })
+}
+
+/// Ask the terminal where the cursor is
+///
+/// On Unix, this writes a Device Status Report (`CSI 6n`) to the controlling terminal and waits
+/// up to `timeout` for its reply.  Input is switched out of line-buffered mode while waiting so
+/// the reply isn't echoed; anything typed ahead of the reply is discarded.  On Windows, the
+/// console is asked directly.
+///
+/// Fails with [`std::io::ErrorKind::TimedOut`] when the terminal doesn't reply, like with
+/// terminals that don't support the report.
+pub fn cursor_position(timeout: std::time::Duration) -> std::io::Result<Position> {
+    sys::cursor_position(timeout)
+}
+
+/// Find a Cursor Position Report (`CSI <row> ; <column> R`) at the end of `bytes`
+#[cfg(unix)]
+fn parse_position_report(bytes: &[u8]) -> Option<Position> {
+    let bytes = bytes.strip_suffix(b"R")?;
+    let start = bytes.windows(2).rposition(|w| w == b"\x1b[")?;
+    let params = std::str::from_utf8(&bytes[start + 2..]).ok()?;
+    let (row, column) = params.split_once(';')?;
+    Some(Position {
+        row: row.parse().ok()?,
+        column: column.parse().ok()?,
+    })
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::io::Read as _;
+    use std::io::Write as _;
+    use std::os::unix::io::AsRawFd as _;
+    use std::os::unix::io::RawFd;
+
+    use super::Position;
+    use super::Size;
+
+    pub(super) fn size() -> Option<Size> {
+        [
+            std::io::stdout().as_raw_fd(),
+            std::io::stderr().as_raw_fd(),
+            std::io::stdin().as_raw_fd(),
+        ]
+        .into_iter()
+        .find_map(fd_size)
+    }
+
+    fn fd_size(fd: RawFd) -> Option<Size> {
+        let mut size = std::mem::MaybeUninit::<libc::winsize>::zeroed();
+        // SAFETY: `TIOCGWINSZ` only writes to `size`
+        if unsafe { libc::ioctl(fd, libc::TIOCGWINSZ, size.as_mut_ptr()) } != 0 {
+            return None;
+        }
+        // SAFETY: initialized by `zeroed` and `TIOCGWINSZ`
+        let size = unsafe { size.assume_init() };
+        (size.ws_col != 0 && size.ws_row != 0).then_some(Size {
+            columns: size.ws_col,
+            rows: size.ws_row,
+        })
+    }
+
+    pub(super) fn cursor_position(timeout: std::time::Duration) -> std::io::Result<Position> {
+        let mut tty = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open("/dev/tty")?;
+        let _raw = RawMode::enable(tty.as_raw_fd())?;
+
+        tty.write_all(b"\x1b[6n")?;
+        tty.flush()?;
+
+        let deadline = std::time::Instant::now() + timeout;
+        let mut reply = Vec::new();
+        loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            if remaining.is_zero() || !poll_read(tty.as_raw_fd(), remaining)? {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::TimedOut,
+                    "terminal did not report the cursor position",
+                ));
+            }
+
+            let mut buffer = [0; 32];
+            let read = tty.read(&mut buffer)?;
+            reply.extend_from_slice(&buffer[..read]);
+            if let Some(position) = super::parse_position_report(&reply) {
+                return Ok(position);
+            }
+        }
+    }
+
+    /// Wait for `fd` to be readable
+    fn poll_read(fd: RawFd, timeout: std::time::Duration) -> std::io::Result<bool> {
+        let mut pollfd = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        let timeout = timeout.as_millis().clamp(1, libc::c_int::MAX as u128) as libc::c_int;
+        loop {
+            // SAFETY: `pollfd` is valid for the one entry passed in
+            match unsafe { libc::poll(&mut pollfd, 1, timeout) } {
+                -1 => {
+                    let err = std::io::Error::last_os_error();
+                    if err.kind() != std::io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+                0 => return Ok(false),
+                _ => return Ok(true),
+            }
+        }
+    }
+
+    /// Turn off line buffering and echo, restoring them when dropped
+    struct RawMode {
+        fd: RawFd,
+        original: libc::termios,
+    }
+
+    impl RawMode {
+        fn enable(fd: RawFd) -> std::io::Result<Self> {
+            let mut termios = std::mem::MaybeUninit::<libc::termios>::zeroed();
+            // SAFETY: `tcgetattr` only writes to `termios`
+            if unsafe { libc::tcgetattr(fd, termios.as_mut_ptr()) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            // SAFETY: initialized by `zeroed` and `tcgetattr`
+            let original = unsafe { termios.assume_init() };
+
+            let mut raw = original;
+            raw.c_lflag &= !(libc::ICANON | libc::ECHO);
+            raw.c_cc[libc::VMIN] = 1;
+            raw.c_cc[libc::VTIME] = 0;
+            // SAFETY: `raw` is a valid `termios` from `tcgetattr`
+            if unsafe { libc::tcsetattr(fd, libc::TCSANOW, &raw) } != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(Self { fd, original })
+        }
+    }
+
+    impl Drop for RawMode {
+        fn drop(&mut self) {
+            // SAFETY: `original` is a valid `termios` from `tcgetattr`
+            unsafe {
+                libc::tcsetattr(self.fd, libc::TCSANOW, &self.original);
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+mod sys {
+    use windows_sys::Win32::System::Console::GetConsoleScreenBufferInfo;
+    use windows_sys::Win32::System::Console::GetStdHandle;
+    use windows_sys::Win32::System::Console::CONSOLE_SCREEN_BUFFER_INFO;
+    use windows_sys::Win32::System::Console::STD_ERROR_HANDLE;
+    use windows_sys::Win32::System::Console::STD_OUTPUT_HANDLE;
+
+    use super::Position;
+    use super::Size;
+
+    pub(super) fn size() -> Option<Size> {
+        let info = screen_buffer_info()?;
+        let window = info.srWindow;
+        Some(Size {
+            columns: u16::try_from(window.Right - window.Left + 1).ok()?,
+            rows: u16::try_from(window.Bottom - window.Top + 1).ok()?,
+        })
+    }
+
+    pub(super) fn cursor_position(_timeout: std::time::Duration) -> std::io::Result<Position> {
+        let info = screen_buffer_info()
+            .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Unsupported, "not a console"))?;
+        let cursor = info.dwCursorPosition;
+        let window = info.srWindow;
+        let row = u16::try_from(cursor.Y - window.Top + 1);
+        let column = u16::try_from(cursor.X - window.Left + 1);
+        match (row, column) {
+            (Ok(row), Ok(column)) => Ok(Position { row, column }),
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "cursor is outside of the window",
+            )),
+        }
+    }
+
+    fn screen_buffer_info() -> Option<CONSOLE_SCREEN_BUFFER_INFO> {
+        [STD_OUTPUT_HANDLE, STD_ERROR_HANDLE]
+            .into_iter()
+            .find_map(|std_handle| {
+                // SAFETY: `GetStdHandle` has no preconditions and `GetConsoleScreenBufferInfo`
+                // only writes to `info`
+                unsafe {
+                    let handle = GetStdHandle(std_handle);
+                    let mut info = std::mem::zeroed::<CONSOLE_SCREEN_BUFFER_INFO>();
+                    (GetConsoleScreenBufferInfo(handle, &mut info) != 0).then_some(info)
+                }
+            })
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod sys {
+    use super::Position;
+    use super::Size;
+
+    pub(super) fn size() -> Option<Size> {
+        None
+    }
+
+    pub(super) fn cursor_position(_timeout: std::time::Duration) -> std::io::Result<Position> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "cursor position can't be queried on this platform",
+        ))
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn position_report() {
+        assert_eq!(
+            parse_position_report(b"\x1b[12;40R"),
+            Some(Position {
+                row: 12,
+                column: 40
+            })
+        );
+        // Typed ahead of the reply
+        assert_eq!(
+            parse_position_report(b"ab\x1b[A\x1b[1;1R"),
+            Some(Position { row: 1, column: 1 })
+        );
+        assert_eq!(parse_position_report(b"\x1b[12;40"), None);
+        assert_eq!(parse_position_report(b"\x1b[12R"), None);
+        assert_eq!(parse_position_report(b"\x1b[;R"), None);
+    }
+}
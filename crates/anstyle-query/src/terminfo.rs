@@ -0,0 +1,489 @@
+//! Embedded terminfo capabilities for common `TERM` values
+//!
+//! Containers, static musl builds, and Windows frequently have no terminfo database to read
+//! capabilities from.  This is a compiled-in subset of the [ncurses terminfo database] for the
+//! terminals most likely to show up in `TERM`, covering only what is needed for styling text.
+//!
+//! Only exact names are recognized, like with a terminfo database.  See [`crate::quirks`] for
+//! where terminals are known to over-report what they support.
+//!
+//! [ncurses terminfo database]: https://invisible-island.net/ncurses/terminfo.src.html
+
+use crate::quirks::ColorLevel;
+use crate::quirks::TermQuirks;
+
+/// Styling capabilities from a terminal's terminfo entry
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TermInfo {
+    /// The `TERM` value for this entry
+    pub name: &'static str,
+    /// Number of colors in the palette (`colors`), `0` when colors aren't supported
+    pub colors: u32,
+    /// Whether bold text is supported (`bold`)
+    pub bold: bool,
+    /// Whether dimmed text is supported (`dim`)
+    pub dimmed: bool,
+    /// Whether italic text is supported (`sitm`)
+    pub italic: bool,
+    /// Whether underlined text is supported (`smul`)
+    pub underline: bool,
+    /// Whether strikethrough text is supported (`smxx`)
+    pub strikethrough: bool,
+    /// Whether curly, dotted, and other underline styles are supported (`Smulx`)
+    pub styled_underline: bool,
+    /// Whether colors are set directly as RGB values (`RGB`)
+    pub rgb: bool,
+}
+
+impl TermInfo {
+    /// Look up the entry for a `TERM` value
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use anstyle_query::terminfo::*;
+    /// # use anstyle_query::quirks::ColorLevel;
+    /// let info = TermInfo::from_term("xterm-256color").unwrap();
+    /// assert_eq!(info.colors, 256);
+    /// assert_eq!(info.color_level(), ColorLevel::Ansi256);
+    ///
+    /// assert_eq!(TermInfo::from_term("not-a-terminal"), None);
+    /// ```
+    pub fn from_term(term: &str) -> Option<&'static Self> {
+        TABLE
+            .binary_search_by(|info| info.name.cmp(term))
+            .ok()
+            .map(|i| &TABLE[i])
+    }
+
+    /// Most colors the terminal can display
+    ///
+    /// An 88 color palette is reported as [`ColorLevel::Ansi`], as 256-color indices would be
+    /// misread.
+    pub fn color_level(&self) -> ColorLevel {
+        if self.rgb {
+            ColorLevel::Rgb
+        } else if 256 <= self.colors {
+            ColorLevel::Ansi256
+        } else if 8 <= self.colors {
+            ColorLevel::Ansi
+        } else {
+            ColorLevel::None
+        }
+    }
+
+    /// The styling this entry claims, limited to what is known to work for the terminal
+    ///
+    /// See [`TermQuirks::from_term`] for the known limitations.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use anstyle_query::terminfo::*;
+    /// let profile = TermInfo::from_term("screen-256color").unwrap().profile();
+    /// assert_eq!(profile.get_colors(), anstyle::ColorLevel::Ansi256);
+    /// assert!(!profile.get_italic());
+    /// ```
+    pub fn profile(&self) -> anstyle::Profile {
+        let quirks = TermQuirks::from_term(self.name);
+        anstyle::Profile::new()
+            .colors(if self.color_level() < quirks.colors {
+                self.color_level()
+            } else {
+                quirks.colors
+            })
+            .effects(self.bold || self.underline)
+            .italic(self.italic && quirks.italic)
+            .dimmed(self.dimmed && quirks.dimmed)
+            .strikethrough(self.strikethrough && quirks.strikethrough)
+            .styled_underlines(self.styled_underline && quirks.styled_underlines)
+    }
+}
+
+/// Embedded entries, sorted by name
+///
+/// Generated from the ncurses terminfo database with `infocmp -1x <name>`
+const TABLE: &[TermInfo] = &[
+    TermInfo {
+        name: "alacritty",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: true,
+        rgb: false,
+    },
+    TermInfo {
+        name: "ansi",
+        colors: 8,
+        bold: true,
+        dimmed: false,
+        italic: false,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "cygwin",
+        colors: 8,
+        bold: true,
+        dimmed: false,
+        italic: false,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "dumb",
+        colors: 0,
+        bold: false,
+        dimmed: false,
+        italic: false,
+        underline: false,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "foot",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "iterm2",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "konsole",
+        colors: 8,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "konsole-256color",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "konsole-direct",
+        colors: 0x100_0000,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: true,
+    },
+    TermInfo {
+        name: "linux",
+        colors: 8,
+        bold: true,
+        dimmed: true,
+        italic: false,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "ms-terminal",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "putty",
+        colors: 8,
+        bold: true,
+        dimmed: false,
+        italic: false,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "putty-256color",
+        colors: 256,
+        bold: true,
+        dimmed: false,
+        italic: false,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "rxvt-unicode",
+        colors: 88,
+        bold: true,
+        dimmed: false,
+        italic: true,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "rxvt-unicode-256color",
+        colors: 256,
+        bold: true,
+        dimmed: false,
+        italic: true,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "screen",
+        colors: 8,
+        bold: true,
+        dimmed: true,
+        italic: false,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "screen-256color",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: false,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "st",
+        colors: 8,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "st-256color",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "tmux",
+        colors: 8,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: true,
+        rgb: false,
+    },
+    TermInfo {
+        name: "tmux-256color",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: true,
+        rgb: false,
+    },
+    TermInfo {
+        name: "tmux-direct",
+        colors: 0x100_0000,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: true,
+        rgb: true,
+    },
+    TermInfo {
+        name: "vt100",
+        colors: 0,
+        bold: true,
+        dimmed: false,
+        italic: false,
+        underline: true,
+        strikethrough: false,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "vte",
+        colors: 8,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: true,
+        rgb: false,
+    },
+    TermInfo {
+        name: "vte-256color",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: true,
+        rgb: false,
+    },
+    TermInfo {
+        name: "wezterm",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "xterm",
+        colors: 8,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "xterm-256color",
+        colors: 256,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: false,
+    },
+    TermInfo {
+        name: "xterm-direct",
+        colors: 0x100_0000,
+        bold: true,
+        dimmed: true,
+        italic: true,
+        underline: true,
+        strikethrough: true,
+        styled_underline: false,
+        rgb: true,
+    },
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sorted() {
+        for pair in TABLE.windows(2) {
+            assert!(
+                pair[0].name < pair[1].name,
+                "{} < {}",
+                pair[0].name,
+                pair[1].name
+            );
+        }
+    }
+
+    #[test]
+    fn lookup() {
+        for info in TABLE {
+            assert_eq!(TermInfo::from_term(info.name), Some(info));
+        }
+        assert_eq!(TermInfo::from_term("xterm-"), None);
+        assert_eq!(TermInfo::from_term(""), None);
+    }
+
+    #[test]
+    fn color_level() {
+        let cases = [
+            ("dumb", ColorLevel::None),
+            ("vt100", ColorLevel::None),
+            ("linux", ColorLevel::Ansi),
+            ("rxvt-unicode", ColorLevel::Ansi),
+            ("screen-256color", ColorLevel::Ansi256),
+            ("xterm-direct", ColorLevel::Rgb),
+        ];
+        for (term, expected) in cases {
+            let info = TermInfo::from_term(term).unwrap();
+            assert_eq!(info.color_level(), expected, "{term}");
+        }
+    }
+
+    /// The built-in profiles shouldn't claim more than terminfo does
+    #[test]
+    fn quirks_agree() {
+        for info in TABLE {
+            let quirks = TermQuirks::from_term(info.name);
+            if quirks != TermQuirks::NONE {
+                assert_eq!(TermQuirks::from(info.profile()), quirks, "{}", info.name);
+            }
+        }
+    }
+
+    #[test]
+    fn effects() {
+        let linux = TermInfo::from_term("linux").unwrap();
+        assert!(linux.dimmed);
+        assert!(!linux.italic);
+        let tmux = TermInfo::from_term("tmux-256color").unwrap();
+        assert!(tmux.italic);
+        assert!(tmux.styled_underline);
+    }
+}
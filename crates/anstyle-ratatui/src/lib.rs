@@ -0,0 +1,158 @@
+mod text;
+
+pub use text::from_spans;
+pub use text::to_text;
+
+mod sealed {
+    pub(crate) trait Sealed {}
+}
+
+trait Ext: sealed::Sealed {
+    fn to_ratatui(self) -> ratatui::style::Style;
+}
+
+impl sealed::Sealed for anstyle::Style {}
+
+impl Ext for anstyle::Style {
+    fn to_ratatui(self) -> ratatui::style::Style {
+        to_ratatui(self)
+    }
+}
+
+pub fn to_ratatui(astyle: anstyle::Style) -> ratatui::style::Style {
+    let mut add_modifier = ratatui::style::Modifier::empty();
+
+    let effects = astyle.get_effects();
+    if effects.contains(anstyle::Effects::BOLD) {
+        add_modifier |= ratatui::style::Modifier::BOLD;
+    }
+    if effects.contains(anstyle::Effects::DIMMED) {
+        add_modifier |= ratatui::style::Modifier::DIM;
+    }
+    if effects.contains(anstyle::Effects::ITALIC) {
+        add_modifier |= ratatui::style::Modifier::ITALIC;
+    }
+    let underlines = anstyle::Effects::UNDERLINE
+        | anstyle::Effects::DOUBLE_UNDERLINE
+        | anstyle::Effects::CURLY_UNDERLINE
+        | anstyle::Effects::DOTTED_UNDERLINE
+        | anstyle::Effects::DASHED_UNDERLINE;
+    if underlines.remove(effects) != underlines {
+        add_modifier |= ratatui::style::Modifier::UNDERLINED;
+    }
+    if effects.contains(anstyle::Effects::BLINK) {
+        add_modifier |= ratatui::style::Modifier::SLOW_BLINK;
+    }
+    if effects.contains(anstyle::Effects::INVERT) {
+        add_modifier |= ratatui::style::Modifier::REVERSED;
+    }
+    if effects.contains(anstyle::Effects::HIDDEN) {
+        add_modifier |= ratatui::style::Modifier::HIDDEN;
+    }
+    if effects.contains(anstyle::Effects::STRIKETHROUGH) {
+        add_modifier |= ratatui::style::Modifier::CROSSED_OUT;
+    }
+
+    ratatui::style::Style {
+        fg: astyle.get_fg_color().map(to_ratatui_color),
+        bg: astyle.get_bg_color().map(to_ratatui_color),
+        underline_color: astyle.get_underline_color().map(to_ratatui_color),
+        add_modifier,
+        sub_modifier: ratatui::style::Modifier::empty(),
+    }
+}
+
+pub fn to_ratatui_color(color: anstyle::Color) -> ratatui::style::Color {
+    match color {
+        anstyle::Color::Ansi(ansi) => ansi_to_ratatui_color(ansi),
+        anstyle::Color::Ansi256(xterm) => ratatui::style::Color::Indexed(xterm.0),
+        anstyle::Color::Rgb(rgb) => ratatui::style::Color::Rgb(rgb.0, rgb.1, rgb.2),
+    }
+}
+
+fn ansi_to_ratatui_color(color: anstyle::AnsiColor) -> ratatui::style::Color {
+    match color {
+        anstyle::AnsiColor::Black => ratatui::style::Color::Black,
+        anstyle::AnsiColor::Red => ratatui::style::Color::Red,
+        anstyle::AnsiColor::Green => ratatui::style::Color::Green,
+        anstyle::AnsiColor::Yellow => ratatui::style::Color::Yellow,
+        anstyle::AnsiColor::Blue => ratatui::style::Color::Blue,
+        anstyle::AnsiColor::Magenta => ratatui::style::Color::Magenta,
+        anstyle::AnsiColor::Cyan => ratatui::style::Color::Cyan,
+        anstyle::AnsiColor::White => ratatui::style::Color::Gray,
+        anstyle::AnsiColor::BrightBlack => ratatui::style::Color::DarkGray,
+        anstyle::AnsiColor::BrightRed => ratatui::style::Color::LightRed,
+        anstyle::AnsiColor::BrightGreen => ratatui::style::Color::LightGreen,
+        anstyle::AnsiColor::BrightYellow => ratatui::style::Color::LightYellow,
+        anstyle::AnsiColor::BrightBlue => ratatui::style::Color::LightBlue,
+        anstyle::AnsiColor::BrightMagenta => ratatui::style::Color::LightMagenta,
+        anstyle::AnsiColor::BrightCyan => ratatui::style::Color::LightCyan,
+        anstyle::AnsiColor::BrightWhite => ratatui::style::Color::White,
+    }
+}
+
+/// Only [`ratatui::style::Style::add_modifier`] is used as `sub_modifier` has no meaning outside
+/// of patching another style
+pub fn to_anstyle(style: ratatui::style::Style) -> anstyle::Style {
+    let mut effects = anstyle::Effects::new();
+
+    let modifier = style.add_modifier;
+    if modifier.contains(ratatui::style::Modifier::BOLD) {
+        effects |= anstyle::Effects::BOLD;
+    }
+    if modifier.contains(ratatui::style::Modifier::DIM) {
+        effects |= anstyle::Effects::DIMMED;
+    }
+    if modifier.contains(ratatui::style::Modifier::ITALIC) {
+        effects |= anstyle::Effects::ITALIC;
+    }
+    if modifier.contains(ratatui::style::Modifier::UNDERLINED) {
+        effects |= anstyle::Effects::UNDERLINE;
+    }
+    if modifier
+        .intersects(ratatui::style::Modifier::SLOW_BLINK | ratatui::style::Modifier::RAPID_BLINK)
+    {
+        effects |= anstyle::Effects::BLINK;
+    }
+    if modifier.contains(ratatui::style::Modifier::REVERSED) {
+        effects |= anstyle::Effects::INVERT;
+    }
+    if modifier.contains(ratatui::style::Modifier::HIDDEN) {
+        effects |= anstyle::Effects::HIDDEN;
+    }
+    if modifier.contains(ratatui::style::Modifier::CROSSED_OUT) {
+        effects |= anstyle::Effects::STRIKETHROUGH;
+    }
+
+    anstyle::Style::new()
+        .fg_color(style.fg.and_then(to_anstyle_color))
+        .bg_color(style.bg.and_then(to_anstyle_color))
+        .underline_color(style.underline_color.and_then(to_anstyle_color))
+        .effects(effects)
+}
+
+/// [`ratatui::style::Color::Reset`] is `None`
+pub fn to_anstyle_color(color: ratatui::style::Color) -> Option<anstyle::Color> {
+    let color = match color {
+        ratatui::style::Color::Reset => return None,
+        ratatui::style::Color::Black => anstyle::AnsiColor::Black.into(),
+        ratatui::style::Color::Red => anstyle::AnsiColor::Red.into(),
+        ratatui::style::Color::Green => anstyle::AnsiColor::Green.into(),
+        ratatui::style::Color::Yellow => anstyle::AnsiColor::Yellow.into(),
+        ratatui::style::Color::Blue => anstyle::AnsiColor::Blue.into(),
+        ratatui::style::Color::Magenta => anstyle::AnsiColor::Magenta.into(),
+        ratatui::style::Color::Cyan => anstyle::AnsiColor::Cyan.into(),
+        ratatui::style::Color::Gray => anstyle::AnsiColor::White.into(),
+        ratatui::style::Color::DarkGray => anstyle::AnsiColor::BrightBlack.into(),
+        ratatui::style::Color::LightRed => anstyle::AnsiColor::BrightRed.into(),
+        ratatui::style::Color::LightGreen => anstyle::AnsiColor::BrightGreen.into(),
+        ratatui::style::Color::LightYellow => anstyle::AnsiColor::BrightYellow.into(),
+        ratatui::style::Color::LightBlue => anstyle::AnsiColor::BrightBlue.into(),
+        ratatui::style::Color::LightMagenta => anstyle::AnsiColor::BrightMagenta.into(),
+        ratatui::style::Color::LightCyan => anstyle::AnsiColor::BrightCyan.into(),
+        ratatui::style::Color::White => anstyle::AnsiColor::BrightWhite.into(),
+        ratatui::style::Color::Indexed(index) => anstyle::Ansi256Color(index).into(),
+        ratatui::style::Color::Rgb(r, g, b) => anstyle::RgbColor(r, g, b).into(),
+    };
+    Some(color)
+}
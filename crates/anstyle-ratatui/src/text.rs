@@ -0,0 +1,119 @@
+/// Parse ANSI-styled output, like captured from a child process, into a [`ratatui::text::Text`]
+///
+/// SGR escape codes become span styles and other escape codes are dropped.  Like
+/// [`str::lines`], a trailing newline does not start a new line.
+///
+/// # Examples
+///
+/// ```rust
+/// let text = anstyle_ratatui::to_text(b"\x1b[1;31merror\x1b[0m: oops\nnext\n");
+/// assert_eq!(text.lines.len(), 2);
+/// assert_eq!(text.lines[0].spans[0].content, "error");
+/// assert_eq!(
+///     text.lines[0].spans[0].style,
+///     anstyle_ratatui::to_ratatui(anstyle::AnsiColor::Red.on_default().bold())
+/// );
+/// assert_eq!(text.lines[1].spans[0].content, "next");
+/// ```
+pub fn to_text(bytes: &[u8]) -> ratatui::text::Text<'static> {
+    let mut parser = vte::Parser::new();
+    let mut spans = anstyle_vte::SpanCollector::new();
+    for byte in bytes {
+        parser.advance(&mut spans, *byte);
+    }
+    from_spans(spans.into_spans())
+}
+
+/// Split [`anstyle_vte::StyledSpan`]s into the lines of a [`ratatui::text::Text`]
+///
+/// For when output arrives incrementally and is collected with an
+/// [`anstyle_vte::SpanCollector`].
+pub fn from_spans(
+    spans: impl IntoIterator<Item = anstyle_vte::StyledSpan>,
+) -> ratatui::text::Text<'static> {
+    let mut lines = Vec::new();
+    let mut line = Vec::new();
+    for span in spans {
+        let style = crate::to_ratatui(span.style);
+        let mut pieces = span.text.split('\n').peekable();
+        while let Some(piece) = pieces.next() {
+            if !piece.is_empty() {
+                line.push(ratatui::text::Span::styled(piece.to_owned(), style));
+            }
+            if pieces.peek().is_some() {
+                lines.push(ratatui::text::Line::from(std::mem::take(&mut line)));
+            }
+        }
+    }
+    if !line.is_empty() {
+        lines.push(ratatui::text::Line::from(line));
+    }
+    ratatui::text::Text::from(lines)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn line(spans: &[(anstyle::Style, &str)]) -> ratatui::text::Line<'static> {
+        ratatui::text::Line::from(
+            spans
+                .iter()
+                .map(|(style, text)| {
+                    ratatui::text::Span::styled((*text).to_owned(), crate::to_ratatui(*style))
+                })
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(to_text(b""), ratatui::text::Text::default());
+    }
+
+    #[test]
+    fn plain() {
+        let plain = anstyle::Style::new();
+        assert_eq!(
+            to_text(b"hello\nworld\n").lines,
+            [line(&[(plain, "hello")]), line(&[(plain, "world")])]
+        );
+    }
+
+    #[test]
+    fn blank_lines() {
+        let plain = anstyle::Style::new();
+        assert_eq!(
+            to_text(b"a\n\nb\n\n").lines,
+            [
+                line(&[(plain, "a")]),
+                line(&[]),
+                line(&[(plain, "b")]),
+                line(&[]),
+            ]
+        );
+    }
+
+    #[test]
+    fn style_spans_lines() {
+        let plain = anstyle::Style::new();
+        let green = anstyle::AnsiColor::Green.on_default();
+        assert_eq!(
+            to_text(b"   \x1b[32mCompiling\nfoo\x1b[0m bar\n").lines,
+            [
+                line(&[(plain, "   "), (green, "Compiling")]),
+                line(&[(green, "foo"), (plain, " bar")]),
+            ]
+        );
+    }
+
+    #[test]
+    fn style_change_before_newline() {
+        let plain = anstyle::Style::new();
+        let red = anstyle::AnsiColor::Red.on_default();
+        assert_eq!(
+            to_text(b"\x1b[31merror\x1b[0m\n\x1b[2Kdone").lines,
+            [line(&[(red, "error")]), line(&[(plain, "done")])]
+        );
+    }
+}
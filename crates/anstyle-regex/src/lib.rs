@@ -0,0 +1,191 @@
+//! Colorize text with regex rules, like `grep --color`
+//!
+//! A [`Colorizer`] holds an ordered list of `(regex, style)` rules.  Each line is matched against
+//! every rule and the matched text is styled, with earlier rules taking precedence where matches
+//! overlap.  Only the escape codes needed are written: unmatched text is left plain and adjacent
+//! text with the same style shares one set of codes.
+//!
+//! Input is expected to be plain text; colorizing text that already has escape codes may match
+//! inside of them.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let colorizer = anstyle_regex::Colorizer::new()
+//!     .rule(r"\berror\b", anstyle::AnsiColor::Red.on_default().bold())
+//!     .unwrap()
+//!     .rule(r"\d+", anstyle::AnsiColor::Cyan.on_default())
+//!     .unwrap();
+//! assert_eq!(
+//!     colorizer.colorize_str("error at line 42"),
+//!     "\x1b[1m\x1b[31merror\x1b[0m at line \x1b[36m42\x1b[0m"
+//! );
+//! ```
+
+mod stream;
+
+pub use regex::Error;
+pub use stream::ColorizeStream;
+
+use anstyle::Style;
+use regex::bytes::Regex;
+
+/// Style text matching an ordered list of regex rules
+#[derive(Clone, Debug, Default)]
+pub struct Colorizer {
+    rules: Vec<(Regex, Style)>,
+}
+
+impl Colorizer {
+    /// No rules, leaving text as-is
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Style text matching `pattern`
+    ///
+    /// Rules added earlier take precedence where matches overlap.
+    pub fn rule(mut self, pattern: &str, style: Style) -> Result<Self, Error> {
+        let regex = Regex::new(pattern)?;
+        self.push(regex, style);
+        Ok(self)
+    }
+
+    /// Style text matching an already compiled `regex`
+    ///
+    /// Rules added earlier take precedence where matches overlap.
+    #[inline]
+    pub fn push(&mut self, regex: Regex, style: Style) {
+        self.rules.push((regex, style));
+    }
+
+    /// Colorize each line of `text`
+    pub fn colorize(&self, text: &[u8]) -> Vec<u8> {
+        let mut output = Vec::with_capacity(text.len());
+        for line in text.split_inclusive(|b| *b == b'\n') {
+            self.colorize_line(line, &mut output);
+        }
+        output
+    }
+
+    /// Colorize each line of `text`
+    ///
+    /// Patterns that can match part of a character, like with `(?-u)`, may have the output
+    /// replace that character with `U+FFFD`.
+    pub fn colorize_str(&self, text: &str) -> String {
+        match String::from_utf8(self.colorize(text.as_bytes())) {
+            Ok(output) => output,
+            Err(err) => String::from_utf8_lossy(err.as_bytes()).into_owned(),
+        }
+    }
+
+    /// Colorize a single line, with or without its line ending, appending it to `output`
+    pub fn colorize_line(&self, line: &[u8], output: &mut Vec<u8>) {
+        let (line, ending) = split_ending(line);
+        let styles = self.styles(line);
+
+        let mut written = Style::new();
+        let mut start = 0;
+        while start < line.len() {
+            let style = styles[start];
+            let end = styles[start..]
+                .iter()
+                .position(|s| *s != style)
+                .map(|len| start + len)
+                .unwrap_or(line.len());
+            if style != written {
+                let _ = written.write_reset_to(output);
+                let _ = style.write_to(output);
+                written = style;
+            }
+            output.extend_from_slice(&line[start..end]);
+            start = end;
+        }
+        let _ = written.write_reset_to(output);
+        output.extend_from_slice(ending);
+    }
+
+    /// The style for each byte of `line`
+    fn styles(&self, line: &[u8]) -> Vec<Style> {
+        let mut styles = vec![None; line.len()];
+        for (regex, style) in &self.rules {
+            for found in regex.find_iter(line) {
+                for slot in &mut styles[found.range()] {
+                    if slot.is_none() {
+                        *slot = Some(*style);
+                    }
+                }
+            }
+        }
+        styles.into_iter().map(Option::unwrap_or_default).collect()
+    }
+}
+
+fn split_ending(line: &[u8]) -> (&[u8], &[u8]) {
+    let content = line.strip_suffix(b"\n").unwrap_or(line);
+    let content = content.strip_suffix(b"\r").unwrap_or(content);
+    line.split_at(content.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn red() -> Style {
+        anstyle::AnsiColor::Red.on_default()
+    }
+
+    fn blue() -> Style {
+        anstyle::AnsiColor::Blue.on_default()
+    }
+
+    #[test]
+    fn no_rules() {
+        let colorizer = Colorizer::new();
+        assert_eq!(colorizer.colorize_str("a\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn no_match() {
+        let colorizer = Colorizer::new().rule("x", red()).unwrap();
+        assert_eq!(colorizer.colorize_str("abc"), "abc");
+    }
+
+    #[test]
+    fn per_line() {
+        let colorizer = Colorizer::new().rule("^.*$", red()).unwrap();
+        assert_eq!(
+            colorizer.colorize_str("a\r\nb\n\nc"),
+            "\x1b[31ma\x1b[0m\r\n\x1b[31mb\x1b[0m\n\n\x1b[31mc\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn earlier_rules_win() {
+        let colorizer = Colorizer::new()
+            .rule("bc", red())
+            .unwrap()
+            .rule("abcd", blue())
+            .unwrap();
+        assert_eq!(
+            colorizer.colorize_str("abcde"),
+            "\x1b[34ma\x1b[0m\x1b[31mbc\x1b[0m\x1b[34md\x1b[0me"
+        );
+    }
+
+    #[test]
+    fn adjacent_matches_merge() {
+        let colorizer = Colorizer::new().rule("a", red()).unwrap();
+        assert_eq!(
+            colorizer.colorize_str("aaba"),
+            "\x1b[31maa\x1b[0mb\x1b[31ma\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn empty_matches() {
+        let colorizer = Colorizer::new().rule("x*", red()).unwrap();
+        assert_eq!(colorizer.colorize_str("ab"), "ab");
+    }
+}
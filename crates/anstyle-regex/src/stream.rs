@@ -0,0 +1,141 @@
+use crate::Colorizer;
+
+/// Colorize lines written through to the wrapped `Write`
+///
+/// Writes are buffered until a newline so rules match whole lines.  Any trailing partial line is
+/// colorized and written on [`flush`][std::io::Write::flush], [`ColorizeStream::finish`], or when
+/// dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Write as _;
+///
+/// let colorizer = anstyle_regex::Colorizer::new()
+///     .rule("WARN", anstyle::AnsiColor::Yellow.on_default())
+///     .unwrap();
+/// let mut stream = anstyle_regex::ColorizeStream::new(Vec::new(), colorizer);
+/// write!(stream, "[WARN] disk ").unwrap();
+/// writeln!(stream, "almost full").unwrap();
+/// let buffer = stream.finish().unwrap();
+/// assert_eq!(buffer, b"[\x1b[33mWARN\x1b[0m] disk almost full\n");
+/// ```
+#[derive(Debug)]
+pub struct ColorizeStream<W>
+where
+    W: std::io::Write,
+{
+    // Only `None` after `finish` or `into_inner`
+    raw: Option<W>,
+    colorizer: Colorizer,
+    buffer: Vec<u8>,
+}
+
+impl<W> ColorizeStream<W>
+where
+    W: std::io::Write,
+{
+    /// Colorize lines with `colorizer`
+    #[inline]
+    pub fn new(raw: W, colorizer: Colorizer) -> Self {
+        Self {
+            raw: Some(raw),
+            colorizer,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Write any trailing partial line and get the wrapped `Write`
+    pub fn finish(mut self) -> std::io::Result<W> {
+        self.write_lines(self.buffer.len())?;
+        Ok(self
+            .raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`"))
+    }
+
+    /// Get the wrapped `Write`, discarding any trailing partial line
+    #[inline]
+    pub fn into_inner(mut self) -> W {
+        self.raw
+            .take()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a reference to the wrapped `Write`
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        self.raw
+            .as_ref()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    /// Get a mutable reference to the wrapped `Write`
+    ///
+    /// Writing to it directly bypasses colorizing and any buffered partial line.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut W {
+        self.raw
+            .as_mut()
+            .expect("only taken on `finish` or `into_inner`")
+    }
+
+    fn write_lines(&mut self, len: usize) -> std::io::Result<()> {
+        if len == 0 {
+            return Ok(());
+        }
+        let output = self.colorizer.colorize(&self.buffer[..len]);
+        self.buffer.drain(..len);
+        self.get_mut().write_all(&output)
+    }
+}
+
+impl<W> std::io::Write for ColorizeStream<W>
+where
+    W: std::io::Write,
+{
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if let Some(newline) = self.buffer.iter().rposition(|b| *b == b'\n') {
+            self.write_lines(newline + 1)?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.write_lines(self.buffer.len())?;
+        self.get_mut().flush()
+    }
+}
+
+impl<W> Drop for ColorizeStream<W>
+where
+    W: std::io::Write,
+{
+    fn drop(&mut self) {
+        if self.raw.is_some() {
+            // Ignore errors as there is no way to report them
+            let _ = self.write_lines(self.buffer.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io::Write as _;
+
+    #[test]
+    fn split_writes() {
+        let colorizer = Colorizer::new()
+            .rule("error", anstyle::AnsiColor::Red.on_default())
+            .unwrap();
+        let mut stream = ColorizeStream::new(Vec::new(), colorizer);
+        stream.write_all(b"err").unwrap();
+        stream.write_all(b"or\nerr").unwrap();
+        assert_eq!(stream.get_ref(), b"\x1b[31merror\x1b[0m\n");
+        stream.write_all(b"or").unwrap();
+        let buffer = stream.finish().unwrap();
+        assert_eq!(buffer, b"\x1b[31merror\x1b[0m\n\x1b[31merror\x1b[0m");
+    }
+}
@@ -0,0 +1,85 @@
+/// Build an `egui` [`LayoutJob`][egui::text::LayoutJob] for drawing `spans` in `font_id`
+///
+/// See [`to_text_format`] for how styles are mapped.
+///
+/// # Examples
+///
+/// ```rust
+/// let theme = anstyle_richtext::Theme::default();
+/// let spans = anstyle_richtext::parse(b"\x1b[31merror\x1b[0m: oops", &theme);
+/// let job = anstyle_richtext::to_layout_job(&spans, egui::FontId::monospace(12.0));
+/// assert_eq!(job.text, "error: oops");
+/// assert_eq!(job.sections.len(), 2);
+/// ```
+pub fn to_layout_job(spans: &[crate::RichSpan], font_id: egui::FontId) -> egui::text::LayoutJob {
+    let mut job = egui::text::LayoutJob::default();
+    for span in spans {
+        job.append(
+            &span.text,
+            0.0,
+            to_text_format(&span.style, font_id.clone()),
+        );
+    }
+    job
+}
+
+/// Convert a [`RichStyle`][crate::RichStyle] to an `egui` [`TextFormat`][egui::TextFormat]
+///
+/// `egui` has no bold text so [`RichStyle::bold`][crate::RichStyle::bold] is dropped; pick a
+/// bold `font_id` when it is set.
+pub fn to_text_format(style: &crate::RichStyle, font_id: egui::FontId) -> egui::TextFormat {
+    let color = to_color32(style.foreground);
+    egui::TextFormat {
+        font_id,
+        color,
+        background: style
+            .background
+            .map(to_color32)
+            .unwrap_or(egui::Color32::TRANSPARENT),
+        italics: style.italic,
+        underline: style
+            .underline
+            .map(|underline| egui::Stroke::new(1.0_f32, to_color32(underline)))
+            .unwrap_or(egui::Stroke::NONE),
+        strikethrough: if style.strikethrough {
+            egui::Stroke::new(1.0_f32, color)
+        } else {
+            egui::Stroke::NONE
+        },
+        ..Default::default()
+    }
+}
+
+fn to_color32(color: anstyle::RgbColor) -> egui::Color32 {
+    egui::Color32::from_rgb(color.r(), color.g(), color.b())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn text_format() {
+        let theme = crate::Theme::default();
+        let style = anstyle::AnsiColor::Red
+            .on(anstyle::AnsiColor::Blue)
+            .italic()
+            .underline()
+            .strikethrough();
+        let format = to_text_format(&theme.resolve(style), egui::FontId::monospace(12.0));
+        let red = egui::Color32::from_rgb(170, 0, 0);
+        assert_eq!(format.color, red);
+        assert_eq!(format.background, egui::Color32::from_rgb(0, 0, 170));
+        assert!(format.italics);
+        assert_eq!(format.underline, egui::Stroke::new(1.0_f32, red));
+        assert_eq!(format.strikethrough, egui::Stroke::new(1.0_f32, red));
+
+        let format = to_text_format(
+            &theme.resolve(anstyle::Style::new()),
+            egui::FontId::monospace(12.0),
+        );
+        assert_eq!(format.background, egui::Color32::TRANSPARENT);
+        assert_eq!(format.underline, egui::Stroke::NONE);
+        assert_eq!(format.strikethrough, egui::Stroke::NONE);
+    }
+}
@@ -0,0 +1,447 @@
+use std::fmt::Write as _;
+
+/// Render `spans` as HTML, styled by the classes in a [`stylesheet`] or [`variable_stylesheet`]
+///
+/// Rather than fixing colors inline, each span gets classes for its style, like
+/// `ansi-fg-red ansi-bold`, so the embedding page can retheme the output.  The spans are wrapped
+/// in `<pre class="ansi">` so whitespace and newlines are kept, like in a terminal.
+///
+/// Class names are:
+/// - `ansi-fg-<color>`, `ansi-bg-<color>`, and `ansi-ul-<color>` for the foreground,
+///   background, and underline colors, where `<color>` is an ANSI color's name, like
+///   `bright-red`, or a 256-color index, like `208`.  RGB colors have no class and are set
+///   inline.
+/// - `ansi-<effect>` for effects, like `ansi-bold` or `ansi-curly-underline`.
+///   [`anstyle::Effects::BLINK`] is dropped.
+/// - [`anstyle::Effects::INVERT`] swaps the color classes, using `ansi-fg-inverse` and
+///   `ansi-bg-inverse` for the default colors.
+///
+/// # Examples
+///
+/// ```rust
+/// let spans = anstyle_richtext::parse_styled(b"\x1b[1;31merror\x1b[0m: a < b");
+/// assert_eq!(
+///     anstyle_richtext::to_html(&spans),
+///     "<pre class=\"ansi\"><span class=\"ansi-fg-red ansi-bold\">error</span>: a &lt; b</pre>"
+/// );
+/// ```
+pub fn to_html(spans: &[anstyle_vte::StyledSpan]) -> String {
+    let mut html = String::from("<pre class=\"ansi\">");
+    for span in spans {
+        if span.text.is_empty() {
+            continue;
+        }
+        let open = write_span(&mut html, span.style);
+        crate::escape(&mut html, &span.text);
+        if open {
+            html.push_str("</span>");
+        }
+    }
+    html.push_str("</pre>");
+    html
+}
+
+/// Generate the CSS for the classes used by [`to_html`], with colors from `theme`
+///
+/// [`anstyle::Effects::DIMMED`] lowers the opacity of the text and its background.
+///
+/// # Examples
+///
+/// ```rust
+/// let css = anstyle_richtext::stylesheet(&anstyle_richtext::Theme::default());
+/// assert!(css.contains(".ansi-fg-red { color: #aa0000; }\n"));
+/// assert!(css.contains(".ansi-bg-208 { background-color: #ff8700; }\n"));
+/// ```
+pub fn stylesheet(theme: &crate::Theme) -> String {
+    themed_rules(theme, Target::Html)
+}
+
+/// Generate the CSS for the classes used by [`to_html`], with colors from CSS custom properties
+///
+/// Colors are read from `--ansi-foreground`, `--ansi-background`, and `--ansi-<color>`, where
+/// `<color>` is an ANSI color's name, like `bright-red`, or a 256-color index, like `208`.
+/// Declare them with [`css_variables`], so the theme can be switched purely in CSS, like for
+/// light and dark modes.
+///
+/// # Examples
+///
+/// ```rust
+/// let css = anstyle_richtext::variable_stylesheet();
+/// assert!(css.contains(".ansi-fg-red { color: var(--ansi-red); }\n"));
+/// assert!(css.contains(".ansi-bg-208 { background-color: var(--ansi-208); }\n"));
+/// ```
+pub fn variable_stylesheet() -> String {
+    rules(Target::Html, |color| {
+        format!("var(--ansi-{})", variable_name(color))
+    })
+}
+
+/// Declare the colors of `theme` as the CSS custom properties read by [`variable_stylesheet`]
+///
+/// # Examples
+///
+/// ```rust
+/// let light = anstyle_richtext::Theme::new(
+///     anstyle_lossy::palette::VGA,
+///     anstyle::RgbColor(0, 0, 0),
+///     anstyle::RgbColor(255, 255, 255),
+/// );
+/// let dark = anstyle_richtext::Theme::default();
+/// let css = format!(
+///     ":root {{\n{}}}\n@media (prefers-color-scheme: dark) {{\n:root {{\n{}}}\n}}\n{}",
+///     anstyle_richtext::css_variables(&light),
+///     anstyle_richtext::css_variables(&dark),
+///     anstyle_richtext::variable_stylesheet(),
+/// );
+/// assert!(css.contains("--ansi-background: #ffffff;\n"));
+/// assert!(css.contains("--ansi-red: #aa0000;\n"));
+/// ```
+pub fn css_variables(theme: &crate::Theme) -> String {
+    let mut css = String::new();
+    for color in variables() {
+        let _ = writeln!(
+            css,
+            "--ansi-{}: {};",
+            variable_name(color),
+            crate::hex(resolve(theme, color))
+        );
+    }
+    css
+}
+
+/// What the classes are styling, as SVG text is colored by `fill` rather than `color`
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Target {
+    Html,
+    /// SVG text, with backgrounds drawn as `<rect>`s by the caller
+    Svg,
+}
+
+impl Target {
+    /// Class prefix and CSS property for the foreground, background, and underline colors
+    fn color_properties(self) -> [(&'static str, &'static str); 3] {
+        match self {
+            Self::Html => [
+                ("fg", "color"),
+                ("bg", "background-color"),
+                ("ul", "text-decoration-color"),
+            ],
+            Self::Svg => [
+                ("fg", "fill"),
+                ("bg", "fill"),
+                ("ul", "text-decoration-color"),
+            ],
+        }
+    }
+}
+
+/// Which of a span's classes to write
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Part {
+    All,
+    Text,
+    /// The background color, and [`anstyle::Effects::DIMMED`] as that applies to it too
+    Background,
+}
+
+/// A color the stylesheet sets
+#[derive(Copy, Clone)]
+enum Variable {
+    Foreground,
+    Background,
+    Color(anstyle::Ansi256Color),
+}
+
+fn variables() -> impl Iterator<Item = Variable> {
+    [Variable::Foreground, Variable::Background]
+        .into_iter()
+        .chain((0..=u8::MAX).map(|index| Variable::Color(anstyle::Ansi256Color(index))))
+}
+
+fn variable_name(color: Variable) -> String {
+    match color {
+        Variable::Foreground => "foreground".to_owned(),
+        Variable::Background => "background".to_owned(),
+        Variable::Color(color) => color_name(color),
+    }
+}
+
+fn resolve(theme: &crate::Theme, color: Variable) -> anstyle::RgbColor {
+    match color {
+        Variable::Foreground => theme.foreground,
+        Variable::Background => theme.background,
+        Variable::Color(color) => theme.color_to_rgb(color.into()),
+    }
+}
+
+/// The rules for the classes of `target`, with colors from `theme`
+pub(crate) fn themed_rules(theme: &crate::Theme, target: Target) -> String {
+    rules(target, |color| crate::hex(resolve(theme, color)))
+}
+
+/// The rules for the classes of `target`, with `value` writing each color
+fn rules(target: Target, value: impl Fn(Variable) -> String) -> String {
+    let mut css = String::new();
+    let [(_, fg), (_, bg), _] = target.color_properties();
+    match target {
+        Target::Html => {
+            let _ = writeln!(
+                css,
+                ".ansi {{ color: {}; background-color: {}; }}",
+                value(Variable::Foreground),
+                value(Variable::Background)
+            );
+        }
+        Target::Svg => {
+            let _ = writeln!(
+                css,
+                ".ansi {{ fill: {}; white-space: pre; }}",
+                value(Variable::Foreground)
+            );
+            let _ = writeln!(
+                css,
+                ".ansi-background {{ fill: {}; }}",
+                value(Variable::Background)
+            );
+        }
+    }
+    for (prefix, property) in target.color_properties() {
+        for index in 0..=u8::MAX {
+            let color = anstyle::Ansi256Color(index);
+            let _ = writeln!(
+                css,
+                ".ansi-{prefix}-{} {{ {property}: {}; }}",
+                color_name(color),
+                value(Variable::Color(color))
+            );
+        }
+    }
+    let _ = writeln!(
+        css,
+        ".ansi-fg-inverse {{ {fg}: {}; }}",
+        value(Variable::Background)
+    );
+    let _ = writeln!(
+        css,
+        ".ansi-bg-inverse {{ {bg}: {}; }}",
+        value(Variable::Foreground)
+    );
+    css.push_str(EFFECT_RULES);
+    let _ = writeln!(css, ".ansi-hidden {{ {fg}: transparent; }}");
+    css
+}
+
+/// Write the opening `<span>` for `style`, returning whether one was needed
+fn write_span(html: &mut String, style: anstyle::Style) -> bool {
+    let start = html.len();
+    html.push_str("<span");
+    if !write_attributes(html, style, Target::Html, Part::All) {
+        html.truncate(start);
+        return false;
+    }
+    html.push('>');
+    true
+}
+
+/// Write the `class` and `style` attributes for `part` of `style`, returning whether any were
+/// needed
+pub(crate) fn write_attributes(
+    markup: &mut String,
+    style: anstyle::Style,
+    target: Target,
+    part: Part,
+) -> bool {
+    let effects = style.get_effects();
+    let mut classes = Vec::new();
+    let mut inline = Vec::new();
+
+    let inverted = effects.contains(anstyle::Effects::INVERT);
+    let (fg, bg) = if inverted {
+        (style.get_bg_color(), style.get_fg_color())
+    } else {
+        (style.get_fg_color(), style.get_bg_color())
+    };
+    let colors = [fg, bg, style.get_underline_color()];
+    for ((prefix, property), color) in target.color_properties().iter().zip(colors) {
+        let wanted = match part {
+            Part::All => true,
+            Part::Text => *prefix != "bg",
+            Part::Background => *prefix == "bg",
+        };
+        if !wanted {
+            continue;
+        }
+        match color {
+            Some(anstyle::Color::Rgb(rgb)) => {
+                inline.push(format!("{property}:{}", crate::hex(rgb)))
+            }
+            Some(color) => {
+                let color = anstyle_lossy::color_to_xterm(color);
+                classes.push(format!("ansi-{prefix}-{}", color_name(color)));
+            }
+            None if inverted && *prefix != "ul" => classes.push(format!("ansi-{prefix}-inverse")),
+            None => {}
+        }
+    }
+    for (effect, class) in EFFECT_CLASSES {
+        let wanted = part != Part::Background || effect == anstyle::Effects::DIMMED;
+        if wanted && effects.contains(effect) {
+            classes.push(class.to_owned());
+        }
+    }
+
+    if classes.is_empty() && inline.is_empty() {
+        return false;
+    }
+    if !classes.is_empty() {
+        let _ = write!(markup, " class=\"{}\"", classes.join(" "));
+    }
+    if !inline.is_empty() {
+        let _ = write!(markup, " style=\"{}\"", inline.join("; "));
+    }
+    true
+}
+
+/// The name of an ANSI color, like `bright-red`, or the index of other 256-colors
+fn color_name(color: anstyle::Ansi256Color) -> String {
+    match ANSI_NAMES.get(usize::from(color.index())) {
+        Some(name) => (*name).to_owned(),
+        None => color.index().to_string(),
+    }
+}
+
+const ANSI_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright-black",
+    "bright-red",
+    "bright-green",
+    "bright-yellow",
+    "bright-blue",
+    "bright-magenta",
+    "bright-cyan",
+    "bright-white",
+];
+
+const EFFECT_CLASSES: [(anstyle::Effects, &str); 10] = [
+    (anstyle::Effects::BOLD, "ansi-bold"),
+    (anstyle::Effects::DIMMED, "ansi-dimmed"),
+    (anstyle::Effects::ITALIC, "ansi-italic"),
+    (anstyle::Effects::UNDERLINE, "ansi-underline"),
+    (anstyle::Effects::DOUBLE_UNDERLINE, "ansi-double-underline"),
+    (anstyle::Effects::CURLY_UNDERLINE, "ansi-curly-underline"),
+    (anstyle::Effects::DOTTED_UNDERLINE, "ansi-dotted-underline"),
+    (anstyle::Effects::DASHED_UNDERLINE, "ansi-dashed-underline"),
+    (anstyle::Effects::HIDDEN, "ansi-hidden"),
+    (anstyle::Effects::STRIKETHROUGH, "ansi-strikethrough"),
+];
+
+/// Rules for [`EFFECT_CLASSES`], besides `ansi-hidden` which is after them to take precedence
+/// over the colors
+const EFFECT_RULES: &str = "\
+.ansi-bold { font-weight: bold; }
+.ansi-dimmed { opacity: 0.5; }
+.ansi-italic { font-style: italic; }
+.ansi-underline, .ansi-double-underline, .ansi-curly-underline, .ansi-dotted-underline, \
+.ansi-dashed-underline { text-decoration-line: underline; }
+.ansi-double-underline { text-decoration-style: double; }
+.ansi-curly-underline { text-decoration-style: wavy; }
+.ansi-dotted-underline { text-decoration-style: dotted; }
+.ansi-dashed-underline { text-decoration-style: dashed; }
+.ansi-strikethrough { text-decoration-line: line-through; }
+.ansi-strikethrough:is(.ansi-underline, .ansi-double-underline, .ansi-curly-underline, \
+.ansi-dotted-underline, .ansi-dashed-underline) { text-decoration-line: underline line-through; }
+";
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn html(style: anstyle::Style) -> String {
+        to_html(&[anstyle_vte::StyledSpan {
+            style,
+            text: "x".to_owned(),
+        }])
+    }
+
+    #[test]
+    fn classes() {
+        let style = anstyle::AnsiColor::BrightRed
+            .on(anstyle::Ansi256Color(208))
+            .bold()
+            .italic()
+            .underline()
+            .strikethrough()
+            .underline_color(Some(anstyle::Ansi256Color(4).into()));
+        assert_eq!(
+            html(style),
+            "<pre class=\"ansi\"><span class=\"ansi-fg-bright-red ansi-bg-208 ansi-ul-blue \
+             ansi-bold ansi-italic ansi-underline ansi-strikethrough\">x</span></pre>"
+        );
+        assert_eq!(
+            html(anstyle::Style::new().blink()),
+            "<pre class=\"ansi\">x</pre>"
+        );
+    }
+
+    #[test]
+    fn rgb_inline() {
+        let style = anstyle::RgbColor(255, 135, 0).on(anstyle::AnsiColor::Blue);
+        assert_eq!(
+            html(style),
+            "<pre class=\"ansi\"><span class=\"ansi-bg-blue\" style=\"color:#ff8700\">x</span>\
+             </pre>"
+        );
+    }
+
+    #[test]
+    fn inverted() {
+        assert_eq!(
+            html(anstyle::Style::new().invert()),
+            "<pre class=\"ansi\"><span class=\"ansi-fg-inverse ansi-bg-inverse\">x</span></pre>"
+        );
+        let style = anstyle::AnsiColor::Red.on_default().invert();
+        assert_eq!(
+            html(style),
+            "<pre class=\"ansi\"><span class=\"ansi-fg-inverse ansi-bg-red\">x</span></pre>"
+        );
+    }
+
+    #[test]
+    fn stylesheet_covers_classes() {
+        let theme = crate::Theme::default();
+        let css = stylesheet(&theme);
+        assert!(css.starts_with(".ansi { color: #aaaaaa; background-color: #000000; }\n"));
+        for class in [
+            "ansi-fg-bright-white",
+            "ansi-bg-16",
+            "ansi-ul-255",
+            "ansi-fg-inverse",
+            "ansi-bg-inverse",
+        ] {
+            assert!(css.contains(&format!(".{class} {{")), "{class}");
+        }
+        for (_, class) in EFFECT_CLASSES {
+            assert!(css.contains(&format!(".{class}")), "{class}");
+        }
+        assert!(css.contains(".ansi-ul-bright-black { text-decoration-color: #555555; }\n"));
+    }
+
+    #[test]
+    fn variables_match_stylesheet() {
+        let theme = crate::Theme::default();
+        let mut resolved = variable_stylesheet();
+        for declaration in css_variables(&theme).lines() {
+            let (name, value) = declaration.split_once(": ").unwrap();
+            let value = value.strip_suffix(';').unwrap();
+            resolved = resolved.replace(&format!("var({name})"), value);
+        }
+        assert_eq!(resolved, stylesheet(&theme));
+    }
+}
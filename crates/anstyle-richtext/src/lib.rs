@@ -0,0 +1,402 @@
+//! Resolve ANSI-styled text into [`RichSpan`]s for GUI toolkits
+//!
+//! Terminal colors are resolved to RGB against a [`Theme`] and effects become font flags, the
+//! shape expected by rich text widgets like egui's `LayoutJob` or iced's `rich_text`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let theme = anstyle_richtext::Theme::default();
+//! let spans = anstyle_richtext::parse(b"\x1b[1;31merror\x1b[0m: oops", &theme);
+//!
+//! assert_eq!(spans[0].text, "error");
+//! assert_eq!(spans[0].style.foreground, anstyle::RgbColor(170, 0, 0));
+//! assert!(spans[0].style.bold);
+//! assert_eq!(spans[1].text, ": oops");
+//! assert_eq!(spans[1].style.foreground, theme.foreground);
+//! ```
+//!
+//! Spans can be rendered as [Pango markup](https://docs.gtk.org/Pango/pango_markup.html) for GTK
+//! with [`to_pango_markup`], or as HTML for Qt's rich text widgets with [`to_qt_rich_text`].
+//!
+//! For web pages, [`to_html`] renders the unresolved spans from [`parse_styled`] with a class per
+//! color and effect, to be themed by a [`stylesheet`], or by a [`variable_stylesheet`] with the
+//! palette in CSS custom properties from [`css_variables`].
+//!
+//! For images of a terminal, like for a README, [`to_svg`] renders spans as SVG with the same
+//! classes, and [`to_animated_svg`] renders a timestamped recording of output as it was written.
+//!
+//! # Features
+//!
+//! - `egui`: Build an `egui` [`LayoutJob`][::egui::text::LayoutJob] with [`to_layout_job`]
+
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
+
+#[cfg(feature = "egui")]
+mod egui_layout;
+mod html;
+mod pango;
+mod qt;
+mod svg;
+
+#[cfg(feature = "egui")]
+pub use egui_layout::to_layout_job;
+#[cfg(feature = "egui")]
+pub use egui_layout::to_text_format;
+pub use html::css_variables;
+pub use html::stylesheet;
+pub use html::to_html;
+pub use html::variable_stylesheet;
+pub use pango::to_pango_markup;
+pub use qt::to_qt_rich_text;
+pub use svg::to_animated_svg;
+pub use svg::to_svg;
+
+/// Colors for resolving [`anstyle::Style`]s to RGB
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Theme {
+    /// Colors for [`anstyle::AnsiColor`] and the first 16 [`anstyle::Ansi256Color`]s
+    pub palette: anstyle_lossy::palette::Palette,
+    /// Text color when a style has none
+    pub foreground: anstyle::RgbColor,
+    /// Background color of the widget, used when swapping or hiding colors
+    pub background: anstyle::RgbColor,
+}
+
+impl Theme {
+    pub const fn new(
+        palette: anstyle_lossy::palette::Palette,
+        foreground: anstyle::RgbColor,
+        background: anstyle::RgbColor,
+    ) -> Self {
+        Self {
+            palette,
+            foreground,
+            background,
+        }
+    }
+
+    pub const fn color_to_rgb(&self, color: anstyle::Color) -> anstyle::RgbColor {
+        anstyle_lossy::color_to_rgb(color, self.palette)
+    }
+
+    /// Resolve `style` to the colors and font flags to draw it with
+    ///
+    /// [`anstyle::Effects::INVERT`], [`anstyle::Effects::HIDDEN`], and
+    /// [`anstyle::Effects::DIMMED`] are applied to the colors.
+    /// [`anstyle::Effects::BLINK`] is dropped.
+    pub fn resolve(&self, style: anstyle::Style) -> RichStyle {
+        let effects = style.get_effects();
+
+        let mut foreground = style
+            .get_fg_color()
+            .map(|color| self.color_to_rgb(color))
+            .unwrap_or(self.foreground);
+        let mut background = style.get_bg_color().map(|color| self.color_to_rgb(color));
+        if effects.contains(anstyle::Effects::INVERT) {
+            let inverted = background.unwrap_or(self.background);
+            background = Some(foreground);
+            foreground = inverted;
+        }
+        let canvas = background.unwrap_or(self.background);
+        if effects.contains(anstyle::Effects::DIMMED) {
+            foreground = blend(foreground, canvas);
+        }
+        if effects.contains(anstyle::Effects::HIDDEN) {
+            foreground = canvas;
+        }
+
+        let underlines = anstyle::Effects::UNDERLINE
+            | anstyle::Effects::DOUBLE_UNDERLINE
+            | anstyle::Effects::CURLY_UNDERLINE
+            | anstyle::Effects::DOTTED_UNDERLINE
+            | anstyle::Effects::DASHED_UNDERLINE;
+        let underline = if underlines.remove(effects) != underlines {
+            let color = style
+                .get_underline_color()
+                .map(|color| self.color_to_rgb(color))
+                .unwrap_or(foreground);
+            Some(color)
+        } else {
+            None
+        };
+
+        RichStyle {
+            foreground,
+            background,
+            underline,
+            bold: effects.contains(anstyle::Effects::BOLD),
+            italic: effects.contains(anstyle::Effects::ITALIC),
+            strikethrough: effects.contains(anstyle::Effects::STRIKETHROUGH),
+        }
+    }
+}
+
+/// VGA colors with light grey text on a black background
+impl Default for Theme {
+    fn default() -> Self {
+        let palette = anstyle_lossy::palette::VGA;
+        Self::new(
+            palette,
+            anstyle_lossy::ansi_to_rgb(anstyle::AnsiColor::White, palette),
+            anstyle_lossy::ansi_to_rgb(anstyle::AnsiColor::Black, palette),
+        )
+    }
+}
+
+fn blend(color: anstyle::RgbColor, canvas: anstyle::RgbColor) -> anstyle::RgbColor {
+    let mix = |c: u8, b: u8| ((u16::from(c) + u16::from(b)) / 2) as u8;
+    anstyle::RgbColor(
+        mix(color.r(), canvas.r()),
+        mix(color.g(), canvas.g()),
+        mix(color.b(), canvas.b()),
+    )
+}
+
+/// Colors and font flags to draw a [`RichSpan`] with
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct RichStyle {
+    pub foreground: anstyle::RgbColor,
+    /// `None` when the widget's background shows through
+    pub background: Option<anstyle::RgbColor>,
+    /// Color of the underline, if underlined
+    pub underline: Option<anstyle::RgbColor>,
+    pub bold: bool,
+    pub italic: bool,
+    pub strikethrough: bool,
+}
+
+/// Text drawn with one [`RichStyle`]
+#[derive(Clone, PartialEq, Eq, Debug, Hash)]
+pub struct RichSpan {
+    pub style: RichStyle,
+    pub text: String,
+}
+
+/// Parse ANSI-styled output into [`RichSpan`]s
+///
+/// SGR escape codes are resolved against `theme` and other escape codes are dropped.  Newlines
+/// are kept in the span text.
+pub fn parse(bytes: &[u8], theme: &Theme) -> Vec<RichSpan> {
+    from_spans(parse_styled(bytes), theme)
+}
+
+/// Parse ANSI-styled output into [`anstyle_vte::StyledSpan`]s, without resolving their styles
+///
+/// SGR escape codes are kept as [`anstyle::Style`]s and other escape codes are dropped.
+/// Newlines are kept in the span text.
+pub fn parse_styled(bytes: &[u8]) -> Vec<anstyle_vte::StyledSpan> {
+    let mut parser = vte::Parser::new();
+    let mut spans = anstyle_vte::SpanCollector::new();
+    for byte in bytes {
+        parser.advance(&mut spans, *byte);
+    }
+    spans.into_spans()
+}
+
+/// Resolve [`anstyle_vte::StyledSpan`]s into [`RichSpan`]s
+///
+/// Neighboring spans that resolve to the same [`RichStyle`] are merged.
+pub fn from_spans(
+    spans: impl IntoIterator<Item = anstyle_vte::StyledSpan>,
+    theme: &Theme,
+) -> Vec<RichSpan> {
+    let mut resolved: Vec<RichSpan> = Vec::new();
+    for span in spans {
+        let style = theme.resolve(span.style);
+        match resolved.last_mut() {
+            Some(last) if last.style == style => last.text.push_str(&span.text),
+            _ => resolved.push(RichSpan {
+                style,
+                text: span.text,
+            }),
+        }
+    }
+    resolved
+}
+
+/// Format `color` as `#rrggbb`
+fn hex(color: anstyle::RgbColor) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+/// Append `text` to `markup`, escaped for HTML, Qt rich text, and Pango markup
+pub(crate) fn escape(markup: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => markup.push_str("&amp;"),
+            '<' => markup.push_str("&lt;"),
+            '>' => markup.push_str("&gt;"),
+            '"' => markup.push_str("&quot;"),
+            '\'' => markup.push_str("&#39;"),
+            c => markup.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const RED: anstyle::RgbColor = anstyle::RgbColor(170, 0, 0);
+    const BLUE: anstyle::RgbColor = anstyle::RgbColor(0, 0, 170);
+
+    fn plain(theme: &Theme) -> RichStyle {
+        RichStyle {
+            foreground: theme.foreground,
+            background: None,
+            underline: None,
+            bold: false,
+            italic: false,
+            strikethrough: false,
+        }
+    }
+
+    #[test]
+    fn resolve_default() {
+        let theme = Theme::default();
+        assert_eq!(theme.resolve(anstyle::Style::new()), plain(&theme));
+    }
+
+    #[test]
+    fn resolve_colors() {
+        let theme = Theme::default();
+        let style = anstyle::AnsiColor::Red.on(anstyle::AnsiColor::Blue);
+        assert_eq!(
+            theme.resolve(style),
+            RichStyle {
+                foreground: RED,
+                background: Some(BLUE),
+                ..plain(&theme)
+            }
+        );
+        let style = anstyle::Ansi256Color(16).on(anstyle::RgbColor(1, 2, 3));
+        assert_eq!(
+            theme.resolve(style),
+            RichStyle {
+                foreground: anstyle::RgbColor(0, 0, 0),
+                background: Some(anstyle::RgbColor(1, 2, 3)),
+                ..plain(&theme)
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_invert() {
+        let theme = Theme::default();
+        assert_eq!(
+            theme.resolve(anstyle::Style::new().invert()),
+            RichStyle {
+                foreground: theme.background,
+                background: Some(theme.foreground),
+                ..plain(&theme)
+            }
+        );
+        let style = anstyle::AnsiColor::Red
+            .on(anstyle::AnsiColor::Blue)
+            .invert();
+        assert_eq!(
+            theme.resolve(style),
+            RichStyle {
+                foreground: BLUE,
+                background: Some(RED),
+                ..plain(&theme)
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_dimmed() {
+        let theme = Theme::default();
+        let style = anstyle::AnsiColor::Red
+            .on(anstyle::AnsiColor::Blue)
+            .dimmed();
+        assert_eq!(
+            theme.resolve(style),
+            RichStyle {
+                foreground: anstyle::RgbColor(85, 0, 85),
+                background: Some(BLUE),
+                ..plain(&theme)
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_hidden() {
+        let theme = Theme::default();
+        let style = anstyle::AnsiColor::Red
+            .on(anstyle::AnsiColor::Blue)
+            .hidden();
+        assert_eq!(
+            theme.resolve(style),
+            RichStyle {
+                foreground: BLUE,
+                background: Some(BLUE),
+                ..plain(&theme)
+            }
+        );
+        assert_eq!(
+            theme.resolve(anstyle::Style::new().hidden()).foreground,
+            theme.background
+        );
+    }
+
+    #[test]
+    fn resolve_font_flags() {
+        let theme = Theme::default();
+        let style = anstyle::Style::new()
+            .bold()
+            .italic()
+            .strikethrough()
+            .blink()
+            .effects(anstyle::Effects::CURLY_UNDERLINE)
+            | anstyle::Effects::BOLD
+            | anstyle::Effects::ITALIC
+            | anstyle::Effects::STRIKETHROUGH;
+        assert_eq!(
+            theme.resolve(style),
+            RichStyle {
+                underline: Some(theme.foreground),
+                bold: true,
+                italic: true,
+                strikethrough: true,
+                ..plain(&theme)
+            }
+        );
+        let style = anstyle::Style::new()
+            .underline()
+            .underline_color(Some(anstyle::AnsiColor::Red.into()));
+        assert_eq!(theme.resolve(style).underline, Some(RED));
+    }
+
+    #[test]
+    fn parse_merges_resolved() {
+        let theme = Theme::default();
+        assert_eq!(
+            parse(b"a\x1b[39mb\n\x1b[31mc\x1b[5md", &theme),
+            [
+                RichSpan {
+                    style: plain(&theme),
+                    text: "ab\n".to_owned(),
+                },
+                RichSpan {
+                    style: RichStyle {
+                        foreground: RED,
+                        ..plain(&theme)
+                    },
+                    text: "cd".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn escaped() {
+        let mut markup = String::new();
+        escape(&mut markup, "<b>\"Tom & Jerry's\"</b>");
+        assert_eq!(
+            markup,
+            "&lt;b&gt;&quot;Tom &amp; Jerry&#39;s&quot;&lt;/b&gt;"
+        );
+    }
+}
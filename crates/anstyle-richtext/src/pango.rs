@@ -0,0 +1,85 @@
+use std::fmt::Write as _;
+
+/// Render `spans` as [Pango markup](https://docs.gtk.org/Pango/pango_markup.html)
+///
+/// This is for showing terminal output in GTK labels and text views, or in desktop
+/// notifications, which accept the same markup.  Each span becomes a `<span>` with its colors
+/// and font flags as attributes and its text escaped.
+///
+/// # Examples
+///
+/// ```rust
+/// let theme = anstyle_richtext::Theme::default();
+/// let spans = anstyle_richtext::parse(b"\x1b[1;31merror\x1b[0m: a < b", &theme);
+/// assert_eq!(
+///     anstyle_richtext::to_pango_markup(&spans),
+///     "<span foreground=\"#aa0000\" weight=\"bold\">error</span>\
+///      <span foreground=\"#aaaaaa\">: a &lt; b</span>"
+/// );
+/// ```
+pub fn to_pango_markup(spans: &[crate::RichSpan]) -> String {
+    let mut markup = String::new();
+    for span in spans {
+        if span.text.is_empty() {
+            continue;
+        }
+        write_attributes(&mut markup, &span.style);
+        crate::escape(&mut markup, &span.text);
+        markup.push_str("</span>");
+    }
+    markup
+}
+
+fn write_attributes(markup: &mut String, style: &crate::RichStyle) {
+    let _ = write!(
+        markup,
+        "<span foreground=\"{}\"",
+        crate::hex(style.foreground)
+    );
+    if let Some(background) = style.background {
+        let _ = write!(markup, " background=\"{}\"", crate::hex(background));
+    }
+    if style.bold {
+        markup.push_str(" weight=\"bold\"");
+    }
+    if style.italic {
+        markup.push_str(" style=\"italic\"");
+    }
+    if let Some(underline) = style.underline {
+        let _ = write!(
+            markup,
+            " underline=\"single\" underline_color=\"{}\"",
+            crate::hex(underline)
+        );
+    }
+    if style.strikethrough {
+        markup.push_str(" strikethrough=\"true\"");
+    }
+    markup.push('>');
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn attributes() {
+        let theme = crate::Theme::default();
+        let style = anstyle::AnsiColor::Red
+            .on(anstyle::AnsiColor::Blue)
+            .bold()
+            .italic()
+            .underline()
+            .strikethrough();
+        let spans = [crate::RichSpan {
+            style: theme.resolve(style),
+            text: "x".to_owned(),
+        }];
+        assert_eq!(
+            to_pango_markup(&spans),
+            "<span foreground=\"#aa0000\" background=\"#0000aa\" weight=\"bold\" \
+             style=\"italic\" underline=\"single\" underline_color=\"#aa0000\" \
+             strikethrough=\"true\">x</span>"
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt::Write as _;
+
+/// Render `spans` as HTML for Qt's rich text widgets, like `QLabel` and `QTextEdit`
+///
+/// Only the [subset of HTML and CSS](https://doc.qt.io/qt-6/richtext-html-subset.html) Qt
+/// supports is used.  The spans are wrapped in `<pre>` so whitespace and newlines are kept, like
+/// in a terminal.  Qt can't color underlines separately, so they take the text's color.
+///
+/// # Examples
+///
+/// ```rust
+/// let theme = anstyle_richtext::Theme::default();
+/// let spans = anstyle_richtext::parse(b"\x1b[1;31merror\x1b[0m: a < b", &theme);
+/// assert_eq!(
+///     anstyle_richtext::to_qt_rich_text(&spans),
+///     "<pre><span style=\"color:#aa0000; font-weight:bold\">error</span>\
+///      <span style=\"color:#aaaaaa\">: a &lt; b</span></pre>"
+/// );
+/// ```
+pub fn to_qt_rich_text(spans: &[crate::RichSpan]) -> String {
+    let mut html = String::from("<pre>");
+    for span in spans {
+        if span.text.is_empty() {
+            continue;
+        }
+        write_style(&mut html, &span.style);
+        crate::escape(&mut html, &span.text);
+        html.push_str("</span>");
+    }
+    html.push_str("</pre>");
+    html
+}
+
+fn write_style(html: &mut String, style: &crate::RichStyle) {
+    let _ = write!(html, "<span style=\"color:{}", crate::hex(style.foreground));
+    if let Some(background) = style.background {
+        let _ = write!(html, "; background-color:{}", crate::hex(background));
+    }
+    if style.bold {
+        html.push_str("; font-weight:bold");
+    }
+    if style.italic {
+        html.push_str("; font-style:italic");
+    }
+    match (style.underline.is_some(), style.strikethrough) {
+        (true, true) => html.push_str("; text-decoration:underline line-through"),
+        (true, false) => html.push_str("; text-decoration:underline"),
+        (false, true) => html.push_str("; text-decoration:line-through"),
+        (false, false) => {}
+    }
+    html.push_str("\">");
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn style() {
+        let theme = crate::Theme::default();
+        let style = anstyle::AnsiColor::Red
+            .on(anstyle::AnsiColor::Blue)
+            .bold()
+            .italic()
+            .underline()
+            .strikethrough();
+        let spans = [crate::RichSpan {
+            style: theme.resolve(style),
+            text: "x".to_owned(),
+        }];
+        assert_eq!(
+            to_qt_rich_text(&spans),
+            "<pre><span style=\"color:#aa0000; background-color:#0000aa; font-weight:bold; \
+             font-style:italic; text-decoration:underline line-through\">x</span></pre>"
+        );
+    }
+}
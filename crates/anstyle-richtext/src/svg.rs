@@ -0,0 +1,296 @@
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crate::html::Part;
+use crate::html::Target;
+
+/// Render `spans` as an SVG image of a terminal, with colors from `theme`
+///
+/// Spans get the same classes as with [`to_html`][crate::to_html], styled by an embedded
+/// [`stylesheet`][crate::stylesheet] that only has the rules for the classes used.  Text is laid
+/// out in a grid of monospace cells, one per `char`, with tabs expanded to every 8 columns.
+///
+/// # Examples
+///
+/// ```rust
+/// let theme = anstyle_richtext::Theme::default();
+/// let spans = anstyle_richtext::parse_styled(b"\x1b[1;31merror\x1b[0m: oops\n");
+/// let svg = anstyle_richtext::to_svg(&spans, &theme);
+/// assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+/// assert!(svg.contains("<tspan x=\"10\" y=\"25\" class=\"ansi-fg-red ansi-bold\">error</tspan>"));
+/// assert!(svg.contains(".ansi-fg-red { fill: #aa0000; }"));
+/// ```
+pub fn to_svg(spans: &[anstyle_vte::StyledSpan], theme: &crate::Theme) -> String {
+    let mut layout = Layout::default();
+    for span in spans {
+        layout.push(span, None);
+    }
+    layout.render(theme)
+}
+
+/// Render a recording of styled output as an animated SVG image of a terminal, with colors from
+/// `theme`
+///
+/// `recording` is the output in chunks, each with the time since the recording started.  Each
+/// chunk's text appears at its time, like it is being typed out, and stays after the last one.
+/// This uses SMIL animations so the image is self-contained, like for showing a demo in a README.
+///
+/// Only appended text is animated: cursor movement and clearing the screen are ignored.  Otherwise
+/// this is like [`to_svg`].
+///
+/// # Examples
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// let theme = anstyle_richtext::Theme::default();
+/// let recording = [
+///     (Duration::ZERO, &b"$ cargo build\n"[..]),
+///     (Duration::from_millis(1500), &b"\x1b[1;32mFinished\x1b[0m\n"[..]),
+/// ];
+/// let svg = anstyle_richtext::to_animated_svg(recording, &theme);
+/// assert!(svg.contains("<set attributeName=\"visibility\" to=\"visible\" begin=\"1.500s\""));
+/// ```
+pub fn to_animated_svg<B>(
+    recording: impl IntoIterator<Item = (Duration, B)>,
+    theme: &crate::Theme,
+) -> String
+where
+    B: AsRef<[u8]>,
+{
+    let mut parser = vte::Parser::new();
+    let mut collector = anstyle_vte::SpanCollector::new();
+    let mut layout = Layout::default();
+    for (time, chunk) in recording {
+        for byte in chunk.as_ref() {
+            parser.advance(&mut collector, *byte);
+        }
+        let appear = (time > Duration::ZERO).then_some(time);
+        for span in collector.take_spans() {
+            layout.push(&span, appear);
+        }
+    }
+    layout.render(theme)
+}
+
+/// Font size, in pixels
+const FONT_SIZE: usize = 15;
+/// Width of a monospace cell, in tenths of a pixel, as monospace fonts are about 0.6em wide
+const CELL_WIDTH: usize = FONT_SIZE * 6;
+const LINE_HEIGHT: usize = 20;
+/// Offset of the text's baseline from the top of its line
+const BASELINE: usize = 15;
+const PADDING: usize = 10;
+const TAB_WIDTH: usize = 8;
+
+/// Text positioned in the grid of cells
+struct Piece {
+    row: usize,
+    column: usize,
+    width: usize,
+    style: anstyle::Style,
+    text: String,
+    /// When the text appears, if not from the start
+    appear: Option<Duration>,
+}
+
+#[derive(Default)]
+struct Layout {
+    pieces: Vec<Piece>,
+    row: usize,
+    column: usize,
+    columns: usize,
+}
+
+impl Layout {
+    fn push(&mut self, span: &anstyle_vte::StyledSpan, appear: Option<Duration>) {
+        let mut piece = String::new();
+        let mut start = self.column;
+        for c in span.text.chars() {
+            match c {
+                '\n' => {
+                    self.push_piece(&mut piece, start, span.style, appear);
+                    self.row += 1;
+                    self.column = 0;
+                    start = 0;
+                }
+                '\t' => {
+                    let spaces = TAB_WIDTH - self.column % TAB_WIDTH;
+                    piece.extend(std::iter::repeat(' ').take(spaces));
+                    self.column += spaces;
+                }
+                c => {
+                    piece.push(c);
+                    self.column += 1;
+                }
+            }
+        }
+        self.push_piece(&mut piece, start, span.style, appear);
+    }
+
+    fn push_piece(
+        &mut self,
+        text: &mut String,
+        column: usize,
+        style: anstyle::Style,
+        appear: Option<Duration>,
+    ) {
+        if text.is_empty() {
+            return;
+        }
+        self.columns = self.columns.max(self.column);
+        self.pieces.push(Piece {
+            row: self.row,
+            column,
+            width: self.column - column,
+            style,
+            text: std::mem::take(text),
+            appear,
+        });
+    }
+
+    fn render(&self, theme: &crate::Theme) -> String {
+        // A trailing newline doesn't start another line
+        let rows = match self.pieces.last() {
+            Some(last) if self.column == 0 => last.row + 1,
+            _ => self.row + 1,
+        };
+        let width = tenths(2 * PADDING * 10 + self.columns * CELL_WIDTH);
+        let height = 2 * PADDING + rows * LINE_HEIGHT;
+
+        let mut backgrounds = String::new();
+        let mut text = String::new();
+        for piece in &self.pieces {
+            let x = tenths(PADDING * 10 + piece.column * CELL_WIDTH);
+            let y = PADDING + piece.row * LINE_HEIGHT;
+            if has_background(piece.style) {
+                let _ = write!(
+                    backgrounds,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{}\" height=\"{LINE_HEIGHT}\"",
+                    tenths(piece.width * CELL_WIDTH)
+                );
+                crate::html::write_attributes(
+                    &mut backgrounds,
+                    piece.style,
+                    Target::Svg,
+                    Part::Background,
+                );
+                write_close(&mut backgrounds, "rect", piece.appear);
+            }
+
+            let _ = write!(text, "<tspan x=\"{x}\" y=\"{}\"", y + BASELINE);
+            crate::html::write_attributes(&mut text, piece.style, Target::Svg, Part::Text);
+            if let Some(appear) = piece.appear {
+                text.push_str(" visibility=\"hidden\">");
+                crate::escape(&mut text, &piece.text);
+                write_set(&mut text, appear);
+                text.push_str("</tspan>");
+            } else {
+                text.push('>');
+                crate::escape(&mut text, &piece.text);
+                text.push_str("</tspan>");
+            }
+        }
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" \
+             font-size=\"{FONT_SIZE}\">"
+        );
+        svg.push_str("<style>\n");
+        svg.push_str(&used_rules(
+            &crate::html::themed_rules(theme, Target::Svg),
+            &backgrounds,
+            &text,
+        ));
+        svg.push_str("</style>\n");
+        svg.push_str("<rect class=\"ansi-background\" width=\"100%\" height=\"100%\"/>\n");
+        svg.push_str(&backgrounds);
+        // Without whitespace between the `<tspan>`s as it would be shown
+        svg.push_str("<text class=\"ansi\" xml:space=\"preserve\">");
+        svg.push_str(&text);
+        svg.push_str("</text>\n");
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Whether `style` needs a `<rect>` drawn behind its text
+fn has_background(style: anstyle::Style) -> bool {
+    style.get_effects().contains(anstyle::Effects::INVERT) || style.get_bg_color().is_some()
+}
+
+/// End an element after its attributes, hiding it until `appear`
+fn write_close(svg: &mut String, element: &str, appear: Option<Duration>) {
+    match appear {
+        Some(appear) => {
+            svg.push_str(" visibility=\"hidden\">");
+            write_set(svg, appear);
+            let _ = writeln!(svg, "</{element}>");
+        }
+        None => svg.push_str("/>\n"),
+    }
+}
+
+fn write_set(svg: &mut String, appear: Duration) {
+    let _ = write!(
+        svg,
+        "<set attributeName=\"visibility\" to=\"visible\" begin=\"{}.{:03}s\" fill=\"freeze\"/>",
+        appear.as_secs(),
+        appear.subsec_millis()
+    );
+}
+
+/// Format a length in tenths of a pixel
+fn tenths(length: usize) -> String {
+    match length % 10 {
+        0 => (length / 10).to_string(),
+        fraction => format!("{}.{fraction}", length / 10),
+    }
+}
+
+/// Keep only the rules of `css` for classes used in `markup`, besides the ones always used
+fn used_rules(css: &str, backgrounds: &str, text: &str) -> String {
+    let mut used = BTreeSet::new();
+    for markup in [backgrounds, text] {
+        for attribute in markup.split(" class=\"").skip(1) {
+            let classes = attribute.split('"').next().unwrap_or_default();
+            used.extend(classes.split(' '));
+        }
+    }
+
+    let mut rules = String::new();
+    for rule in css.lines() {
+        let (selectors, _) = rule.split_once(" {").unwrap_or((rule, ""));
+        let always = selectors == ".ansi" || selectors == ".ansi-background";
+        if always || subjects(selectors).any(|class| used.contains(class)) {
+            rules.push_str(rule);
+            rules.push('\n');
+        }
+    }
+    rules
+}
+
+/// The class each selector in a list applies to, like `a` for `.a:is(.b, .c)`
+fn subjects(selectors: &str) -> impl Iterator<Item = &str> {
+    let mut depth = 0;
+    selectors
+        .split(move |c| {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+            c == ',' && depth == 0
+        })
+        .filter_map(|selector| {
+            let class = selector.trim().strip_prefix('.')?;
+            let end = class
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+                .unwrap_or(class.len());
+            Some(&class[..end])
+        })
+}
@@ -0,0 +1,27 @@
+use std::time::Duration;
+
+#[test]
+fn test_styles() {
+    let text = "\x1b[1;31merror\x1b[0m: \x1b[4:3;58;5;4mcurly\x1b[0m \x1b[7minverse\x1b[0m\n\
+        \x1b[38;2;255;135;0;44mrgb on blue\x1b[0m\t\x1b[2;42mdim\x1b[0m <&>\n";
+    let spans = anstyle_richtext::parse_styled(text.as_bytes());
+    let svg = anstyle_richtext::to_svg(&spans, &anstyle_richtext::Theme::default());
+    snapbox::assert_eq_path("tests/svgs/styles.svg", svg);
+}
+
+#[test]
+fn test_animated() {
+    let recording = [
+        (Duration::ZERO, &b"$ cargo build\n"[..]),
+        (
+            Duration::from_millis(800),
+            &b"   \x1b[1;32mCompiling\x1b[0m demo\n"[..],
+        ),
+        (
+            Duration::from_millis(2250),
+            &b"    \x1b[1;32mFinished\x1b[0m dev [unoptimized]\n"[..],
+        ),
+    ];
+    let svg = anstyle_richtext::to_animated_svg(recording, &anstyle_richtext::Theme::default());
+    snapbox::assert_eq_path("tests/svgs/animated.svg", svg);
+}
@@ -0,0 +1,133 @@
+//! Read back the output of grotty, like from `man`
+
+use anstyle::{Effects, Style};
+
+/// A section of text, along with its styling
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StyledSpan {
+    pub style: Style,
+    pub text: String,
+}
+
+/// Parse grotty output into styled spans
+///
+/// Supports both of grotty's output modes:
+/// - SGR escape codes (the default)
+/// - Backspace overstriking (`-c`, or `MAN_KEEP_FORMATTING` with some pagers), where `c\bc` is
+///   bold and `_\bc` is underlined
+///
+/// Adjacent text with the same style is merged into one span.
+///
+/// ```rust
+/// let text = "N\x08NA\x08AM\x08ME\x08E\n       _\x08l_\x08s - list";
+///
+/// let spans = anstyle_roff::parse_grotty(text);
+/// assert_eq!(spans[0].text, "NAME");
+/// assert_eq!(spans[0].style, anstyle::Style::new().bold());
+/// assert_eq!(spans[2].text, "ls");
+/// assert_eq!(spans[2].style, anstyle::Style::new().underline());
+/// ```
+pub fn parse_grotty(text: &str) -> Vec<StyledSpan> {
+    let mut spans: Vec<StyledSpan> = Vec::new();
+    for styled in crate::styled_str::styled_stream(text) {
+        let mut chars = styled.text.chars().peekable();
+        while let Some(mut c) = chars.next() {
+            let mut effects = Effects::new();
+            while chars.peek() == Some(&'\x08') {
+                let mut lookahead = chars.clone();
+                lookahead.next();
+                let Some(next) = lookahead.next() else {
+                    break;
+                };
+                chars = lookahead;
+                if next == c {
+                    effects |= Effects::BOLD;
+                } else if c == '_' {
+                    effects |= Effects::UNDERLINE;
+                    c = next;
+                } else if next == '_' {
+                    effects |= Effects::UNDERLINE;
+                } else {
+                    // Overstruck symbols, like `+\bo` for a bullet, are shown as the last one
+                    c = next;
+                }
+            }
+
+            let style = styled.style | effects;
+            match spans.last_mut() {
+                Some(last) if last.style == style => last.text.push(c),
+                _ => spans.push(StyledSpan {
+                    style,
+                    text: c.to_string(),
+                }),
+            }
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[track_caller]
+    fn assert_spans(text: &str, expected: &[(Style, &str)]) {
+        let actual = parse_grotty(text);
+        let actual = actual
+            .iter()
+            .map(|span| (span.style, span.text.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn plain() {
+        assert_spans("hello world", &[(Style::new(), "hello world")]);
+        assert_spans("", &[]);
+    }
+
+    #[test]
+    fn overstrike_bold() {
+        assert_spans(
+            "-\x08-a\x08a plain",
+            &[(Style::new().bold(), "-a"), (Style::new(), " plain")],
+        );
+    }
+
+    #[test]
+    fn overstrike_underline() {
+        assert_spans(
+            "_\x08f_\x08i_\x08l_\x08e",
+            &[(Style::new().underline(), "file")],
+        );
+        assert_spans("f\x08_", &[(Style::new().underline(), "f")]);
+    }
+
+    #[test]
+    fn overstrike_bold_underline() {
+        assert_spans("_\x08x\x08x", &[(Style::new().bold().underline(), "x")]);
+    }
+
+    #[test]
+    fn overstrike_symbol() {
+        assert_spans("+\x08o item", &[(Style::new(), "o item")]);
+    }
+
+    #[test]
+    fn trailing_backspace() {
+        assert_spans("a\x08", &[(Style::new(), "a\x08")]);
+    }
+
+    #[test]
+    fn sgr() {
+        assert_spans(
+            "\x1b[1mNAME\x1b[0m\n       \x1b[4mls\x1b[24m - list",
+            &[
+                (Style::new().bold(), "NAME"),
+                (Style::new(), "\n       "),
+                (Style::new().underline(), "ls"),
+                (Style::new(), " - list"),
+            ],
+        );
+    }
+}
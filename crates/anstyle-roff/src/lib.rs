@@ -1,13 +1,19 @@
-//! Convert from ansi stylings to ROFF Control Lines
+//! Convert from ansi stylings to ROFF Control Lines, and back from grotty output
 //! Currently uses [roff](https://docs.rs/roff/0.2.1/roff/) as the engine for generating
 //! roff output.
 
+mod grotty;
+mod man;
 mod styled_str;
 use anstyle::{Ansi256Color, AnsiColor, Color, RgbColor, Style};
 use anstyle_lossy::palette::Palette;
 use roff::{bold, italic, Roff};
 use styled_str::StyledStr;
 
+pub use grotty::parse_grotty;
+pub use grotty::StyledSpan;
+pub use man::ManStylesheet;
+
 /// Static Strings defining ROFF Control Requests
 mod control_requests {
     /// Control to Create a Color definition
@@ -0,0 +1,186 @@
+//! Restyle man pages
+
+use std::fmt::Write as _;
+
+use anstyle::{AnsiColor, Effects, Style};
+
+use crate::grotty::parse_grotty;
+
+/// Map the emphasis in a rendered man page to a user's styles
+///
+/// Man pages only have bold and underlined (or italic) text, by convention for:
+/// - Section headings, like `NAME`, which are bold and start at the beginning of the line
+/// - Literal text, like commands and options, which are bold
+/// - Placeholders, like arguments, which are underlined
+///
+/// ```rust
+/// let page = "N\x08NA\x08AM\x08ME\x08E\n       l\x08ls\x08s _\x08f_\x08i_\x08l_\x08e\n";
+///
+/// let colorized = anstyle_roff::ManStylesheet::new()
+///     .heading(anstyle::AnsiColor::Yellow.on_default())
+///     .literal(anstyle::AnsiColor::Green.on_default())
+///     .placeholder(anstyle::AnsiColor::Cyan.on_default())
+///     .colorize(page);
+/// assert_eq!(
+///     colorized,
+///     "\x1b[33mNAME\x1b[0m\n       \x1b[32mls\x1b[0m \x1b[36mfile\x1b[0m\n"
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ManStylesheet {
+    heading: Style,
+    literal: Style,
+    placeholder: Style,
+}
+
+impl ManStylesheet {
+    /// Bold yellow headings, bold green literals, and underlined cyan placeholders
+    pub const fn new() -> Self {
+        Self {
+            heading: AnsiColor::Yellow.on_default().bold(),
+            literal: AnsiColor::Green.on_default().bold(),
+            placeholder: AnsiColor::Cyan.on_default().underline(),
+        }
+    }
+
+    /// Style for section headings
+    pub const fn heading(mut self, style: Style) -> Self {
+        self.heading = style;
+        self
+    }
+
+    /// Style for bold text that isn't a heading
+    pub const fn literal(mut self, style: Style) -> Self {
+        self.literal = style;
+        self
+    }
+
+    /// Style for underlined or italic text
+    pub const fn placeholder(mut self, style: Style) -> Self {
+        self.placeholder = style;
+        self
+    }
+
+    /// Restyle grotty output, see [`parse_grotty`]
+    ///
+    /// Text without emphasis keeps its style.
+    pub fn colorize(&self, text: &str) -> String {
+        let spans = parse_grotty(text);
+
+        let mut lines: Vec<Vec<(Style, &str)>> = vec![Vec::new()];
+        for span in &spans {
+            let mut parts = span.text.split('\n');
+            if let Some(first) = parts.next() {
+                lines
+                    .last_mut()
+                    .expect("always at least one line")
+                    .push((span.style, first));
+            }
+            for part in parts {
+                lines.push(vec![(span.style, part)]);
+            }
+        }
+
+        let mut colorized = String::new();
+        for (i, line) in lines.iter().enumerate() {
+            if i != 0 {
+                colorized.push('\n');
+            }
+            let is_heading = is_heading(line);
+            for (style, text) in line {
+                if text.is_empty() {
+                    continue;
+                }
+                let style = if is_heading {
+                    self.heading
+                } else {
+                    self.restyle(*style)
+                };
+                let _ = write!(
+                    &mut colorized,
+                    "{}{}{}",
+                    style.render(),
+                    text,
+                    style.render_reset()
+                );
+            }
+        }
+        colorized
+    }
+
+    fn restyle(&self, style: Style) -> Style {
+        let effects = style.get_effects();
+        if effects.contains(Effects::BOLD) {
+            self.literal
+        } else if effects.contains(Effects::UNDERLINE) || effects.contains(Effects::ITALIC) {
+            self.placeholder
+        } else {
+            style
+        }
+    }
+}
+
+impl Default for ManStylesheet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether the line is bold and unindented
+fn is_heading(line: &[(Style, &str)]) -> bool {
+    let starts_unindented = line
+        .iter()
+        .find(|(_, text)| !text.is_empty())
+        .map(|(_, text)| !text.starts_with(char::is_whitespace))
+        .unwrap_or(false);
+    starts_unindented
+        && line
+            .iter()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .all(|(style, _)| style.get_effects().contains(Effects::BOLD))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain() -> ManStylesheet {
+        ManStylesheet::new()
+            .heading(Style::new().bold())
+            .literal(Style::new().italic())
+            .placeholder(Style::new().dimmed())
+    }
+
+    #[test]
+    fn heading() {
+        assert_eq!(
+            plain().colorize("\x1b[1mSYNOPSIS\x1b[0m\n"),
+            "\x1b[1mSYNOPSIS\x1b[0m\n"
+        );
+        assert_eq!(
+            plain().colorize("\x1b[1mEXIT STATUS\x1b[0m"),
+            "\x1b[1mEXIT STATUS\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn indented_bold_is_literal() {
+        assert_eq!(
+            plain().colorize("       \x1b[1m-a\x1b[0m, \x1b[1m--all\x1b[0m"),
+            "       \x1b[3m-a\x1b[0m, \x1b[3m--all\x1b[0m"
+        );
+    }
+
+    #[test]
+    fn partially_bold_line_is_not_heading() {
+        assert_eq!(
+            plain().colorize("\x1b[1mls\x1b[0m [\x1b[4mOPTION\x1b[0m]"),
+            "\x1b[3mls\x1b[0m [\x1b[2mOPTION\x1b[0m]"
+        );
+    }
+
+    #[test]
+    fn unstyled() {
+        assert_eq!(plain().colorize("plain\n\ntext"), "plain\n\ntext");
+    }
+}
@@ -0,0 +1,105 @@
+//! bash prompts
+//!
+//! Readline counts every byte of a prompt as visible unless it is marked otherwise, which breaks
+//! line-wrapping and cursor movement, so escape codes are wrapped in [`Markers`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use anstyle_shell::bash::Markers;
+//!
+//! let style = anstyle::AnsiColor::Green.on_default().bold();
+//! assert_eq!(
+//!     anstyle_shell::bash::paint(style, r"\w", Markers::Prompt),
+//!     "\\[\x1b[1m\x1b[32m\\]\\w\\[\x1b[0m\\]"
+//! );
+//! ```
+
+/// How to mark escape codes as zero-width
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Markers {
+    /// `\[` and `\]`, for `PS1` and the other prompt variables
+    Prompt,
+    /// `\001` and `\002`, for readline directly, like with the output of a `PROMPT_COMMAND`
+    Readline,
+}
+
+impl Markers {
+    fn start(self) -> &'static str {
+        match self {
+            Self::Prompt => "\\[",
+            Self::Readline => "\x01",
+        }
+    }
+
+    fn end(self) -> &'static str {
+        match self {
+            Self::Prompt => "\\]",
+            Self::Readline => "\x02",
+        }
+    }
+}
+
+/// Marked escape codes to start `style`
+pub fn render(style: anstyle::Style, markers: Markers) -> String {
+    wrap(&style.render().to_string(), markers)
+}
+
+/// Marked escape codes to end `style`
+pub fn render_reset(style: anstyle::Style, markers: Markers) -> String {
+    wrap(&style.render_reset().to_string(), markers)
+}
+
+/// Style `text` for a prompt
+///
+/// `text` is included as-is, so prompt escapes, like `\w`, still work.
+pub fn paint(style: anstyle::Style, text: &str, markers: Markers) -> String {
+    format!(
+        "{}{}{}",
+        render(style, markers),
+        text,
+        render_reset(style, markers)
+    )
+}
+
+fn wrap(codes: &str, markers: Markers) -> String {
+    if codes.is_empty() {
+        String::new()
+    } else {
+        format!("{}{}{}", markers.start(), codes, markers.end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anstyle::AnsiColor::*;
+    use anstyle::*;
+
+    #[test]
+    fn plain() {
+        assert_eq!(paint(Style::new(), r"\u@\h", Markers::Prompt), r"\u@\h");
+        assert_eq!(paint(Style::new(), "x", Markers::Readline), "x");
+    }
+
+    #[test]
+    fn prompt() {
+        assert_eq!(
+            paint(Blue.on_default(), r"\w", Markers::Prompt),
+            "\\[\x1b[34m\\]\\w\\[\x1b[0m\\]"
+        );
+    }
+
+    #[test]
+    fn readline() {
+        assert_eq!(
+            paint(
+                Ansi256Color(208).on_default().italic(),
+                "main",
+                Markers::Readline
+            ),
+            "\x01\x1b[3m\x1b[38;5;208m\x02main\x01\x1b[0m\x02"
+        );
+    }
+}
@@ -0,0 +1,352 @@
+//! fish `set_color` arguments
+//!
+//! fish styles, like `fish_color_command`, are lists of `set_color` arguments.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let style = anstyle_shell::fish::parse("ff8700 --background=brblack --bold").unwrap();
+//! assert_eq!(
+//!     style,
+//!     anstyle::RgbColor(0xff, 0x87, 0x00).on(anstyle::AnsiColor::BrightBlack).bold()
+//! );
+//!
+//! assert_eq!(
+//!     anstyle_shell::fish::render(style),
+//!     ["ff8700", "--background=brblack", "--bold"]
+//! );
+//! ```
+
+use anstyle::{AnsiColor, Effects};
+
+/// Parse `set_color` arguments, like the value of `fish_color_command`, into an `anstyle::Style`
+///
+/// When several colors are given, fish picks the one best suited to the terminal, so here the
+/// first one is used.
+pub fn parse(args: &str) -> Result<anstyle::Style, Error> {
+    let error =
+        |kind: fn(String, String) -> Error, word: &str| kind(args.to_owned(), word.to_owned());
+
+    let mut fg = None;
+    let mut bg = None;
+    let mut effects = Effects::new();
+    let mut words = args.split_whitespace();
+    while let Some(word) = words.next() {
+        if word == "--" {
+            continue;
+        } else if let Some(long) = word.strip_prefix("--") {
+            let (name, value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value)),
+                None => (long, None),
+            };
+            match name {
+                "background" => {
+                    let value = value
+                        .or_else(|| words.next())
+                        .ok_or_else(|| error(Error::missing_value, word))?;
+                    let color =
+                        parse_color(value).ok_or_else(|| error(Error::invalid_color, value))?;
+                    bg.get_or_insert(color);
+                }
+                "underline" => {
+                    let effect = match value {
+                        None | Some("single") => Effects::UNDERLINE,
+                        Some("double") => Effects::DOUBLE_UNDERLINE,
+                        Some("curly") => Effects::CURLY_UNDERLINE,
+                        Some("dotted") => Effects::DOTTED_UNDERLINE,
+                        Some("dashed") => Effects::DASHED_UNDERLINE,
+                        Some(_) => return Err(error(Error::unknown_option, word)),
+                    };
+                    effects |= effect;
+                }
+                _ => {
+                    let effect = EFFECTS
+                        .iter()
+                        .find(|(_, short, long)| *long == name && value.is_none() && *short != 'u')
+                        .map(|(effect, _, _)| *effect)
+                        .ok_or_else(|| error(Error::unknown_option, word))?;
+                    effects |= effect;
+                }
+            }
+        } else if let Some(short) = word.strip_prefix('-').filter(|s| !s.is_empty()) {
+            for (i, flag) in short.char_indices() {
+                if flag == 'b' {
+                    let rest = &short[i + 1..];
+                    let value = if rest.is_empty() {
+                        words
+                            .next()
+                            .ok_or_else(|| error(Error::missing_value, word))?
+                    } else {
+                        rest
+                    };
+                    let color =
+                        parse_color(value).ok_or_else(|| error(Error::invalid_color, value))?;
+                    bg.get_or_insert(color);
+                    break;
+                }
+                let effect = EFFECTS
+                    .iter()
+                    .find(|(_, short, _)| *short == flag)
+                    .map(|(effect, _, _)| *effect)
+                    .ok_or_else(|| error(Error::unknown_option, word))?;
+                effects |= effect;
+            }
+        } else {
+            let color = parse_color(word).ok_or_else(|| error(Error::invalid_color, word))?;
+            fg.get_or_insert(color);
+        }
+    }
+    Ok(anstyle::Style::new()
+        .fg_color(fg.flatten())
+        .bg_color(bg.flatten())
+        .effects(effects))
+}
+
+/// Render an `anstyle::Style` as `set_color` arguments
+///
+/// 256-colors beyond the 16 ANSI colors are written as RGB, as fish has no names for them.
+/// Effects fish doesn't support, like [`Effects::BLINK`] and [`Effects::STRIKETHROUGH`], and
+/// underline colors are skipped.  Extended underlines are written as `--underline`.
+pub fn render(style: anstyle::Style) -> Vec<String> {
+    let mut args = Vec::new();
+    match style.get_fg_color() {
+        Some(color) => args.push(render_color(color)),
+        None if style.get_bg_color().is_none() => args.push("normal".to_owned()),
+        None => {}
+    }
+    if let Some(color) = style.get_bg_color() {
+        args.push(format!("--background={}", render_color(color)));
+    }
+    let effects = style.get_effects();
+    for (effect, _, name) in EFFECTS {
+        if effects.contains(effect) {
+            args.push(format!("--{name}"));
+        }
+    }
+    let underlines = Effects::DOUBLE_UNDERLINE
+        | Effects::CURLY_UNDERLINE
+        | Effects::DOTTED_UNDERLINE
+        | Effects::DASHED_UNDERLINE;
+    if !effects.contains(Effects::UNDERLINE) && underlines.remove(effects) != underlines {
+        args.push("--underline".to_owned());
+    }
+    args
+}
+
+/// Parse a color, where `Some(None)` is `normal`
+fn parse_color(s: &str) -> Option<Option<anstyle::Color>> {
+    let lower = s.to_ascii_lowercase();
+    if lower == "normal" {
+        return Some(None);
+    }
+    if let Some((_, color)) = NAMES.iter().find(|(name, _)| *name == lower) {
+        return Some(Some((*color).into()));
+    }
+    let hex = lower.strip_prefix('#').unwrap_or(&lower);
+    if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    let digit = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok();
+    let rgb = match hex.len() {
+        3 => anstyle::RgbColor(digit(0)? * 0x11, digit(1)? * 0x11, digit(2)? * 0x11),
+        6 => anstyle::RgbColor(
+            u8::from_str_radix(&hex[0..2], 16).ok()?,
+            u8::from_str_radix(&hex[2..4], 16).ok()?,
+            u8::from_str_radix(&hex[4..6], 16).ok()?,
+        ),
+        _ => return None,
+    };
+    Some(Some(rgb.into()))
+}
+
+fn render_color(color: anstyle::Color) -> String {
+    let ansi = match color {
+        anstyle::Color::Ansi(color) => Some(color),
+        anstyle::Color::Ansi256(color) => color.into_ansi(),
+        anstyle::Color::Rgb(_) => None,
+    };
+    if let Some(ansi) = ansi {
+        let (name, _) = NAMES
+            .iter()
+            .find(|(_, color)| *color == ansi)
+            .expect("all ANSI colors are named");
+        return (*name).to_owned();
+    }
+    let rgb = anstyle_lossy::color_to_rgb(color, anstyle_lossy::palette::VGA);
+    format!("{:02x}{:02x}{:02x}", rgb.r(), rgb.g(), rgb.b())
+}
+
+/// Effects with their short and long `set_color` options, in the order they are rendered
+const EFFECTS: [(Effects, char, &str); 5] = [
+    (Effects::BOLD, 'o', "bold"),
+    (Effects::DIMMED, 'd', "dim"),
+    (Effects::ITALIC, 'i', "italics"),
+    (Effects::INVERT, 'r', "reverse"),
+    (Effects::UNDERLINE, 'u', "underline"),
+];
+
+/// fish's color names, with `grey` and `brgrey` as aliases
+const NAMES: [(&str, AnsiColor); 18] = [
+    ("black", AnsiColor::Black),
+    ("red", AnsiColor::Red),
+    ("green", AnsiColor::Green),
+    ("yellow", AnsiColor::Yellow),
+    ("blue", AnsiColor::Blue),
+    ("magenta", AnsiColor::Magenta),
+    ("cyan", AnsiColor::Cyan),
+    ("white", AnsiColor::White),
+    ("brblack", AnsiColor::BrightBlack),
+    ("brred", AnsiColor::BrightRed),
+    ("brgreen", AnsiColor::BrightGreen),
+    ("bryellow", AnsiColor::BrightYellow),
+    ("brblue", AnsiColor::BrightBlue),
+    ("brmagenta", AnsiColor::BrightMagenta),
+    ("brcyan", AnsiColor::BrightCyan),
+    ("brwhite", AnsiColor::BrightWhite),
+    ("grey", AnsiColor::White),
+    ("brgrey", AnsiColor::BrightBlack),
+];
+
+/// Type for errors returned by the parser.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A color couldn't be parsed.
+    InvalidColor { args: String, word: String },
+    /// An unknown option appeared.
+    UnknownOption { args: String, word: String },
+    /// `--background` was missing its color.
+    MissingValue { args: String, word: String },
+}
+
+impl Error {
+    fn invalid_color(args: String, word: String) -> Self {
+        Self::InvalidColor { args, word }
+    }
+
+    fn unknown_option(args: String, word: String) -> Self {
+        Self::UnknownOption { args, word }
+    }
+
+    fn missing_value(args: String, word: String) -> Self {
+        Self::MissingValue { args, word }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidColor { args, word } => {
+                write!(
+                    fmt,
+                    "Error parsing \"{}\": invalid color \"{}\"",
+                    args, word
+                )
+            }
+            Self::UnknownOption { args, word } => {
+                write!(
+                    fmt,
+                    "Error parsing \"{}\": unknown option \"{}\"",
+                    args, word
+                )
+            }
+            Self::MissingValue { args, word } => {
+                write!(
+                    fmt,
+                    "Error parsing \"{}\": \"{}\" needs a color",
+                    args, word
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::Error::*;
+    use super::*;
+
+    use anstyle::AnsiColor::*;
+    use anstyle::*;
+
+    #[test]
+    fn test_parse() {
+        macro_rules! test {
+            ($s:expr => $style:expr) => {
+                assert_eq!(parse($s).unwrap(), $style);
+            };
+        }
+
+        test!("" => Style::new());
+        test!("normal" => Style::new());
+        test!("red" => Red.on_default());
+        test!("BrRed" => BrightRed.on_default());
+        test!("brgrey" => BrightBlack.on_default());
+        test!("555 brblack" => RgbColor(0x55, 0x55, 0x55).on_default());
+        test!("#ff8700" => RgbColor(0xff, 0x87, 0x00).on_default());
+        test!("red --background=blue" => Red.on(Blue));
+        test!("red --background blue" => Red.on(Blue));
+        test!("red -b blue" => Red.on(Blue));
+        test!("red -bblue" => Red.on(Blue));
+        test!("-b blue" => Style::new().bg_color(Some(Blue.into())));
+        test!("red -o -i" => Red.on_default().bold().italic());
+        test!("-odiru" => Style::new().bold().dimmed().italic().invert().underline());
+        test!("-ob blue" => Style::new().bold().bg_color(Some(Blue.into())));
+        test!("--bold --dim --italics --reverse --underline" =>
+            Style::new().bold().dimmed().italic().invert().underline());
+        test!("--underline=curly" => Style::new().effects(Effects::CURLY_UNDERLINE));
+    }
+
+    #[test]
+    fn test_parse_err() {
+        macro_rules! test {
+            ($s:expr => $err:ident $word:expr) => {
+                assert_eq!(
+                    parse($s),
+                    Err($err {
+                        args: $s.to_string(),
+                        word: $word.to_string()
+                    })
+                );
+            };
+        }
+
+        test!("purple" => InvalidColor "purple");
+        test!("ff87" => InvalidColor "ff87");
+        test!("red -b notacolor" => InvalidColor "notacolor");
+        test!("red --blink" => UnknownOption "--blink");
+        test!("red --bold=yes" => UnknownOption "--bold=yes");
+        test!("red -x" => UnknownOption "-x");
+        test!("red --underline=wavy" => UnknownOption "--underline=wavy");
+        test!("red -b" => MissingValue "-b");
+    }
+
+    #[test]
+    fn test_render() {
+        macro_rules! test {
+            ($style:expr => $args:expr) => {
+                assert_eq!(render($style), $args);
+                assert_eq!(parse(&$args.join(" ")).unwrap(), $style);
+            };
+        }
+
+        test!(Style::new() => ["normal"]);
+        test!(Red.on(BrightBlue) => ["red", "--background=brblue"]);
+        test!(Style::new().bg_color(Some(Blue.into())) => ["--background=blue"]);
+        test!(RgbColor(0x20, 0x40, 0x60).on_default().bold().underline() =>
+            ["204060", "--bold", "--underline"]);
+        test!(Style::new().dimmed().italic().invert() => ["normal", "--dim", "--italics", "--reverse"]);
+    }
+
+    #[test]
+    fn test_render_lossy() {
+        assert_eq!(render(Ansi256Color(9).on_default()), ["brred"]);
+        assert_eq!(render(Ansi256Color(208).on_default()), ["ff8700"]);
+        assert_eq!(
+            render(Style::new().effects(Effects::CURLY_UNDERLINE).blink()),
+            ["normal", "--underline"]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+//! Render `anstyle::Style`s for shell prompts
+//!
+//! Shells measure the width of a prompt to handle line editing and wrapping, so escape codes
+//! need to be marked as zero-width in each shell's own way.
+//!
+//! - [`bash`]: escape codes wrapped in `\[`/`\]` or `\001`/`\002`
+//! - [`fish`]: `set_color` arguments, like `ff8700 --bold`
+//! - [`powershell`]: `$PSStyle` expressions, like `$PSStyle.Foreground.Red + $PSStyle.Bold`, and
+//!   `[System.ConsoleColor]`
+//! - [`zsh`]: prompt escapes, like `%F{208}%B`
+
+pub mod bash;
+pub mod fish;
+pub mod powershell;
+pub mod zsh;
+
+/// Raw SGR codes for the effects a shell has no prompt escape for, with their resets
+fn raw_effects(style: anstyle::Style, handled: anstyle::Effects) -> (String, String) {
+    let effects = style.get_effects().remove(handled);
+    let raw = anstyle::Style::new()
+        .effects(effects)
+        .underline_color(style.get_underline_color());
+    let start = raw.render().to_string();
+
+    let mut codes: Vec<&str> = Vec::new();
+    for (effect, code) in EFFECT_RESETS {
+        if effects.contains(effect) && !codes.contains(&code) {
+            codes.push(code);
+        }
+    }
+    if style.get_underline_color().is_some() {
+        codes.push("59");
+    }
+    let reset = if codes.is_empty() {
+        String::new()
+    } else {
+        format!("\x1b[{}m", codes.join(";"))
+    };
+    (start, reset)
+}
+
+const EFFECT_RESETS: [(anstyle::Effects, &str); 12] = [
+    (anstyle::Effects::BOLD, "22"),
+    (anstyle::Effects::DIMMED, "22"),
+    (anstyle::Effects::ITALIC, "23"),
+    (anstyle::Effects::UNDERLINE, "24"),
+    (anstyle::Effects::DOUBLE_UNDERLINE, "24"),
+    (anstyle::Effects::CURLY_UNDERLINE, "24"),
+    (anstyle::Effects::DOTTED_UNDERLINE, "24"),
+    (anstyle::Effects::DASHED_UNDERLINE, "24"),
+    (anstyle::Effects::BLINK, "25"),
+    (anstyle::Effects::INVERT, "27"),
+    (anstyle::Effects::HIDDEN, "28"),
+    (anstyle::Effects::STRIKETHROUGH, "29"),
+];
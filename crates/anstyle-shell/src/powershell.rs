@@ -0,0 +1,429 @@
+//! PowerShell `$PSStyle` expressions and `[System.ConsoleColor]`
+//!
+//! # Examples
+//!
+//! ```rust
+//! let style = anstyle::AnsiColor::BrightBlue.on_default().bold();
+//! let expression = anstyle_shell::powershell::render(style);
+//! assert_eq!(expression, "$PSStyle.Foreground.BrightBlue + $PSStyle.Bold");
+//! assert_eq!(anstyle_shell::powershell::parse(&expression).unwrap(), style);
+//! ```
+
+use anstyle::{AnsiColor, Effects};
+
+/// Parse a `$PSStyle` expression, like `$PSStyle.Foreground.Red + $PSStyle.Bold`
+///
+/// Terms are applied in order, so `$PSStyle.BoldOff` and `$PSStyle.Reset` undo earlier terms.
+pub fn parse(expression: &str) -> Result<anstyle::Style, Error> {
+    let mut style = anstyle::Style::new();
+    for term in expression.split('+').map(str::trim) {
+        if term == "\"\"" || term == "''" {
+            continue;
+        }
+        let unknown = || Error::UnknownTerm {
+            expression: expression.to_owned(),
+            term: term.to_owned(),
+        };
+        let lower = term.to_ascii_lowercase();
+        let member = lower.strip_prefix("$psstyle.").ok_or_else(unknown)?;
+        if member == "reset" {
+            style = anstyle::Style::new();
+        } else if let Some(color) = member.strip_prefix("foreground.") {
+            style = style.fg_color(Some(parse_color(color).ok_or_else(unknown)?));
+        } else if let Some(color) = member.strip_prefix("background.") {
+            style = style.bg_color(Some(parse_color(color).ok_or_else(unknown)?));
+        } else if let Some(effect) = member.strip_suffix("off").and_then(parse_effect) {
+            style = style.effects(style.get_effects().remove(effect));
+        } else if let Some(effect) = parse_effect(member) {
+            style |= effect;
+        } else {
+            return Err(unknown());
+        }
+    }
+    Ok(style)
+}
+
+/// Render an `anstyle::Style` as a `$PSStyle` expression
+///
+/// 256-colors are written with `FromRgb`, as `$PSStyle` has no members for them.  Underline
+/// colors are skipped and extended underlines are written as `Underline`.
+pub fn render(style: anstyle::Style) -> String {
+    let mut terms = Vec::new();
+    if let Some(color) = style.get_fg_color() {
+        terms.push(format!("$PSStyle.Foreground.{}", render_color(color)));
+    }
+    if let Some(color) = style.get_bg_color() {
+        terms.push(format!("$PSStyle.Background.{}", render_color(color)));
+    }
+    let effects = style.get_effects();
+    for (effect, name) in EFFECTS {
+        if effects.contains(effect) {
+            terms.push(format!("$PSStyle.{name}"));
+        }
+    }
+    let underlines = Effects::DOUBLE_UNDERLINE
+        | Effects::CURLY_UNDERLINE
+        | Effects::DOTTED_UNDERLINE
+        | Effects::DASHED_UNDERLINE;
+    if !effects.contains(Effects::UNDERLINE) && underlines.remove(effects) != underlines {
+        terms.push("$PSStyle.Underline".to_owned());
+    }
+    if terms.is_empty() {
+        "\"\"".to_owned()
+    } else {
+        terms.join(" + ")
+    }
+}
+
+/// `[System.ConsoleColor]`, as used by `Write-Host` and `$Host.UI.RawUI`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConsoleColor {
+    Black = 0,
+    DarkBlue = 1,
+    DarkGreen = 2,
+    DarkCyan = 3,
+    DarkRed = 4,
+    DarkMagenta = 5,
+    DarkYellow = 6,
+    Gray = 7,
+    DarkGray = 8,
+    Blue = 9,
+    Green = 10,
+    Cyan = 11,
+    Red = 12,
+    Magenta = 13,
+    Yellow = 14,
+    White = 15,
+}
+
+impl ConsoleColor {
+    /// The closest `ConsoleColor` to `color`, with `palette` for 256-colors and RGB colors
+    ///
+    /// ```rust
+    /// use anstyle_shell::powershell::ConsoleColor;
+    ///
+    /// let orange = anstyle::RgbColor(0xff, 0x87, 0x00).into();
+    /// let nearest = ConsoleColor::nearest(orange, anstyle_lossy::palette::WIN10_CONSOLE);
+    /// assert_eq!(nearest, ConsoleColor::DarkYellow);
+    /// ```
+    pub fn nearest(color: anstyle::Color, palette: anstyle_lossy::palette::Palette) -> Self {
+        Self::from_ansi(anstyle_lossy::color_to_ansi(color, palette))
+    }
+
+    pub const fn from_ansi(color: AnsiColor) -> Self {
+        match color {
+            AnsiColor::Black => Self::Black,
+            AnsiColor::Red => Self::DarkRed,
+            AnsiColor::Green => Self::DarkGreen,
+            AnsiColor::Yellow => Self::DarkYellow,
+            AnsiColor::Blue => Self::DarkBlue,
+            AnsiColor::Magenta => Self::DarkMagenta,
+            AnsiColor::Cyan => Self::DarkCyan,
+            AnsiColor::White => Self::Gray,
+            AnsiColor::BrightBlack => Self::DarkGray,
+            AnsiColor::BrightRed => Self::Red,
+            AnsiColor::BrightGreen => Self::Green,
+            AnsiColor::BrightYellow => Self::Yellow,
+            AnsiColor::BrightBlue => Self::Blue,
+            AnsiColor::BrightMagenta => Self::Magenta,
+            AnsiColor::BrightCyan => Self::Cyan,
+            AnsiColor::BrightWhite => Self::White,
+        }
+    }
+
+    pub const fn to_ansi(self) -> AnsiColor {
+        match self {
+            Self::Black => AnsiColor::Black,
+            Self::DarkRed => AnsiColor::Red,
+            Self::DarkGreen => AnsiColor::Green,
+            Self::DarkYellow => AnsiColor::Yellow,
+            Self::DarkBlue => AnsiColor::Blue,
+            Self::DarkMagenta => AnsiColor::Magenta,
+            Self::DarkCyan => AnsiColor::Cyan,
+            Self::Gray => AnsiColor::White,
+            Self::DarkGray => AnsiColor::BrightBlack,
+            Self::Red => AnsiColor::BrightRed,
+            Self::Green => AnsiColor::BrightGreen,
+            Self::Yellow => AnsiColor::BrightYellow,
+            Self::Blue => AnsiColor::BrightBlue,
+            Self::Magenta => AnsiColor::BrightMagenta,
+            Self::Cyan => AnsiColor::BrightCyan,
+            Self::White => AnsiColor::BrightWhite,
+        }
+    }
+
+    /// The name PowerShell uses, like `DarkYellow`
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Black => "Black",
+            Self::DarkBlue => "DarkBlue",
+            Self::DarkGreen => "DarkGreen",
+            Self::DarkCyan => "DarkCyan",
+            Self::DarkRed => "DarkRed",
+            Self::DarkMagenta => "DarkMagenta",
+            Self::DarkYellow => "DarkYellow",
+            Self::Gray => "Gray",
+            Self::DarkGray => "DarkGray",
+            Self::Blue => "Blue",
+            Self::Green => "Green",
+            Self::Cyan => "Cyan",
+            Self::Red => "Red",
+            Self::Magenta => "Magenta",
+            Self::Yellow => "Yellow",
+            Self::White => "White",
+        }
+    }
+}
+
+impl From<ConsoleColor> for AnsiColor {
+    fn from(color: ConsoleColor) -> Self {
+        color.to_ansi()
+    }
+}
+
+impl From<AnsiColor> for ConsoleColor {
+    fn from(color: AnsiColor) -> Self {
+        Self::from_ansi(color)
+    }
+}
+
+impl std::fmt::Display for ConsoleColor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ConsoleColor {
+    type Err = Error;
+
+    /// Parse a name, case-insensitively, or a number, like PowerShell does
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        (0..16)
+            .map(|i| Self::from_ansi(anstyle::Ansi256Color(i).into_ansi().expect("16 colors")))
+            .find(|color| color.name().eq_ignore_ascii_case(s) || (*color as u8).to_string() == s)
+            .ok_or_else(|| Error::InvalidConsoleColor { name: s.to_owned() })
+    }
+}
+
+/// Parse a lowercase `$PSStyle.Foreground` member
+fn parse_color(member: &str) -> Option<anstyle::Color> {
+    if let Some(args) = member
+        .strip_prefix("fromrgb(")
+        .and_then(|args| args.strip_suffix(')'))
+    {
+        let args = args.split(',').map(str::trim).collect::<Vec<_>>();
+        let rgb = match args.as_slice() {
+            [rgb] => {
+                let rgb = match rgb.strip_prefix("0x") {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                    None => rgb.parse().ok()?,
+                };
+                if 0xffffff < rgb {
+                    return None;
+                }
+                anstyle::RgbColor((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+            }
+            [r, g, b] => anstyle::RgbColor(r.parse().ok()?, g.parse().ok()?, b.parse().ok()?),
+            _ => return None,
+        };
+        return Some(rgb.into());
+    }
+    NAMES
+        .iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case(member))
+        .map(|(color, _)| (*color).into())
+}
+
+fn render_color(color: anstyle::Color) -> String {
+    let ansi = match color {
+        anstyle::Color::Ansi(color) => Some(color),
+        anstyle::Color::Ansi256(color) => color.into_ansi(),
+        anstyle::Color::Rgb(_) => None,
+    };
+    if let Some(ansi) = ansi {
+        let (_, name) = NAMES
+            .iter()
+            .find(|(color, _)| *color == ansi)
+            .expect("all ANSI colors are named");
+        return (*name).to_owned();
+    }
+    let rgb = anstyle_lossy::color_to_rgb(color, anstyle_lossy::palette::VGA);
+    format!("FromRgb(0x{:02x}{:02x}{:02x})", rgb.r(), rgb.g(), rgb.b())
+}
+
+fn parse_effect(member: &str) -> Option<Effects> {
+    EFFECTS
+        .iter()
+        .find(|(_, name)| name.eq_ignore_ascii_case(member))
+        .map(|(effect, _)| *effect)
+}
+
+/// `$PSStyle` effects, in the order they are rendered
+const EFFECTS: [(Effects, &str); 8] = [
+    (Effects::BOLD, "Bold"),
+    (Effects::DIMMED, "Dim"),
+    (Effects::ITALIC, "Italic"),
+    (Effects::UNDERLINE, "Underline"),
+    (Effects::BLINK, "Blink"),
+    (Effects::INVERT, "Reverse"),
+    (Effects::HIDDEN, "Hidden"),
+    (Effects::STRIKETHROUGH, "Strikethrough"),
+];
+
+const NAMES: [(AnsiColor, &str); 16] = [
+    (AnsiColor::Black, "Black"),
+    (AnsiColor::Red, "Red"),
+    (AnsiColor::Green, "Green"),
+    (AnsiColor::Yellow, "Yellow"),
+    (AnsiColor::Blue, "Blue"),
+    (AnsiColor::Magenta, "Magenta"),
+    (AnsiColor::Cyan, "Cyan"),
+    (AnsiColor::White, "White"),
+    (AnsiColor::BrightBlack, "BrightBlack"),
+    (AnsiColor::BrightRed, "BrightRed"),
+    (AnsiColor::BrightGreen, "BrightGreen"),
+    (AnsiColor::BrightYellow, "BrightYellow"),
+    (AnsiColor::BrightBlue, "BrightBlue"),
+    (AnsiColor::BrightMagenta, "BrightMagenta"),
+    (AnsiColor::BrightCyan, "BrightCyan"),
+    (AnsiColor::BrightWhite, "BrightWhite"),
+];
+
+/// Type for errors returned by the parsers.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A term wasn't a known `$PSStyle` member.
+    UnknownTerm { expression: String, term: String },
+    /// An unknown `[System.ConsoleColor]`.
+    InvalidConsoleColor { name: String },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnknownTerm { expression, term } => {
+                write!(
+                    fmt,
+                    "Error parsing \"{}\": unknown term \"{}\"",
+                    expression, term
+                )
+            }
+            Self::InvalidConsoleColor { name } => {
+                write!(fmt, "Invalid console color \"{}\"", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anstyle::AnsiColor::*;
+    use anstyle::*;
+
+    #[test]
+    fn test_parse() {
+        macro_rules! test {
+            ($s:expr => $style:expr) => {
+                assert_eq!(parse($s).unwrap(), $style);
+            };
+        }
+
+        test!("\"\"" => Style::new());
+        test!("$PSStyle.Foreground.Red" => Red.on_default());
+        test!("$psstyle.foreground.brightred+$PSStyle.Background.Blue" => BrightRed.on(Blue));
+        test!("$PSStyle.Foreground.FromRgb(0x204060)" => RgbColor(0x20, 0x40, 0x60).on_default());
+        test!("$PSStyle.Foreground.FromRgb(2113632)" => RgbColor(0x20, 0x40, 0x60).on_default());
+        test!("$PSStyle.Foreground.FromRgb(32, 64, 96)" => RgbColor(0x20, 0x40, 0x60).on_default());
+        test!("$PSStyle.Bold + $PSStyle.Italic + $PSStyle.BoldOff" => Style::new().italic());
+        test!("$PSStyle.Foreground.Red + $PSStyle.Bold + $PSStyle.Reset" => Style::new());
+    }
+
+    #[test]
+    fn test_parse_err() {
+        macro_rules! test {
+            ($s:expr => $term:expr) => {
+                assert_eq!(
+                    parse($s),
+                    Err(Error::UnknownTerm {
+                        expression: $s.to_string(),
+                        term: $term.to_string()
+                    })
+                );
+            };
+        }
+
+        test!("$PSStyle.Foreground.Purple" => "$PSStyle.Foreground.Purple");
+        test!("$PSStyle.Foreground.FromRgb(0x1000000)" => "$PSStyle.Foreground.FromRgb(0x1000000)");
+        test!("$PSStyle.Bold + $PSStyle.Shiny" => "$PSStyle.Shiny");
+        test!("\"`e[1m\"" => "\"`e[1m\"");
+    }
+
+    #[test]
+    fn test_render() {
+        macro_rules! test {
+            ($style:expr => $s:expr) => {
+                assert_eq!(render($style), $s);
+                assert_eq!(parse($s).unwrap(), $style);
+            };
+        }
+
+        test!(Style::new() => "\"\"");
+        test!(Red.on(BrightBlack) => "$PSStyle.Foreground.Red + $PSStyle.Background.BrightBlack");
+        test!(RgbColor(0x20, 0x40, 0x60).on_default() => "$PSStyle.Foreground.FromRgb(0x204060)");
+        test!(Style::new().bold().dimmed().italic().underline().blink().invert().hidden().strikethrough() =>
+            "$PSStyle.Bold + $PSStyle.Dim + $PSStyle.Italic + $PSStyle.Underline + $PSStyle.Blink + $PSStyle.Reverse + $PSStyle.Hidden + $PSStyle.Strikethrough");
+    }
+
+    #[test]
+    fn test_render_lossy() {
+        assert_eq!(
+            render(Ansi256Color(208).on_default()),
+            "$PSStyle.Foreground.FromRgb(0xff8700)"
+        );
+        assert_eq!(
+            render(Style::new().effects(Effects::CURLY_UNDERLINE)),
+            "$PSStyle.Underline"
+        );
+    }
+
+    #[test]
+    fn console_color() {
+        for i in 0..16 {
+            let ansi = Ansi256Color(i).into_ansi().unwrap();
+            let console = ConsoleColor::from_ansi(ansi);
+            assert_eq!(console.to_ansi(), ansi);
+            assert_eq!(console.name().parse::<ConsoleColor>(), Ok(console));
+            assert_eq!(
+                (console as u8).to_string().parse::<ConsoleColor>(),
+                Ok(console)
+            );
+        }
+        assert_eq!(
+            "darkyellow".parse::<ConsoleColor>(),
+            Ok(ConsoleColor::DarkYellow)
+        );
+        assert!("Purple".parse::<ConsoleColor>().is_err());
+    }
+
+    #[test]
+    fn nearest() {
+        let palette = anstyle_lossy::palette::WIN10_CONSOLE;
+        assert_eq!(
+            ConsoleColor::nearest(Blue.into(), palette),
+            ConsoleColor::DarkBlue
+        );
+        assert_eq!(
+            ConsoleColor::nearest(Ansi256Color(196).into(), palette),
+            ConsoleColor::DarkRed
+        );
+        assert_eq!(
+            ConsoleColor::nearest(RgbColor(0x10, 0x10, 0x10).into(), palette),
+            ConsoleColor::Black
+        );
+    }
+}
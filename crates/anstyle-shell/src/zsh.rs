@@ -0,0 +1,133 @@
+//! zsh prompt escapes
+//!
+//! zsh tracks the state of its own escapes, like `%F{208}` and `%B`, when measuring a prompt, so
+//! they are used where they exist.  Other effects are written as raw escape codes inside of
+//! `%{...%}`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let style = anstyle::Ansi256Color(208).on_default().bold();
+//! assert_eq!(anstyle_shell::zsh::paint(style, "~/src"), "%F{208}%B~/src%b%f");
+//! ```
+
+/// Prompt escapes to start `style`
+pub fn render(style: anstyle::Style) -> String {
+    let mut prompt = String::new();
+    if let Some(fg) = style.get_fg_color() {
+        prompt.push_str(&format!("%F{{{}}}", color(fg)));
+    }
+    if let Some(bg) = style.get_bg_color() {
+        prompt.push_str(&format!("%K{{{}}}", color(bg)));
+    }
+    let effects = style.get_effects();
+    for (effect, start, _) in EFFECTS {
+        if effects.contains(effect) {
+            prompt.push_str(start);
+        }
+    }
+    let (raw, _) = crate::raw_effects(style, HANDLED);
+    if !raw.is_empty() {
+        prompt.push_str(&format!("%{{{raw}%}}"));
+    }
+    prompt
+}
+
+/// Prompt escapes to end `style`, as started by [`render`]
+pub fn render_reset(style: anstyle::Style) -> String {
+    let mut prompt = String::new();
+    let (_, raw) = crate::raw_effects(style, HANDLED);
+    if !raw.is_empty() {
+        prompt.push_str(&format!("%{{{raw}%}}"));
+    }
+    let effects = style.get_effects();
+    for (effect, _, end) in EFFECTS.iter().rev() {
+        if effects.contains(*effect) {
+            prompt.push_str(end);
+        }
+    }
+    if style.get_bg_color().is_some() {
+        prompt.push_str("%k");
+    }
+    if style.get_fg_color().is_some() {
+        prompt.push_str("%f");
+    }
+    prompt
+}
+
+/// Style `text` for a prompt, escaping any `%` in it
+pub fn paint(style: anstyle::Style, text: &str) -> String {
+    format!(
+        "{}{}{}",
+        render(style),
+        text.replace('%', "%%"),
+        render_reset(style)
+    )
+}
+
+/// Effects with a prompt escape
+const EFFECTS: [(anstyle::Effects, &str, &str); 3] = [
+    (anstyle::Effects::BOLD, "%B", "%b"),
+    (anstyle::Effects::UNDERLINE, "%U", "%u"),
+    (anstyle::Effects::INVERT, "%S", "%s"),
+];
+
+const HANDLED: anstyle::Effects = anstyle::Effects::BOLD
+    .insert(anstyle::Effects::UNDERLINE)
+    .insert(anstyle::Effects::INVERT);
+
+/// 16 and 256 colors are written as indexes, and RGB colors as `#rrggbb`, which needs zsh 5.7
+fn color(color: anstyle::Color) -> String {
+    match color {
+        anstyle::Color::Ansi(color) => anstyle::Ansi256Color::from_ansi(color).index().to_string(),
+        anstyle::Color::Ansi256(color) => color.index().to_string(),
+        anstyle::Color::Rgb(color) => {
+            format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anstyle::AnsiColor::*;
+    use anstyle::*;
+
+    #[test]
+    fn plain() {
+        assert_eq!(paint(Style::new(), "100%"), "100%%");
+    }
+
+    #[test]
+    fn colors() {
+        assert_eq!(paint(Red.on(BrightBlue), "x"), "%F{1}%K{12}x%k%f");
+        assert_eq!(
+            paint(RgbColor(0x20, 0x40, 0x60).on(Ansi256Color(17)), "x"),
+            "%F{#204060}%K{17}x%k%f"
+        );
+    }
+
+    #[test]
+    fn effects() {
+        let style = Style::new().bold().underline().invert();
+        assert_eq!(paint(style, "x"), "%B%U%Sx%s%u%b");
+    }
+
+    #[test]
+    fn raw_effects() {
+        let style = Red.on_default().bold().italic().strikethrough();
+        assert_eq!(
+            paint(style, "x"),
+            "%F{1}%B%{\x1b[3m\x1b[9m%}x%{\x1b[23;29m%}%b%f"
+        );
+
+        let style = Style::new()
+            .effects(Effects::CURLY_UNDERLINE)
+            .underline_color(Some(Ansi256Color(196).into()));
+        assert_eq!(
+            paint(style, "x"),
+            "%{\x1b[4:3m\x1b[58;5;196m%}x%{\x1b[24;59m%}"
+        );
+    }
+}
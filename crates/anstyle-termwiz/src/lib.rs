@@ -0,0 +1,140 @@
+use termwiz::cell::{Blink, CellAttributes, Intensity, Underline};
+use termwiz::color::{ColorAttribute, SrgbaTuple};
+
+mod sealed {
+    pub(crate) trait Sealed {}
+}
+
+trait Ext: sealed::Sealed {
+    fn to_termwiz(self) -> CellAttributes;
+}
+
+impl sealed::Sealed for anstyle::Style {}
+
+impl Ext for anstyle::Style {
+    fn to_termwiz(self) -> CellAttributes {
+        to_termwiz_attributes(self)
+    }
+}
+
+pub fn to_termwiz_attributes(style: anstyle::Style) -> CellAttributes {
+    let mut attributes = CellAttributes::default();
+    if let Some(fg) = style.get_fg_color() {
+        attributes.set_foreground(to_termwiz_color(fg));
+    }
+    if let Some(bg) = style.get_bg_color() {
+        attributes.set_background(to_termwiz_color(bg));
+    }
+    if let Some(underline) = style.get_underline_color() {
+        attributes.set_underline_color(to_termwiz_color(underline));
+    }
+
+    let effects = style.get_effects();
+    if effects.contains(anstyle::Effects::BOLD) {
+        attributes.set_intensity(Intensity::Bold);
+    } else if effects.contains(anstyle::Effects::DIMMED) {
+        attributes.set_intensity(Intensity::Half);
+    }
+    if effects.contains(anstyle::Effects::ITALIC) {
+        attributes.set_italic(true);
+    }
+    if effects.contains(anstyle::Effects::UNDERLINE) {
+        attributes.set_underline(Underline::Single);
+    } else if effects.contains(anstyle::Effects::DOUBLE_UNDERLINE) {
+        attributes.set_underline(Underline::Double);
+    } else if effects.contains(anstyle::Effects::CURLY_UNDERLINE) {
+        attributes.set_underline(Underline::Curly);
+    } else if effects.contains(anstyle::Effects::DOTTED_UNDERLINE) {
+        attributes.set_underline(Underline::Dotted);
+    } else if effects.contains(anstyle::Effects::DASHED_UNDERLINE) {
+        attributes.set_underline(Underline::Dashed);
+    }
+    if effects.contains(anstyle::Effects::BLINK) {
+        attributes.set_blink(Blink::Slow);
+    }
+    if effects.contains(anstyle::Effects::INVERT) {
+        attributes.set_reverse(true);
+    }
+    if effects.contains(anstyle::Effects::HIDDEN) {
+        attributes.set_invisible(true);
+    }
+    if effects.contains(anstyle::Effects::STRIKETHROUGH) {
+        attributes.set_strikethrough(true);
+    }
+    attributes
+}
+
+/// RGB colors fall back to the closest 256-color
+pub fn to_termwiz_color(color: anstyle::Color) -> ColorAttribute {
+    match color {
+        anstyle::Color::Ansi(ansi) => {
+            ColorAttribute::PaletteIndex(anstyle::Ansi256Color::from_ansi(ansi).index())
+        }
+        anstyle::Color::Ansi256(xterm) => ColorAttribute::PaletteIndex(xterm.index()),
+        anstyle::Color::Rgb(rgb) => ColorAttribute::TrueColorWithPaletteFallback(
+            SrgbaTuple::from((rgb.r(), rgb.g(), rgb.b())),
+            anstyle_lossy::rgb_to_xterm(rgb).index(),
+        ),
+    }
+}
+
+/// Hyperlinks, images, and other non-styling attributes are dropped
+///
+/// When both bold and dimmed would apply, termwiz only keeps one, so they don't round-trip.
+pub fn to_anstyle(attributes: &CellAttributes) -> anstyle::Style {
+    let mut effects = anstyle::Effects::new();
+    match attributes.intensity() {
+        Intensity::Normal => {}
+        Intensity::Bold => effects |= anstyle::Effects::BOLD,
+        Intensity::Half => effects |= anstyle::Effects::DIMMED,
+    }
+    if attributes.italic() {
+        effects |= anstyle::Effects::ITALIC;
+    }
+    match attributes.underline() {
+        Underline::None => {}
+        Underline::Single => effects |= anstyle::Effects::UNDERLINE,
+        Underline::Double => effects |= anstyle::Effects::DOUBLE_UNDERLINE,
+        Underline::Curly => effects |= anstyle::Effects::CURLY_UNDERLINE,
+        Underline::Dotted => effects |= anstyle::Effects::DOTTED_UNDERLINE,
+        Underline::Dashed => effects |= anstyle::Effects::DASHED_UNDERLINE,
+    }
+    match attributes.blink() {
+        Blink::None => {}
+        Blink::Slow | Blink::Rapid => effects |= anstyle::Effects::BLINK,
+    }
+    if attributes.reverse() {
+        effects |= anstyle::Effects::INVERT;
+    }
+    if attributes.invisible() {
+        effects |= anstyle::Effects::HIDDEN;
+    }
+    if attributes.strikethrough() {
+        effects |= anstyle::Effects::STRIKETHROUGH;
+    }
+
+    anstyle::Style::new()
+        .fg_color(to_anstyle_color(attributes.foreground()))
+        .bg_color(to_anstyle_color(attributes.background()))
+        .underline_color(to_anstyle_color(attributes.underline_color()))
+        .effects(effects)
+}
+
+/// [`ColorAttribute::Default`] is `None`, and true colors keep their RGB value
+pub fn to_anstyle_color(color: ColorAttribute) -> Option<anstyle::Color> {
+    match color {
+        ColorAttribute::TrueColorWithPaletteFallback(rgb, _)
+        | ColorAttribute::TrueColorWithDefaultFallback(rgb) => {
+            let (r, g, b, _) = rgb.to_srgb_u8();
+            Some(anstyle::RgbColor(r, g, b).into())
+        }
+        ColorAttribute::PaletteIndex(index) => {
+            let xterm = anstyle::Ansi256Color(index);
+            Some(match xterm.into_ansi() {
+                Some(ansi) => ansi.into(),
+                None => xterm.into(),
+            })
+        }
+        ColorAttribute::Default => None,
+    }
+}
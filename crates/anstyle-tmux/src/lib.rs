@@ -0,0 +1,333 @@
+//! `anstyle_tmux::parse` parses a tmux style string into an `anstyle::Style`, and
+//! `anstyle_tmux::render` goes the other way:
+//!
+//! # Examples
+//!
+//! ```rust
+//! let style = anstyle_tmux::parse("fg=colour208,bg=default,bold").unwrap();
+//! assert_eq!(style, anstyle::Ansi256Color(208).on_default() | anstyle::Effects::BOLD);
+//!
+//! assert_eq!(anstyle_tmux::render(style), "fg=colour208,bold");
+//! ```
+
+mod names;
+
+/// Parse a tmux style string, like for `status-style`, into an `anstyle::Style`.
+///
+/// Layout options, like `align=` and `list=`, and `overline`, which has no `anstyle`
+/// equivalent, are ignored.
+pub fn parse(s: &str) -> Result<anstyle::Style, Error> {
+    let mut style = anstyle::Style::new();
+    let mut effects = anstyle::Effects::new();
+    for word in s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|w| !w.is_empty())
+    {
+        let lower = word.to_lowercase();
+        if lower == "default" {
+            style = style.fg_color(None).bg_color(None);
+        } else if lower == "none" {
+            effects = anstyle::Effects::new();
+        } else if let Some((key, value)) = lower.split_once('=') {
+            match key {
+                "fg" | "bg" | "us" => {
+                    let color = parse_color(value).ok_or_else(|| Error::InvalidColor {
+                        style: s.to_owned(),
+                        word: word.to_owned(),
+                    })?;
+                    style = match key {
+                        "fg" => style.fg_color(color),
+                        "bg" => style.bg_color(color),
+                        _ => style.underline_color(color),
+                    };
+                }
+                "align" | "fill" | "list" | "range" | "width" => {}
+                _ => {
+                    return Err(Error::UnknownWord {
+                        style: s.to_owned(),
+                        word: word.to_owned(),
+                    })
+                }
+            }
+        } else if IGNORED.contains(&lower.as_str()) {
+            continue;
+        } else if let Some(effect) = lower.strip_prefix("no").and_then(parse_effect) {
+            effects = effects.remove(effect);
+        } else if let Some(effect) = parse_effect(&lower) {
+            effects = effects.insert(effect);
+        } else {
+            return Err(Error::UnknownWord {
+                style: s.to_owned(),
+                word: word.to_owned(),
+            });
+        }
+    }
+    style |= effects;
+    Ok(style)
+}
+
+/// Render an `anstyle::Style` as a tmux style string.
+///
+/// ```rust
+/// let style = anstyle::AnsiColor::BrightRed.on(anstyle::RgbColor(0x20, 0x40, 0x60)).italic();
+/// assert_eq!(anstyle_tmux::render(style), "fg=brightred,bg=#204060,italics");
+/// ```
+pub fn render(style: anstyle::Style) -> String {
+    let mut words = Vec::new();
+    if let Some(color) = style.get_fg_color() {
+        words.push(format!("fg={}", render_color(color)));
+    }
+    if let Some(color) = style.get_bg_color() {
+        words.push(format!("bg={}", render_color(color)));
+    }
+    if let Some(color) = style.get_underline_color() {
+        words.push(format!("us={}", render_color(color)));
+    }
+    let effects = style.get_effects();
+    for (effect, name) in EFFECTS {
+        if effects.contains(effect) {
+            words.push(name.to_owned());
+        }
+    }
+    if words.is_empty() {
+        "default".to_owned()
+    } else {
+        words.join(",")
+    }
+}
+
+/// Parse a tmux colour, where `Some(None)` is the terminal's default
+///
+/// Supports the names of the 16 ANSI colors, `colour0` through `colour255`, `#rrggbb`, and the
+/// X11 color names, like `darkorange`.
+fn parse_color(s: &str) -> Option<Option<anstyle::Color>> {
+    let s = s.to_lowercase();
+    if let Some(hex) = s.strip_prefix('#') {
+        if hex.len() != 6 {
+            return None;
+        }
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let rgb = anstyle::RgbColor((value >> 16) as u8, (value >> 8) as u8, value as u8);
+        return Some(Some(rgb.into()));
+    }
+    if let Some(index) = s.strip_prefix("colour").or_else(|| s.strip_prefix("color")) {
+        return index
+            .parse::<u8>()
+            .ok()
+            .map(|index| Some(anstyle::Ansi256Color(index).into()));
+    }
+    if s == "default" || s == "terminal" {
+        return Some(None);
+    }
+    if let Some(color) = ANSI
+        .iter()
+        .zip(0..)
+        .find(|((name, _), i)| **name == s || i.to_string() == s)
+        .map(|((_, color), _)| *color)
+    {
+        return Some(Some(color.into()));
+    }
+    if let Some(color) = ANSI
+        .iter()
+        .zip(90..)
+        .find(|((name, _), i)| s.strip_prefix("bright") == Some(*name) || i.to_string() == s)
+        .map(|((_, color), _)| color.bright(true))
+    {
+        return Some(Some(color.into()));
+    }
+    let name = s.replace(' ', "");
+    names::NAMES
+        .binary_search_by_key(&name.as_str(), |(name, _)| name)
+        .ok()
+        .map(|i| Some(names::NAMES[i].1.into()))
+}
+
+fn render_color(color: anstyle::Color) -> String {
+    match color {
+        anstyle::Color::Ansi(color) => {
+            let index = anstyle::Ansi256Color::from_ansi(color).index() as usize;
+            if index < 8 {
+                ANSI[index].0.to_owned()
+            } else {
+                format!("bright{}", ANSI[index - 8].0)
+            }
+        }
+        anstyle::Color::Ansi256(color) => format!("colour{}", color.index()),
+        anstyle::Color::Rgb(color) => {
+            format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+        }
+    }
+}
+
+/// Options that don't affect the style
+const IGNORED: [&str; 10] = [
+    "ignore",
+    "noignore",
+    "push-default",
+    "pop-default",
+    "nolist",
+    "norange",
+    "noalign",
+    "nofill",
+    "overline",
+    "nooverline",
+];
+
+const ANSI: [(&str, anstyle::AnsiColor); 8] = [
+    ("black", anstyle::AnsiColor::Black),
+    ("red", anstyle::AnsiColor::Red),
+    ("green", anstyle::AnsiColor::Green),
+    ("yellow", anstyle::AnsiColor::Yellow),
+    ("blue", anstyle::AnsiColor::Blue),
+    ("magenta", anstyle::AnsiColor::Magenta),
+    ("cyan", anstyle::AnsiColor::Cyan),
+    ("white", anstyle::AnsiColor::White),
+];
+
+/// tmux attributes, in the order they are rendered
+const EFFECTS: [(anstyle::Effects, &str); 12] = [
+    (anstyle::Effects::BOLD, "bold"),
+    (anstyle::Effects::DIMMED, "dim"),
+    (anstyle::Effects::ITALIC, "italics"),
+    (anstyle::Effects::UNDERLINE, "underscore"),
+    (anstyle::Effects::DOUBLE_UNDERLINE, "double-underscore"),
+    (anstyle::Effects::CURLY_UNDERLINE, "curly-underscore"),
+    (anstyle::Effects::DOTTED_UNDERLINE, "dotted-underscore"),
+    (anstyle::Effects::DASHED_UNDERLINE, "dashed-underscore"),
+    (anstyle::Effects::BLINK, "blink"),
+    (anstyle::Effects::INVERT, "reverse"),
+    (anstyle::Effects::HIDDEN, "hidden"),
+    (anstyle::Effects::STRIKETHROUGH, "strikethrough"),
+];
+
+fn parse_effect(word: &str) -> Option<anstyle::Effects> {
+    if word == "bright" {
+        return Some(anstyle::Effects::BOLD);
+    }
+    EFFECTS
+        .iter()
+        .find(|(_, name)| *name == word)
+        .map(|(effect, _)| *effect)
+}
+
+/// Type for errors returned by the parser.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// A `fg=`, `bg=`, or `us=` had an unknown colour.
+    InvalidColor { style: String, word: String },
+    /// An unknown word appeared.
+    UnknownWord { style: String, word: String },
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::InvalidColor { style, word } => {
+                write!(
+                    fmt,
+                    "Error parsing style \"{}\": invalid colour \"{}\"",
+                    style, word
+                )
+            }
+            Self::UnknownWord { style, word } => {
+                write!(
+                    fmt,
+                    "Error parsing style \"{}\": unknown word: \"{}\"",
+                    style, word
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[cfg(test)]
+mod tests {
+    use super::Error::*;
+    use super::*;
+
+    use anstyle::AnsiColor::*;
+    use anstyle::*;
+
+    #[test]
+    fn test_parse_style() {
+        macro_rules! test {
+            ($s:expr => $style:expr) => {
+                assert_eq!(parse($s).unwrap(), $style);
+            };
+        }
+
+        test!("" => Style::new());
+        test!("default" => Style::new());
+        test!("fg=red" => Red.on_default());
+        test!("fg=red,bg=blue" => Red.on(Blue));
+        test!("fg=red bg=blue" => Red.on(Blue));
+        test!("bg=blue,fg=red" => Red.on(Blue));
+        test!("fg=Red,BG=Blue" => Red.on(Blue));
+        test!("fg=1,bg=4" => Red.on(Blue));
+        test!("fg=brightred,bg=97" => BrightRed.on(BrightWhite));
+        test!("fg=colour208,bg=color17" => Ansi256Color(208).on(Ansi256Color(17)));
+        test!("fg=#204060" => RgbColor(0x20, 0x40, 0x60).on_default());
+        test!("fg=darkorange" => RgbColor(255, 140, 0).on_default());
+        test!("fg=DarkOrange" => RgbColor(255, 140, 0).on_default());
+        test!("fg=red,fg=default" => Style::new());
+        test!("fg=red,bg=blue,default" => Style::new());
+        test!("us=red,curly-underscore" => Style::new().underline_color(Some(Red.into())) | Effects::CURLY_UNDERLINE);
+
+        test!("bold" => Style::new().bold());
+        test!("bright" => Style::new().bold());
+        test!("bold,dim,italics,underscore,blink,reverse,hidden,strikethrough" =>
+            Style::new().bold().dimmed().italic().underline().blink().invert().hidden().strikethrough());
+        test!("bold,nobold" => Style::new());
+        test!("bold,italics,none" => Style::new());
+        test!("fg=red,align=centre,list=on,overline" => Red.on_default());
+    }
+
+    #[test]
+    fn test_parse_style_err() {
+        macro_rules! test {
+            ($s:expr => $err:ident $word:expr) => {
+                assert_eq!(
+                    parse($s),
+                    Err($err {
+                        style: $s.to_string(),
+                        word: $word.to_string()
+                    })
+                );
+            };
+        }
+
+        test!("fg=notacolour" => InvalidColor "fg=notacolour");
+        test!("fg=colour256" => InvalidColor "fg=colour256");
+        test!("bg=#12345" => InvalidColor "bg=#12345");
+        test!("shiny" => UnknownWord "shiny");
+        test!("noshiny" => UnknownWord "noshiny");
+        test!("xx=red" => UnknownWord "xx=red");
+    }
+
+    #[test]
+    fn test_render_style() {
+        macro_rules! test {
+            ($style:expr => $s:expr) => {
+                assert_eq!(render($style), $s);
+                assert_eq!(parse($s).unwrap(), $style);
+            };
+        }
+
+        test!(Style::new() => "default");
+        test!(Red.on(Blue) => "fg=red,bg=blue");
+        test!(Style::new().bg_color(Some(BrightBlack.into())) => "bg=brightblack");
+        test!(Ansi256Color(208).on_default().bold() => "fg=colour208,bold");
+        test!(RgbColor(0x20, 0x40, 0x60).on_default() => "fg=#204060");
+        test!(Style::new().underline_color(Some(Red.into())) | Effects::DOTTED_UNDERLINE => "us=red,dotted-underscore");
+        test!(Style::new().dimmed().italic().blink().invert().hidden().strikethrough() =>
+            "dim,italics,blink,reverse,hidden,strikethrough");
+    }
+
+    #[test]
+    fn names_are_sorted() {
+        assert!(names::NAMES.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+}
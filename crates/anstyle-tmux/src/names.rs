@@ -0,0 +1,663 @@
+//! tmux's color names, from X11's `rgb.txt`
+
+/// Lowercase names, sorted for binary search
+pub(crate) const NAMES: &[(&str, anstyle::RgbColor)] = &[
+    ("aliceblue", anstyle::RgbColor(240, 248, 255)),
+    ("antiquewhite", anstyle::RgbColor(250, 235, 215)),
+    ("antiquewhite1", anstyle::RgbColor(255, 239, 219)),
+    ("antiquewhite2", anstyle::RgbColor(238, 223, 204)),
+    ("antiquewhite3", anstyle::RgbColor(205, 192, 176)),
+    ("antiquewhite4", anstyle::RgbColor(139, 131, 120)),
+    ("aquamarine", anstyle::RgbColor(127, 255, 212)),
+    ("aquamarine1", anstyle::RgbColor(127, 255, 212)),
+    ("aquamarine2", anstyle::RgbColor(118, 238, 198)),
+    ("aquamarine3", anstyle::RgbColor(102, 205, 170)),
+    ("aquamarine4", anstyle::RgbColor(69, 139, 116)),
+    ("azure", anstyle::RgbColor(240, 255, 255)),
+    ("azure1", anstyle::RgbColor(240, 255, 255)),
+    ("azure2", anstyle::RgbColor(224, 238, 238)),
+    ("azure3", anstyle::RgbColor(193, 205, 205)),
+    ("azure4", anstyle::RgbColor(131, 139, 139)),
+    ("beige", anstyle::RgbColor(245, 245, 220)),
+    ("bisque", anstyle::RgbColor(255, 228, 196)),
+    ("bisque1", anstyle::RgbColor(255, 228, 196)),
+    ("bisque2", anstyle::RgbColor(238, 213, 183)),
+    ("bisque3", anstyle::RgbColor(205, 183, 158)),
+    ("bisque4", anstyle::RgbColor(139, 125, 107)),
+    ("black", anstyle::RgbColor(0, 0, 0)),
+    ("blanchedalmond", anstyle::RgbColor(255, 235, 205)),
+    ("blue", anstyle::RgbColor(0, 0, 255)),
+    ("blue1", anstyle::RgbColor(0, 0, 255)),
+    ("blue2", anstyle::RgbColor(0, 0, 238)),
+    ("blue3", anstyle::RgbColor(0, 0, 205)),
+    ("blue4", anstyle::RgbColor(0, 0, 139)),
+    ("blueviolet", anstyle::RgbColor(138, 43, 226)),
+    ("brown", anstyle::RgbColor(165, 42, 42)),
+    ("brown1", anstyle::RgbColor(255, 64, 64)),
+    ("brown2", anstyle::RgbColor(238, 59, 59)),
+    ("brown3", anstyle::RgbColor(205, 51, 51)),
+    ("brown4", anstyle::RgbColor(139, 35, 35)),
+    ("burlywood", anstyle::RgbColor(222, 184, 135)),
+    ("burlywood1", anstyle::RgbColor(255, 211, 155)),
+    ("burlywood2", anstyle::RgbColor(238, 197, 145)),
+    ("burlywood3", anstyle::RgbColor(205, 170, 125)),
+    ("burlywood4", anstyle::RgbColor(139, 115, 85)),
+    ("cadetblue", anstyle::RgbColor(95, 158, 160)),
+    ("cadetblue1", anstyle::RgbColor(152, 245, 255)),
+    ("cadetblue2", anstyle::RgbColor(142, 229, 238)),
+    ("cadetblue3", anstyle::RgbColor(122, 197, 205)),
+    ("cadetblue4", anstyle::RgbColor(83, 134, 139)),
+    ("chartreuse", anstyle::RgbColor(127, 255, 0)),
+    ("chartreuse1", anstyle::RgbColor(127, 255, 0)),
+    ("chartreuse2", anstyle::RgbColor(118, 238, 0)),
+    ("chartreuse3", anstyle::RgbColor(102, 205, 0)),
+    ("chartreuse4", anstyle::RgbColor(69, 139, 0)),
+    ("chocolate", anstyle::RgbColor(210, 105, 30)),
+    ("chocolate1", anstyle::RgbColor(255, 127, 36)),
+    ("chocolate2", anstyle::RgbColor(238, 118, 33)),
+    ("chocolate3", anstyle::RgbColor(205, 102, 29)),
+    ("chocolate4", anstyle::RgbColor(139, 69, 19)),
+    ("coral", anstyle::RgbColor(255, 127, 80)),
+    ("coral1", anstyle::RgbColor(255, 114, 86)),
+    ("coral2", anstyle::RgbColor(238, 106, 80)),
+    ("coral3", anstyle::RgbColor(205, 91, 69)),
+    ("coral4", anstyle::RgbColor(139, 62, 47)),
+    ("cornflowerblue", anstyle::RgbColor(100, 149, 237)),
+    ("cornsilk", anstyle::RgbColor(255, 248, 220)),
+    ("cornsilk1", anstyle::RgbColor(255, 248, 220)),
+    ("cornsilk2", anstyle::RgbColor(238, 232, 205)),
+    ("cornsilk3", anstyle::RgbColor(205, 200, 177)),
+    ("cornsilk4", anstyle::RgbColor(139, 136, 120)),
+    ("cyan", anstyle::RgbColor(0, 255, 255)),
+    ("cyan1", anstyle::RgbColor(0, 255, 255)),
+    ("cyan2", anstyle::RgbColor(0, 238, 238)),
+    ("cyan3", anstyle::RgbColor(0, 205, 205)),
+    ("cyan4", anstyle::RgbColor(0, 139, 139)),
+    ("darkblue", anstyle::RgbColor(0, 0, 139)),
+    ("darkcyan", anstyle::RgbColor(0, 139, 139)),
+    ("darkgoldenrod", anstyle::RgbColor(184, 134, 11)),
+    ("darkgoldenrod1", anstyle::RgbColor(255, 185, 15)),
+    ("darkgoldenrod2", anstyle::RgbColor(238, 173, 14)),
+    ("darkgoldenrod3", anstyle::RgbColor(205, 149, 12)),
+    ("darkgoldenrod4", anstyle::RgbColor(139, 101, 8)),
+    ("darkgray", anstyle::RgbColor(169, 169, 169)),
+    ("darkgreen", anstyle::RgbColor(0, 100, 0)),
+    ("darkgrey", anstyle::RgbColor(169, 169, 169)),
+    ("darkkhaki", anstyle::RgbColor(189, 183, 107)),
+    ("darkmagenta", anstyle::RgbColor(139, 0, 139)),
+    ("darkolivegreen", anstyle::RgbColor(85, 107, 47)),
+    ("darkolivegreen1", anstyle::RgbColor(202, 255, 112)),
+    ("darkolivegreen2", anstyle::RgbColor(188, 238, 104)),
+    ("darkolivegreen3", anstyle::RgbColor(162, 205, 90)),
+    ("darkolivegreen4", anstyle::RgbColor(110, 139, 61)),
+    ("darkorange", anstyle::RgbColor(255, 140, 0)),
+    ("darkorange1", anstyle::RgbColor(255, 127, 0)),
+    ("darkorange2", anstyle::RgbColor(238, 118, 0)),
+    ("darkorange3", anstyle::RgbColor(205, 102, 0)),
+    ("darkorange4", anstyle::RgbColor(139, 69, 0)),
+    ("darkorchid", anstyle::RgbColor(153, 50, 204)),
+    ("darkorchid1", anstyle::RgbColor(191, 62, 255)),
+    ("darkorchid2", anstyle::RgbColor(178, 58, 238)),
+    ("darkorchid3", anstyle::RgbColor(154, 50, 205)),
+    ("darkorchid4", anstyle::RgbColor(104, 34, 139)),
+    ("darkred", anstyle::RgbColor(139, 0, 0)),
+    ("darksalmon", anstyle::RgbColor(233, 150, 122)),
+    ("darkseagreen", anstyle::RgbColor(143, 188, 143)),
+    ("darkseagreen1", anstyle::RgbColor(193, 255, 193)),
+    ("darkseagreen2", anstyle::RgbColor(180, 238, 180)),
+    ("darkseagreen3", anstyle::RgbColor(155, 205, 155)),
+    ("darkseagreen4", anstyle::RgbColor(105, 139, 105)),
+    ("darkslateblue", anstyle::RgbColor(72, 61, 139)),
+    ("darkslategray", anstyle::RgbColor(47, 79, 79)),
+    ("darkslategray1", anstyle::RgbColor(151, 255, 255)),
+    ("darkslategray2", anstyle::RgbColor(141, 238, 238)),
+    ("darkslategray3", anstyle::RgbColor(121, 205, 205)),
+    ("darkslategray4", anstyle::RgbColor(82, 139, 139)),
+    ("darkslategrey", anstyle::RgbColor(47, 79, 79)),
+    ("darkturquoise", anstyle::RgbColor(0, 206, 209)),
+    ("darkviolet", anstyle::RgbColor(148, 0, 211)),
+    ("debianred", anstyle::RgbColor(215, 7, 81)),
+    ("deeppink", anstyle::RgbColor(255, 20, 147)),
+    ("deeppink1", anstyle::RgbColor(255, 20, 147)),
+    ("deeppink2", anstyle::RgbColor(238, 18, 137)),
+    ("deeppink3", anstyle::RgbColor(205, 16, 118)),
+    ("deeppink4", anstyle::RgbColor(139, 10, 80)),
+    ("deepskyblue", anstyle::RgbColor(0, 191, 255)),
+    ("deepskyblue1", anstyle::RgbColor(0, 191, 255)),
+    ("deepskyblue2", anstyle::RgbColor(0, 178, 238)),
+    ("deepskyblue3", anstyle::RgbColor(0, 154, 205)),
+    ("deepskyblue4", anstyle::RgbColor(0, 104, 139)),
+    ("dimgray", anstyle::RgbColor(105, 105, 105)),
+    ("dimgrey", anstyle::RgbColor(105, 105, 105)),
+    ("dodgerblue", anstyle::RgbColor(30, 144, 255)),
+    ("dodgerblue1", anstyle::RgbColor(30, 144, 255)),
+    ("dodgerblue2", anstyle::RgbColor(28, 134, 238)),
+    ("dodgerblue3", anstyle::RgbColor(24, 116, 205)),
+    ("dodgerblue4", anstyle::RgbColor(16, 78, 139)),
+    ("firebrick", anstyle::RgbColor(178, 34, 34)),
+    ("firebrick1", anstyle::RgbColor(255, 48, 48)),
+    ("firebrick2", anstyle::RgbColor(238, 44, 44)),
+    ("firebrick3", anstyle::RgbColor(205, 38, 38)),
+    ("firebrick4", anstyle::RgbColor(139, 26, 26)),
+    ("floralwhite", anstyle::RgbColor(255, 250, 240)),
+    ("forestgreen", anstyle::RgbColor(34, 139, 34)),
+    ("gainsboro", anstyle::RgbColor(220, 220, 220)),
+    ("ghostwhite", anstyle::RgbColor(248, 248, 255)),
+    ("gold", anstyle::RgbColor(255, 215, 0)),
+    ("gold1", anstyle::RgbColor(255, 215, 0)),
+    ("gold2", anstyle::RgbColor(238, 201, 0)),
+    ("gold3", anstyle::RgbColor(205, 173, 0)),
+    ("gold4", anstyle::RgbColor(139, 117, 0)),
+    ("goldenrod", anstyle::RgbColor(218, 165, 32)),
+    ("goldenrod1", anstyle::RgbColor(255, 193, 37)),
+    ("goldenrod2", anstyle::RgbColor(238, 180, 34)),
+    ("goldenrod3", anstyle::RgbColor(205, 155, 29)),
+    ("goldenrod4", anstyle::RgbColor(139, 105, 20)),
+    ("gray", anstyle::RgbColor(190, 190, 190)),
+    ("gray0", anstyle::RgbColor(0, 0, 0)),
+    ("gray1", anstyle::RgbColor(3, 3, 3)),
+    ("gray10", anstyle::RgbColor(26, 26, 26)),
+    ("gray100", anstyle::RgbColor(255, 255, 255)),
+    ("gray11", anstyle::RgbColor(28, 28, 28)),
+    ("gray12", anstyle::RgbColor(31, 31, 31)),
+    ("gray13", anstyle::RgbColor(33, 33, 33)),
+    ("gray14", anstyle::RgbColor(36, 36, 36)),
+    ("gray15", anstyle::RgbColor(38, 38, 38)),
+    ("gray16", anstyle::RgbColor(41, 41, 41)),
+    ("gray17", anstyle::RgbColor(43, 43, 43)),
+    ("gray18", anstyle::RgbColor(46, 46, 46)),
+    ("gray19", anstyle::RgbColor(48, 48, 48)),
+    ("gray2", anstyle::RgbColor(5, 5, 5)),
+    ("gray20", anstyle::RgbColor(51, 51, 51)),
+    ("gray21", anstyle::RgbColor(54, 54, 54)),
+    ("gray22", anstyle::RgbColor(56, 56, 56)),
+    ("gray23", anstyle::RgbColor(59, 59, 59)),
+    ("gray24", anstyle::RgbColor(61, 61, 61)),
+    ("gray25", anstyle::RgbColor(64, 64, 64)),
+    ("gray26", anstyle::RgbColor(66, 66, 66)),
+    ("gray27", anstyle::RgbColor(69, 69, 69)),
+    ("gray28", anstyle::RgbColor(71, 71, 71)),
+    ("gray29", anstyle::RgbColor(74, 74, 74)),
+    ("gray3", anstyle::RgbColor(8, 8, 8)),
+    ("gray30", anstyle::RgbColor(77, 77, 77)),
+    ("gray31", anstyle::RgbColor(79, 79, 79)),
+    ("gray32", anstyle::RgbColor(82, 82, 82)),
+    ("gray33", anstyle::RgbColor(84, 84, 84)),
+    ("gray34", anstyle::RgbColor(87, 87, 87)),
+    ("gray35", anstyle::RgbColor(89, 89, 89)),
+    ("gray36", anstyle::RgbColor(92, 92, 92)),
+    ("gray37", anstyle::RgbColor(94, 94, 94)),
+    ("gray38", anstyle::RgbColor(97, 97, 97)),
+    ("gray39", anstyle::RgbColor(99, 99, 99)),
+    ("gray4", anstyle::RgbColor(10, 10, 10)),
+    ("gray40", anstyle::RgbColor(102, 102, 102)),
+    ("gray41", anstyle::RgbColor(105, 105, 105)),
+    ("gray42", anstyle::RgbColor(107, 107, 107)),
+    ("gray43", anstyle::RgbColor(110, 110, 110)),
+    ("gray44", anstyle::RgbColor(112, 112, 112)),
+    ("gray45", anstyle::RgbColor(115, 115, 115)),
+    ("gray46", anstyle::RgbColor(117, 117, 117)),
+    ("gray47", anstyle::RgbColor(120, 120, 120)),
+    ("gray48", anstyle::RgbColor(122, 122, 122)),
+    ("gray49", anstyle::RgbColor(125, 125, 125)),
+    ("gray5", anstyle::RgbColor(13, 13, 13)),
+    ("gray50", anstyle::RgbColor(127, 127, 127)),
+    ("gray51", anstyle::RgbColor(130, 130, 130)),
+    ("gray52", anstyle::RgbColor(133, 133, 133)),
+    ("gray53", anstyle::RgbColor(135, 135, 135)),
+    ("gray54", anstyle::RgbColor(138, 138, 138)),
+    ("gray55", anstyle::RgbColor(140, 140, 140)),
+    ("gray56", anstyle::RgbColor(143, 143, 143)),
+    ("gray57", anstyle::RgbColor(145, 145, 145)),
+    ("gray58", anstyle::RgbColor(148, 148, 148)),
+    ("gray59", anstyle::RgbColor(150, 150, 150)),
+    ("gray6", anstyle::RgbColor(15, 15, 15)),
+    ("gray60", anstyle::RgbColor(153, 153, 153)),
+    ("gray61", anstyle::RgbColor(156, 156, 156)),
+    ("gray62", anstyle::RgbColor(158, 158, 158)),
+    ("gray63", anstyle::RgbColor(161, 161, 161)),
+    ("gray64", anstyle::RgbColor(163, 163, 163)),
+    ("gray65", anstyle::RgbColor(166, 166, 166)),
+    ("gray66", anstyle::RgbColor(168, 168, 168)),
+    ("gray67", anstyle::RgbColor(171, 171, 171)),
+    ("gray68", anstyle::RgbColor(173, 173, 173)),
+    ("gray69", anstyle::RgbColor(176, 176, 176)),
+    ("gray7", anstyle::RgbColor(18, 18, 18)),
+    ("gray70", anstyle::RgbColor(179, 179, 179)),
+    ("gray71", anstyle::RgbColor(181, 181, 181)),
+    ("gray72", anstyle::RgbColor(184, 184, 184)),
+    ("gray73", anstyle::RgbColor(186, 186, 186)),
+    ("gray74", anstyle::RgbColor(189, 189, 189)),
+    ("gray75", anstyle::RgbColor(191, 191, 191)),
+    ("gray76", anstyle::RgbColor(194, 194, 194)),
+    ("gray77", anstyle::RgbColor(196, 196, 196)),
+    ("gray78", anstyle::RgbColor(199, 199, 199)),
+    ("gray79", anstyle::RgbColor(201, 201, 201)),
+    ("gray8", anstyle::RgbColor(20, 20, 20)),
+    ("gray80", anstyle::RgbColor(204, 204, 204)),
+    ("gray81", anstyle::RgbColor(207, 207, 207)),
+    ("gray82", anstyle::RgbColor(209, 209, 209)),
+    ("gray83", anstyle::RgbColor(212, 212, 212)),
+    ("gray84", anstyle::RgbColor(214, 214, 214)),
+    ("gray85", anstyle::RgbColor(217, 217, 217)),
+    ("gray86", anstyle::RgbColor(219, 219, 219)),
+    ("gray87", anstyle::RgbColor(222, 222, 222)),
+    ("gray88", anstyle::RgbColor(224, 224, 224)),
+    ("gray89", anstyle::RgbColor(227, 227, 227)),
+    ("gray9", anstyle::RgbColor(23, 23, 23)),
+    ("gray90", anstyle::RgbColor(229, 229, 229)),
+    ("gray91", anstyle::RgbColor(232, 232, 232)),
+    ("gray92", anstyle::RgbColor(235, 235, 235)),
+    ("gray93", anstyle::RgbColor(237, 237, 237)),
+    ("gray94", anstyle::RgbColor(240, 240, 240)),
+    ("gray95", anstyle::RgbColor(242, 242, 242)),
+    ("gray96", anstyle::RgbColor(245, 245, 245)),
+    ("gray97", anstyle::RgbColor(247, 247, 247)),
+    ("gray98", anstyle::RgbColor(250, 250, 250)),
+    ("gray99", anstyle::RgbColor(252, 252, 252)),
+    ("green", anstyle::RgbColor(0, 255, 0)),
+    ("green1", anstyle::RgbColor(0, 255, 0)),
+    ("green2", anstyle::RgbColor(0, 238, 0)),
+    ("green3", anstyle::RgbColor(0, 205, 0)),
+    ("green4", anstyle::RgbColor(0, 139, 0)),
+    ("greenyellow", anstyle::RgbColor(173, 255, 47)),
+    ("grey", anstyle::RgbColor(190, 190, 190)),
+    ("grey0", anstyle::RgbColor(0, 0, 0)),
+    ("grey1", anstyle::RgbColor(3, 3, 3)),
+    ("grey10", anstyle::RgbColor(26, 26, 26)),
+    ("grey100", anstyle::RgbColor(255, 255, 255)),
+    ("grey11", anstyle::RgbColor(28, 28, 28)),
+    ("grey12", anstyle::RgbColor(31, 31, 31)),
+    ("grey13", anstyle::RgbColor(33, 33, 33)),
+    ("grey14", anstyle::RgbColor(36, 36, 36)),
+    ("grey15", anstyle::RgbColor(38, 38, 38)),
+    ("grey16", anstyle::RgbColor(41, 41, 41)),
+    ("grey17", anstyle::RgbColor(43, 43, 43)),
+    ("grey18", anstyle::RgbColor(46, 46, 46)),
+    ("grey19", anstyle::RgbColor(48, 48, 48)),
+    ("grey2", anstyle::RgbColor(5, 5, 5)),
+    ("grey20", anstyle::RgbColor(51, 51, 51)),
+    ("grey21", anstyle::RgbColor(54, 54, 54)),
+    ("grey22", anstyle::RgbColor(56, 56, 56)),
+    ("grey23", anstyle::RgbColor(59, 59, 59)),
+    ("grey24", anstyle::RgbColor(61, 61, 61)),
+    ("grey25", anstyle::RgbColor(64, 64, 64)),
+    ("grey26", anstyle::RgbColor(66, 66, 66)),
+    ("grey27", anstyle::RgbColor(69, 69, 69)),
+    ("grey28", anstyle::RgbColor(71, 71, 71)),
+    ("grey29", anstyle::RgbColor(74, 74, 74)),
+    ("grey3", anstyle::RgbColor(8, 8, 8)),
+    ("grey30", anstyle::RgbColor(77, 77, 77)),
+    ("grey31", anstyle::RgbColor(79, 79, 79)),
+    ("grey32", anstyle::RgbColor(82, 82, 82)),
+    ("grey33", anstyle::RgbColor(84, 84, 84)),
+    ("grey34", anstyle::RgbColor(87, 87, 87)),
+    ("grey35", anstyle::RgbColor(89, 89, 89)),
+    ("grey36", anstyle::RgbColor(92, 92, 92)),
+    ("grey37", anstyle::RgbColor(94, 94, 94)),
+    ("grey38", anstyle::RgbColor(97, 97, 97)),
+    ("grey39", anstyle::RgbColor(99, 99, 99)),
+    ("grey4", anstyle::RgbColor(10, 10, 10)),
+    ("grey40", anstyle::RgbColor(102, 102, 102)),
+    ("grey41", anstyle::RgbColor(105, 105, 105)),
+    ("grey42", anstyle::RgbColor(107, 107, 107)),
+    ("grey43", anstyle::RgbColor(110, 110, 110)),
+    ("grey44", anstyle::RgbColor(112, 112, 112)),
+    ("grey45", anstyle::RgbColor(115, 115, 115)),
+    ("grey46", anstyle::RgbColor(117, 117, 117)),
+    ("grey47", anstyle::RgbColor(120, 120, 120)),
+    ("grey48", anstyle::RgbColor(122, 122, 122)),
+    ("grey49", anstyle::RgbColor(125, 125, 125)),
+    ("grey5", anstyle::RgbColor(13, 13, 13)),
+    ("grey50", anstyle::RgbColor(127, 127, 127)),
+    ("grey51", anstyle::RgbColor(130, 130, 130)),
+    ("grey52", anstyle::RgbColor(133, 133, 133)),
+    ("grey53", anstyle::RgbColor(135, 135, 135)),
+    ("grey54", anstyle::RgbColor(138, 138, 138)),
+    ("grey55", anstyle::RgbColor(140, 140, 140)),
+    ("grey56", anstyle::RgbColor(143, 143, 143)),
+    ("grey57", anstyle::RgbColor(145, 145, 145)),
+    ("grey58", anstyle::RgbColor(148, 148, 148)),
+    ("grey59", anstyle::RgbColor(150, 150, 150)),
+    ("grey6", anstyle::RgbColor(15, 15, 15)),
+    ("grey60", anstyle::RgbColor(153, 153, 153)),
+    ("grey61", anstyle::RgbColor(156, 156, 156)),
+    ("grey62", anstyle::RgbColor(158, 158, 158)),
+    ("grey63", anstyle::RgbColor(161, 161, 161)),
+    ("grey64", anstyle::RgbColor(163, 163, 163)),
+    ("grey65", anstyle::RgbColor(166, 166, 166)),
+    ("grey66", anstyle::RgbColor(168, 168, 168)),
+    ("grey67", anstyle::RgbColor(171, 171, 171)),
+    ("grey68", anstyle::RgbColor(173, 173, 173)),
+    ("grey69", anstyle::RgbColor(176, 176, 176)),
+    ("grey7", anstyle::RgbColor(18, 18, 18)),
+    ("grey70", anstyle::RgbColor(179, 179, 179)),
+    ("grey71", anstyle::RgbColor(181, 181, 181)),
+    ("grey72", anstyle::RgbColor(184, 184, 184)),
+    ("grey73", anstyle::RgbColor(186, 186, 186)),
+    ("grey74", anstyle::RgbColor(189, 189, 189)),
+    ("grey75", anstyle::RgbColor(191, 191, 191)),
+    ("grey76", anstyle::RgbColor(194, 194, 194)),
+    ("grey77", anstyle::RgbColor(196, 196, 196)),
+    ("grey78", anstyle::RgbColor(199, 199, 199)),
+    ("grey79", anstyle::RgbColor(201, 201, 201)),
+    ("grey8", anstyle::RgbColor(20, 20, 20)),
+    ("grey80", anstyle::RgbColor(204, 204, 204)),
+    ("grey81", anstyle::RgbColor(207, 207, 207)),
+    ("grey82", anstyle::RgbColor(209, 209, 209)),
+    ("grey83", anstyle::RgbColor(212, 212, 212)),
+    ("grey84", anstyle::RgbColor(214, 214, 214)),
+    ("grey85", anstyle::RgbColor(217, 217, 217)),
+    ("grey86", anstyle::RgbColor(219, 219, 219)),
+    ("grey87", anstyle::RgbColor(222, 222, 222)),
+    ("grey88", anstyle::RgbColor(224, 224, 224)),
+    ("grey89", anstyle::RgbColor(227, 227, 227)),
+    ("grey9", anstyle::RgbColor(23, 23, 23)),
+    ("grey90", anstyle::RgbColor(229, 229, 229)),
+    ("grey91", anstyle::RgbColor(232, 232, 232)),
+    ("grey92", anstyle::RgbColor(235, 235, 235)),
+    ("grey93", anstyle::RgbColor(237, 237, 237)),
+    ("grey94", anstyle::RgbColor(240, 240, 240)),
+    ("grey95", anstyle::RgbColor(242, 242, 242)),
+    ("grey96", anstyle::RgbColor(245, 245, 245)),
+    ("grey97", anstyle::RgbColor(247, 247, 247)),
+    ("grey98", anstyle::RgbColor(250, 250, 250)),
+    ("grey99", anstyle::RgbColor(252, 252, 252)),
+    ("honeydew", anstyle::RgbColor(240, 255, 240)),
+    ("honeydew1", anstyle::RgbColor(240, 255, 240)),
+    ("honeydew2", anstyle::RgbColor(224, 238, 224)),
+    ("honeydew3", anstyle::RgbColor(193, 205, 193)),
+    ("honeydew4", anstyle::RgbColor(131, 139, 131)),
+    ("hotpink", anstyle::RgbColor(255, 105, 180)),
+    ("hotpink1", anstyle::RgbColor(255, 110, 180)),
+    ("hotpink2", anstyle::RgbColor(238, 106, 167)),
+    ("hotpink3", anstyle::RgbColor(205, 96, 144)),
+    ("hotpink4", anstyle::RgbColor(139, 58, 98)),
+    ("indianred", anstyle::RgbColor(205, 92, 92)),
+    ("indianred1", anstyle::RgbColor(255, 106, 106)),
+    ("indianred2", anstyle::RgbColor(238, 99, 99)),
+    ("indianred3", anstyle::RgbColor(205, 85, 85)),
+    ("indianred4", anstyle::RgbColor(139, 58, 58)),
+    ("ivory", anstyle::RgbColor(255, 255, 240)),
+    ("ivory1", anstyle::RgbColor(255, 255, 240)),
+    ("ivory2", anstyle::RgbColor(238, 238, 224)),
+    ("ivory3", anstyle::RgbColor(205, 205, 193)),
+    ("ivory4", anstyle::RgbColor(139, 139, 131)),
+    ("khaki", anstyle::RgbColor(240, 230, 140)),
+    ("khaki1", anstyle::RgbColor(255, 246, 143)),
+    ("khaki2", anstyle::RgbColor(238, 230, 133)),
+    ("khaki3", anstyle::RgbColor(205, 198, 115)),
+    ("khaki4", anstyle::RgbColor(139, 134, 78)),
+    ("lavender", anstyle::RgbColor(230, 230, 250)),
+    ("lavenderblush", anstyle::RgbColor(255, 240, 245)),
+    ("lavenderblush1", anstyle::RgbColor(255, 240, 245)),
+    ("lavenderblush2", anstyle::RgbColor(238, 224, 229)),
+    ("lavenderblush3", anstyle::RgbColor(205, 193, 197)),
+    ("lavenderblush4", anstyle::RgbColor(139, 131, 134)),
+    ("lawngreen", anstyle::RgbColor(124, 252, 0)),
+    ("lemonchiffon", anstyle::RgbColor(255, 250, 205)),
+    ("lemonchiffon1", anstyle::RgbColor(255, 250, 205)),
+    ("lemonchiffon2", anstyle::RgbColor(238, 233, 191)),
+    ("lemonchiffon3", anstyle::RgbColor(205, 201, 165)),
+    ("lemonchiffon4", anstyle::RgbColor(139, 137, 112)),
+    ("lightblue", anstyle::RgbColor(173, 216, 230)),
+    ("lightblue1", anstyle::RgbColor(191, 239, 255)),
+    ("lightblue2", anstyle::RgbColor(178, 223, 238)),
+    ("lightblue3", anstyle::RgbColor(154, 192, 205)),
+    ("lightblue4", anstyle::RgbColor(104, 131, 139)),
+    ("lightcoral", anstyle::RgbColor(240, 128, 128)),
+    ("lightcyan", anstyle::RgbColor(224, 255, 255)),
+    ("lightcyan1", anstyle::RgbColor(224, 255, 255)),
+    ("lightcyan2", anstyle::RgbColor(209, 238, 238)),
+    ("lightcyan3", anstyle::RgbColor(180, 205, 205)),
+    ("lightcyan4", anstyle::RgbColor(122, 139, 139)),
+    ("lightgoldenrod", anstyle::RgbColor(238, 221, 130)),
+    ("lightgoldenrod1", anstyle::RgbColor(255, 236, 139)),
+    ("lightgoldenrod2", anstyle::RgbColor(238, 220, 130)),
+    ("lightgoldenrod3", anstyle::RgbColor(205, 190, 112)),
+    ("lightgoldenrod4", anstyle::RgbColor(139, 129, 76)),
+    ("lightgoldenrodyellow", anstyle::RgbColor(250, 250, 210)),
+    ("lightgray", anstyle::RgbColor(211, 211, 211)),
+    ("lightgreen", anstyle::RgbColor(144, 238, 144)),
+    ("lightgrey", anstyle::RgbColor(211, 211, 211)),
+    ("lightpink", anstyle::RgbColor(255, 182, 193)),
+    ("lightpink1", anstyle::RgbColor(255, 174, 185)),
+    ("lightpink2", anstyle::RgbColor(238, 162, 173)),
+    ("lightpink3", anstyle::RgbColor(205, 140, 149)),
+    ("lightpink4", anstyle::RgbColor(139, 95, 101)),
+    ("lightsalmon", anstyle::RgbColor(255, 160, 122)),
+    ("lightsalmon1", anstyle::RgbColor(255, 160, 122)),
+    ("lightsalmon2", anstyle::RgbColor(238, 149, 114)),
+    ("lightsalmon3", anstyle::RgbColor(205, 129, 98)),
+    ("lightsalmon4", anstyle::RgbColor(139, 87, 66)),
+    ("lightseagreen", anstyle::RgbColor(32, 178, 170)),
+    ("lightskyblue", anstyle::RgbColor(135, 206, 250)),
+    ("lightskyblue1", anstyle::RgbColor(176, 226, 255)),
+    ("lightskyblue2", anstyle::RgbColor(164, 211, 238)),
+    ("lightskyblue3", anstyle::RgbColor(141, 182, 205)),
+    ("lightskyblue4", anstyle::RgbColor(96, 123, 139)),
+    ("lightslateblue", anstyle::RgbColor(132, 112, 255)),
+    ("lightslategray", anstyle::RgbColor(119, 136, 153)),
+    ("lightslategrey", anstyle::RgbColor(119, 136, 153)),
+    ("lightsteelblue", anstyle::RgbColor(176, 196, 222)),
+    ("lightsteelblue1", anstyle::RgbColor(202, 225, 255)),
+    ("lightsteelblue2", anstyle::RgbColor(188, 210, 238)),
+    ("lightsteelblue3", anstyle::RgbColor(162, 181, 205)),
+    ("lightsteelblue4", anstyle::RgbColor(110, 123, 139)),
+    ("lightyellow", anstyle::RgbColor(255, 255, 224)),
+    ("lightyellow1", anstyle::RgbColor(255, 255, 224)),
+    ("lightyellow2", anstyle::RgbColor(238, 238, 209)),
+    ("lightyellow3", anstyle::RgbColor(205, 205, 180)),
+    ("lightyellow4", anstyle::RgbColor(139, 139, 122)),
+    ("limegreen", anstyle::RgbColor(50, 205, 50)),
+    ("linen", anstyle::RgbColor(250, 240, 230)),
+    ("magenta", anstyle::RgbColor(255, 0, 255)),
+    ("magenta1", anstyle::RgbColor(255, 0, 255)),
+    ("magenta2", anstyle::RgbColor(238, 0, 238)),
+    ("magenta3", anstyle::RgbColor(205, 0, 205)),
+    ("magenta4", anstyle::RgbColor(139, 0, 139)),
+    ("maroon", anstyle::RgbColor(176, 48, 96)),
+    ("maroon1", anstyle::RgbColor(255, 52, 179)),
+    ("maroon2", anstyle::RgbColor(238, 48, 167)),
+    ("maroon3", anstyle::RgbColor(205, 41, 144)),
+    ("maroon4", anstyle::RgbColor(139, 28, 98)),
+    ("mediumaquamarine", anstyle::RgbColor(102, 205, 170)),
+    ("mediumblue", anstyle::RgbColor(0, 0, 205)),
+    ("mediumorchid", anstyle::RgbColor(186, 85, 211)),
+    ("mediumorchid1", anstyle::RgbColor(224, 102, 255)),
+    ("mediumorchid2", anstyle::RgbColor(209, 95, 238)),
+    ("mediumorchid3", anstyle::RgbColor(180, 82, 205)),
+    ("mediumorchid4", anstyle::RgbColor(122, 55, 139)),
+    ("mediumpurple", anstyle::RgbColor(147, 112, 219)),
+    ("mediumpurple1", anstyle::RgbColor(171, 130, 255)),
+    ("mediumpurple2", anstyle::RgbColor(159, 121, 238)),
+    ("mediumpurple3", anstyle::RgbColor(137, 104, 205)),
+    ("mediumpurple4", anstyle::RgbColor(93, 71, 139)),
+    ("mediumseagreen", anstyle::RgbColor(60, 179, 113)),
+    ("mediumslateblue", anstyle::RgbColor(123, 104, 238)),
+    ("mediumspringgreen", anstyle::RgbColor(0, 250, 154)),
+    ("mediumturquoise", anstyle::RgbColor(72, 209, 204)),
+    ("mediumvioletred", anstyle::RgbColor(199, 21, 133)),
+    ("midnightblue", anstyle::RgbColor(25, 25, 112)),
+    ("mintcream", anstyle::RgbColor(245, 255, 250)),
+    ("mistyrose", anstyle::RgbColor(255, 228, 225)),
+    ("mistyrose1", anstyle::RgbColor(255, 228, 225)),
+    ("mistyrose2", anstyle::RgbColor(238, 213, 210)),
+    ("mistyrose3", anstyle::RgbColor(205, 183, 181)),
+    ("mistyrose4", anstyle::RgbColor(139, 125, 123)),
+    ("moccasin", anstyle::RgbColor(255, 228, 181)),
+    ("navajowhite", anstyle::RgbColor(255, 222, 173)),
+    ("navajowhite1", anstyle::RgbColor(255, 222, 173)),
+    ("navajowhite2", anstyle::RgbColor(238, 207, 161)),
+    ("navajowhite3", anstyle::RgbColor(205, 179, 139)),
+    ("navajowhite4", anstyle::RgbColor(139, 121, 94)),
+    ("navy", anstyle::RgbColor(0, 0, 128)),
+    ("navyblue", anstyle::RgbColor(0, 0, 128)),
+    ("oldlace", anstyle::RgbColor(253, 245, 230)),
+    ("olivedrab", anstyle::RgbColor(107, 142, 35)),
+    ("olivedrab1", anstyle::RgbColor(192, 255, 62)),
+    ("olivedrab2", anstyle::RgbColor(179, 238, 58)),
+    ("olivedrab3", anstyle::RgbColor(154, 205, 50)),
+    ("olivedrab4", anstyle::RgbColor(105, 139, 34)),
+    ("orange", anstyle::RgbColor(255, 165, 0)),
+    ("orange1", anstyle::RgbColor(255, 165, 0)),
+    ("orange2", anstyle::RgbColor(238, 154, 0)),
+    ("orange3", anstyle::RgbColor(205, 133, 0)),
+    ("orange4", anstyle::RgbColor(139, 90, 0)),
+    ("orangered", anstyle::RgbColor(255, 69, 0)),
+    ("orangered1", anstyle::RgbColor(255, 69, 0)),
+    ("orangered2", anstyle::RgbColor(238, 64, 0)),
+    ("orangered3", anstyle::RgbColor(205, 55, 0)),
+    ("orangered4", anstyle::RgbColor(139, 37, 0)),
+    ("orchid", anstyle::RgbColor(218, 112, 214)),
+    ("orchid1", anstyle::RgbColor(255, 131, 250)),
+    ("orchid2", anstyle::RgbColor(238, 122, 233)),
+    ("orchid3", anstyle::RgbColor(205, 105, 201)),
+    ("orchid4", anstyle::RgbColor(139, 71, 137)),
+    ("palegoldenrod", anstyle::RgbColor(238, 232, 170)),
+    ("palegreen", anstyle::RgbColor(152, 251, 152)),
+    ("palegreen1", anstyle::RgbColor(154, 255, 154)),
+    ("palegreen2", anstyle::RgbColor(144, 238, 144)),
+    ("palegreen3", anstyle::RgbColor(124, 205, 124)),
+    ("palegreen4", anstyle::RgbColor(84, 139, 84)),
+    ("paleturquoise", anstyle::RgbColor(175, 238, 238)),
+    ("paleturquoise1", anstyle::RgbColor(187, 255, 255)),
+    ("paleturquoise2", anstyle::RgbColor(174, 238, 238)),
+    ("paleturquoise3", anstyle::RgbColor(150, 205, 205)),
+    ("paleturquoise4", anstyle::RgbColor(102, 139, 139)),
+    ("palevioletred", anstyle::RgbColor(219, 112, 147)),
+    ("palevioletred1", anstyle::RgbColor(255, 130, 171)),
+    ("palevioletred2", anstyle::RgbColor(238, 121, 159)),
+    ("palevioletred3", anstyle::RgbColor(205, 104, 137)),
+    ("palevioletred4", anstyle::RgbColor(139, 71, 93)),
+    ("papayawhip", anstyle::RgbColor(255, 239, 213)),
+    ("peachpuff", anstyle::RgbColor(255, 218, 185)),
+    ("peachpuff1", anstyle::RgbColor(255, 218, 185)),
+    ("peachpuff2", anstyle::RgbColor(238, 203, 173)),
+    ("peachpuff3", anstyle::RgbColor(205, 175, 149)),
+    ("peachpuff4", anstyle::RgbColor(139, 119, 101)),
+    ("peru", anstyle::RgbColor(205, 133, 63)),
+    ("pink", anstyle::RgbColor(255, 192, 203)),
+    ("pink1", anstyle::RgbColor(255, 181, 197)),
+    ("pink2", anstyle::RgbColor(238, 169, 184)),
+    ("pink3", anstyle::RgbColor(205, 145, 158)),
+    ("pink4", anstyle::RgbColor(139, 99, 108)),
+    ("plum", anstyle::RgbColor(221, 160, 221)),
+    ("plum1", anstyle::RgbColor(255, 187, 255)),
+    ("plum2", anstyle::RgbColor(238, 174, 238)),
+    ("plum3", anstyle::RgbColor(205, 150, 205)),
+    ("plum4", anstyle::RgbColor(139, 102, 139)),
+    ("powderblue", anstyle::RgbColor(176, 224, 230)),
+    ("purple", anstyle::RgbColor(160, 32, 240)),
+    ("purple1", anstyle::RgbColor(155, 48, 255)),
+    ("purple2", anstyle::RgbColor(145, 44, 238)),
+    ("purple3", anstyle::RgbColor(125, 38, 205)),
+    ("purple4", anstyle::RgbColor(85, 26, 139)),
+    ("red", anstyle::RgbColor(255, 0, 0)),
+    ("red1", anstyle::RgbColor(255, 0, 0)),
+    ("red2", anstyle::RgbColor(238, 0, 0)),
+    ("red3", anstyle::RgbColor(205, 0, 0)),
+    ("red4", anstyle::RgbColor(139, 0, 0)),
+    ("rosybrown", anstyle::RgbColor(188, 143, 143)),
+    ("rosybrown1", anstyle::RgbColor(255, 193, 193)),
+    ("rosybrown2", anstyle::RgbColor(238, 180, 180)),
+    ("rosybrown3", anstyle::RgbColor(205, 155, 155)),
+    ("rosybrown4", anstyle::RgbColor(139, 105, 105)),
+    ("royalblue", anstyle::RgbColor(65, 105, 225)),
+    ("royalblue1", anstyle::RgbColor(72, 118, 255)),
+    ("royalblue2", anstyle::RgbColor(67, 110, 238)),
+    ("royalblue3", anstyle::RgbColor(58, 95, 205)),
+    ("royalblue4", anstyle::RgbColor(39, 64, 139)),
+    ("saddlebrown", anstyle::RgbColor(139, 69, 19)),
+    ("salmon", anstyle::RgbColor(250, 128, 114)),
+    ("salmon1", anstyle::RgbColor(255, 140, 105)),
+    ("salmon2", anstyle::RgbColor(238, 130, 98)),
+    ("salmon3", anstyle::RgbColor(205, 112, 84)),
+    ("salmon4", anstyle::RgbColor(139, 76, 57)),
+    ("sandybrown", anstyle::RgbColor(244, 164, 96)),
+    ("seagreen", anstyle::RgbColor(46, 139, 87)),
+    ("seagreen1", anstyle::RgbColor(84, 255, 159)),
+    ("seagreen2", anstyle::RgbColor(78, 238, 148)),
+    ("seagreen3", anstyle::RgbColor(67, 205, 128)),
+    ("seagreen4", anstyle::RgbColor(46, 139, 87)),
+    ("seashell", anstyle::RgbColor(255, 245, 238)),
+    ("seashell1", anstyle::RgbColor(255, 245, 238)),
+    ("seashell2", anstyle::RgbColor(238, 229, 222)),
+    ("seashell3", anstyle::RgbColor(205, 197, 191)),
+    ("seashell4", anstyle::RgbColor(139, 134, 130)),
+    ("sienna", anstyle::RgbColor(160, 82, 45)),
+    ("sienna1", anstyle::RgbColor(255, 130, 71)),
+    ("sienna2", anstyle::RgbColor(238, 121, 66)),
+    ("sienna3", anstyle::RgbColor(205, 104, 57)),
+    ("sienna4", anstyle::RgbColor(139, 71, 38)),
+    ("skyblue", anstyle::RgbColor(135, 206, 235)),
+    ("skyblue1", anstyle::RgbColor(135, 206, 255)),
+    ("skyblue2", anstyle::RgbColor(126, 192, 238)),
+    ("skyblue3", anstyle::RgbColor(108, 166, 205)),
+    ("skyblue4", anstyle::RgbColor(74, 112, 139)),
+    ("slateblue", anstyle::RgbColor(106, 90, 205)),
+    ("slateblue1", anstyle::RgbColor(131, 111, 255)),
+    ("slateblue2", anstyle::RgbColor(122, 103, 238)),
+    ("slateblue3", anstyle::RgbColor(105, 89, 205)),
+    ("slateblue4", anstyle::RgbColor(71, 60, 139)),
+    ("slategray", anstyle::RgbColor(112, 128, 144)),
+    ("slategray1", anstyle::RgbColor(198, 226, 255)),
+    ("slategray2", anstyle::RgbColor(185, 211, 238)),
+    ("slategray3", anstyle::RgbColor(159, 182, 205)),
+    ("slategray4", anstyle::RgbColor(108, 123, 139)),
+    ("slategrey", anstyle::RgbColor(112, 128, 144)),
+    ("snow", anstyle::RgbColor(255, 250, 250)),
+    ("snow1", anstyle::RgbColor(255, 250, 250)),
+    ("snow2", anstyle::RgbColor(238, 233, 233)),
+    ("snow3", anstyle::RgbColor(205, 201, 201)),
+    ("snow4", anstyle::RgbColor(139, 137, 137)),
+    ("springgreen", anstyle::RgbColor(0, 255, 127)),
+    ("springgreen1", anstyle::RgbColor(0, 255, 127)),
+    ("springgreen2", anstyle::RgbColor(0, 238, 118)),
+    ("springgreen3", anstyle::RgbColor(0, 205, 102)),
+    ("springgreen4", anstyle::RgbColor(0, 139, 69)),
+    ("steelblue", anstyle::RgbColor(70, 130, 180)),
+    ("steelblue1", anstyle::RgbColor(99, 184, 255)),
+    ("steelblue2", anstyle::RgbColor(92, 172, 238)),
+    ("steelblue3", anstyle::RgbColor(79, 148, 205)),
+    ("steelblue4", anstyle::RgbColor(54, 100, 139)),
+    ("tan", anstyle::RgbColor(210, 180, 140)),
+    ("tan1", anstyle::RgbColor(255, 165, 79)),
+    ("tan2", anstyle::RgbColor(238, 154, 73)),
+    ("tan3", anstyle::RgbColor(205, 133, 63)),
+    ("tan4", anstyle::RgbColor(139, 90, 43)),
+    ("thistle", anstyle::RgbColor(216, 191, 216)),
+    ("thistle1", anstyle::RgbColor(255, 225, 255)),
+    ("thistle2", anstyle::RgbColor(238, 210, 238)),
+    ("thistle3", anstyle::RgbColor(205, 181, 205)),
+    ("thistle4", anstyle::RgbColor(139, 123, 139)),
+    ("tomato", anstyle::RgbColor(255, 99, 71)),
+    ("tomato1", anstyle::RgbColor(255, 99, 71)),
+    ("tomato2", anstyle::RgbColor(238, 92, 66)),
+    ("tomato3", anstyle::RgbColor(205, 79, 57)),
+    ("tomato4", anstyle::RgbColor(139, 54, 38)),
+    ("turquoise", anstyle::RgbColor(64, 224, 208)),
+    ("turquoise1", anstyle::RgbColor(0, 245, 255)),
+    ("turquoise2", anstyle::RgbColor(0, 229, 238)),
+    ("turquoise3", anstyle::RgbColor(0, 197, 205)),
+    ("turquoise4", anstyle::RgbColor(0, 134, 139)),
+    ("violet", anstyle::RgbColor(238, 130, 238)),
+    ("violetred", anstyle::RgbColor(208, 32, 144)),
+    ("violetred1", anstyle::RgbColor(255, 62, 150)),
+    ("violetred2", anstyle::RgbColor(238, 58, 140)),
+    ("violetred3", anstyle::RgbColor(205, 50, 120)),
+    ("violetred4", anstyle::RgbColor(139, 34, 82)),
+    ("wheat", anstyle::RgbColor(245, 222, 179)),
+    ("wheat1", anstyle::RgbColor(255, 231, 186)),
+    ("wheat2", anstyle::RgbColor(238, 216, 174)),
+    ("wheat3", anstyle::RgbColor(205, 186, 150)),
+    ("wheat4", anstyle::RgbColor(139, 126, 102)),
+    ("white", anstyle::RgbColor(255, 255, 255)),
+    ("whitesmoke", anstyle::RgbColor(245, 245, 245)),
+    ("yellow", anstyle::RgbColor(255, 255, 0)),
+    ("yellow1", anstyle::RgbColor(255, 255, 0)),
+    ("yellow2", anstyle::RgbColor(238, 238, 0)),
+    ("yellow3", anstyle::RgbColor(205, 205, 0)),
+    ("yellow4", anstyle::RgbColor(139, 139, 0)),
+    ("yellowgreen", anstyle::RgbColor(154, 205, 50)),
+];
@@ -0,0 +1,181 @@
+//! Generate Vim `highlight` commands from `anstyle::Style`s
+//!
+//! Each style is written for both color terminals (`cterm*`) and the GUI (`gui*`), so a CLI's
+//! theme can be turned into a matching editor colorscheme.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let style = anstyle::Ansi256Color(208).on_default().bold();
+//! let command = anstyle_vim::highlight("Keyword", style, anstyle_lossy::palette::VGA);
+//! assert_eq!(
+//!     command,
+//!     "highlight Keyword cterm=bold ctermfg=208 ctermbg=NONE gui=bold guifg=#ff8700 guibg=NONE"
+//! );
+//! ```
+
+use std::fmt::Write as _;
+
+use anstyle::Effects;
+use anstyle_lossy::palette::Palette;
+
+/// Render a `highlight` command for `group`
+///
+/// `palette` is used for the GUI colors of 16-color and 256-color styles.  Effects Vim doesn't
+/// support, like [`Effects::DIMMED`], [`Effects::BLINK`], and [`Effects::HIDDEN`], are skipped.
+pub fn highlight(group: &str, style: anstyle::Style, palette: Palette) -> String {
+    let attrs = attrs(style.get_effects());
+    let mut command = format!("highlight {group} cterm={attrs}");
+    let _ = write!(
+        &mut command,
+        " ctermfg={} ctermbg={}",
+        cterm_color(style.get_fg_color()),
+        cterm_color(style.get_bg_color()),
+    );
+    let _ = write!(
+        &mut command,
+        " gui={attrs} guifg={} guibg={}",
+        gui_color(style.get_fg_color(), palette),
+        gui_color(style.get_bg_color(), palette),
+    );
+    if let Some(color) = style.get_underline_color() {
+        let _ = write!(
+            &mut command,
+            " ctermul={} guisp={}",
+            cterm_color(Some(color)),
+            gui_color(Some(color), palette),
+        );
+    }
+    command
+}
+
+/// Render a colorscheme script of `highlight` commands
+///
+/// # Examples
+///
+/// ```rust
+/// let groups = [
+///     ("Comment", anstyle::AnsiColor::BrightBlack.on_default().italic()),
+///     ("Error", anstyle::AnsiColor::Red.on_default().bold()),
+/// ];
+/// let script = anstyle_vim::colorscheme("mytheme", groups, anstyle_lossy::palette::VGA);
+/// assert!(script.starts_with("highlight clear\n"));
+/// assert!(script.contains("let g:colors_name = \"mytheme\"\n"));
+/// assert!(script.contains("highlight Error cterm=bold ctermfg=1"));
+/// ```
+pub fn colorscheme<'g>(
+    name: &str,
+    groups: impl IntoIterator<Item = (&'g str, anstyle::Style)>,
+    palette: Palette,
+) -> String {
+    let mut script = String::new();
+    script.push_str("highlight clear\n");
+    script.push_str("if exists(\"syntax_on\")\n  syntax reset\nendif\n");
+    let _ = writeln!(&mut script, "let g:colors_name = \"{name}\"");
+    for (group, style) in groups {
+        script.push_str(&highlight(group, style, palette));
+        script.push('\n');
+    }
+    script
+}
+
+const ATTRS: [(Effects, &str); 9] = [
+    (Effects::BOLD, "bold"),
+    (Effects::ITALIC, "italic"),
+    (Effects::UNDERLINE, "underline"),
+    (Effects::DOUBLE_UNDERLINE, "underdouble"),
+    (Effects::CURLY_UNDERLINE, "undercurl"),
+    (Effects::DOTTED_UNDERLINE, "underdotted"),
+    (Effects::DASHED_UNDERLINE, "underdashed"),
+    (Effects::INVERT, "reverse"),
+    (Effects::STRIKETHROUGH, "strikethrough"),
+];
+
+fn attrs(effects: Effects) -> String {
+    let attrs = ATTRS
+        .iter()
+        .filter(|(effect, _)| effects.contains(*effect))
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>();
+    if attrs.is_empty() {
+        "NONE".to_owned()
+    } else {
+        attrs.join(",")
+    }
+}
+
+fn cterm_color(color: Option<anstyle::Color>) -> String {
+    match color {
+        Some(color) => anstyle_lossy::color_to_xterm(color).index().to_string(),
+        None => "NONE".to_owned(),
+    }
+}
+
+fn gui_color(color: Option<anstyle::Color>, palette: Palette) -> String {
+    match color {
+        Some(color) => {
+            let rgb = anstyle_lossy::color_to_rgb(color, palette);
+            format!("#{:02x}{:02x}{:02x}", rgb.r(), rgb.g(), rgb.b())
+        }
+        None => "NONE".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use anstyle::AnsiColor::*;
+    use anstyle::*;
+
+    const PALETTE: Palette = anstyle_lossy::palette::VGA;
+
+    #[test]
+    fn plain() {
+        assert_eq!(
+            highlight("Normal", Style::new(), PALETTE),
+            "highlight Normal cterm=NONE ctermfg=NONE ctermbg=NONE gui=NONE guifg=NONE guibg=NONE"
+        );
+    }
+
+    #[test]
+    fn ansi() {
+        assert_eq!(
+            highlight("Error", BrightRed.on(Black), PALETTE),
+            "highlight Error cterm=NONE ctermfg=9 ctermbg=0 gui=NONE guifg=#ff5555 guibg=#000000"
+        );
+    }
+
+    #[test]
+    fn rgb() {
+        assert_eq!(
+            highlight("Type", RgbColor(0x5f, 0x87, 0xd7).on_default(), PALETTE),
+            "highlight Type cterm=NONE ctermfg=68 ctermbg=NONE gui=NONE guifg=#5f87d7 guibg=NONE"
+        );
+    }
+
+    #[test]
+    fn effects() {
+        let style = Style::new()
+            .bold()
+            .italic()
+            .invert()
+            .strikethrough()
+            .blink();
+        assert_eq!(
+            highlight("Todo", style, PALETTE),
+            "highlight Todo cterm=bold,italic,reverse,strikethrough ctermfg=NONE ctermbg=NONE gui=bold,italic,reverse,strikethrough guifg=NONE guibg=NONE"
+        );
+    }
+
+    #[test]
+    fn underline_color() {
+        let style = Style::new()
+            .effects(Effects::CURLY_UNDERLINE)
+            .underline_color(Some(Ansi256Color(196).into()));
+        assert_eq!(
+            highlight("SpellBad", style, PALETTE),
+            "highlight SpellBad cterm=undercurl ctermfg=NONE ctermbg=NONE gui=undercurl guifg=NONE guibg=NONE ctermul=196 guisp=#ff0000"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Collect [`anstyle::Style`]s from the [`vte`] parser
+//!
+//! For applications already embedding `vte` that want typed styles rather than raw SGR
+//! parameters.
+//!
+//! # Examples
+//!
+//! ```rust
+//! let mut parser = vte::Parser::new();
+//! let mut spans = anstyle_vte::SpanCollector::new();
+//! for byte in b"\x1b[1;31merror\x1b[0m: oops" {
+//!     parser.advance(&mut spans, *byte);
+//! }
+//!
+//! let spans = spans.into_spans();
+//! assert_eq!(spans[0].style, anstyle::AnsiColor::Red.on_default().bold());
+//! assert_eq!(spans[0].text, "error");
+//! assert_eq!(spans[1].style, anstyle::Style::new());
+//! assert_eq!(spans[1].text, ": oops");
+//! ```
+
+/// Apply the parameters of an SGR escape code (`CSI ... m`) to `style`
+///
+/// Returns the new style and whether every parameter was understood.
+#[inline]
+pub fn apply_sgr(style: anstyle::Style, params: &vte::Params) -> (anstyle::Style, bool) {
+    apply_sgr_with(style, params, anstyle::Sgr21::default())
+}
+
+/// Apply the parameters of an SGR escape code (`CSI ... m`) to `style`, interpreting SGR 21 as
+/// `sgr21`
+///
+/// Returns the new style and whether every parameter was understood.  See
+/// [`anstyle_parse::apply_sgr`].
+#[inline]
+pub fn apply_sgr_with(
+    style: anstyle::Style,
+    params: &vte::Params,
+    sgr21: anstyle::Sgr21,
+) -> (anstyle::Style, bool) {
+    anstyle_parse::apply_sgr(style, params.iter(), sgr21)
+}
+
+/// Text written with one style
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StyledSpan {
+    pub style: anstyle::Style,
+    pub text: String,
+}
+
+/// A [`vte::Perform`] that tracks the active style and collects text into [`StyledSpan`]s
+///
+/// Newlines and tabs are kept as text while other control characters and escape codes, besides
+/// SGR, are dropped.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SpanCollector {
+    style: anstyle::Style,
+    spans: Vec<StyledSpan>,
+}
+
+impl SpanCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The style that will apply to the next text
+    pub fn style(&self) -> anstyle::Style {
+        self.style
+    }
+
+    /// The spans collected so far
+    pub fn spans(&self) -> &[StyledSpan] {
+        &self.spans
+    }
+
+    /// Take the spans collected so far, keeping the active style
+    pub fn take_spans(&mut self) -> Vec<StyledSpan> {
+        std::mem::take(&mut self.spans)
+    }
+
+    pub fn into_spans(self) -> Vec<StyledSpan> {
+        self.spans
+    }
+
+    fn push(&mut self, c: char) {
+        match self.spans.last_mut() {
+            Some(span) if span.style == self.style => span.text.push(c),
+            _ => self.spans.push(StyledSpan {
+                style: self.style,
+                text: c.to_string(),
+            }),
+        }
+    }
+}
+
+impl vte::Perform for SpanCollector {
+    fn print(&mut self, c: char) {
+        self.push(c);
+    }
+
+    fn execute(&mut self, byte: u8) {
+        if byte == b'\n' || byte == b'\t' {
+            self.push(byte as char);
+        }
+    }
+
+    fn csi_dispatch(
+        &mut self,
+        params: &vte::Params,
+        intermediates: &[u8],
+        ignore: bool,
+        action: char,
+    ) {
+        if action == 'm' && intermediates.is_empty() && !ignore {
+            let (style, _) = apply_sgr(self.style, params);
+            self.style = style;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn collect(input: &str) -> Vec<StyledSpan> {
+        let mut parser = vte::Parser::new();
+        let mut spans = SpanCollector::new();
+        for byte in input.as_bytes() {
+            parser.advance(&mut spans, *byte);
+        }
+        spans.into_spans()
+    }
+
+    fn span(style: anstyle::Style, text: &str) -> StyledSpan {
+        StyledSpan {
+            style,
+            text: text.to_owned(),
+        }
+    }
+
+    #[test]
+    fn plain() {
+        assert_eq!(
+            collect("hello\n\tworld\r\x07"),
+            [span(anstyle::Style::new(), "hello\n\tworld")]
+        );
+    }
+
+    #[test]
+    fn styled() {
+        let red = anstyle::AnsiColor::Red.on_default();
+        assert_eq!(
+            collect("a\x1b[31mb\x1b[1mc\x1b[22md\x1b[me"),
+            [
+                span(anstyle::Style::new(), "a"),
+                span(red, "b"),
+                span(red.bold(), "c"),
+                span(red, "d"),
+                span(anstyle::Style::new(), "e"),
+            ]
+        );
+    }
+
+    #[test]
+    fn merges_same_style() {
+        assert_eq!(
+            collect("\x1b[32ma\x1b[32mb\x1b[2Kc"),
+            [span(anstyle::AnsiColor::Green.on_default(), "abc")]
+        );
+    }
+
+    #[test]
+    fn sgr21() {
+        struct Apply(anstyle::Style);
+        impl vte::Perform for Apply {
+            fn csi_dispatch(&mut self, params: &vte::Params, _: &[u8], _: bool, _: char) {
+                self.0 = apply_sgr_with(self.0, params, anstyle::Sgr21::BoldOff).0;
+            }
+        }
+
+        let mut parser = vte::Parser::new();
+        let mut apply = Apply(anstyle::Style::new());
+        for byte in b"\x1b[1;4m\x1b[21m" {
+            parser.advance(&mut apply, *byte);
+        }
+        assert_eq!(apply.0, anstyle::Style::new().underline());
+    }
+
+    #[test]
+    fn out_of_range_colors() {
+        assert_eq!(
+            collect("\x1b[38;5;300ma\x1b[48;2;256;0;0mb"),
+            [span(anstyle::Style::new(), "ab")]
+        );
+    }
+
+    #[test]
+    fn skips_empty_styles() {
+        assert_eq!(
+            collect("\x1b[1m\x1b[0mplain"),
+            [span(anstyle::Style::new(), "plain")]
+        );
+    }
+}
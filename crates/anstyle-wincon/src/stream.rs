@@ -7,6 +7,15 @@ pub trait WinconStream {
         bg: Option<anstyle::AnsiColor>,
         data: &[u8],
     ) -> std::io::Result<usize>;
+
+    /// Whether the console interprets ANSI escape codes itself, checked for this stream's handle
+    ///
+    /// This is `true` for a ConPTY, like in Windows Terminal, and for modern consoles, so ANSI
+    /// escape codes can be passed through rather than translated to console attributes.
+    /// Virtual terminal processing is enabled for the handle as a side effect.
+    fn supports_ansi(&self) -> bool {
+        false
+    }
 }
 
 impl WinconStream for Box<dyn std::io::Write> {
@@ -29,6 +38,10 @@ impl WinconStream for &'_ mut Box<dyn std::io::Write> {
     ) -> std::io::Result<usize> {
         (**self).write_colored(fg, bg, data)
     }
+
+    fn supports_ansi(&self) -> bool {
+        (**self).supports_ansi()
+    }
 }
 
 impl WinconStream for std::fs::File {
@@ -40,6 +53,11 @@ impl WinconStream for std::fs::File {
     ) -> std::io::Result<usize> {
         crate::ansi::write_colored(self, fg, bg, data)
     }
+
+    #[cfg(windows)]
+    fn supports_ansi(&self) -> bool {
+        crate::windows::enable_virtual_terminal_processing(self).is_ok()
+    }
 }
 
 impl WinconStream for &'_ mut std::fs::File {
@@ -51,6 +69,10 @@ impl WinconStream for &'_ mut std::fs::File {
     ) -> std::io::Result<usize> {
         (**self).write_colored(fg, bg, data)
     }
+
+    fn supports_ansi(&self) -> bool {
+        (**self).supports_ansi()
+    }
 }
 
 impl WinconStream for Vec<u8> {
@@ -85,6 +107,11 @@ impl WinconStream for std::io::Stdout {
         // Ensure exclusive access
         self.lock().write_colored(fg, bg, data)
     }
+
+    #[cfg(windows)]
+    fn supports_ansi(&self) -> bool {
+        crate::windows::enable_virtual_terminal_processing(self).is_ok()
+    }
 }
 
 impl WinconStream for std::io::Stderr {
@@ -97,6 +124,11 @@ impl WinconStream for std::io::Stderr {
         // Ensure exclusive access
         self.lock().write_colored(fg, bg, data)
     }
+
+    #[cfg(windows)]
+    fn supports_ansi(&self) -> bool {
+        crate::windows::enable_virtual_terminal_processing(self).is_ok()
+    }
 }
 
 #[cfg(not(windows))]
@@ -140,6 +172,10 @@ mod platform {
             let initial = crate::windows::stdout_initial_colors();
             crate::windows::write_colored(self, fg, bg, data, initial)
         }
+
+        fn supports_ansi(&self) -> bool {
+            crate::windows::enable_virtual_terminal_processing(self).is_ok()
+        }
     }
 
     impl WinconStream for std::io::StderrLock<'_> {
@@ -152,6 +188,10 @@ mod platform {
             let initial = crate::windows::stderr_initial_colors();
             crate::windows::write_colored(self, fg, bg, data, initial)
         }
+
+        fn supports_ansi(&self) -> bool {
+            crate::windows::enable_virtual_terminal_processing(self).is_ok()
+        }
     }
 }
 
@@ -164,6 +204,10 @@ impl WinconStream for &'_ mut std::io::StdoutLock<'_> {
     ) -> std::io::Result<usize> {
         (**self).write_colored(fg, bg, data)
     }
+
+    fn supports_ansi(&self) -> bool {
+        (**self).supports_ansi()
+    }
 }
 
 impl WinconStream for &'_ mut std::io::StderrLock<'_> {
@@ -175,4 +219,8 @@ impl WinconStream for &'_ mut std::io::StderrLock<'_> {
     ) -> std::io::Result<usize> {
         (**self).write_colored(fg, bg, data)
     }
+
+    fn supports_ansi(&self) -> bool {
+        (**self).supports_ansi()
+    }
 }
@@ -59,6 +59,17 @@ fn get_colors_<S: AsHandle>(stream: &S) -> StdioColorInnerResult {
     Ok((fg, bg))
 }
 
+/// Enable ANSI escape codes (`ENABLE_VIRTUAL_TERMINAL_PROCESSING`) for the console behind `stream`
+///
+/// This succeeds within a ConPTY, like in Windows Terminal, and for modern consoles.  Unlike
+/// `anstyle_query::windows::enable_ansi_colors`, only `stream`'s handle is checked, so a
+/// redirected stdout or stderr doesn't affect the other.
+pub fn enable_virtual_terminal_processing<S: AsHandle>(stream: &S) -> std::io::Result<()> {
+    let handle = stream.as_handle();
+    let handle = handle.as_raw_handle();
+    inner::enable_virtual_terminal_processing(handle).map_err(Into::into)
+}
+
 pub(crate) fn write_colored<S: AsHandle + std::io::Write>(
     stream: &mut S,
     fg: Option<anstyle::AnsiColor>,
@@ -87,7 +98,9 @@ pub(crate) fn write_colored<S: AsHandle + std::io::Write>(
 
 mod inner {
     use windows_sys::Win32::System::Console::CONSOLE_CHARACTER_ATTRIBUTES;
+    use windows_sys::Win32::System::Console::CONSOLE_MODE;
     use windows_sys::Win32::System::Console::CONSOLE_SCREEN_BUFFER_INFO;
+    use windows_sys::Win32::System::Console::ENABLE_VIRTUAL_TERMINAL_PROCESSING;
     use windows_sys::Win32::System::Console::FOREGROUND_BLUE;
     use windows_sys::Win32::System::Console::FOREGROUND_GREEN;
     use windows_sys::Win32::System::Console::FOREGROUND_INTENSITY;
@@ -163,6 +176,30 @@ mod inner {
         }
     }
 
+    pub(crate) fn enable_virtual_terminal_processing(handle: RawHandle) -> Result<(), IoError> {
+        unsafe {
+            let handle = std::mem::transmute(handle);
+            if handle == 0 {
+                return Err(IoError::BrokenPipe);
+            }
+
+            let mut mode: CONSOLE_MODE = 0;
+            if windows_sys::Win32::System::Console::GetConsoleMode(handle, &mut mode) == 0 {
+                return Err(IoError::last_os_error());
+            }
+            if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 {
+                return Ok(());
+            }
+
+            mode |= ENABLE_VIRTUAL_TERMINAL_PROCESSING;
+            if windows_sys::Win32::System::Console::SetConsoleMode(handle, mode) != 0 {
+                Ok(())
+            } else {
+                Err(IoError::last_os_error())
+            }
+        }
+    }
+
     pub(crate) fn get_colors(
         info: &CONSOLE_SCREEN_BUFFER_INFO,
     ) -> (anstyle::AnsiColor, anstyle::AnsiColor) {
@@ -0,0 +1,103 @@
+use arbitrary::Arbitrary;
+use arbitrary::Unstructured;
+
+use crate::Ansi256Color;
+use crate::AnsiColor;
+use crate::Color;
+use crate::Effects;
+use crate::RgbColor;
+use crate::Style;
+
+impl<'a> Arbitrary<'a> for AnsiColor {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let (color, _) = u.choose(&crate::color::ANSI_NAMES)?;
+        Ok(*color)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (1, Some(1))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Ansi256Color {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        u.arbitrary().map(Self)
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        u8::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for RgbColor {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let (r, g, b) = u.arbitrary()?;
+        Ok(Self(r, g, b))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        <(u8, u8, u8)>::size_hint(depth)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Color {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let color = match u.int_in_range(0..=2)? {
+            0 => Self::Ansi(u.arbitrary()?),
+            1 => Self::Ansi256(u.arbitrary()?),
+            _ => Self::Rgb(u.arbitrary()?),
+        };
+        Ok(color)
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (2, Some(4))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Effects {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        let all = Self::from_bits(u16::MAX >> (16 - crate::effect::METADATA.len()))
+            .expect("one bit per effect");
+        let bits = u.int_in_range(0..=all.bits())?;
+        Ok(Self::from_bits(bits).expect("within `all`"))
+    }
+
+    fn size_hint(_depth: usize) -> (usize, Option<usize>) {
+        (2, Some(2))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Style {
+    fn arbitrary(u: &mut Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(Self::new()
+            .fg_color(u.arbitrary()?)
+            .bg_color(u.arbitrary()?)
+            .underline_color(u.arbitrary()?)
+            .effects(u.arbitrary()?))
+    }
+
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and_all(&[
+            <Option<Color>>::size_hint(depth),
+            <Option<Color>>::size_hint(depth),
+            <Option<Color>>::size_hint(depth),
+            Effects::size_hint(depth),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn covers_every_effect() {
+        let all = crate::style::EFFECT_NAMES
+            .iter()
+            .fold(Effects::new(), |all, (effect, _)| all | *effect);
+        let data = [0xff; 2];
+        let effects = Effects::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(effects, all);
+    }
+}
@@ -0,0 +1,170 @@
+/// Render `value` at the end of `string`
+///
+/// Nothing is written when it doesn't fit, so escape codes are never cut off.
+///
+/// # Examples
+///
+/// ```rust
+/// let style = anstyle::AnsiColor::Red.on_default().bold();
+/// let mut string = heapless::String::<16>::new();
+/// anstyle::write_heapless(&mut string, style.render()).unwrap();
+/// assert_eq!(string, "\x1b[1m\x1b[31m");
+///
+/// let result = anstyle::write_heapless(&mut string, style.render());
+/// assert_eq!(result, Err(anstyle::Truncated));
+/// assert_eq!(string, "\x1b[1m\x1b[31m");
+/// ```
+#[cfg(feature = "heapless")]
+pub fn write_heapless<const N: usize>(
+    string: &mut heapless::String<N>,
+    value: impl core::fmt::Display,
+) -> Result<(), Truncated> {
+    write_bounded(string, value)
+}
+
+/// Render `value` at the end of `string`
+///
+/// Nothing is written when it doesn't fit, so escape codes are never cut off.
+///
+/// # Examples
+///
+/// ```rust
+/// let style = anstyle::AnsiColor::Red.on_default().bold();
+/// let mut string = arrayvec::ArrayString::<16>::new();
+/// anstyle::write_arrayvec(&mut string, style.render()).unwrap();
+/// assert_eq!(string.as_str(), "\x1b[1m\x1b[31m");
+///
+/// let result = anstyle::write_arrayvec(&mut string, style.render());
+/// assert_eq!(result, Err(anstyle::Truncated));
+/// assert_eq!(string.as_str(), "\x1b[1m\x1b[31m");
+/// ```
+#[cfg(feature = "arrayvec")]
+pub fn write_arrayvec<const N: usize>(
+    string: &mut arrayvec::ArrayString<N>,
+    value: impl core::fmt::Display,
+) -> Result<(), Truncated> {
+    write_bounded(string, value)
+}
+
+/// Rendering didn't fit in the space left
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Truncated;
+
+impl core::fmt::Display for Truncated {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("rendering exceeded the buffer's capacity")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Truncated {}
+
+/// A fixed-capacity string
+#[cfg(any(feature = "heapless", feature = "arrayvec"))]
+trait BoundedString {
+    fn len(&self) -> usize;
+
+    fn truncate(&mut self, len: usize);
+
+    /// Append `s`, failing without writing anything when it doesn't fit
+    fn try_push_str(&mut self, s: &str) -> Result<(), Truncated>;
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> BoundedString for heapless::String<N> {
+    fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    fn try_push_str(&mut self, s: &str) -> Result<(), Truncated> {
+        self.push_str(s).map_err(|()| Truncated)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const N: usize> BoundedString for arrayvec::ArrayString<N> {
+    fn len(&self) -> usize {
+        self.as_str().len()
+    }
+
+    fn truncate(&mut self, len: usize) {
+        self.truncate(len);
+    }
+
+    fn try_push_str(&mut self, s: &str) -> Result<(), Truncated> {
+        self.try_push_str(s).map_err(|_| Truncated)
+    }
+}
+
+#[cfg(any(feature = "heapless", feature = "arrayvec"))]
+fn write_bounded(
+    string: &mut impl BoundedString,
+    value: impl core::fmt::Display,
+) -> Result<(), Truncated> {
+    use core::fmt::Write as _;
+
+    let start = string.len();
+    let mut shim = Shim {
+        string,
+        truncated: false,
+    };
+    if write!(shim, "{value}").is_err() {
+        let truncated = shim.truncated;
+        string.truncate(start);
+        if truncated {
+            return Err(Truncated);
+        }
+        panic!("a Display implementation returned an error unexpectedly");
+    }
+    Ok(())
+}
+
+/// Record whether `fmt::Write` failed from running out of space
+#[cfg(any(feature = "heapless", feature = "arrayvec"))]
+struct Shim<'s, S> {
+    string: &'s mut S,
+    truncated: bool,
+}
+
+#[cfg(any(feature = "heapless", feature = "arrayvec"))]
+impl<S: BoundedString> core::fmt::Write for Shim<'_, S> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.string.try_push_str(s).map_err(|Truncated| {
+            self.truncated = true;
+            core::fmt::Error
+        })
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "heapless")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn exact_fit() {
+        let style = crate::RgbColor(255, 255, 255).on_default();
+        let mut string = heapless::String::<{ crate::RgbColor::MAX_RENDERED_LEN }>::new();
+        assert_eq!(
+            write_heapless(
+                &mut string,
+                style.render_with(crate::RenderOptions::new().colons(true))
+            ),
+            Ok(())
+        );
+        assert_eq!(string.len(), crate::RgbColor::MAX_RENDERED_LEN);
+    }
+
+    #[test]
+    fn partial_write_undone() {
+        let mut string = heapless::String::<8>::new();
+        string.push_str("ab").unwrap();
+        let style = crate::Style::new().bold().italic();
+        assert_eq!(write_heapless(&mut string, style.render()), Err(Truncated));
+        assert_eq!(string, "ab");
+    }
+}
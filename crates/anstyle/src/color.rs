@@ -7,6 +7,10 @@ pub enum Color {
 }
 
 impl Color {
+    /// The most bytes any color's code can render to, with any
+    /// [`RenderOptions`][crate::RenderOptions]
+    pub const MAX_RENDERED_LEN: usize = RgbColor::MAX_RENDERED_LEN;
+
     /// Create a [`Style`][crate::Style] with this as the foreground
     #[inline]
     pub fn on(self, background: impl Into<Color>) -> crate::Style {
@@ -26,8 +30,8 @@ impl Color {
     pub fn render_fg(self) -> impl core::fmt::Display + Copy + Clone {
         match self {
             Self::Ansi(color) => DisplayBuffer::default().write_str(color.as_fg_str()),
-            Self::Ansi256(color) => color.as_fg_buffer(),
-            Self::Rgb(color) => color.as_fg_buffer(),
+            Self::Ansi256(color) => color.as_fg_buffer(false),
+            Self::Rgb(color) => color.as_fg_buffer(false),
         }
     }
 
@@ -36,51 +40,106 @@ impl Color {
     pub(crate) fn write_fg_to(self, write: &mut dyn std::io::Write) -> std::io::Result<()> {
         let buffer = match self {
             Self::Ansi(color) => DisplayBuffer::default().write_str(color.as_fg_str()),
-            Self::Ansi256(color) => color.as_fg_buffer(),
-            Self::Rgb(color) => color.as_fg_buffer(),
+            Self::Ansi256(color) => color.as_fg_buffer(false),
+            Self::Rgb(color) => color.as_fg_buffer(false),
         };
         buffer.write_to(write)
     }
 
+    /// Render the ANSI code for a foreground color into `buffer`, returning what was written
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut buffer = [0; anstyle::Color::MAX_RENDERED_LEN];
+    /// let color = anstyle::Color::from(anstyle::Ansi256Color(208));
+    /// assert_eq!(color.render_fg_into(&mut buffer), "\x1b[38;5;208m");
+    /// ```
+    #[inline]
+    pub fn render_fg_into(self, buffer: &mut [u8; Self::MAX_RENDERED_LEN]) -> &str {
+        let mut writer = BufferWriter::new(buffer);
+        writer.write_str(self.as_fg_buffer(false).as_str());
+        writer.finish()
+    }
+
     /// Render the ANSI code for a background color
     #[inline]
     pub fn render_bg(self) -> impl core::fmt::Display + Copy + Clone {
         match self {
             Self::Ansi(color) => DisplayBuffer::default().write_str(color.as_bg_str()),
-            Self::Ansi256(color) => color.as_bg_buffer(),
-            Self::Rgb(color) => color.as_bg_buffer(),
+            Self::Ansi256(color) => color.as_bg_buffer(false),
+            Self::Rgb(color) => color.as_bg_buffer(false),
         }
     }
 
+    /// Render the ANSI code for a background color into `buffer`, returning what was written
+    #[inline]
+    pub fn render_bg_into(self, buffer: &mut [u8; Self::MAX_RENDERED_LEN]) -> &str {
+        let mut writer = BufferWriter::new(buffer);
+        writer.write_str(self.as_bg_buffer(false).as_str());
+        writer.finish()
+    }
+
     #[inline]
     #[cfg(feature = "std")]
     pub(crate) fn write_bg_to(self, write: &mut dyn std::io::Write) -> std::io::Result<()> {
         let buffer = match self {
             Self::Ansi(color) => DisplayBuffer::default().write_str(color.as_bg_str()),
-            Self::Ansi256(color) => color.as_bg_buffer(),
-            Self::Rgb(color) => color.as_bg_buffer(),
+            Self::Ansi256(color) => color.as_bg_buffer(false),
+            Self::Rgb(color) => color.as_bg_buffer(false),
         };
         buffer.write_to(write)
     }
 
     #[inline]
-    pub(crate) fn render_underline(self) -> impl core::fmt::Display + Copy + Clone {
+    #[cfg(feature = "std")]
+    pub(crate) fn write_underline_to(self, write: &mut dyn std::io::Write) -> std::io::Result<()> {
+        self.as_underline_buffer(false).write_to(write)
+    }
+
+    /// Render the ANSI code for a foreground color, with or without colon-separated sub-parameters
+    #[inline]
+    pub(crate) fn render_fg_with(self, colons: bool) -> impl core::fmt::Display + Copy {
+        self.as_fg_buffer(colons)
+    }
+
+    #[inline]
+    pub(crate) fn as_fg_buffer(self, colons: bool) -> DisplayBuffer {
         match self {
-            Self::Ansi(color) => color.as_underline_buffer(),
-            Self::Ansi256(color) => color.as_underline_buffer(),
-            Self::Rgb(color) => color.as_underline_buffer(),
+            Self::Ansi(color) => DisplayBuffer::default().write_str(color.as_fg_str()),
+            Self::Ansi256(color) => color.as_fg_buffer(colons),
+            Self::Rgb(color) => color.as_fg_buffer(colons),
         }
     }
 
+    /// Render the ANSI code for a background color, with or without colon-separated sub-parameters
     #[inline]
-    #[cfg(feature = "std")]
-    pub(crate) fn write_underline_to(self, write: &mut dyn std::io::Write) -> std::io::Result<()> {
-        let buffer = match self {
-            Self::Ansi(color) => color.as_underline_buffer(),
-            Self::Ansi256(color) => color.as_underline_buffer(),
-            Self::Rgb(color) => color.as_underline_buffer(),
-        };
-        buffer.write_to(write)
+    pub(crate) fn render_bg_with(self, colons: bool) -> impl core::fmt::Display + Copy {
+        self.as_bg_buffer(colons)
+    }
+
+    #[inline]
+    pub(crate) fn as_bg_buffer(self, colons: bool) -> DisplayBuffer {
+        match self {
+            Self::Ansi(color) => DisplayBuffer::default().write_str(color.as_bg_str()),
+            Self::Ansi256(color) => color.as_bg_buffer(colons),
+            Self::Rgb(color) => color.as_bg_buffer(colons),
+        }
+    }
+
+    /// Render the ANSI code for an underline color, with or without colon-separated sub-parameters
+    #[inline]
+    pub(crate) fn render_underline_with(self, colons: bool) -> impl core::fmt::Display + Copy {
+        self.as_underline_buffer(colons)
+    }
+
+    #[inline]
+    pub(crate) fn as_underline_buffer(self, colons: bool) -> DisplayBuffer {
+        match self {
+            Self::Ansi(color) => color.as_underline_buffer(colons),
+            Self::Ansi256(color) => color.as_underline_buffer(colons),
+            Self::Rgb(color) => color.as_underline_buffer(colons),
+        }
     }
 }
 
@@ -175,6 +234,12 @@ pub enum AnsiColor {
 }
 
 impl AnsiColor {
+    /// The most bytes the color's codes can render to, with any
+    /// [`RenderOptions`][crate::RenderOptions]
+    ///
+    /// This is for the underline color, like `\x1b[58;5;15m`.
+    pub const MAX_RENDERED_LEN: usize = "\x1b[58;5;15m".len();
+
     /// Create a [`Style`][crate::Style] with this as the foreground
     #[inline]
     pub fn on(self, background: impl Into<Color>) -> crate::Style {
@@ -246,9 +311,9 @@ impl AnsiColor {
     }
 
     #[inline]
-    fn as_underline_buffer(&self) -> DisplayBuffer {
+    fn as_underline_buffer(&self, colons: bool) -> DisplayBuffer {
         // No per-color codes; must delegate to `Ansi256Color`
-        Ansi256Color::from(*self).as_underline_buffer()
+        Ansi256Color::from(*self).as_underline_buffer(colons)
     }
 
     /// Change the color to/from bright
@@ -330,6 +395,10 @@ impl AnsiColor {
 pub struct Ansi256Color(pub u8);
 
 impl Ansi256Color {
+    /// The most bytes the color's codes can render to, with any
+    /// [`RenderOptions`][crate::RenderOptions]
+    pub const MAX_RENDERED_LEN: usize = "\x1b[38;5;255m".len();
+
     /// Create a [`Style`][crate::Style] with this as the foreground
     #[inline]
     pub fn on(self, background: impl Into<Color>) -> crate::Style {
@@ -397,13 +466,14 @@ impl Ansi256Color {
     /// Render the ANSI code for a foreground color
     #[inline]
     pub fn render_fg(self) -> impl core::fmt::Display + Copy + Clone {
-        self.as_fg_buffer()
+        self.as_fg_buffer(false)
     }
 
     #[inline]
-    fn as_fg_buffer(&self) -> DisplayBuffer {
+    fn as_fg_buffer(&self, colons: bool) -> DisplayBuffer {
+        let prefix = if colons { "\x1B[38:5:" } else { "\x1B[38;5;" };
         DisplayBuffer::default()
-            .write_str("\x1B[38;5;")
+            .write_str(prefix)
             .write_code(self.index())
             .write_str("m")
     }
@@ -411,21 +481,23 @@ impl Ansi256Color {
     /// Render the ANSI code for a background color
     #[inline]
     pub fn render_bg(self) -> impl core::fmt::Display + Copy + Clone {
-        self.as_bg_buffer()
+        self.as_bg_buffer(false)
     }
 
     #[inline]
-    fn as_bg_buffer(&self) -> DisplayBuffer {
+    fn as_bg_buffer(&self, colons: bool) -> DisplayBuffer {
+        let prefix = if colons { "\x1B[48:5:" } else { "\x1B[48;5;" };
         DisplayBuffer::default()
-            .write_str("\x1B[48;5;")
+            .write_str(prefix)
             .write_code(self.index())
             .write_str("m")
     }
 
     #[inline]
-    fn as_underline_buffer(&self) -> DisplayBuffer {
+    fn as_underline_buffer(&self, colons: bool) -> DisplayBuffer {
+        let prefix = if colons { "\x1B[58:5:" } else { "\x1B[58;5;" };
         DisplayBuffer::default()
-            .write_str("\x1B[58;5;")
+            .write_str(prefix)
             .write_code(self.index())
             .write_str("m")
     }
@@ -450,6 +522,10 @@ impl From<AnsiColor> for Ansi256Color {
 pub struct RgbColor(pub u8, pub u8, pub u8);
 
 impl RgbColor {
+    /// The most bytes the color's codes can render to, with any
+    /// [`RenderOptions`][crate::RenderOptions]
+    pub const MAX_RENDERED_LEN: usize = "\x1b[38:2::255:255:255m".len();
+
     /// Create a [`Style`][crate::Style] with this as the foreground
     #[inline]
     pub fn on(self, background: impl Into<Color>) -> crate::Style {
@@ -479,20 +555,55 @@ impl RgbColor {
         self.2
     }
 
+    /// Parse a hex code, like `#ff8800` or the shorthand `#f80`, with or without the `#`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// const ORANGE: Option<anstyle::RgbColor> = anstyle::RgbColor::from_hex("#ff8800");
+    /// assert_eq!(ORANGE, Some(anstyle::RgbColor(255, 136, 0)));
+    /// assert_eq!(anstyle::RgbColor::from_hex("F80"), ORANGE);
+    /// assert_eq!(anstyle::RgbColor::from_hex("#ff88"), None);
+    /// ```
+    pub const fn from_hex(hex: &str) -> Option<Self> {
+        let mut digits = hex.as_bytes();
+        if let [b'#', rest @ ..] = digits {
+            digits = rest;
+        }
+        match digits {
+            [r, g, b] => match (hex_digit(*r), hex_digit(*g), hex_digit(*b)) {
+                (Some(r), Some(g), Some(b)) => Some(Self(r * 0x11, g * 0x11, b * 0x11)),
+                _ => None,
+            },
+            [r1, r2, g1, g2, b1, b2] => {
+                match (hex_byte(*r1, *r2), hex_byte(*g1, *g2), hex_byte(*b1, *b2)) {
+                    (Some(r), Some(g), Some(b)) => Some(Self(r, g, b)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
     /// Render the ANSI code for a foreground color
     #[inline]
     pub fn render_fg(self) -> impl core::fmt::Display + Copy + Clone {
-        self.as_fg_buffer()
+        self.as_fg_buffer(false)
     }
 
     #[inline]
-    fn as_fg_buffer(&self) -> DisplayBuffer {
+    fn as_fg_buffer(&self, colons: bool) -> DisplayBuffer {
+        let (prefix, separator) = if colons {
+            ("\x1B[38:2::", ":")
+        } else {
+            ("\x1B[38;2;", ";")
+        };
         DisplayBuffer::default()
-            .write_str("\x1B[38;2;")
+            .write_str(prefix)
             .write_code(self.r())
-            .write_str(";")
+            .write_str(separator)
             .write_code(self.g())
-            .write_str(";")
+            .write_str(separator)
             .write_code(self.b())
             .write_str("m")
     }
@@ -500,34 +611,231 @@ impl RgbColor {
     /// Render the ANSI code for a background color
     #[inline]
     pub fn render_bg(self) -> impl core::fmt::Display + Copy + Clone {
-        self.as_bg_buffer()
+        self.as_bg_buffer(false)
     }
 
     #[inline]
-    fn as_bg_buffer(&self) -> DisplayBuffer {
+    fn as_bg_buffer(&self, colons: bool) -> DisplayBuffer {
+        let (prefix, separator) = if colons {
+            ("\x1B[48:2::", ":")
+        } else {
+            ("\x1B[48;2;", ";")
+        };
         DisplayBuffer::default()
-            .write_str("\x1B[48;2;")
+            .write_str(prefix)
             .write_code(self.r())
-            .write_str(";")
+            .write_str(separator)
             .write_code(self.g())
-            .write_str(";")
+            .write_str(separator)
             .write_code(self.b())
             .write_str("m")
     }
 
     #[inline]
-    fn as_underline_buffer(&self) -> DisplayBuffer {
+    fn as_underline_buffer(&self, colons: bool) -> DisplayBuffer {
+        let (prefix, separator) = if colons {
+            ("\x1B[58:2::", ":")
+        } else {
+            ("\x1B[58;2;", ";")
+        };
         DisplayBuffer::default()
-            .write_str("\x1B[58;2;")
+            .write_str(prefix)
             .write_code(self.r())
-            .write_str(";")
+            .write_str(separator)
             .write_code(self.g())
-            .write_str(";")
+            .write_str(separator)
             .write_code(self.b())
             .write_str("m")
     }
 }
 
+const fn hex_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+const fn hex_byte(high: u8, low: u8) -> Option<u8> {
+    match (hex_digit(high), hex_digit(low)) {
+        (Some(high), Some(low)) => Some(high << 4 | low),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "lossy")]
+impl RgbColor {
+    /// Approximate with the closest [`AnsiColor`] in `palette`
+    ///
+    /// `palette` holds the RGB value for each [`AnsiColor`], in [`Ansi256Color`] index order.
+    /// For popular palettes and other conversions, see
+    /// [anstyle-lossy](https://docs.rs/anstyle-lossy).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use anstyle::*;
+    /// let palette = [
+    ///     RgbColor(0, 0, 0),
+    ///     RgbColor(170, 0, 0),
+    ///     RgbColor(0, 170, 0),
+    ///     RgbColor(170, 85, 0),
+    ///     RgbColor(0, 0, 170),
+    ///     RgbColor(170, 0, 170),
+    ///     RgbColor(0, 170, 170),
+    ///     RgbColor(170, 170, 170),
+    ///     RgbColor(85, 85, 85),
+    ///     RgbColor(255, 85, 85),
+    ///     RgbColor(85, 255, 85),
+    ///     RgbColor(255, 255, 85),
+    ///     RgbColor(85, 85, 255),
+    ///     RgbColor(255, 85, 255),
+    ///     RgbColor(85, 255, 255),
+    ///     RgbColor(255, 255, 255),
+    /// ];
+    /// assert_eq!(RgbColor(200, 10, 20).to_ansi16(&palette), AnsiColor::Red);
+    /// ```
+    pub const fn to_ansi16(self, palette: &[RgbColor; 16]) -> AnsiColor {
+        self.find_ansi16(palette)
+    }
+
+    /// Approximate with the closest [`Ansi256Color`] in the 6x6x6 color cube or grayscale ramp
+    ///
+    /// The 16 ANSI colors are skipped as their values depend on the terminal's palette.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use anstyle::*;
+    /// assert_eq!(RgbColor(255, 135, 0).to_xterm(), Ansi256Color(208));
+    /// ```
+    pub const fn to_xterm(self) -> Ansi256Color {
+        self.find_xterm()
+    }
+}
+
+#[cfg(feature = "lossy")]
+impl Ansi256Color {
+    /// The RGB value xterm uses by default, looking up the ANSI colors in `palette`
+    ///
+    /// `palette` holds the RGB value for each [`AnsiColor`], in [`Ansi256Color`] index order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use anstyle::*;
+    /// let palette = [RgbColor(0, 0, 0); 16];
+    /// assert_eq!(Ansi256Color(208).to_rgb(&palette), RgbColor(255, 135, 0));
+    /// assert_eq!(Ansi256Color(244).to_rgb(&palette), RgbColor(128, 128, 128));
+    /// ```
+    pub const fn to_rgb(self, palette: &[RgbColor; 16]) -> RgbColor {
+        self.xterm_rgb(palette)
+    }
+}
+
+impl Ansi256Color {
+    /// The RGB value of the 6x6x6 color cube and grayscale ramp, falling back to `palette`
+    pub(crate) const fn xterm_rgb(self, palette: &[RgbColor; 16]) -> RgbColor {
+        const fn cube_level(level: u8) -> u8 {
+            if level == 0 {
+                0
+            } else {
+                55 + 40 * level
+            }
+        }
+
+        match self.0 {
+            0..=15 => palette[self.0 as usize],
+            16..=231 => {
+                let index = self.0 - 16;
+                RgbColor(
+                    cube_level(index / 36),
+                    cube_level(index / 6 % 6),
+                    cube_level(index % 6),
+                )
+            }
+            232..=255 => {
+                let gray = 8 + 10 * (self.0 - 232);
+                RgbColor(gray, gray, gray)
+            }
+        }
+    }
+}
+
+impl RgbColor {
+    /// The closest [`AnsiColor`] in `palette`, in [`Ansi256Color`] index order
+    pub(crate) const fn find_ansi16(self, palette: &[RgbColor; 16]) -> AnsiColor {
+        let mut best_index = 0;
+        let mut best_distance = self.distance(palette[best_index]);
+
+        let mut index = best_index + 1;
+        while index < palette.len() {
+            let distance = self.distance(palette[index]);
+            if distance < best_distance {
+                best_index = index;
+                best_distance = distance;
+            }
+
+            index += 1;
+        }
+
+        match Ansi256Color(best_index as u8).into_ansi() {
+            Some(color) => color,
+            None => {
+                // Panic
+                #[allow(clippy::no_effect)]
+                ["best_index is out of bounds"][best_index];
+                // Make compiler happy
+                AnsiColor::Black
+            }
+        }
+    }
+
+    /// The closest color in the 6x6x6 color cube or grayscale ramp
+    pub(crate) const fn find_xterm(self) -> Ansi256Color {
+        // The ANSI colors aren't used as the ramp and cube are independent of the palette
+        const PALETTE: [RgbColor; 16] = [RgbColor(0, 0, 0); 16];
+
+        let mut best_index = 16;
+        let mut best_distance = self.distance(Ansi256Color(best_index).xterm_rgb(&PALETTE));
+
+        let mut index = best_index;
+        while index < u8::MAX {
+            index += 1;
+            let distance = self.distance(Ansi256Color(index).xterm_rgb(&PALETTE));
+            if distance < best_distance {
+                best_index = index;
+                best_distance = distance;
+            }
+        }
+
+        Ansi256Color(best_index)
+    }
+
+    /// Low-cost approximation from <https://www.compuphase.com/cmetric.htm>, modified to avoid sqrt
+    pub(crate) const fn distance(self, other: RgbColor) -> u32 {
+        let c1_r = self.r() as i32;
+        let c1_g = self.g() as i32;
+        let c1_b = self.b() as i32;
+        let c2_r = other.r() as i32;
+        let c2_g = other.g() as i32;
+        let c2_b = other.b() as i32;
+
+        let r_sum = c1_r + c2_r;
+        let r_delta = c1_r - c2_r;
+        let g_delta = c1_g - c2_g;
+        let b_delta = c1_b - c2_b;
+
+        let r = (2 * 512 + r_sum) * r_delta * r_delta;
+        let g = 4 * g_delta * g_delta * (1 << 8);
+        let b = (2 * 767 - r_sum) * b_delta * b_delta;
+
+        (r + g + b) as u32
+    }
+}
+
 impl From<(u8, u8, u8)> for RgbColor {
     #[inline]
     fn from(inner: (u8, u8, u8)) -> Self {
@@ -537,8 +845,8 @@ impl From<(u8, u8, u8)> for RgbColor {
 }
 
 #[derive(Copy, Clone, Default, Debug)]
-struct DisplayBuffer {
-    buffer: [u8; 19],
+pub(crate) struct DisplayBuffer {
+    buffer: [u8; Color::MAX_RENDERED_LEN],
     len: usize,
 }
 
@@ -560,7 +868,7 @@ impl DisplayBuffer {
         let c2: u8 = (code / 10) % 10;
         let c3: u8 = code % 10;
 
-        let mut printed = true;
+        let mut printed = false;
         if c1 != 0 {
             printed = true;
             self.buffer[self.len] = b'0' + c1;
@@ -578,7 +886,7 @@ impl DisplayBuffer {
     }
 
     #[inline]
-    fn as_str(&self) -> &str {
+    pub(crate) fn as_str(&self) -> &str {
         // SAFETY: Only `&str` can be written to the buffer
         unsafe { core::str::from_utf8_unchecked(&self.buffer[0..self.len]) }
     }
@@ -590,6 +898,34 @@ impl DisplayBuffer {
     }
 }
 
+/// Copy rendered codes into a caller's buffer
+///
+/// The buffer must be large enough for everything written.
+pub(crate) struct BufferWriter<'b> {
+    buffer: &'b mut [u8],
+    len: usize,
+}
+
+impl<'b> BufferWriter<'b> {
+    #[inline]
+    pub(crate) fn new(buffer: &'b mut [u8]) -> Self {
+        Self { buffer, len: 0 }
+    }
+
+    #[inline]
+    pub(crate) fn write_str(&mut self, part: &str) {
+        let end = self.len + part.len();
+        self.buffer[self.len..end].copy_from_slice(part.as_bytes());
+        self.len = end;
+    }
+
+    #[inline]
+    pub(crate) fn finish(self) -> &'b str {
+        // SAFETY: Only `&str` can be written to the buffer
+        unsafe { core::str::from_utf8_unchecked(&self.buffer[0..self.len]) }
+    }
+}
+
 impl core::fmt::Display for DisplayBuffer {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
@@ -597,15 +933,214 @@ impl core::fmt::Display for DisplayBuffer {
     }
 }
 
+/// Parse a color name, a 256-color index, or an RGB hex code, like `#ff8700` or `#f80`
+///
+/// # Examples
+///
+/// ```rust
+/// use anstyle::Color;
+///
+/// assert_eq!("bright-blue".parse(), Ok(Color::from(anstyle::AnsiColor::BrightBlue)));
+/// assert_eq!("208".parse(), Ok(Color::from(anstyle::Ansi256Color(208))));
+/// assert_eq!("#ff8700".parse(), Ok(Color::from(anstyle::RgbColor(255, 135, 0))));
+/// assert_eq!("orange".parse::<Color>(), Err(anstyle::ParseColorError));
+/// ```
+impl core::str::FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with('#') {
+            RgbColor::from_hex(s).map(Self::Rgb).ok_or(ParseColorError)
+        } else if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+            s.parse::<u8>()
+                .map(|index| Ansi256Color(index).into())
+                .map_err(|_| ParseColorError)
+        } else {
+            s.parse::<AnsiColor>().map(Self::from)
+        }
+    }
+}
+
+/// Parse a color name, like `red` or `bright-blue`, ignoring case
+///
+/// # Examples
+///
+/// ```rust
+/// use anstyle::AnsiColor;
+///
+/// assert_eq!("Bright-Black".parse(), Ok(AnsiColor::BrightBlack));
+/// assert_eq!("grey".parse::<AnsiColor>(), Err(anstyle::ParseColorError));
+/// ```
+impl core::str::FromStr for AnsiColor {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ANSI_NAMES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(s))
+            .map(|(color, _)| *color)
+            .ok_or(ParseColorError)
+    }
+}
+
+/// Write `color` in the form [`Color::from_str`][core::str::FromStr::from_str] parses
+pub(crate) fn write_name(color: Color, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match color {
+        Color::Ansi(color) => f.write_str(ANSI_NAMES[color as usize].1),
+        Color::Ansi256(color) => write!(f, "{}", color.0),
+        Color::Rgb(color) => write!(f, "#{:02x}{:02x}{:02x}", color.0, color.1, color.2),
+    }
+}
+
+/// A color couldn't be parsed
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParseColorError;
+
+impl core::fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("expected a color name, a 256-color index, or `#rrggbb`")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseColorError {}
+
+pub(crate) const ANSI_NAMES: [(AnsiColor, &str); 16] = [
+    (AnsiColor::Black, "black"),
+    (AnsiColor::Red, "red"),
+    (AnsiColor::Green, "green"),
+    (AnsiColor::Yellow, "yellow"),
+    (AnsiColor::Blue, "blue"),
+    (AnsiColor::Magenta, "magenta"),
+    (AnsiColor::Cyan, "cyan"),
+    (AnsiColor::White, "white"),
+    (AnsiColor::BrightBlack, "bright-black"),
+    (AnsiColor::BrightRed, "bright-red"),
+    (AnsiColor::BrightGreen, "bright-green"),
+    (AnsiColor::BrightYellow, "bright-yellow"),
+    (AnsiColor::BrightBlue, "bright-blue"),
+    (AnsiColor::BrightMagenta, "bright-magenta"),
+    (AnsiColor::BrightCyan, "bright-cyan"),
+    (AnsiColor::BrightWhite, "bright-white"),
+];
+
 #[cfg(test)]
 #[cfg(feature = "std")]
 mod test {
     use super::*;
 
+    #[test]
+    fn from_str() {
+        for (color, name) in ANSI_NAMES {
+            assert_eq!(name.parse(), Ok(Color::Ansi(color)));
+        }
+        assert_eq!("0".parse(), Ok(Color::Ansi256(Ansi256Color(0))));
+        assert_eq!("#0A0b0C".parse(), Ok(Color::Rgb(RgbColor(10, 11, 12))));
+        assert_eq!("#FfF".parse(), Ok(Color::Rgb(RgbColor(255, 255, 255))));
+        for invalid in [
+            "",
+            "256",
+            "-1",
+            "+1",
+            "#ff870",
+            "##fff",
+            "#+f8700",
+            "#ff8700 ",
+            "bright blue",
+        ] {
+            assert_eq!(
+                invalid.parse::<Color>(),
+                Err(ParseColorError),
+                "{invalid:?}"
+            );
+        }
+    }
+
     #[test]
     fn max_display_buffer() {
         let c = RgbColor(255, 255, 255);
         let actual = c.render_fg().to_string();
         assert_eq!(actual, "\u{1b}[38;2;255;255;255m");
+
+        let c = Color::from(RgbColor(255, 255, 255));
+        let actual = c.render_underline_with(true).to_string();
+        assert_eq!(actual, "\u{1b}[58:2::255:255:255m");
+    }
+
+    #[test]
+    fn max_rendered_len() {
+        fn max_len(colors: impl Iterator<Item = Color>) -> usize {
+            colors
+                .flat_map(|color| {
+                    [false, true].map(|colons| {
+                        [
+                            color.as_fg_buffer(colons).as_str().len(),
+                            color.as_bg_buffer(colons).as_str().len(),
+                            color.as_underline_buffer(colons).as_str().len(),
+                        ]
+                    })
+                })
+                .flatten()
+                .max()
+                .unwrap()
+        }
+
+        let ansi = (0..16).map(|i| Color::from(Ansi256Color(i).into_ansi().unwrap()));
+        assert_eq!(max_len(ansi), AnsiColor::MAX_RENDERED_LEN);
+        let ansi256 = (0..=255).map(|i| Color::from(Ansi256Color(i)));
+        assert_eq!(max_len(ansi256), Ansi256Color::MAX_RENDERED_LEN);
+        let rgb = (0..=255).map(|i| Color::from(RgbColor(i, i, i)));
+        assert_eq!(max_len(rgb), RgbColor::MAX_RENDERED_LEN);
+    }
+
+    #[test]
+    fn render_into() {
+        let colors = [
+            Color::from(AnsiColor::BrightWhite),
+            Color::from(Ansi256Color(0)),
+            Color::from(RgbColor(255, 0, 10)),
+        ];
+        for color in colors {
+            let mut buffer = [0; Color::MAX_RENDERED_LEN];
+            assert_eq!(
+                color.render_fg_into(&mut buffer),
+                color.render_fg().to_string()
+            );
+            assert_eq!(
+                color.render_bg_into(&mut buffer),
+                color.render_bg().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn unpadded_display_buffer() {
+        let c = RgbColor(0, 5, 10);
+        let actual = c.render_fg().to_string();
+        assert_eq!(actual, "\u{1b}[38;2;0;5;10m");
+
+        let c = Ansi256Color(1);
+        let actual = c.render_bg().to_string();
+        assert_eq!(actual, "\u{1b}[48;5;1m");
+    }
+
+    #[test]
+    #[cfg(feature = "lossy")]
+    fn to_ansi16_exact() {
+        let palette: [RgbColor; 16] =
+            core::array::from_fn(|i| RgbColor(i as u8 * 16, i as u8 * 8, 255 - i as u8 * 16));
+        for (i, rgb) in palette.iter().enumerate() {
+            let expected = Ansi256Color(i as u8).into_ansi().unwrap();
+            assert_eq!(rgb.to_ansi16(&palette), expected);
+        }
+    }
+
+    #[test]
+    fn xterm_round_trip() {
+        let palette = [RgbColor(0, 0, 0); 16];
+        for index in 16..=255 {
+            let color = Ansi256Color(index);
+            assert_eq!(color.xterm_rgb(&palette).find_xterm(), color, "{index}");
+        }
     }
 }
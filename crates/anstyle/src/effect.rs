@@ -17,6 +17,7 @@ impl Effects {
     pub const ITALIC: Self = Effects(1 << 2);
     /// Style extensions exist for Kitty, VTE, mintty and iTerm2.
     pub const UNDERLINE: Self = Effects(1 << 3);
+    /// Rendered as SGR 21, which some terminals treat as "bold off"; see [`Sgr21`]
     pub const DOUBLE_UNDERLINE: Self = Effects(1 << 4);
     pub const CURLY_UNDERLINE: Self = Effects(1 << 5);
     pub const DOTTED_UNDERLINE: Self = Effects(1 << 6);
@@ -28,6 +29,9 @@ impl Effects {
     ///  Characters legible but marked as if for deletion. Not supported in Terminal.app
     pub const STRIKETHROUGH: Self = Effects(1 << 11);
 
+    /// The most bytes effects can render to, with any [`Sgr21`]
+    pub const MAX_RENDERED_LEN: usize = 56;
+
     /// No effects enabled
     ///
     /// # Examples
@@ -154,10 +158,70 @@ impl Effects {
         }
     }
 
+    /// The effects as bits, one per constant, in the order they are declared
+    #[cfg(any(feature = "serde", feature = "arbitrary"))]
+    pub(crate) const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// The effects from [`Effects::bits`], or `None` if any bit isn't an effect
+    #[cfg(any(feature = "serde", feature = "arbitrary"))]
+    pub(crate) const fn from_bits(bits: u16) -> Option<Self> {
+        if bits >> METADATA.len() == 0 {
+            Some(Self(bits))
+        } else {
+            None
+        }
+    }
+
     /// Render the ANSI code
     #[inline]
     pub fn render(self) -> impl core::fmt::Display + Copy + Clone {
-        EffectsDisplay(self)
+        EffectsDisplay(self, Sgr21::DoubleUnderline)
+    }
+
+    /// Render the ANSI code for a terminal with the given [`Sgr21`] interpretation
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let effects = anstyle::Effects::DOUBLE_UNDERLINE;
+    /// assert_eq!(effects.render().to_string(), "\x1b[21m");
+    /// assert_eq!(
+    ///     effects.render_with(anstyle::Sgr21::BoldOff).to_string(),
+    ///     "\x1b[4:2m"
+    /// );
+    /// ```
+    #[inline]
+    pub fn render_with(self, sgr21: Sgr21) -> impl core::fmt::Display + Copy + Clone {
+        EffectsDisplay(self, sgr21)
+    }
+
+    /// Render the ANSI code into `buffer`, returning what was written
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut buffer = [0; anstyle::Effects::MAX_RENDERED_LEN];
+    /// let effects = anstyle::Effects::BOLD | anstyle::Effects::CURLY_UNDERLINE;
+    /// assert_eq!(effects.render_into(&mut buffer), "\x1b[1m\x1b[4:3m");
+    /// ```
+    #[inline]
+    pub fn render_into(self, buffer: &mut [u8; Self::MAX_RENDERED_LEN]) -> &str {
+        let mut writer = crate::color::BufferWriter::new(buffer);
+        self.write_into(Sgr21::DoubleUnderline, &mut writer);
+        writer.finish()
+    }
+
+    #[inline]
+    pub(crate) fn write_into(self, sgr21: Sgr21, writer: &mut crate::color::BufferWriter<'_>) {
+        for index in self.index_iter() {
+            if index == DOUBLE_UNDERLINE_INDEX {
+                writer.write_str(sgr21.double_underline());
+            } else {
+                writer.write_str(METADATA[index].escape);
+            }
+        }
     }
 
     #[inline]
@@ -251,6 +315,61 @@ impl core::ops::SubAssign for Effects {
     }
 }
 
+/// How a terminal interprets SGR 21
+///
+/// ECMA-48 defines SGR 21 as double underline and that is how [`Effects::DOUBLE_UNDERLINE`] is
+/// rendered by default.  Some terminals, like older Linux consoles, instead treat it as "bold off".
+/// For those, [`Effects::render_with`] renders double underline as `4:2`, which both kinds of
+/// terminals understand, and parsers can use [`Sgr21::apply`] to interpret it either way.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Sgr21 {
+    /// SGR 21 is double underline
+    #[default]
+    DoubleUnderline,
+    /// SGR 21 turns off bold and dimmed, like SGR 22
+    BoldOff,
+}
+
+impl Sgr21 {
+    /// Apply SGR 21 to `effects`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let effects = anstyle::Effects::BOLD | anstyle::Effects::UNDERLINE;
+    /// assert_eq!(
+    ///     anstyle::Sgr21::DoubleUnderline.apply(effects),
+    ///     anstyle::Effects::BOLD | anstyle::Effects::DOUBLE_UNDERLINE
+    /// );
+    /// assert_eq!(
+    ///     anstyle::Sgr21::BoldOff.apply(effects),
+    ///     anstyle::Effects::UNDERLINE
+    /// );
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn apply(self, effects: Effects) -> Effects {
+        match self {
+            Self::DoubleUnderline => effects.remove(UNDERLINES).insert(Effects::DOUBLE_UNDERLINE),
+            Self::BoldOff => effects.remove(Effects::BOLD.insert(Effects::DIMMED)),
+        }
+    }
+
+    #[inline]
+    const fn double_underline(self) -> &'static str {
+        match self {
+            Self::DoubleUnderline => escape!("21"),
+            Self::BoldOff => escape!("4:2"),
+        }
+    }
+}
+
+pub(crate) const UNDERLINES: Effects = Effects::UNDERLINE
+    .insert(Effects::DOUBLE_UNDERLINE)
+    .insert(Effects::CURLY_UNDERLINE)
+    .insert(Effects::DOTTED_UNDERLINE)
+    .insert(Effects::DASHED_UNDERLINE);
+
 pub(crate) struct Metadata {
     pub(crate) name: &'static str,
     pub(crate) escape: &'static str,
@@ -308,18 +427,24 @@ pub(crate) const METADATA: [Metadata; 12] = [
 ];
 
 #[derive(Copy, Clone, Default, Debug)]
-struct EffectsDisplay(Effects);
+struct EffectsDisplay(Effects, Sgr21);
 
 impl core::fmt::Display for EffectsDisplay {
     #[inline]
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         for index in self.0.index_iter() {
-            METADATA[index].escape.fmt(f)?;
+            if index == DOUBLE_UNDERLINE_INDEX {
+                self.1.double_underline().fmt(f)?;
+            } else {
+                METADATA[index].escape.fmt(f)?;
+            }
         }
         Ok(())
     }
 }
 
+const DOUBLE_UNDERLINE_INDEX: usize = Effects::DOUBLE_UNDERLINE.0.trailing_zeros() as usize;
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct EffectIter {
     index: usize,
@@ -17,23 +17,36 @@
 //! 3. `const` friendly API for callers to statically define their stylesheet
 //!
 //! For integration with text styling crate, see:
+//! - [anstyle-anes](https://docs.rs/anstyle-anes)
 //! - [anstyle-ansi-term](https://docs.rs/anstyle-ansi-term)
+//! - [anstyle-colored](https://docs.rs/anstyle-colored)
+//! - [anstyle-console](https://docs.rs/anstyle-console)
 //! - [anstyle-crossterm](https://docs.rs/anstyle-crossterm)
+//! - [anstyle-nu-ansi-term](https://docs.rs/anstyle-nu-ansi-term)
 //! - [anstyle-owo-colors](https://docs.rs/anstyle-owo-colors)
+//! - [anstyle-ratatui](https://docs.rs/anstyle-ratatui)
 //! - [anstyle-termcolor](https://docs.rs/anstyle-termcolor)
+//! - [anstyle-termwiz](https://docs.rs/anstyle-termwiz)
 //! - [anstyle-yansi](https://docs.rs/anstyle-yansi)
 //!
 //! User-styling parsers:
 //! - [anstyle-git](https://docs.rs/anstyle-git): Parse Git style descriptions
 //! - [anstyle-ls](https://docs.rs/anstyle-ls): Parse LS_COLORS style descriptions
+//! - [anstyle-tmux](https://docs.rs/anstyle-tmux): Parse tmux style strings
 //!
 //! Convert to other formats
 //! - [anstream](https://docs.rs/anstream): A simple cross platform library for writing colored text to a terminal
 //! - [anstyle-roff](https://docs.rs/anstyle-roff): For converting to ROFF
+//! - [anstyle-richtext](https://docs.rs/anstyle-richtext): For resolving to RGB spans for GUI rich text
+//! - [anstyle-shell](https://docs.rs/anstyle-shell): For rendering styles in shell prompts
+//! - [anstyle-vim](https://docs.rs/anstyle-vim): For generating Vim `highlight` commands
 //!
 //! Utilities
 //! - [anstyle-lossy](https://docs.rs/anstyle-lossy): Convert between `anstyle::Color` types
+//! - [anstyle-markup](https://docs.rs/anstyle-markup): Write styled text with inline markup
 //! - [anstyle-parse](https://docs.rs/anstyle-parse): Parsing ANSI Style Escapes
+//! - [anstyle-regex](https://docs.rs/anstyle-regex): Colorize text with regex rules
+//! - [anstyle-vte](https://docs.rs/anstyle-vte): Collect styled spans with the `vte` parser
 //! - [anstyle-wincon](https://docs.rs/anstyle-wincon): Styling legacy Microsoft terminals
 //!
 //! # Examples
@@ -44,16 +57,31 @@
 //! ```
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impl;
+mod bounded;
 mod color;
 mod effect;
+mod lines;
+mod osc;
+mod profile;
+mod render;
 mod reset;
+#[cfg(feature = "serde")]
+mod serde_impl;
 mod style;
 
+pub use bounded::*;
 pub use color::*;
 pub use effect::*;
+pub use lines::*;
+pub use osc::*;
+pub use profile::*;
+pub use render::*;
 pub use reset::*;
 pub use style::*;
@@ -0,0 +1,116 @@
+/// Text with a [`Style`][crate::Style] applied to each line
+///
+/// Created by [`Style::lines`][crate::Style::lines].  As [`Display`][core::fmt::Display], this
+/// keeps the line endings of the original text.  As an [`Iterator`], this yields each line
+/// without its line ending, like [`str::lines`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StyledLines<'t> {
+    style: crate::Style,
+    text: &'t str,
+}
+
+impl<'t> StyledLines<'t> {
+    #[inline]
+    pub(crate) fn new(style: crate::Style, text: &'t str) -> Self {
+        Self { style, text }
+    }
+}
+
+impl<'t> Iterator for StyledLines<'t> {
+    type Item = StyledLine<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.text.is_empty() {
+            return None;
+        }
+
+        let (line, rest) = match self.text.find('\n') {
+            Some(index) => (&self.text[..index], &self.text[index + 1..]),
+            None => (self.text, ""),
+        };
+        self.text = rest;
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        Some(StyledLine {
+            style: self.style,
+            text: line,
+        })
+    }
+}
+
+impl core::fmt::Display for StyledLines<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, line) in self.text.split('\n').enumerate() {
+            if i != 0 {
+                f.write_str("\n")?;
+            }
+            let (line, cr) = match line.strip_suffix('\r') {
+                Some(line) => (line, "\r"),
+                None => (line, ""),
+            };
+            StyledLine {
+                style: self.style,
+                text: line,
+            }
+            .fmt(f)?;
+            f.write_str(cr)?;
+        }
+        Ok(())
+    }
+}
+
+/// A single line from [`StyledLines`], rendered with its style and reset
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct StyledLine<'t> {
+    style: crate::Style,
+    text: &'t str,
+}
+
+impl<'t> StyledLine<'t> {
+    /// The unstyled text of the line
+    #[inline]
+    pub fn text(&self) -> &'t str {
+        self.text
+    }
+}
+
+impl core::fmt::Display for StyledLine<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.text.is_empty() {
+            return Ok(());
+        }
+        write!(
+            f,
+            "{}{}{}",
+            self.style.render(),
+            self.text,
+            self.style.render_reset()
+        )
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    #[test]
+    fn display_line_endings() {
+        let style = crate::Style::new().bold();
+        let actual = style.lines("a\r\n\nb").to_string();
+        assert_eq!(actual, "\x1b[1ma\x1b[0m\r\n\n\x1b[1mb\x1b[0m");
+    }
+
+    #[test]
+    fn iter_matches_str_lines() {
+        let style = crate::Style::new().bold();
+        for text in ["", "a", "a\n", "a\r\nb", "\n\nc\n"] {
+            let actual = style.lines(text).map(|l| l.text()).collect::<Vec<_>>();
+            let expected = text.lines().collect::<Vec<_>>();
+            assert_eq!(actual, expected, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn plain() {
+        let actual = crate::Style::new().lines("a\nb").to_string();
+        assert_eq!(actual, "a\nb");
+    }
+}
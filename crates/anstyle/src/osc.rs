@@ -0,0 +1,411 @@
+/// Set the terminal's window title with OSC 2
+///
+/// Control characters in the title are dropped so they can't end the sequence early.
+///
+/// # Examples
+///
+/// ```rust
+/// let title = anstyle::Title::new("vim: main.rs");
+/// assert_eq!(title.render().to_string(), "\x1b]2;vim: main.rs\x1b\\");
+///
+/// let title = title.icon_name(true).bell(true);
+/// assert_eq!(title.render().to_string(), "\x1b]0;vim: main.rs\x07");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Title<'t> {
+    text: &'t str,
+    icon_name: bool,
+    bell: bool,
+}
+
+impl<'t> Title<'t> {
+    /// Set the window title to `text`
+    #[inline]
+    pub const fn new(text: &'t str) -> Self {
+        Self {
+            text,
+            icon_name: false,
+            bell: false,
+        }
+    }
+
+    /// Also set the icon name, with OSC 0
+    #[must_use]
+    #[inline]
+    pub const fn icon_name(mut self, yes: bool) -> Self {
+        self.icon_name = yes;
+        self
+    }
+
+    /// End the sequence with `BEL`, rather than `ESC \`
+    ///
+    /// Some older terminals only recognize `BEL`.
+    #[must_use]
+    #[inline]
+    pub const fn bell(mut self, yes: bool) -> Self {
+        self.bell = yes;
+        self
+    }
+
+    /// Get the title
+    #[inline]
+    pub const fn get_text(self) -> &'t str {
+        self.text
+    }
+
+    /// Check if the icon name is also set
+    #[inline]
+    pub const fn get_icon_name(self) -> bool {
+        self.icon_name
+    }
+
+    /// Check if the sequence ends with `BEL`
+    #[inline]
+    pub const fn get_bell(self) -> bool {
+        self.bell
+    }
+
+    /// Render the OSC code
+    #[inline]
+    pub fn render(self) -> impl core::fmt::Display + Copy + 't {
+        TitleDisplay(self)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct TitleDisplay<'t>(Title<'t>);
+
+impl core::fmt::Display for TitleDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let kind = if self.0.icon_name { "0" } else { "2" };
+        write!(f, "\x1b]{kind};")?;
+        write_text(f, self.0.text, None)?;
+        write_terminator(f, self.0.bell)
+    }
+}
+
+/// Report the current working directory with OSC 7
+///
+/// Terminals use this to open new tabs and windows in the same directory.  The path is sent as a
+/// `file://` URL, percent-encoding anything that could be misread, like spaces and `;`.
+///
+/// # Examples
+///
+/// ```rust
+/// let cwd = anstyle::WorkingDirectory::new("/home/me/my project").host("laptop");
+/// assert_eq!(
+///     cwd.render().to_string(),
+///     "\x1b]7;file://laptop/home/me/my%20project\x1b\\"
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WorkingDirectory<'p> {
+    path: &'p str,
+    host: &'p str,
+    bell: bool,
+}
+
+impl<'p> WorkingDirectory<'p> {
+    /// Report `path`, an absolute path with `/` separators
+    #[inline]
+    pub const fn new(path: &'p str) -> Self {
+        Self {
+            path,
+            host: "",
+            bell: false,
+        }
+    }
+
+    /// The machine `path` is on, usually the hostname
+    ///
+    /// Without it, terminals assume the path is local.
+    #[must_use]
+    #[inline]
+    pub const fn host(mut self, host: &'p str) -> Self {
+        self.host = host;
+        self
+    }
+
+    /// End the sequence with `BEL`, rather than `ESC \`
+    ///
+    /// Some older terminals only recognize `BEL`.
+    #[must_use]
+    #[inline]
+    pub const fn bell(mut self, yes: bool) -> Self {
+        self.bell = yes;
+        self
+    }
+
+    /// Get the path
+    #[inline]
+    pub const fn get_path(self) -> &'p str {
+        self.path
+    }
+
+    /// Get the host, empty when the path is local
+    #[inline]
+    pub const fn get_host(self) -> &'p str {
+        self.host
+    }
+
+    /// Check if the sequence ends with `BEL`
+    #[inline]
+    pub const fn get_bell(self) -> bool {
+        self.bell
+    }
+
+    /// Render the OSC code
+    #[inline]
+    pub fn render(self) -> impl core::fmt::Display + Copy + 'p {
+        WorkingDirectoryDisplay(self)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct WorkingDirectoryDisplay<'p>(WorkingDirectory<'p>);
+
+impl core::fmt::Display for WorkingDirectoryDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("\x1b]7;file://")?;
+        write_percent_encoded(f, self.0.host, b"")?;
+        if !self.0.path.starts_with('/') {
+            f.write_str("/")?;
+        }
+        write_percent_encoded(f, self.0.path, b"/:@")?;
+        write_terminator(f, self.0.bell)
+    }
+}
+
+/// An escape sequence for showing a desktop notification
+///
+/// Terminals support one or the other, if either; see `anstyle_query::notification` for
+/// detecting which.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub enum NotificationProtocol {
+    /// OSC 9, from iTerm2, with only a body
+    ///
+    /// The title is shown at the start of the body.
+    #[default]
+    Osc9,
+    /// OSC 777, from rxvt-unicode's `notify` extension, with a title and body
+    Osc777,
+}
+
+/// Show a desktop notification, like when a long-running command finishes
+///
+/// Control characters are dropped so they can't end the sequence early.
+///
+/// # Examples
+///
+/// ```rust
+/// let notification = anstyle::Notification::new("tests passed").title("cargo");
+/// assert_eq!(
+///     notification.render().to_string(),
+///     "\x1b]9;cargo: tests passed\x1b\\"
+/// );
+///
+/// let notification = notification.protocol(anstyle::NotificationProtocol::Osc777);
+/// assert_eq!(
+///     notification.render().to_string(),
+///     "\x1b]777;notify;cargo;tests passed\x1b\\"
+/// );
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Notification<'t> {
+    title: &'t str,
+    body: &'t str,
+    protocol: NotificationProtocol,
+    bell: bool,
+}
+
+impl<'t> Notification<'t> {
+    /// Notify with `body` and no title
+    #[inline]
+    pub const fn new(body: &'t str) -> Self {
+        Self {
+            title: "",
+            body,
+            protocol: NotificationProtocol::Osc9,
+            bell: false,
+        }
+    }
+
+    /// Set the title
+    ///
+    /// With [`NotificationProtocol::Osc777`], any `;` in the title is replaced with `,` as it
+    /// would otherwise end the title.
+    #[must_use]
+    #[inline]
+    pub const fn title(mut self, title: &'t str) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Select the escape sequence to render
+    #[must_use]
+    #[inline]
+    pub const fn protocol(mut self, protocol: NotificationProtocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
+
+    /// End the sequence with `BEL`, rather than `ESC \`
+    ///
+    /// Some older terminals only recognize `BEL`.
+    #[must_use]
+    #[inline]
+    pub const fn bell(mut self, yes: bool) -> Self {
+        self.bell = yes;
+        self
+    }
+
+    /// Get the title, empty when there is none
+    #[inline]
+    pub const fn get_title(self) -> &'t str {
+        self.title
+    }
+
+    /// Get the body
+    #[inline]
+    pub const fn get_body(self) -> &'t str {
+        self.body
+    }
+
+    /// Get the escape sequence rendered
+    #[inline]
+    pub const fn get_protocol(self) -> NotificationProtocol {
+        self.protocol
+    }
+
+    /// Check if the sequence ends with `BEL`
+    #[inline]
+    pub const fn get_bell(self) -> bool {
+        self.bell
+    }
+
+    /// Render the OSC code
+    #[inline]
+    pub fn render(self) -> impl core::fmt::Display + Copy + 't {
+        NotificationDisplay(self)
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct NotificationDisplay<'t>(Notification<'t>);
+
+impl core::fmt::Display for NotificationDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let Notification {
+            title,
+            body,
+            protocol,
+            bell,
+        } = self.0;
+        match protocol {
+            NotificationProtocol::Osc9 => {
+                f.write_str("\x1b]9;")?;
+                if !title.is_empty() {
+                    write_text(f, title, None)?;
+                    f.write_str(": ")?;
+                }
+            }
+            NotificationProtocol::Osc777 => {
+                f.write_str("\x1b]777;notify;")?;
+                write_text(f, title, Some((';', ',')))?;
+                f.write_str(";")?;
+            }
+        }
+        write_text(f, body, None)?;
+        write_terminator(f, bell)
+    }
+}
+
+/// Write `text` without control characters, applying `replace`
+fn write_text(
+    f: &mut core::fmt::Formatter<'_>,
+    text: &str,
+    replace: Option<(char, char)>,
+) -> core::fmt::Result {
+    for c in text.chars().filter(|c| !c.is_control()) {
+        let c = match replace {
+            Some((from, to)) if c == from => to,
+            _ => c,
+        };
+        write!(f, "{c}")?;
+    }
+    Ok(())
+}
+
+fn write_percent_encoded(
+    f: &mut core::fmt::Formatter<'_>,
+    text: &str,
+    allowed: &[u8],
+) -> core::fmt::Result {
+    for byte in text.bytes() {
+        let unreserved = byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~');
+        if unreserved || allowed.contains(&byte) {
+            write!(f, "{}", byte as char)?;
+        } else {
+            write!(f, "%{byte:02X}")?;
+        }
+    }
+    Ok(())
+}
+
+fn write_terminator(f: &mut core::fmt::Formatter<'_>, bell: bool) -> core::fmt::Result {
+    if bell {
+        f.write_str("\x07")
+    } else {
+        f.write_str("\x1b\\")
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn title_controls_dropped() {
+        let title = Title::new("a\x1b\\b\x07c\u{9c}d");
+        assert_eq!(title.render().to_string(), "\x1b]2;a\\bcd\x1b\\");
+    }
+
+    #[test]
+    fn notification_osc777() {
+        let notification = Notification::new("a;b\x07")
+            .title("c;d")
+            .protocol(NotificationProtocol::Osc777)
+            .bell(true);
+        assert_eq!(
+            notification.render().to_string(),
+            "\x1b]777;notify;c,d;a;b\x07"
+        );
+    }
+
+    #[test]
+    fn notification_untitled() {
+        let notification = Notification::new("done");
+        assert_eq!(notification.render().to_string(), "\x1b]9;done\x1b\\");
+        let notification = notification.protocol(NotificationProtocol::Osc777);
+        assert_eq!(
+            notification.render().to_string(),
+            "\x1b]777;notify;;done\x1b\\"
+        );
+    }
+
+    #[test]
+    fn cwd_encoding() {
+        let cwd = WorkingDirectory::new("/tmp/50%;ü").host("a/b");
+        assert_eq!(
+            cwd.render().to_string(),
+            "\x1b]7;file://a%2Fb/tmp/50%25%3B%C3%BC\x1b\\"
+        );
+    }
+
+    #[test]
+    fn cwd_relative() {
+        let cwd = WorkingDirectory::new("C:/Users").bell(true);
+        assert_eq!(cwd.render().to_string(), "\x1b]7;file:///C:/Users\x07");
+    }
+}
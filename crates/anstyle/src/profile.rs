@@ -0,0 +1,415 @@
+use crate::Color;
+use crate::Effects;
+use crate::RgbColor;
+use crate::Style;
+
+/// How many colors a terminal can display
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ColorLevel {
+    /// No color support
+    None,
+    /// The 16 [ANSI colors](https://en.wikipedia.org/wiki/ANSI_escape_code#3-bit_and_4-bit)
+    Ansi,
+    /// The 256 [xterm colors](https://en.wikipedia.org/wiki/ANSI_escape_code#8-bit)
+    Ansi256,
+    /// 24-bit [RGB colors](https://en.wikipedia.org/wiki/ANSI_escape_code#24-bit)
+    Rgb,
+}
+
+/// The styling a terminal supports, for degrading styles gracefully
+///
+/// See [`Style::render_for`].
+///
+/// # Examples
+///
+/// ```rust
+/// let profile = anstyle::Profile::new()
+///     .colors(anstyle::ColorLevel::Ansi256)
+///     .italic(false);
+/// let style = anstyle::RgbColor(255, 135, 0).on_default().italic();
+/// assert_eq!(style.render_for(profile).to_string(), "\x1b[38;5;208m");
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Profile {
+    colors: ColorLevel,
+    effects: bool,
+    italic: bool,
+    dimmed: bool,
+    strikethrough: bool,
+    styled_underlines: bool,
+    hyperlinks: bool,
+}
+
+impl Profile {
+    /// xterm, with `TERM=xterm`
+    ///
+    /// Many terminals claim to be xterm, so this is what they generally have in common.
+    pub const XTERM: Self = Self::new()
+        .colors(ColorLevel::Ansi)
+        .styled_underlines(false)
+        .hyperlinks(false);
+
+    /// xterm, with `TERM=xterm-256color`
+    pub const XTERM_256COLOR: Self = Self::XTERM.colors(ColorLevel::Ansi256);
+
+    /// The legacy Windows console (conhost), as emulated through the console API
+    pub const WINDOWS_CONSOLE: Self = Self::new()
+        .colors(ColorLevel::Ansi)
+        .italic(false)
+        .styled_underlines(false)
+        .hyperlinks(false);
+
+    /// The Linux virtual console, with `TERM=linux`
+    ///
+    /// Italic and strikethrough are shown as colors, if at all.
+    pub const LINUX_CONSOLE: Self = Self::new()
+        .colors(ColorLevel::Ansi)
+        .italic(false)
+        .strikethrough(false)
+        .styled_underlines(false)
+        .hyperlinks(false);
+
+    /// GNU screen, with `TERM=screen`
+    pub const SCREEN: Self = Self::new()
+        .colors(ColorLevel::Ansi)
+        .italic(false)
+        .strikethrough(false)
+        .styled_underlines(false)
+        .hyperlinks(false);
+
+    /// tmux, with `TERM=tmux`
+    pub const TMUX: Self = Self::new().colors(ColorLevel::Ansi).hyperlinks(false);
+
+    /// rxvt-unicode, with `TERM=rxvt-unicode`
+    ///
+    /// Its default palette has 88 colors, which 256-color indices would be misread with.
+    pub const RXVT_UNICODE: Self = Self::new()
+        .colors(ColorLevel::Ansi)
+        .dimmed(false)
+        .strikethrough(false)
+        .styled_underlines(false)
+        .hyperlinks(false);
+
+    /// rxvt, with `TERM=rxvt`
+    pub const RXVT: Self = Self::RXVT_UNICODE.italic(false);
+
+    /// A terminal without any styling, with `TERM=dumb`
+    pub const DUMB: Self = Self::new()
+        .colors(ColorLevel::None)
+        .effects(false)
+        .hyperlinks(false);
+
+    /// The GitHub Actions log viewer
+    pub const GITHUB_ACTIONS: Self = Self::new().styled_underlines(false).hyperlinks(false);
+
+    /// Everything is supported
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            colors: ColorLevel::Rgb,
+            effects: true,
+            italic: true,
+            dimmed: true,
+            strikethrough: true,
+            styled_underlines: true,
+            hyperlinks: true,
+        }
+    }
+
+    /// Look up a built-in profile
+    ///
+    /// Other than `conhost` and `github-actions`, names are `TERM` values.
+    ///
+    /// | Name | Profile |
+    /// |------|---------|
+    /// | `xterm` | [`Profile::XTERM`] |
+    /// | `xterm-256color` | [`Profile::XTERM_256COLOR`] |
+    /// | `xterm-kitty`, `xterm-ghostty` | [`Profile::new`] |
+    /// | `conhost` | [`Profile::WINDOWS_CONSOLE`] |
+    /// | `linux` | [`Profile::LINUX_CONSOLE`] |
+    /// | `screen` | [`Profile::SCREEN`] |
+    /// | `tmux` | [`Profile::TMUX`] |
+    /// | `rxvt-unicode` | [`Profile::RXVT_UNICODE`] |
+    /// | `rxvt` | [`Profile::RXVT`] |
+    /// | `dumb` | [`Profile::DUMB`] |
+    /// | `github-actions` | [`Profile::GITHUB_ACTIONS`] |
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let profile = anstyle::Profile::from_name("linux").unwrap();
+    /// assert_eq!(profile.get_colors(), anstyle::ColorLevel::Ansi);
+    /// assert_eq!(anstyle::Profile::from_name("vt52"), None);
+    /// ```
+    pub fn from_name(name: &str) -> Option<Self> {
+        BUILTIN
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, profile)| *profile)
+    }
+
+    /// Most colors the terminal can display
+    ///
+    /// Colors beyond this are approximated with the closest supported color, using xterm's
+    /// default palette for the ANSI colors.
+    #[must_use]
+    #[inline]
+    pub const fn colors(mut self, colors: ColorLevel) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Whether text effects, like bold and underline, are rendered
+    #[must_use]
+    #[inline]
+    pub const fn effects(mut self, yes: bool) -> Self {
+        self.effects = yes;
+        self
+    }
+
+    /// Whether italic text is rendered, rather than ignored or shown as a color
+    #[must_use]
+    #[inline]
+    pub const fn italic(mut self, yes: bool) -> Self {
+        self.italic = yes;
+        self
+    }
+
+    /// Whether dimmed text is rendered
+    #[must_use]
+    #[inline]
+    pub const fn dimmed(mut self, yes: bool) -> Self {
+        self.dimmed = yes;
+        self
+    }
+
+    /// Whether strikethrough text is rendered
+    #[must_use]
+    #[inline]
+    pub const fn strikethrough(mut self, yes: bool) -> Self {
+        self.strikethrough = yes;
+        self
+    }
+
+    /// Whether double, curly, dotted, and dashed underlines and underline colors are rendered
+    ///
+    /// Without them, styled underlines become a plain underline without its color.
+    #[must_use]
+    #[inline]
+    pub const fn styled_underlines(mut self, yes: bool) -> Self {
+        self.styled_underlines = yes;
+        self
+    }
+
+    /// Whether OSC 8 hyperlinks are supported
+    ///
+    /// Styles don't carry links, so this is for callers deciding whether to write them.
+    #[must_use]
+    #[inline]
+    pub const fn hyperlinks(mut self, yes: bool) -> Self {
+        self.hyperlinks = yes;
+        self
+    }
+
+    /// Get the most colors the terminal can display
+    #[inline]
+    pub const fn get_colors(self) -> ColorLevel {
+        self.colors
+    }
+
+    /// Check if text effects are rendered
+    #[inline]
+    pub const fn get_effects(self) -> bool {
+        self.effects
+    }
+
+    /// Check if italic text is rendered
+    #[inline]
+    pub const fn get_italic(self) -> bool {
+        self.italic
+    }
+
+    /// Check if dimmed text is rendered
+    #[inline]
+    pub const fn get_dimmed(self) -> bool {
+        self.dimmed
+    }
+
+    /// Check if strikethrough text is rendered
+    #[inline]
+    pub const fn get_strikethrough(self) -> bool {
+        self.strikethrough
+    }
+
+    /// Check if styled underlines and underline colors are rendered
+    #[inline]
+    pub const fn get_styled_underlines(self) -> bool {
+        self.styled_underlines
+    }
+
+    /// Check if OSC 8 hyperlinks are supported
+    #[inline]
+    pub const fn get_hyperlinks(self) -> bool {
+        self.hyperlinks
+    }
+
+    /// Drop or substitute what `style` uses that isn't supported
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let profile = anstyle::Profile::new()
+    ///     .colors(anstyle::ColorLevel::Ansi)
+    ///     .styled_underlines(false);
+    /// let style = anstyle::Ansi256Color(196).on_default() | anstyle::Effects::CURLY_UNDERLINE;
+    /// assert_eq!(
+    ///     profile.adapt(style),
+    ///     anstyle::AnsiColor::BrightRed.on_default() | anstyle::Effects::UNDERLINE
+    /// );
+    /// ```
+    pub const fn adapt(self, style: Style) -> Style {
+        let mut effects = style.get_effects();
+        let mut underline = style.get_underline_color();
+        if !self.effects {
+            effects = Effects::new();
+            underline = None;
+        }
+        if !self.italic {
+            effects = effects.remove(Effects::ITALIC);
+        }
+        if !self.dimmed {
+            effects = effects.remove(Effects::DIMMED);
+        }
+        if !self.strikethrough {
+            effects = effects.remove(Effects::STRIKETHROUGH);
+        }
+        if !self.styled_underlines {
+            let styled = effects.contains(Effects::DOUBLE_UNDERLINE)
+                || effects.contains(Effects::CURLY_UNDERLINE)
+                || effects.contains(Effects::DOTTED_UNDERLINE)
+                || effects.contains(Effects::DASHED_UNDERLINE);
+            if styled {
+                effects = effects.remove(STYLED_UNDERLINES).insert(Effects::UNDERLINE);
+            }
+            underline = None;
+        }
+
+        style
+            .effects(effects)
+            .fg_color(self.adapt_color(style.get_fg_color()))
+            .bg_color(self.adapt_color(style.get_bg_color()))
+            .underline_color(self.adapt_color(underline))
+    }
+
+    const fn adapt_color(self, color: Option<Color>) -> Option<Color> {
+        let Some(color) = color else {
+            return None;
+        };
+        let color = match (self.colors, color) {
+            (ColorLevel::None, _) => return None,
+            (ColorLevel::Rgb, color)
+            | (ColorLevel::Ansi256, color @ (Color::Ansi(_) | Color::Ansi256(_)))
+            | (ColorLevel::Ansi, color @ Color::Ansi(_)) => color,
+            (ColorLevel::Ansi256, Color::Rgb(rgb)) => Color::Ansi256(rgb.find_xterm()),
+            (ColorLevel::Ansi, Color::Ansi256(index)) => match index.into_ansi() {
+                Some(ansi) => Color::Ansi(ansi),
+                None => Color::Ansi(index.xterm_rgb(&XTERM_ANSI).find_ansi16(&XTERM_ANSI)),
+            },
+            (ColorLevel::Ansi, Color::Rgb(rgb)) => Color::Ansi(rgb.find_ansi16(&XTERM_ANSI)),
+        };
+        Some(color)
+    }
+}
+
+impl Default for Profile {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Known terminals and log viewers, the one place their limitations are recorded
+const BUILTIN: [(&str, Profile); 12] = [
+    ("xterm", Profile::XTERM),
+    ("xterm-256color", Profile::XTERM_256COLOR),
+    // Terminals with their own terminfo entries but named as xterm variants
+    ("xterm-kitty", Profile::new()),
+    ("xterm-ghostty", Profile::new()),
+    ("conhost", Profile::WINDOWS_CONSOLE),
+    ("linux", Profile::LINUX_CONSOLE),
+    ("screen", Profile::SCREEN),
+    ("tmux", Profile::TMUX),
+    ("rxvt-unicode", Profile::RXVT_UNICODE),
+    ("rxvt", Profile::RXVT),
+    ("dumb", Profile::DUMB),
+    ("github-actions", Profile::GITHUB_ACTIONS),
+];
+
+const STYLED_UNDERLINES: Effects = Effects::DOUBLE_UNDERLINE
+    .insert(Effects::CURLY_UNDERLINE)
+    .insert(Effects::DOTTED_UNDERLINE)
+    .insert(Effects::DASHED_UNDERLINE);
+
+/// xterm's default colors for [`AnsiColor`][crate::AnsiColor]
+const XTERM_ANSI: [RgbColor; 16] = [
+    RgbColor(0, 0, 0),
+    RgbColor(205, 0, 0),
+    RgbColor(0, 205, 0),
+    RgbColor(205, 205, 0),
+    RgbColor(0, 0, 238),
+    RgbColor(205, 0, 205),
+    RgbColor(0, 205, 205),
+    RgbColor(229, 229, 229),
+    RgbColor(127, 127, 127),
+    RgbColor(255, 0, 0),
+    RgbColor(0, 255, 0),
+    RgbColor(255, 255, 0),
+    RgbColor(92, 92, 255),
+    RgbColor(255, 0, 255),
+    RgbColor(0, 255, 255),
+    RgbColor(255, 255, 255),
+];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AnsiColor;
+
+    #[test]
+    fn no_colors() {
+        let profile = Profile::new().colors(ColorLevel::None);
+        let style = AnsiColor::Red.on(AnsiColor::Blue).bold();
+        assert_eq!(profile.adapt(style), Style::new().bold());
+    }
+
+    #[test]
+    fn rgb_to_ansi() {
+        let profile = Profile::new().colors(ColorLevel::Ansi);
+        let style = RgbColor(10, 10, 200).on(RgbColor(250, 250, 250));
+        assert_eq!(
+            profile.adapt(style),
+            AnsiColor::Blue.on(AnsiColor::BrightWhite)
+        );
+    }
+
+    #[test]
+    fn dumb() {
+        let style = RgbColor(10, 10, 200).on_default().bold().underline();
+        assert_eq!(Profile::DUMB.adapt(style), Style::new());
+    }
+
+    #[test]
+    fn linux_console() {
+        let style = Style::new().italic().dimmed().strikethrough();
+        assert_eq!(Profile::LINUX_CONSOLE.adapt(style), Style::new().dimmed());
+    }
+
+    #[test]
+    fn plain_underline_kept() {
+        let profile = Profile::new().styled_underlines(false);
+        let style = Style::new()
+            .underline()
+            .underline_color(Some(AnsiColor::Red.into()));
+        assert_eq!(profile.adapt(style), Style::new().underline());
+    }
+}
@@ -0,0 +1,66 @@
+/// How to render escape codes, for terminals that disagree on their form
+///
+/// # Examples
+///
+/// ```rust
+/// let options = anstyle::RenderOptions::new().colons(true);
+/// let style = anstyle::RgbColor(255, 128, 64).on(anstyle::Ansi256Color(17));
+/// assert_eq!(
+///     style.render_with(options).to_string(),
+///     "\x1b[38:2::255:128:64m\x1b[48:5:17m"
+/// );
+/// ```
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
+pub struct RenderOptions {
+    sgr21: crate::Sgr21,
+    colons: bool,
+}
+
+impl RenderOptions {
+    /// The most widely supported forms
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            sgr21: crate::Sgr21::DoubleUnderline,
+            colons: false,
+        }
+    }
+
+    /// Render for a terminal with the given [`Sgr21`][crate::Sgr21] interpretation
+    #[must_use]
+    #[inline]
+    pub const fn sgr21(mut self, sgr21: crate::Sgr21) -> Self {
+        self.sgr21 = sgr21;
+        self
+    }
+
+    /// Separate the parts of extended colors with colons, like `38:2::r:g:b` and `58:5:n`
+    ///
+    /// The colon form is what ITU T.416 specifies while the semicolon form, the default, is what
+    /// most terminals and parsers have historically supported.
+    #[must_use]
+    #[inline]
+    pub const fn colons(mut self, yes: bool) -> Self {
+        self.colons = yes;
+        self
+    }
+
+    /// Get the [`Sgr21`][crate::Sgr21] interpretation rendered for
+    #[inline]
+    pub const fn get_sgr21(self) -> crate::Sgr21 {
+        self.sgr21
+    }
+
+    /// Check if extended colors are separated with colons
+    #[inline]
+    pub const fn get_colons(self) -> bool {
+        self.colons
+    }
+}
+
+impl From<crate::Sgr21> for RenderOptions {
+    #[inline]
+    fn from(sgr21: crate::Sgr21) -> Self {
+        Self::new().sgr21(sgr21)
+    }
+}
@@ -0,0 +1,333 @@
+//! In human-readable formats, colors and effects are written by name, like `"bright-blue"` or
+//! `"#ff8700"`, and styles as their spec, like `"bold fg:red"`.  In binary formats, they are
+//! written as numbers.
+
+use serde::de;
+use serde::ser::SerializeSeq as _;
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+use serde::Serializer;
+
+use crate::Ansi256Color;
+use crate::AnsiColor;
+use crate::Color;
+use crate::Effects;
+use crate::RgbColor;
+use crate::Style;
+
+impl Serialize for AnsiColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(crate::color::ANSI_NAMES[*self as usize].1)
+        } else {
+            serializer.serialize_u8(*self as u8)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AnsiColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StrVisitor("an ANSI color name", |s: &str| s.parse().ok()))
+        } else {
+            let index = u8::deserialize(deserializer)?;
+            ansi_from_index(index).ok_or_else(|| {
+                de::Error::invalid_value(de::Unexpected::Unsigned(index.into()), &"0 through 15")
+            })
+        }
+    }
+}
+
+impl Serialize for Ansi256Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ansi256Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u8::deserialize(deserializer).map(Self)
+    }
+}
+
+impl Serialize for RgbColor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&ColorName(Color::Rgb(*self)))
+        } else {
+            (self.0, self.1, self.2).serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RgbColor {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StrVisitor("an RGB color, like `#ff8700`", |s: &str| {
+                match s.parse() {
+                    Ok(Color::Rgb(color)) => Some(color),
+                    _ => None,
+                }
+            }))
+        } else {
+            let (r, g, b) = Deserialize::deserialize(deserializer)?;
+            Ok(Self(r, g, b))
+        }
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&ColorName(*self))
+        } else {
+            // The kind of color in the top byte
+            let packed = match *self {
+                Self::Ansi(color) => color as u32,
+                Self::Ansi256(color) => 1 << 24 | u32::from(color.0),
+                Self::Rgb(color) => {
+                    2 << 24
+                        | u32::from(color.0) << 16
+                        | u32::from(color.1) << 8
+                        | u32::from(color.2)
+                }
+            };
+            serializer.serialize_u32(packed)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StrVisitor(
+                "a color name, a 256-color index, or `#rrggbb`",
+                |s: &str| s.parse().ok(),
+            ))
+        } else {
+            let packed = u32::deserialize(deserializer)?;
+            let [kind, r, g, b] = packed.to_be_bytes();
+            let color = match kind {
+                0 if r == 0 && g == 0 => ansi_from_index(b).map(Self::Ansi),
+                1 if r == 0 && g == 0 => Some(Self::Ansi256(Ansi256Color(b))),
+                2 => Some(Self::Rgb(RgbColor(r, g, b))),
+                _ => None,
+            };
+            color.ok_or_else(|| {
+                de::Error::invalid_value(de::Unexpected::Unsigned(packed.into()), &"a packed color")
+            })
+        }
+    }
+}
+
+impl Serialize for Effects {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let names = crate::style::EFFECT_NAMES
+                .iter()
+                .filter(|(effect, _)| self.contains(*effect));
+            let mut seq = serializer.serialize_seq(Some(names.clone().count()))?;
+            for (_, name) in names {
+                seq.serialize_element(name)?;
+            }
+            seq.end()
+        } else {
+            serializer.serialize_u16(self.bits())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Effects {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_seq(EffectsVisitor)
+        } else {
+            let bits = u16::deserialize(deserializer)?;
+            Self::from_bits(bits).ok_or_else(|| {
+                de::Error::invalid_value(de::Unexpected::Unsigned(bits.into()), &"effect bits")
+            })
+        }
+    }
+}
+
+impl Serialize for Style {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            (
+                self.get_fg_color(),
+                self.get_bg_color(),
+                self.get_underline_color(),
+                self.get_effects(),
+            )
+                .serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Style {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(StrVisitor("a style, like `bold fg:red`", |s: &str| {
+                s.parse().ok()
+            }))
+        } else {
+            let (fg, bg, underline, effects) = Deserialize::deserialize(deserializer)?;
+            Ok(Self::new()
+                .fg_color(fg)
+                .bg_color(bg)
+                .underline_color(underline)
+                .effects(effects))
+        }
+    }
+}
+
+fn ansi_from_index(index: u8) -> Option<AnsiColor> {
+    crate::color::ANSI_NAMES
+        .get(usize::from(index))
+        .map(|(color, _)| *color)
+}
+
+struct ColorName(Color);
+
+impl core::fmt::Display for ColorName {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        crate::color::write_name(self.0, f)
+    }
+}
+
+/// Parse a string, without needing to allocate
+struct StrVisitor<F>(&'static str, F);
+
+impl<'de, T, F> de::Visitor<'de> for StrVisitor<F>
+where
+    F: Fn(&str) -> Option<T>,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.0)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        (self.1)(v).ok_or_else(|| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+}
+
+struct EffectsVisitor;
+
+impl<'de> de::Visitor<'de> for EffectsVisitor {
+    type Value = Effects;
+
+    fn expecting(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("a list of effect names")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut effects = Effects::new();
+        while let Some(EffectName(effect)) = seq.next_element()? {
+            effects |= effect;
+        }
+        Ok(effects)
+    }
+}
+
+struct EffectName(Effects);
+
+impl<'de> Deserialize<'de> for EffectName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(StrVisitor("an effect name, like `bold`", |s: &str| {
+            crate::style::EFFECT_NAMES
+                .iter()
+                .find(|(_, name)| name.eq_ignore_ascii_case(s))
+                .map(|(effect, _)| EffectName(*effect))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_test::assert_tokens;
+    use serde_test::Configure as _;
+    use serde_test::Token;
+
+    #[test]
+    fn readable() {
+        assert_tokens(
+            &AnsiColor::BrightBlue.readable(),
+            &[Token::Str("bright-blue")],
+        );
+        assert_tokens(&Ansi256Color(208).readable(), &[Token::U8(208)]);
+        assert_tokens(&RgbColor(255, 135, 0).readable(), &[Token::Str("#ff8700")]);
+        assert_tokens(
+            &Color::from(Ansi256Color(208)).readable(),
+            &[Token::Str("208")],
+        );
+        assert_tokens(
+            &(Effects::BOLD | Effects::CURLY_UNDERLINE).readable(),
+            &[
+                Token::Seq { len: Some(2) },
+                Token::Str("bold"),
+                Token::Str("curly-underline"),
+                Token::SeqEnd,
+            ],
+        );
+        assert_tokens(
+            &AnsiColor::Red.on(RgbColor(34, 34, 34)).bold().readable(),
+            &[Token::Str("bold fg:red bg:#222222")],
+        );
+    }
+
+    #[test]
+    fn compact() {
+        assert_tokens(&AnsiColor::BrightBlue.compact(), &[Token::U8(12)]);
+        assert_tokens(
+            &RgbColor(255, 135, 0).compact(),
+            &[
+                Token::Tuple { len: 3 },
+                Token::U8(255),
+                Token::U8(135),
+                Token::U8(0),
+                Token::TupleEnd,
+            ],
+        );
+        assert_tokens(
+            &Color::from(Ansi256Color(208)).compact(),
+            &[Token::U32(0x0100_00d0)],
+        );
+        assert_tokens(
+            &AnsiColor::Red.on(RgbColor(34, 34, 34)).bold().compact(),
+            &[
+                Token::Tuple { len: 4 },
+                Token::Some,
+                Token::U32(1),
+                Token::Some,
+                Token::U32(0x0222_2222),
+                Token::None,
+                Token::U16(1),
+                Token::TupleEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        use serde_test::assert_de_tokens_error;
+
+        assert_de_tokens_error::<serde_test::Compact<AnsiColor>>(
+            &[Token::U8(16)],
+            "invalid value: integer `16`, expected 0 through 15",
+        );
+        assert_de_tokens_error::<serde_test::Readable<Color>>(
+            &[Token::Str("orange")],
+            "invalid value: string \"orange\", expected a color name, a 256-color index, or `#rrggbb`",
+        );
+        assert_de_tokens_error::<serde_test::Compact<Effects>>(
+            &[Token::U16(1 << 12)],
+            "invalid value: integer `4096`, expected effect bits",
+        );
+    }
+}
@@ -17,6 +17,12 @@ pub struct Style {
 
 /// # Core
 impl Style {
+    /// The most bytes a style can render to, with any [`RenderOptions`][crate::RenderOptions]
+    ///
+    /// For stack-allocating a buffer for [`Style::render_into`].
+    pub const MAX_RENDERED_LEN: usize =
+        crate::Effects::MAX_RENDERED_LEN + 3 * crate::Color::MAX_RENDERED_LEN;
+
     /// No effects enabled
     ///
     /// # Examples
@@ -93,7 +99,70 @@ impl Style {
     /// Render the ANSI code
     #[inline]
     pub fn render(self) -> impl core::fmt::Display + Copy + Clone {
-        StyleDisplay(self)
+        StyleDisplay(self, crate::RenderOptions::new())
+    }
+
+    /// Render the ANSI code into `buffer`, returning what was written
+    ///
+    /// Unlike [`Style::render`], this can't fail and doesn't go through [`core::fmt`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let mut buffer = [0; anstyle::Style::MAX_RENDERED_LEN];
+    /// let style = anstyle::AnsiColor::Red.on_default().bold();
+    /// assert_eq!(style.render_into(&mut buffer), "\x1b[1m\x1b[31m");
+    /// ```
+    #[inline]
+    pub fn render_into(self, buffer: &mut [u8; Self::MAX_RENDERED_LEN]) -> &str {
+        let mut writer = crate::color::BufferWriter::new(buffer);
+        self.effects
+            .write_into(crate::Sgr21::DoubleUnderline, &mut writer);
+        if let Some(fg) = self.fg {
+            writer.write_str(fg.as_fg_buffer(false).as_str());
+        }
+        if let Some(bg) = self.bg {
+            writer.write_str(bg.as_bg_buffer(false).as_str());
+        }
+        if let Some(underline) = self.underline {
+            writer.write_str(underline.as_underline_buffer(false).as_str());
+        }
+        writer.finish()
+    }
+
+    /// Render the ANSI code in the forms chosen by [`RenderOptions`][crate::RenderOptions]
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let style = anstyle::Style::new().effects(anstyle::Effects::DOUBLE_UNDERLINE);
+    /// assert_eq!(
+    ///     style.render_with(anstyle::Sgr21::BoldOff).to_string(),
+    ///     "\x1b[4:2m"
+    /// );
+    /// ```
+    #[inline]
+    pub fn render_with(
+        self,
+        options: impl Into<crate::RenderOptions>,
+    ) -> impl core::fmt::Display + Copy + Clone {
+        StyleDisplay(self, options.into())
+    }
+
+    /// Render the ANSI code, dropping or substituting what `profile` doesn't support
+    ///
+    /// See [`Profile::adapt`][crate::Profile::adapt].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let profile = anstyle::Profile::new().colors(anstyle::ColorLevel::None);
+    /// let style = anstyle::AnsiColor::Red.on_default().bold();
+    /// assert_eq!(style.render_for(profile).to_string(), "\x1b[1m");
+    /// ```
+    #[inline]
+    pub fn render_for(self, profile: crate::Profile) -> impl core::fmt::Display + Copy + Clone {
+        StyleDisplay(profile.adapt(self), crate::RenderOptions::new())
     }
 
     /// Write the ANSI code
@@ -141,6 +210,45 @@ impl Style {
             Ok(())
         }
     }
+
+    /// Render the ANSI code to change from this style to `next`
+    ///
+    /// Only what differs is changed, like `\x1b[22m` to drop bold, unless resetting and rendering
+    /// `next` is shorter.  This keeps output small when rendering many adjacent styled cells.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let warning = anstyle::AnsiColor::Yellow.on_default().bold();
+    /// let note = anstyle::AnsiColor::Cyan.on_default().bold();
+    /// assert_eq!(warning.transition_to(note).to_string(), "\x1b[36m");
+    /// assert_eq!(note.transition_to(note.effects(anstyle::Effects::new())).to_string(), "\x1b[22m");
+    /// assert_eq!(note.transition_to(anstyle::Style::new()).to_string(), "\x1b[0m");
+    /// ```
+    #[inline]
+    pub fn transition_to(self, next: Style) -> impl core::fmt::Display + Copy {
+        StyleTransition(self, next)
+    }
+
+    /// Apply the style to each line of `text`
+    ///
+    /// The style is rendered at the start of every line and reset before every line ending, so
+    /// each line is styled on its own for tools like `grep`, `head`, and pagers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// let style = anstyle::AnsiColor::Red.on_default();
+    /// let text = style.lines("one\ntwo\n").to_string();
+    /// assert_eq!(text, "\x1b[31mone\x1b[0m\n\x1b[31mtwo\x1b[0m\n");
+    ///
+    /// let lines = style.lines("one\ntwo\n").map(|l| l.to_string()).collect::<Vec<_>>();
+    /// assert_eq!(lines, ["\x1b[31mone\x1b[0m", "\x1b[31mtwo\x1b[0m"]);
+    /// ```
+    #[inline]
+    pub fn lines(self, text: &str) -> crate::StyledLines<'_> {
+        crate::StyledLines::new(self, text)
+    }
 }
 
 /// # Convenience
@@ -374,25 +482,326 @@ impl core::cmp::PartialEq<crate::Effects> for Style {
     }
 }
 
+/// Write the style as a spec, like `bold underline fg:red bg:#222222`
+///
+/// This is for storing styles in config files and environment variables.  Effects are listed by
+/// name, like `double-underline`, and colors are prefixed with `fg:`, `bg:`, or
+/// `underline-color:`.  Colors are written in the forms [`Color`][crate::Color] parses, so the
+/// spec parses back to the same style.  A plain style is empty.
+///
+/// To render the ANSI code, see [`Style::render`].
+///
+/// # Examples
+///
+/// ```rust
+/// let style = anstyle::AnsiColor::Red.on(anstyle::RgbColor(34, 34, 34)).bold().underline();
+/// let spec = style.to_string();
+/// assert_eq!(spec, "bold underline fg:red bg:#222222");
+/// assert_eq!(spec.parse(), Ok(style));
+/// ```
+impl core::fmt::Display for Style {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut separator = "";
+        for (effect, name) in EFFECT_NAMES {
+            if self.effects.contains(effect) {
+                write!(f, "{separator}{name}")?;
+                separator = " ";
+            }
+        }
+        let colors = [
+            ("fg:", self.fg),
+            ("bg:", self.bg),
+            ("underline-color:", self.underline),
+        ];
+        for (prefix, color) in colors {
+            if let Some(color) = color {
+                write!(f, "{separator}{prefix}")?;
+                crate::color::write_name(color, f)?;
+                separator = " ";
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse a spec written by [`Style`]'s `Display`, ignoring case
+///
+/// Words are separated by whitespace and may be in any order, with later colors replacing
+/// earlier ones.
+///
+/// # Examples
+///
+/// ```rust
+/// let style: anstyle::Style = "fg:208 Italic".parse().unwrap();
+/// assert_eq!(style, anstyle::Ansi256Color(208).on_default().italic());
+/// assert_eq!("".parse(), Ok(anstyle::Style::new()));
+/// assert_eq!("fg:".parse::<anstyle::Style>(), Err(anstyle::ParseStyleError));
+/// ```
+impl core::str::FromStr for Style {
+    type Err = ParseStyleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut style = Self::new();
+        for word in s.split_ascii_whitespace() {
+            let color = |prefix: &str| {
+                let value = word
+                    .get(..prefix.len())?
+                    .eq_ignore_ascii_case(prefix)
+                    .then(|| &word[prefix.len()..])?;
+                Some(value.parse::<crate::Color>().map_err(|_| ParseStyleError))
+            };
+            if let Some(fg) = color("fg:") {
+                style = style.fg_color(Some(fg?));
+            } else if let Some(bg) = color("bg:") {
+                style = style.bg_color(Some(bg?));
+            } else if let Some(underline) = color("underline-color:") {
+                style = style.underline_color(Some(underline?));
+            } else {
+                let (effect, _) = EFFECT_NAMES
+                    .iter()
+                    .find(|(_, name)| name.eq_ignore_ascii_case(word))
+                    .ok_or(ParseStyleError)?;
+                style |= *effect;
+            }
+        }
+        Ok(style)
+    }
+}
+
+/// A style spec couldn't be parsed
+///
+/// See [`Style`]'s `FromStr`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ParseStyleError;
+
+impl core::fmt::Display for ParseStyleError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("expected effects, like `bold`, and colors, like `fg:red`")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseStyleError {}
+
+pub(crate) const EFFECT_NAMES: [(crate::Effects, &str); 12] = [
+    (crate::Effects::BOLD, "bold"),
+    (crate::Effects::DIMMED, "dimmed"),
+    (crate::Effects::ITALIC, "italic"),
+    (crate::Effects::UNDERLINE, "underline"),
+    (crate::Effects::DOUBLE_UNDERLINE, "double-underline"),
+    (crate::Effects::CURLY_UNDERLINE, "curly-underline"),
+    (crate::Effects::DOTTED_UNDERLINE, "dotted-underline"),
+    (crate::Effects::DASHED_UNDERLINE, "dashed-underline"),
+    (crate::Effects::BLINK, "blink"),
+    (crate::Effects::INVERT, "invert"),
+    (crate::Effects::HIDDEN, "hidden"),
+    (crate::Effects::STRIKETHROUGH, "strikethrough"),
+];
+
 #[derive(Copy, Clone, Default, Debug)]
-struct StyleDisplay(Style);
+struct StyleDisplay(Style, crate::RenderOptions);
 
 impl core::fmt::Display for StyleDisplay {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        self.0.effects.render().fmt(f)?;
+        let colons = self.1.get_colons();
+        self.0.effects.render_with(self.1.get_sgr21()).fmt(f)?;
 
         if let Some(fg) = self.0.fg {
-            fg.render_fg().fmt(f)?;
+            fg.render_fg_with(colons).fmt(f)?;
         }
 
         if let Some(bg) = self.0.bg {
-            bg.render_bg().fmt(f)?;
+            bg.render_bg_with(colons).fmt(f)?;
         }
 
         if let Some(underline) = self.0.underline {
-            underline.render_underline().fmt(f)?;
+            underline.render_underline_with(colons).fmt(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+struct StyleTransition(Style, Style);
+
+impl StyleTransition {
+    /// Write the codes for changing only what differs
+    fn write_changes(&self, write: &mut dyn core::fmt::Write) -> core::fmt::Result {
+        use crate::Effects;
+        const INTENSITY: Effects = Effects::BOLD.insert(Effects::DIMMED);
+
+        let (from, to) = (self.0, self.1);
+        let removed = from.effects.remove(to.effects);
+        let mut added = to.effects.remove(from.effects);
+        if !intersection(removed, INTENSITY).is_plain() {
+            write.write_str(escape!("22"))?;
+            // Bold and dimmed are cleared together
+            added |= intersection(to.effects, INTENSITY);
+        }
+        if removed.contains(Effects::ITALIC) {
+            write.write_str(escape!("23"))?;
+        }
+        if !intersection(removed, crate::effect::UNDERLINES).is_plain() {
+            write.write_str(escape!("24"))?;
+            added |= intersection(to.effects, crate::effect::UNDERLINES);
+        }
+        if removed.contains(Effects::BLINK) {
+            write.write_str(escape!("25"))?;
         }
+        if removed.contains(Effects::INVERT) {
+            write.write_str(escape!("27"))?;
+        }
+        if removed.contains(Effects::HIDDEN) {
+            write.write_str(escape!("28"))?;
+        }
+        if removed.contains(Effects::STRIKETHROUGH) {
+            write.write_str(escape!("29"))?;
+        }
+        write!(write, "{}", added.render())?;
 
+        if from.fg != to.fg {
+            match to.fg {
+                Some(fg) => write!(write, "{}", fg.render_fg())?,
+                None => write.write_str(escape!("39"))?,
+            }
+        }
+        if from.bg != to.bg {
+            match to.bg {
+                Some(bg) => write!(write, "{}", bg.render_bg())?,
+                None => write.write_str(escape!("49"))?,
+            }
+        }
+        if from.underline != to.underline {
+            match to.underline {
+                Some(underline) => write!(write, "{}", underline.render_underline_with(false))?,
+                None => write.write_str(escape!("59"))?,
+            }
+        }
         Ok(())
     }
 }
+
+impl core::fmt::Display for StyleTransition {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        use core::fmt::Write as _;
+
+        let (from, to) = (self.0, self.1);
+        if from == to {
+            return Ok(());
+        }
+
+        let mut full = LenCounter(0);
+        let _ = write!(full, "{}{}", from.render_reset(), to.render());
+        let mut partial = LenCounter(0);
+        let _ = self.write_changes(&mut partial);
+        if partial.0 < full.0 {
+            self.write_changes(f)
+        } else {
+            write!(f, "{}{}", from.render_reset(), to.render())
+        }
+    }
+}
+
+fn intersection(a: crate::Effects, b: crate::Effects) -> crate::Effects {
+    a.remove(a.remove(b))
+}
+
+/// Measure what would be written
+struct LenCounter(usize);
+
+impl core::fmt::Write for LenCounter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.0 += s.len();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod test {
+    use super::*;
+
+    #[test]
+    fn max_rendered_len() {
+        let all = crate::Effects::BOLD
+            | crate::Effects::DIMMED
+            | crate::Effects::ITALIC
+            | crate::Effects::UNDERLINE
+            | crate::Effects::DOUBLE_UNDERLINE
+            | crate::Effects::CURLY_UNDERLINE
+            | crate::Effects::DOTTED_UNDERLINE
+            | crate::Effects::DASHED_UNDERLINE
+            | crate::Effects::BLINK
+            | crate::Effects::INVERT
+            | crate::Effects::HIDDEN
+            | crate::Effects::STRIKETHROUGH;
+        let max = Style::new()
+            .effects(all)
+            .fg_color(Some(crate::RgbColor(255, 255, 255).into()))
+            .bg_color(Some(crate::RgbColor(255, 255, 255).into()))
+            .underline_color(Some(crate::RgbColor(255, 255, 255).into()));
+        let options = crate::RenderOptions::new()
+            .sgr21(crate::Sgr21::BoldOff)
+            .colons(true);
+        assert_eq!(
+            max.render_with(options).to_string().len(),
+            Style::MAX_RENDERED_LEN
+        );
+        assert_eq!(
+            all.render_with(crate::Sgr21::BoldOff).to_string().len(),
+            crate::Effects::MAX_RENDERED_LEN
+        );
+
+        let mut buffer = [0; Style::MAX_RENDERED_LEN];
+        assert_eq!(max.render_into(&mut buffer), max.render().to_string());
+    }
+
+    #[test]
+    fn spec_round_trip() {
+        let all = EFFECT_NAMES
+            .iter()
+            .fold(crate::Effects::new(), |all, (effect, _)| all | *effect);
+        let styles = [
+            Style::new(),
+            Style::new().effects(all),
+            Style::new()
+                .fg_color(Some(crate::AnsiColor::BrightBlack.into()))
+                .bg_color(Some(crate::Ansi256Color(1).into()))
+                .underline_color(Some(crate::RgbColor(0, 128, 255).into())),
+        ];
+        for style in styles {
+            assert_eq!(style.to_string().parse(), Ok(style), "{style}");
+        }
+        for invalid in ["bold,italic", "fg:red:", "fg :red", "underline-color"] {
+            assert_eq!(
+                invalid.parse::<Style>(),
+                Err(ParseStyleError),
+                "{invalid:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn transition_to() {
+        let red = crate::AnsiColor::Red.on_default();
+        assert_eq!(
+            red.bold().dimmed().transition_to(red.dimmed()).to_string(),
+            "\x1b[22m\x1b[2m"
+        );
+        assert_eq!(
+            (red | crate::Effects::CURLY_UNDERLINE)
+                .transition_to(red.underline().italic())
+                .to_string(),
+            "\x1b[24m\x1b[3m\x1b[4m"
+        );
+        assert_eq!(
+            red.underline_color(Some(crate::AnsiColor::Blue.into()))
+                .transition_to(red.bg_color(Some(crate::AnsiColor::Blue.into())))
+                .to_string(),
+            "\x1b[44m\x1b[59m"
+        );
+        assert_eq!(red.transition_to(red).to_string(), "");
+        assert_eq!(Style::new().transition_to(red).to_string(), "\x1b[31m");
+    }
+}